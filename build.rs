@@ -0,0 +1,670 @@
+//! Generates `data::block::Block` from the vanilla block report in `reports/blocks.json`, the
+//! built-in registries in `data::registry::builtin` from `reports/registries.json`, and the
+//! default stack size of each item from `reports/items.json`.
+//!
+//! The reports come from running the server jar with
+//! `java -DbundlerMainClass=net.minecraft.data.Main -jar server.jar --reports`. The checked-in
+//! `blocks.json` was instead generated from the vanilla 1.21.4 block data published with the
+//! `azalea-block` crate: it has every block and state with vanilla's ids and defaults, but leaves
+//! out the `definition` of each block, which nothing here reads. `registries.json` was likewise
+//! generated from the vanilla 1.21.4 registries published with the `azalea-registry` crate, and
+//! has every entry of the registries used here, by their protocol ids. `items.json` only has the
+//! `max_stack_size` of each item's default components, taken by name from the vanilla 1.21.8 item
+//! data published with `azalea-inventory`, as no such data was published for 1.21.4.
+
+use serde_json::{Map, Value};
+use std::{collections::HashMap, fmt::Write, fs, path::Path};
+
+const REPORT: &str = "reports/blocks.json";
+const REGISTRIES: &str = "reports/registries.json";
+const ITEMS: &str = "reports/items.json";
+
+const KEYWORDS: &[&str] = &["type", "match", "mod", "move", "ref", "in", "loop", "box"];
+
+enum Kind {
+    Bool,
+    Int { min: u32 },
+    Enum(String),
+}
+
+struct Property {
+    name: String,
+    values: Vec<String>,
+    kind: Kind,
+}
+
+impl Property {
+    fn field(&self) -> String {
+        if KEYWORDS.contains(&self.name.as_str()) {
+            format!("r#{}", self.name)
+        } else {
+            self.name.clone()
+        }
+    }
+
+    fn ty(&self) -> String {
+        match &self.kind {
+            Kind::Bool => "bool".to_string(),
+            Kind::Int { .. } => "u8".to_string(),
+            Kind::Enum(name) => format!("property::{name}"),
+        }
+    }
+
+    /// Expression for the index of the bound field in this property's values.
+    fn index(&self) -> String {
+        let field = self.field();
+        match &self.kind {
+            Kind::Bool if self.values[0] == "true" => format!("usize::from(!*{field})"),
+            Kind::Bool => format!("usize::from(*{field})"),
+            Kind::Int { min: 0 } => format!("usize::from(*{field})"),
+            Kind::Int { min } => format!("usize::from(*{field}) - {min}"),
+            Kind::Enum(_) => format!("*{field} as usize"),
+        }
+    }
+
+    /// Expression for the value at index `index`, which is known to be in range.
+    fn value_at(&self, index: &str) -> String {
+        match &self.kind {
+            Kind::Bool => format!("[{}][{index}]", self.values.join(", ")),
+            Kind::Int { min: 0 } => format!("({index}) as u8"),
+            Kind::Int { min } => format!("({index} + {min}) as u8"),
+            Kind::Enum(name) => format!("property::{name}::from_repr({index}).unwrap()"),
+        }
+    }
+
+    /// The value at `index` written out as a literal.
+    fn literal(&self, index: usize) -> String {
+        match &self.kind {
+            Kind::Bool | Kind::Int { .. } => self.values[index].clone(),
+            Kind::Enum(name) => format!("property::{name}::{}", pascal_case(&self.values[index])),
+        }
+    }
+}
+
+struct Block {
+    id: String,
+    variant: String,
+    properties: Vec<Property>,
+    base: u32,
+    default: Vec<usize>,
+}
+
+impl Block {
+    fn states(&self) -> u32 {
+        self.properties
+            .iter()
+            .map(|property| property.values.len() as u32)
+            .product()
+    }
+
+    /// How far apart consecutive values of each property are in state ids.
+    fn strides(&self) -> Vec<usize> {
+        let mut stride = self.states() as usize;
+        self.properties
+            .iter()
+            .map(|property| {
+                stride /= property.values.len();
+                stride
+            })
+            .collect()
+    }
+
+    /// The pattern binding every property, or nothing for a block without any.
+    fn pattern(&self) -> String {
+        if self.properties.is_empty() {
+            format!("Self::{}", self.variant)
+        } else {
+            let fields: Vec<_> = self.properties.iter().map(Property::field).collect();
+            format!("Self::{} {{ {} }}", self.variant, fields.join(", "))
+        }
+    }
+
+    /// The state with each property set to the expression given by `value`.
+    fn construct(&self, value: impl Fn(usize, &Property) -> String) -> String {
+        if self.properties.is_empty() {
+            return format!("Self::{}", self.variant);
+        }
+
+        let fields: Vec<_> = self
+            .properties
+            .iter()
+            .enumerate()
+            .map(|(i, property)| format!("{}: {}", property.field(), value(i, property)))
+            .collect();
+        format!("Self::{} {{ {} }}", self.variant, fields.join(", "))
+    }
+}
+
+/// The values of an enum property, with how many blocks have them and the first one that does.
+struct ValueSet<'a> {
+    values: Vec<String>,
+    blocks: usize,
+    first: &'a str,
+}
+
+fn pascal_case(s: &str) -> String {
+    s.split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            chars.next().map_or(String::new(), |first| {
+                first.to_uppercase().chain(chars).collect()
+            })
+        })
+        .collect()
+}
+
+fn is_bool(values: &[String]) -> bool {
+    values == ["true", "false"] || values == ["false", "true"]
+}
+
+fn is_int(values: &[String]) -> Option<u32> {
+    let ints: Vec<u32> = values
+        .iter()
+        .map(|value| value.parse().ok())
+        .collect::<Option<_>>()?;
+    ints.windows(2)
+        .all(|pair| pair[1] == pair[0] + 1)
+        .then_some(ints[0])
+}
+
+fn strings(value: &Value) -> Vec<String> {
+    value
+        .as_array()
+        .expect("Property values should be an array")
+        .iter()
+        .map(|value| {
+            value
+                .as_str()
+                .expect("Property value should be a string")
+                .to_string()
+        })
+        .collect()
+}
+
+fn read_blocks(report: &Map<String, Value>) -> Vec<Block> {
+    // Enum properties with the same name share a type when they have the same values. The values
+    // most blocks use get the property's name, and any others are named for the first block with
+    // them, like `Facing` and `DispenserFacing`.
+    let mut value_sets: HashMap<&str, Vec<ValueSet>> = HashMap::new();
+    for (id, definition) in report {
+        let path = id.strip_prefix("minecraft:").unwrap_or(id);
+        for (name, values) in definition["properties"].as_object().into_iter().flatten() {
+            let values = strings(values);
+            if is_bool(&values) || is_int(&values).is_some() {
+                continue;
+            }
+            let sets = value_sets.entry(name).or_default();
+            match sets.iter_mut().find(|set| set.values == values) {
+                Some(set) => set.blocks += 1,
+                None => sets.push(ValueSet {
+                    values,
+                    blocks: 1,
+                    first: path,
+                }),
+            }
+        }
+    }
+    let mut enum_names: HashMap<(&str, Vec<String>), String> = HashMap::new();
+    for (name, sets) in &value_sets {
+        let common = sets
+            .iter()
+            .enumerate()
+            .max_by_key(|(i, set)| (set.blocks, usize::MAX - i))
+            .map(|(i, _)| i);
+        for (i, set) in sets.iter().enumerate() {
+            let enum_name = if Some(i) == common {
+                pascal_case(name)
+            } else {
+                pascal_case(set.first) + &pascal_case(name)
+            };
+            enum_names.insert((name, set.values.clone()), enum_name);
+        }
+    }
+
+    let mut blocks: Vec<Block> = report
+        .iter()
+        .map(|(id, definition)| {
+            let path = id
+                .strip_prefix("minecraft:")
+                .expect("Block should be vanilla");
+            let properties: Vec<Property> = definition["properties"]
+                .as_object()
+                .into_iter()
+                .flatten()
+                .map(|(name, values)| {
+                    let values = strings(values);
+                    let kind = if is_bool(&values) {
+                        Kind::Bool
+                    } else if let Some(min) = is_int(&values) {
+                        Kind::Int { min }
+                    } else {
+                        Kind::Enum(enum_names[&(name.as_str(), values.clone())].clone())
+                    };
+                    Property {
+                        name: name.clone(),
+                        values,
+                        kind,
+                    }
+                })
+                .collect();
+
+            let states = definition["states"]
+                .as_array()
+                .expect("Block should have states");
+            let base = states
+                .iter()
+                .map(|state| state["id"].as_u64().unwrap() as u32)
+                .min()
+                .expect("Block should have at least one state");
+
+            let mut block = Block {
+                id: id.clone(),
+                variant: pascal_case(path),
+                properties,
+                base,
+                default: vec![],
+            };
+
+            // The ids are assumed to count through every combination of values with the last
+            // property changing fastest, so check that against each state
+            assert_eq!(
+                states.len() as u32,
+                block.states(),
+                "{id} has missing states"
+            );
+            for state in states {
+                let mut offset = 0;
+                let mut indices = vec![];
+                for property in &block.properties {
+                    let value = state["properties"][&property.name]
+                        .as_str()
+                        .expect("State should have a value for each property");
+                    let index = property.values.iter().position(|v| v == value).unwrap();
+                    offset = offset * property.values.len() as u32 + index as u32;
+                    indices.push(index);
+                }
+                assert_eq!(
+                    state["id"].as_u64().unwrap() as u32,
+                    block.base + offset,
+                    "{id} has states out of order"
+                );
+                if state["default"].as_bool() == Some(true) {
+                    block.default = indices;
+                }
+            }
+
+            block
+        })
+        .collect();
+
+    blocks.sort_by_key(|block| block.base);
+    let mut next = 0;
+    for block in &blocks {
+        assert_eq!(next, block.base, "{} leaves a gap in state ids", block.id);
+        next += block.states();
+    }
+
+    blocks
+}
+
+fn write_properties(out: &mut String, blocks: &[Block]) {
+    let mut written = vec![];
+    for property in blocks.iter().flat_map(|block| &block.properties) {
+        let Kind::Enum(name) = &property.kind else {
+            continue;
+        };
+        if written.contains(name) {
+            continue;
+        }
+        written.push(name.clone());
+
+        writeln!(
+            out,
+            "#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, strum::EnumString, strum::IntoStaticStr, strum::FromRepr)]"
+        )
+        .unwrap();
+        writeln!(out, "pub enum {name} {{").unwrap();
+        for value in &property.values {
+            writeln!(out, "    #[strum(serialize = {value:?})]").unwrap();
+            writeln!(out, "    {},", pascal_case(value)).unwrap();
+        }
+        writeln!(out, "}}\n").unwrap();
+    }
+}
+
+fn write_blocks(out: &mut String, blocks: &[Block]) {
+    let last = blocks.last().expect("Report should have blocks");
+    writeln!(
+        out,
+        "/// How many block states there are, and so one more than the highest state id."
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "pub const STATE_COUNT: u32 = {};\n",
+        last.base + last.states()
+    )
+    .unwrap();
+
+    writeln!(
+        out,
+        "#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]"
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "#[serde(try_from = \"BlockState\", into = \"BlockState\")]"
+    )
+    .unwrap();
+    writeln!(out, "pub enum Block {{").unwrap();
+    for block in blocks {
+        if block.properties.is_empty() {
+            writeln!(out, "    {},", block.variant).unwrap();
+        } else {
+            writeln!(out, "    {} {{", block.variant).unwrap();
+            for property in &block.properties {
+                writeln!(out, "        {}: {},", property.field(), property.ty()).unwrap();
+            }
+            writeln!(out, "    }},").unwrap();
+        }
+    }
+    writeln!(out, "}}\n").unwrap();
+
+    writeln!(out, "impl Block {{").unwrap();
+
+    writeln!(
+        out,
+        "    /// The block's identifier, e.g. `minecraft:stone`."
+    )
+    .unwrap();
+    writeln!(out, "    pub const fn name(&self) -> &'static str {{").unwrap();
+    writeln!(out, "        match self {{").unwrap();
+    for block in blocks {
+        writeln!(
+            out,
+            "            Self::{} {{ .. }} => {:?},",
+            block.variant, block.id
+        )
+        .unwrap();
+    }
+    writeln!(out, "        }}\n    }}\n").unwrap();
+
+    writeln!(out, "    /// The default state of the block called `name`.").unwrap();
+    writeln!(
+        out,
+        "    pub fn default_state(name: &str) -> Option<Self> {{"
+    )
+    .unwrap();
+    writeln!(out, "        Some(match name {{").unwrap();
+    for block in blocks {
+        let default = block.construct(|i, property| property.literal(block.default[i]));
+        writeln!(out, "            {:?} => {default},", block.id).unwrap();
+    }
+    writeln!(out, "            _ => return None,").unwrap();
+    writeln!(out, "        }})\n    }}\n").unwrap();
+
+    writeln!(
+        out,
+        "    /// The global id the network uses for this state."
+    )
+    .unwrap();
+    writeln!(out, "    pub fn state_id(&self) -> u32 {{").unwrap();
+    writeln!(
+        out,
+        "        let (base, offset): (u32, usize) = match self {{"
+    )
+    .unwrap();
+    for block in blocks {
+        let terms: Vec<_> = block
+            .properties
+            .iter()
+            .zip(block.strides())
+            .map(|(property, stride)| match stride {
+                1 => property.index(),
+                _ if matches!(property.kind, Kind::Int { min } if min > 0) => {
+                    format!("({}) * {stride}", property.index())
+                }
+                _ => format!("{} * {stride}", property.index()),
+            })
+            .collect();
+        let offset = if terms.is_empty() {
+            "0".to_string()
+        } else {
+            terms.join(" + ")
+        };
+        writeln!(
+            out,
+            "            {} => ({}, {offset}),",
+            block.pattern(),
+            block.base
+        )
+        .unwrap();
+    }
+    writeln!(out, "        }};\n        base + offset as u32\n    }}\n").unwrap();
+
+    writeln!(out, "    pub fn from_state_id(id: u32) -> Option<Self> {{").unwrap();
+    writeln!(out, "        Some(match id {{").unwrap();
+    for block in blocks {
+        let end = block.base + block.states() - 1;
+        let strides = block.strides();
+        let state = block.construct(|i, property| {
+            property.value_at(&match (i, strides[i]) {
+                (0, 1) => "offset".to_string(),
+                (0, stride) => format!("offset / {stride}"),
+                (_, 1) => format!("offset % {}", property.values.len()),
+                (_, stride) => format!("offset / {stride} % {}", property.values.len()),
+            })
+        });
+        if block.properties.is_empty() {
+            writeln!(out, "            {} => {state},", block.base).unwrap();
+        } else {
+            writeln!(out, "            {}..={end} => {{", block.base).unwrap();
+            writeln!(
+                out,
+                "                let offset = (id - {}) as usize;",
+                block.base
+            )
+            .unwrap();
+            writeln!(out, "                {state}\n            }}").unwrap();
+        }
+    }
+    writeln!(out, "            _ => return None,").unwrap();
+    writeln!(out, "        }})\n    }}\n").unwrap();
+
+    writeln!(
+        out,
+        "    /// Each property and its value, as written in NBT and commands."
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "    pub fn properties(&self) -> Vec<(&'static str, &'static str)> {{"
+    )
+    .unwrap();
+    writeln!(out, "        match self {{").unwrap();
+    for block in blocks {
+        let values: Vec<_> = block
+            .properties
+            .iter()
+            .map(|property| {
+                let values: Vec<_> = property.values.iter().map(|v| format!("{v:?}")).collect();
+                format!(
+                    "({:?}, [{}][{}])",
+                    property.name,
+                    values.join(", "),
+                    property.index()
+                )
+            })
+            .collect();
+        writeln!(
+            out,
+            "            {} => vec![{}],",
+            block.pattern(),
+            values.join(", ")
+        )
+        .unwrap();
+    }
+    writeln!(out, "        }}\n    }}\n").unwrap();
+
+    writeln!(
+        out,
+        "    /// Changes one property from its name and value as written in NBT and commands."
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "    pub fn set_property(&mut self, name: &str, value: &str) -> Result<(), &'static str> {{"
+    )
+    .unwrap();
+    writeln!(out, "        match (self, name) {{").unwrap();
+    for block in blocks {
+        for property in &block.properties {
+            let parse = match &property.kind {
+                Kind::Bool => {
+                    "value.parse().map_err(|_| \"Invalid boolean property\")?".to_string()
+                }
+                Kind::Int { min } => format!(
+                    "value.parse().ok().filter(|value| ({min}..={}).contains(value)).ok_or(\"Invalid integer property\")?",
+                    *min as usize + property.values.len() - 1
+                ),
+                Kind::Enum(_) => {
+                    "value.parse().map_err(|_| \"Invalid property value\")?".to_string()
+                }
+            };
+            writeln!(
+                out,
+                "            (Self::{} {{ {}, .. }}, {:?}) => *{} = {parse},",
+                block.variant,
+                property.field(),
+                property.name,
+                property.field()
+            )
+            .unwrap();
+        }
+    }
+    writeln!(
+        out,
+        "            _ => return Err(\"Unknown block property\"),"
+    )
+    .unwrap();
+    writeln!(out, "        }}\n        Ok(())\n    }}").unwrap();
+
+    writeln!(out, "}}").unwrap();
+}
+
+/// A `BuiltinRegistry` constant named after the registry, e.g. `ITEM` for `minecraft:item`.
+fn write_registry(out: &mut String, id: &str, entries: &[&str]) {
+    let name = id
+        .strip_prefix("minecraft:")
+        .expect("Registry should be vanilla")
+        .replace('/', "_")
+        .to_uppercase();
+    writeln!(out, "/// `{id}`, in network id order.").unwrap();
+    writeln!(
+        out,
+        "pub const {name}: BuiltinRegistry = BuiltinRegistry::new({id:?}, &{entries:?});\n"
+    )
+    .unwrap();
+}
+
+fn write_registries(out: &mut String, report: &Map<String, Value>, blocks: &[Block]) {
+    let ids: Vec<_> = blocks.iter().map(|block| block.id.as_str()).collect();
+    write_registry(out, "minecraft:block", &ids);
+
+    for (id, registry) in report {
+        let mut entries: Vec<(&str, u64)> = registry["entries"]
+            .as_object()
+            .expect("Registry should have entries")
+            .iter()
+            .map(|(entry, value)| {
+                let protocol_id = value["protocol_id"]
+                    .as_u64()
+                    .expect("Entry should have a protocol id");
+                (entry.as_str(), protocol_id)
+            })
+            .collect();
+        entries.sort_by_key(|(_, protocol_id)| *protocol_id);
+        for (i, (entry, protocol_id)) in entries.iter().enumerate() {
+            assert_eq!(i as u64, *protocol_id, "{entry} leaves a gap in {id}");
+        }
+
+        let entries: Vec<_> = entries.into_iter().map(|(entry, _)| entry).collect();
+        write_registry(out, id, &entries);
+    }
+}
+
+/// The default `max_stack_size` of every item, in network id order.
+fn write_items(out: &mut String, report: &Map<String, Value>, registries: &Map<String, Value>) {
+    let mut entries: Vec<(&String, u64)> = registries["minecraft:item"]["entries"]
+        .as_object()
+        .expect("Item registry should have entries")
+        .iter()
+        .map(|(entry, value)| (entry, value["protocol_id"].as_u64().unwrap()))
+        .collect();
+    entries.sort_by_key(|(_, protocol_id)| *protocol_id);
+
+    let sizes: Vec<_> = entries
+        .into_iter()
+        .map(|(id, _)| {
+            report[id]["components"]["minecraft:max_stack_size"]
+                .as_u64()
+                .unwrap_or_else(|| panic!("{id} should have a max stack size"))
+        })
+        .collect();
+    writeln!(
+        out,
+        "[{}]",
+        sizes
+            .iter()
+            .map(u64::to_string)
+            .collect::<Vec<_>>()
+            .join(", ")
+    )
+    .unwrap();
+}
+
+fn main() {
+    println!("cargo::rerun-if-changed={REPORT}");
+    println!("cargo::rerun-if-changed={REGISTRIES}");
+    println!("cargo::rerun-if-changed={ITEMS}");
+
+    let report: Value =
+        serde_json::from_str(&fs::read_to_string(REPORT).expect("Block report should be readable"))
+            .expect("Block report should be valid JSON");
+    let blocks = read_blocks(
+        report
+            .as_object()
+            .expect("Block report should be an object"),
+    );
+
+    let out_dir = std::env::var("OUT_DIR").unwrap();
+    let mut properties = String::new();
+    write_properties(&mut properties, &blocks);
+    fs::write(Path::new(&out_dir).join("block_properties.rs"), properties).unwrap();
+
+    let mut out = String::new();
+    write_blocks(&mut out, &blocks);
+    fs::write(Path::new(&out_dir).join("blocks.rs"), out).unwrap();
+
+    let registries: Value = serde_json::from_str(
+        &fs::read_to_string(REGISTRIES).expect("Registry report should be readable"),
+    )
+    .expect("Registry report should be valid JSON");
+    let registries = registries
+        .as_object()
+        .expect("Registry report should be an object");
+    let mut out = String::new();
+    write_registries(&mut out, registries, &blocks);
+    fs::write(Path::new(&out_dir).join("registries.rs"), out).unwrap();
+
+    let items: Value =
+        serde_json::from_str(&fs::read_to_string(ITEMS).expect("Item report should be readable"))
+            .expect("Item report should be valid JSON");
+    let mut out = String::new();
+    write_items(
+        &mut out,
+        items.as_object().expect("Item report should be an object"),
+        registries,
+    );
+    fs::write(Path::new(&out_dir).join("max_stack_sizes.rs"), out).unwrap();
+}
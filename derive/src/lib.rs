@@ -0,0 +1,243 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{
+    Data, DeriveInput, Expr, Fields, Ident, Path, Type, parse_macro_input, parse_quote,
+    spanned::Spanned,
+};
+
+/// Derives `mc_server_rs::types::Type`.
+///
+/// Structs are encoded field by field in declaration order. Enums are encoded as a `VarInt`
+/// discriminant followed by the fields of the variant. The discriminant of a variant is taken from
+/// `#[mc(id = ...)]`, then from its explicit discriminant, and otherwise counts up from the
+/// previous variant (starting at 0).
+///
+/// A field's wire codec can be overridden with one of:
+/// - `#[mc(var_int)]`: an `i32` encoded as a `VarInt`
+/// - `#[mc(var_long)]`: an `i64` encoded as a `VarLong`
+/// - `#[mc(prefixed)]`: a `Vec<T>` prefixed with its length as a `VarInt`
+/// - `#[mc(rest)]`: a `Vec<u8>` that consumes the rest of the input
+/// - `#[mc(with = path::to::Codec)]`: any implementor of `mc_server_rs::types::Codec`
+#[proc_macro_derive(Type, attributes(mc))]
+pub fn derive_type(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand(mut input: DeriveInput) -> syn::Result<TokenStream2> {
+    for param in input.generics.type_params_mut() {
+        param.bounds.push(parse_quote!(::mc_server_rs::types::Type));
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let (write, read) = match &input.data {
+        Data::Struct(data) => {
+            let (pattern, write) = write_fields(&data.fields)?;
+            let read = read_fields(&data.fields)?;
+            (
+                quote! {
+                    let Self #pattern = self;
+                    #write
+                },
+                quote! { Ok(Self #read) },
+            )
+        }
+        Data::Enum(data) => {
+            let mut consts = Vec::new();
+            let mut write_arms = Vec::new();
+            let mut read_arms = Vec::new();
+            let mut previous: Option<Expr> = None;
+
+            for (i, variant) in data.variants.iter().enumerate() {
+                let id = match variant_id(&variant.attrs)? {
+                    Some(id) => id,
+                    None => match (&variant.discriminant, &previous) {
+                        (Some((_, discriminant)), _) => discriminant.clone(),
+                        (None, Some(previous)) => parse_quote!((#previous) + 1),
+                        (None, None) => parse_quote!(0),
+                    },
+                };
+                let constant = format_ident!("__ID_{}", i);
+                consts.push(quote! { const #constant: i32 = #id; });
+                previous = Some(id);
+
+                let variant_name = &variant.ident;
+                let (pattern, write) = write_fields(&variant.fields)?;
+                let read = read_fields(&variant.fields)?;
+
+                write_arms.push(quote! {
+                    Self::#variant_name #pattern => {
+                        ::mc_server_rs::types::Type::write(
+                            &::mc_server_rs::types::VarInt::new(#constant),
+                            &mut writer,
+                        )?;
+                        #write
+                    }
+                });
+                read_arms.push(quote! {
+                    #constant => Ok(Self::#variant_name #read),
+                });
+            }
+
+            let message = format!("Invalid {name} discriminant {{}}");
+
+            (
+                quote! {
+                    #(#consts)*
+                    match self {
+                        #(#write_arms)*
+                    }
+                },
+                quote! {
+                    #(#consts)*
+                    let id = *<::mc_server_rs::types::VarInt as ::mc_server_rs::types::Type>::read(&mut reader)?;
+                    match id {
+                        #(#read_arms)*
+                        _ => Err(::std::io::Error::new(
+                            ::std::io::ErrorKind::InvalidData,
+                            format!(#message, id),
+                        )),
+                    }
+                },
+            )
+        }
+        Data::Union(data) => {
+            return Err(syn::Error::new(
+                data.union_token.span(),
+                "Type cannot be derived for unions",
+            ));
+        }
+    };
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics ::mc_server_rs::types::Type for #name #ty_generics #where_clause {
+            #[allow(unused_variables, unused_mut)]
+            fn write(&self, mut writer: impl ::std::io::Write) -> ::std::io::Result<()> {
+                #write
+                Ok(())
+            }
+
+            #[allow(unused_variables, unused_mut)]
+            fn read(mut reader: impl ::std::io::Read) -> ::std::io::Result<Self> {
+                #read
+            }
+        }
+    })
+}
+
+fn binding(i: usize, ident: Option<&Ident>) -> Ident {
+    match ident {
+        Some(ident) => format_ident!("__field_{}", ident),
+        None => format_ident!("__field_{}", i),
+    }
+}
+
+fn write_fields(fields: &Fields) -> syn::Result<(TokenStream2, TokenStream2)> {
+    let mut writes = Vec::new();
+    let mut bindings = Vec::new();
+
+    for (i, field) in fields.iter().enumerate() {
+        let binding = binding(i, field.ident.as_ref());
+        writes.push(match field_codec(&field.attrs)? {
+            None => quote! { ::mc_server_rs::types::Type::write(#binding, &mut writer)?; },
+            Some(codec) => {
+                let ty = &field.ty;
+                quote! {
+                    <#codec as ::mc_server_rs::types::Codec<#ty>>::write(#binding, &mut writer)?;
+                }
+            }
+        });
+        bindings.push(match &field.ident {
+            Some(ident) => quote! { #ident: #binding },
+            None => quote! { #binding },
+        });
+    }
+
+    let pattern = match fields {
+        Fields::Named(_) => quote! { { #(#bindings),* } },
+        Fields::Unnamed(_) => quote! { ( #(#bindings),* ) },
+        Fields::Unit => quote! {},
+    };
+
+    Ok((pattern, quote! { #(#writes)* }))
+}
+
+fn read_fields(fields: &Fields) -> syn::Result<TokenStream2> {
+    let mut reads = Vec::new();
+
+    for field in fields {
+        let ty = &field.ty;
+        let read = match field_codec(&field.attrs)? {
+            None => quote! {
+                <#ty as ::mc_server_rs::types::Type>::read(&mut reader)?
+            },
+            Some(codec) => quote! {
+                <#codec as ::mc_server_rs::types::Codec<#ty>>::read(&mut reader)?
+            },
+        };
+        reads.push(match &field.ident {
+            Some(ident) => quote! { #ident: #read },
+            None => read,
+        });
+    }
+
+    Ok(match fields {
+        Fields::Named(_) => quote! { { #(#reads),* } },
+        Fields::Unnamed(_) => quote! { ( #(#reads),* ) },
+        Fields::Unit => quote! {},
+    })
+}
+
+fn field_codec(attrs: &[syn::Attribute]) -> syn::Result<Option<Type>> {
+    let mut codec = None;
+
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("mc")) {
+        attr.parse_nested_meta(|meta| {
+            let path: Type = if meta.path.is_ident("var_int") {
+                parse_quote!(::mc_server_rs::types::AsVarInt)
+            } else if meta.path.is_ident("var_long") {
+                parse_quote!(::mc_server_rs::types::AsVarLong)
+            } else if meta.path.is_ident("prefixed") {
+                parse_quote!(::mc_server_rs::types::Prefixed)
+            } else if meta.path.is_ident("rest") {
+                parse_quote!(::mc_server_rs::types::Rest)
+            } else if meta.path.is_ident("with") {
+                let path: Path = meta.value()?.parse()?;
+                parse_quote!(#path)
+            } else {
+                return Err(meta.error("Unknown field attribute"));
+            };
+
+            if codec.replace(path).is_some() {
+                return Err(meta.error("Only one codec can be specified per field"));
+            }
+
+            Ok(())
+        })?;
+    }
+
+    Ok(codec)
+}
+
+fn variant_id(attrs: &[syn::Attribute]) -> syn::Result<Option<Expr>> {
+    let mut id = None;
+
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("mc")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("id") {
+                id = Some(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("Unknown variant attribute"))
+            }
+        })?;
+    }
+
+    Ok(id)
+}
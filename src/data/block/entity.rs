@@ -0,0 +1,878 @@
+use crate::{
+    OneOf,
+    data::{
+        Components, Identifier, Item, ItemWithSlot,
+        block::Block,
+        components::{BannerPattern, Bee},
+        entity::{
+            McUuid,
+            vehicle::{Container, SpawnData, SpawnPotential},
+        },
+        item::{ContainerKind, ItemError},
+        player::Profile,
+        unknown_fields,
+    },
+    text_component::TextComponent,
+};
+use fastnbt::{IntArray, Value};
+use serde::{Deserialize, Serialize};
+use serde_with::{FromInto, serde_as, skip_serializing_none};
+use std::collections::HashMap;
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(try_from = "HashMap<String, Value>")]
+pub struct BlockEntity {
+    #[serde(flatten)]
+    kind: BlockEntityKind,
+    // Left out of item components
+    x: Option<i32>,
+    y: Option<i32>,
+    z: Option<i32>,
+    components: Option<Components>,
+    /// Fields that aren't modelled, kept as they were saved
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+/// Everything but the kind, which is read on its own so the rest can be told apart from it
+#[derive(Deserialize)]
+struct BlockEntityCommon {
+    x: Option<i32>,
+    y: Option<i32>,
+    z: Option<i32>,
+    components: Option<Components>,
+}
+
+impl TryFrom<HashMap<String, Value>> for BlockEntity {
+    type Error = fastnbt::error::Error;
+
+    fn try_from(fields: HashMap<String, Value>) -> Result<Self, Self::Error> {
+        let Some(Value::String(id)) = fields.get("id") else {
+            return Err(serde::de::Error::missing_field("id"));
+        };
+
+        let nbt = fastnbt::to_bytes(&fields)?;
+        let BlockEntityCommon {
+            x,
+            y,
+            z,
+            components,
+        } = fastnbt::from_bytes(&nbt)?;
+        let kind = if IDS.contains(&id.as_str()) {
+            fastnbt::from_bytes(&nbt)?
+        } else {
+            // The fields every block entity has are kept on their own
+            let mut fields = fields.clone();
+            for key in ["x", "y", "z", "components"] {
+                fields.remove(key);
+            }
+            BlockEntityKind::Unknown(Value::Compound(fields))
+        };
+        let mut block_entity = Self {
+            kind,
+            x,
+            y,
+            z,
+            components,
+            extra: HashMap::new(),
+        };
+        block_entity.extra = unknown_fields(&block_entity, fields)?;
+        Ok(block_entity)
+    }
+}
+
+impl BlockEntity {
+    /// The block's position, which item components leave out
+    pub const fn position(&self) -> Option<(i32, i32, i32)> {
+        match (self.x, self.y, self.z) {
+            (Some(x), Some(y), Some(z)) => Some((x, y, z)),
+            _ => None,
+        }
+    }
+
+    pub const fn kind(&self) -> &BlockEntityKind {
+        &self.kind
+    }
+
+    /// The data sent to clients, which already know the id and position.
+    pub fn network_nbt(&self) -> Result<fastnbt::Value, fastnbt::error::Error> {
+        let mut value = fastnbt::from_bytes(&fastnbt::to_bytes(self)?)?;
+        if let fastnbt::Value::Compound(compound) = &mut value {
+            for key in ["id", "x", "y", "z"] {
+                compound.remove(key);
+            }
+        }
+
+        Ok(value)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(tag = "id")]
+pub enum BlockEntityKind {
+    #[serde(rename = "minecraft:banner")]
+    Banner(Banner),
+    #[serde(rename = "minecraft:barrel")]
+    Barrel(ContainerBlock),
+    #[serde(rename = "minecraft:beacon")]
+    Beacon(Beacon),
+    #[serde(rename = "minecraft:bed")]
+    Bed,
+    #[serde(rename = "minecraft:beehive")]
+    Beehive(Beehive),
+    #[serde(rename = "minecraft:bell")]
+    Bell,
+    #[serde(rename = "minecraft:blast_furnace")]
+    BlastFurnace(Furnace),
+    #[serde(rename = "minecraft:brewing_stand")]
+    BrewingStand(BrewingStand),
+    #[serde(rename = "minecraft:brushable_block")]
+    BrushableBlock(BrushableBlock),
+    #[serde(rename = "minecraft:calibrated_sculk_sensor")]
+    CalibratedSculkSensor(SculkSensor),
+    #[serde(rename = "minecraft:campfire")]
+    Campfire(Campfire),
+    #[serde(rename = "minecraft:chest")]
+    Chest(ContainerBlock),
+    #[serde(rename = "minecraft:chiseled_bookshelf")]
+    ChiseledBookshelf(ChiseledBookshelf),
+    #[serde(rename = "minecraft:command_block")]
+    CommandBlock(CommandBlock),
+    #[serde(rename = "minecraft:comparator")]
+    Comparator(Comparator),
+    #[serde(rename = "minecraft:conduit")]
+    Conduit(Conduit),
+    #[serde(rename = "minecraft:crafter")]
+    Crafter(Crafter),
+    #[serde(rename = "minecraft:creaking_heart")]
+    CreakingHeart(CreakingHeart),
+    #[serde(rename = "minecraft:daylight_detector")]
+    DaylightDetector,
+    #[serde(rename = "minecraft:decorated_pot")]
+    DecoratedPot(DecoratedPot),
+    #[serde(rename = "minecraft:dispenser")]
+    Dispenser(ContainerBlock),
+    #[serde(rename = "minecraft:dropper")]
+    Dropper(ContainerBlock),
+    #[serde(rename = "minecraft:enchanting_table")]
+    EnchantingTable(EnchantingTable),
+    #[serde(rename = "minecraft:end_gateway")]
+    EndGateway(EndGateway),
+    #[serde(rename = "minecraft:end_portal")]
+    EndPortal,
+    #[serde(rename = "minecraft:ender_chest")]
+    EnderChest,
+    #[serde(rename = "minecraft:furnace")]
+    Furnace(Furnace),
+    #[serde(rename = "minecraft:hanging_sign")]
+    HangingSign(Sign),
+    #[serde(rename = "minecraft:hopper")]
+    Hopper(Hopper),
+    #[serde(rename = "minecraft:jigsaw")]
+    Jigsaw(Jigsaw),
+    #[serde(rename = "minecraft:jukebox")]
+    Jukebox(Jukebox),
+    #[serde(rename = "minecraft:lectern")]
+    Lectern(Lectern),
+    #[serde(rename = "minecraft:mob_spawner")]
+    MobSpawner(Spawner),
+    #[serde(rename = "minecraft:piston")]
+    Piston(Piston),
+    #[serde(rename = "minecraft:sculk_catalyst")]
+    SculkCatalyst(SculkCatalyst),
+    #[serde(rename = "minecraft:sculk_sensor")]
+    SculkSensor(SculkSensor),
+    #[serde(rename = "minecraft:sculk_shrieker")]
+    SculkShrieker(SculkShrieker),
+    #[serde(rename = "minecraft:shulker_box")]
+    ShulkerBox(ContainerBlock),
+    #[serde(rename = "minecraft:sign")]
+    Sign(Sign),
+    #[serde(rename = "minecraft:skull")]
+    Skull(Skull),
+    #[serde(rename = "minecraft:smoker")]
+    Smoker(Furnace),
+    #[serde(rename = "minecraft:structure_block")]
+    StructureBlock(Box<StructureBlock>),
+    #[serde(rename = "minecraft:trapped_chest")]
+    TrappedChest(ContainerBlock),
+    #[serde(rename = "minecraft:trial_spawner")]
+    TrialSpawner(Box<TrialSpawner>),
+    #[serde(rename = "minecraft:vault")]
+    Vault(Box<Vault>),
+    /// A block entity from a mod or a later version, kept as it was saved but for the fields
+    /// every block entity has
+    #[serde(untagged, skip_deserializing)]
+    Unknown(Value),
+}
+
+/// The ids of every kind but [`BlockEntityKind::Unknown`]
+const IDS: [&str; 45] = [
+    "minecraft:banner",
+    "minecraft:barrel",
+    "minecraft:beacon",
+    "minecraft:bed",
+    "minecraft:beehive",
+    "minecraft:bell",
+    "minecraft:blast_furnace",
+    "minecraft:brewing_stand",
+    "minecraft:brushable_block",
+    "minecraft:calibrated_sculk_sensor",
+    "minecraft:campfire",
+    "minecraft:chest",
+    "minecraft:chiseled_bookshelf",
+    "minecraft:command_block",
+    "minecraft:comparator",
+    "minecraft:conduit",
+    "minecraft:crafter",
+    "minecraft:creaking_heart",
+    "minecraft:daylight_detector",
+    "minecraft:decorated_pot",
+    "minecraft:dispenser",
+    "minecraft:dropper",
+    "minecraft:enchanting_table",
+    "minecraft:end_gateway",
+    "minecraft:end_portal",
+    "minecraft:ender_chest",
+    "minecraft:furnace",
+    "minecraft:hanging_sign",
+    "minecraft:hopper",
+    "minecraft:jigsaw",
+    "minecraft:jukebox",
+    "minecraft:lectern",
+    "minecraft:mob_spawner",
+    "minecraft:piston",
+    "minecraft:sculk_catalyst",
+    "minecraft:sculk_sensor",
+    "minecraft:sculk_shrieker",
+    "minecraft:shulker_box",
+    "minecraft:sign",
+    "minecraft:skull",
+    "minecraft:smoker",
+    "minecraft:structure_block",
+    "minecraft:trapped_chest",
+    "minecraft:trial_spawner",
+    "minecraft:vault",
+];
+
+impl BlockEntityKind {
+    /// The id of the block entity type in the network registry, which unknown ones don't have
+    pub const fn network_id(&self) -> Option<i32> {
+        Some(match self {
+            Self::Furnace(_) => 0,
+            Self::Chest(_) => 1,
+            Self::TrappedChest(_) => 2,
+            Self::EnderChest => 3,
+            Self::Jukebox(_) => 4,
+            Self::Dispenser(_) => 5,
+            Self::Dropper(_) => 6,
+            Self::Sign(_) => 7,
+            Self::HangingSign(_) => 8,
+            Self::MobSpawner(_) => 9,
+            Self::CreakingHeart(_) => 10,
+            Self::Piston(_) => 11,
+            Self::BrewingStand(_) => 12,
+            Self::EnchantingTable(_) => 13,
+            Self::EndPortal => 14,
+            Self::Beacon(_) => 15,
+            Self::Skull(_) => 16,
+            Self::DaylightDetector => 17,
+            Self::Hopper(_) => 18,
+            Self::Comparator(_) => 19,
+            Self::Banner(_) => 20,
+            Self::StructureBlock(_) => 21,
+            Self::EndGateway(_) => 22,
+            Self::CommandBlock(_) => 23,
+            Self::ShulkerBox(_) => 24,
+            Self::Bed => 25,
+            Self::Conduit(_) => 26,
+            Self::Barrel(_) => 27,
+            Self::Smoker(_) => 28,
+            Self::BlastFurnace(_) => 29,
+            Self::Lectern(_) => 30,
+            Self::Bell => 31,
+            Self::Jigsaw(_) => 32,
+            Self::Campfire(_) => 33,
+            Self::Beehive(_) => 34,
+            Self::SculkSensor(_) => 35,
+            Self::CalibratedSculkSensor(_) => 36,
+            Self::SculkCatalyst(_) => 37,
+            Self::SculkShrieker(_) => 38,
+            Self::ChiseledBookshelf(_) => 39,
+            Self::BrushableBlock(_) => 40,
+            Self::DecoratedPot(_) => 41,
+            Self::Crafter(_) => 42,
+            Self::TrialSpawner(_) => 43,
+            Self::Vault(_) => 44,
+            Self::Unknown(_) => return None,
+        })
+    }
+
+    /// What kind of container the block entity is, if it holds items in slots
+    pub const fn container_kind(&self) -> Option<ContainerKind> {
+        match self {
+            Self::Barrel(_) | Self::Chest(_) | Self::ShulkerBox(_) | Self::TrappedChest(_) => {
+                Some(ContainerKind::Chest)
+            }
+            Self::Dispenser(_) | Self::Dropper(_) | Self::Crafter(_) => {
+                Some(ContainerKind::Dispenser)
+            }
+            Self::Hopper(_) => Some(ContainerKind::Hopper),
+            Self::Furnace(_) | Self::BlastFurnace(_) | Self::Smoker(_) => {
+                Some(ContainerKind::Furnace)
+            }
+            Self::BrewingStand(_) => Some(ContainerKind::BrewingStand),
+            Self::Campfire(_) => Some(ContainerKind::Campfire),
+            Self::ChiseledBookshelf(_) => Some(ContainerKind::ChiseledBookshelf),
+            _ => None,
+        }
+    }
+
+    /// The items in the block entity's slots
+    pub fn items(&self) -> Option<&[ItemWithSlot]> {
+        match self {
+            Self::Barrel(container)
+            | Self::Chest(container)
+            | Self::Dispenser(container)
+            | Self::Dropper(container)
+            | Self::ShulkerBox(container)
+            | Self::TrappedChest(container) => Some(container.container.items()),
+            Self::Hopper(hopper) => Some(hopper.container.container.items()),
+            Self::Furnace(furnace) | Self::BlastFurnace(furnace) | Self::Smoker(furnace) => {
+                Some(&furnace.items)
+            }
+            Self::BrewingStand(brewing_stand) => Some(&brewing_stand.items),
+            Self::Campfire(campfire) => Some(&campfire.items),
+            Self::Crafter(crafter) => Some(crafter.container.container.items()),
+            Self::ChiseledBookshelf(bookshelf) => Some(&bookshelf.items),
+            _ => None,
+        }
+    }
+
+    pub fn items_mut(&mut self) -> Option<&mut Vec<ItemWithSlot>> {
+        match self {
+            Self::Barrel(container)
+            | Self::Chest(container)
+            | Self::Dispenser(container)
+            | Self::Dropper(container)
+            | Self::ShulkerBox(container)
+            | Self::TrappedChest(container) => Some(container.container.items_mut()),
+            Self::Hopper(hopper) => Some(hopper.container.container.items_mut()),
+            Self::Furnace(furnace) | Self::BlastFurnace(furnace) | Self::Smoker(furnace) => {
+                Some(&mut furnace.items)
+            }
+            Self::BrewingStand(brewing_stand) => Some(&mut brewing_stand.items),
+            Self::Campfire(campfire) => Some(&mut campfire.items),
+            Self::Crafter(crafter) => Some(crafter.container.container.items_mut()),
+            Self::ChiseledBookshelf(bookshelf) => Some(&mut bookshelf.items),
+            _ => None,
+        }
+    }
+
+    /// Checks the items in the block entity's slots
+    pub fn validate_items(&self) -> Result<(), ItemError> {
+        match (self.container_kind(), self.items()) {
+            (Some(kind), Some(items)) => kind.validate(items),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Chests, barrels, shulker boxes, dispensers and droppers.
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct ContainerBlock {
+    custom_name: Option<TextComponent>,
+    #[serde(flatten)]
+    container: Container,
+    // An item predicate
+    #[serde(rename = "lock")]
+    lock: Option<fastnbt::Value>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct Hopper {
+    #[serde(flatten)]
+    container: ContainerBlock,
+    transfer_cooldown: i32,
+}
+
+#[serde_as]
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Sign {
+    front_text: SignText,
+    back_text: SignText,
+    #[serde(default)]
+    #[serde_as(as = "FromInto<OneOf<bool, i8>>")]
+    is_waxed: bool,
+}
+
+#[serde_as]
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SignText {
+    #[serde(default)]
+    #[serde_as(as = "FromInto<OneOf<bool, i8>>")]
+    has_glowing_text: bool,
+    #[serde(default = "default_sign_color")]
+    color: String,
+    messages: [TextComponent; 4],
+    filtered_messages: Option<[TextComponent; 4]>,
+}
+
+fn default_sign_color() -> String {
+    "black".to_string()
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Banner {
+    #[serde(rename = "CustomName")]
+    custom_name: Option<TextComponent>,
+    #[serde(default)]
+    patterns: Vec<BannerPattern>,
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Beacon {
+    #[serde(rename = "CustomName")]
+    custom_name: Option<TextComponent>,
+    primary_effect: Option<Identifier>,
+    secondary_effect: Option<Identifier>,
+    lock: Option<fastnbt::Value>,
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Beehive {
+    #[serde(default)]
+    bees: Vec<Bee>,
+    flower_pos: Option<[i32; 3]>,
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct Spawner {
+    delay: i16,
+    max_nearby_entities: Option<i16>,
+    max_spawn_delay: Option<i16>,
+    min_spawn_delay: Option<i16>,
+    required_player_range: Option<i16>,
+    spawn_count: Option<i16>,
+    // Boxed for indirection
+    spawn_data: Option<Box<SpawnData>>,
+    #[serde(default)]
+    spawn_potentials: Vec<SpawnPotential>,
+    spawn_range: Option<i16>,
+}
+
+#[serde_as]
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct CommandBlock {
+    custom_name: Option<TextComponent>,
+    command: String,
+    last_output: Option<TextComponent>,
+    success_count: i32,
+    #[serde(default = "default_true")]
+    #[serde_as(as = "FromInto<OneOf<bool, i8>>")]
+    track_output: bool,
+    #[serde(rename = "auto", default)]
+    #[serde_as(as = "FromInto<OneOf<bool, i8>>")]
+    auto: bool,
+    #[serde(rename = "powered", default)]
+    #[serde_as(as = "FromInto<OneOf<bool, i8>>")]
+    powered: bool,
+    #[serde(rename = "conditionMet", default)]
+    #[serde_as(as = "FromInto<OneOf<bool, i8>>")]
+    condition_met: bool,
+    #[serde(default = "default_true")]
+    #[serde_as(as = "FromInto<OneOf<bool, i8>>")]
+    update_last_execution: bool,
+    last_execution: Option<i64>,
+}
+
+const fn default_true() -> bool {
+    true
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Skull {
+    custom_name: Option<TextComponent>,
+    note_block_sound: Option<Identifier>,
+    profile: Option<Profile>,
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct Lectern {
+    book: Option<Item>,
+    #[serde(default)]
+    page: i32,
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Jukebox {
+    #[serde(rename = "RecordItem")]
+    record_item: Option<Item>,
+    #[serde(default)]
+    ticks_since_song_started: i64,
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct Campfire {
+    #[serde(default)]
+    items: Vec<ItemWithSlot>,
+    cooking_times: Option<IntArray>,
+    cooking_total_times: Option<IntArray>,
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct BrewingStand {
+    custom_name: Option<TextComponent>,
+    #[serde(default)]
+    items: Vec<ItemWithSlot>,
+    brew_time: i16,
+    fuel: i8,
+    #[serde(rename = "lock")]
+    lock: Option<fastnbt::Value>,
+}
+
+/// Furnaces, blast furnaces and smokers.
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Furnace {
+    #[serde(rename = "CustomName")]
+    custom_name: Option<TextComponent>,
+    #[serde(rename = "Items", default)]
+    items: Vec<ItemWithSlot>,
+    #[serde(default)]
+    lit_time_remaining: i16,
+    #[serde(default)]
+    lit_total_time: i16,
+    #[serde(default)]
+    cooking_time_spent: i16,
+    #[serde(default)]
+    cooking_total_time: i16,
+    /// How many times each recipe has been used since experience was last handed out
+    #[serde(rename = "RecipesUsed", default)]
+    recipes_used: HashMap<Identifier, i32>,
+    lock: Option<fastnbt::Value>,
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+pub struct DecoratedPot {
+    /// The back, left, right and front faces, as item ids
+    #[serde(default)]
+    sherds: Vec<Identifier>,
+    item: Option<Item>,
+    #[serde(rename = "LootTable")]
+    loot_table: Option<Identifier>,
+    #[serde(rename = "LootTableSeed")]
+    loot_table_seed: Option<i64>,
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+pub struct TrialSpawner {
+    required_player_range: Option<i32>,
+    target_cooldown_length: Option<i32>,
+    normal_config: Option<TrialSpawnerConfig>,
+    ominous_config: Option<TrialSpawnerConfig>,
+    #[serde(default)]
+    registered_players: Vec<McUuid>,
+    #[serde(default)]
+    current_mobs: Vec<McUuid>,
+    #[serde(default)]
+    cooldown_ends_at: i64,
+    #[serde(default)]
+    next_mob_spawns_at: i64,
+    #[serde(default)]
+    total_mobs_spawned: i32,
+    spawn_data: Option<Box<SpawnData>>,
+    ejecting_loot_table: Option<Identifier>,
+}
+
+/// Either a config given inline or the id of one from the `trial_spawner` registry.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum TrialSpawnerConfig {
+    Inline(Box<TrialSpawnerConfigInner>),
+    Reference(Identifier),
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+pub struct TrialSpawnerConfigInner {
+    spawn_range: Option<i32>,
+    total_mobs: Option<f32>,
+    simultaneous_mobs: Option<f32>,
+    total_mobs_added_per_player: Option<f32>,
+    simultaneous_mobs_added_per_player: Option<f32>,
+    ticks_between_spawn: Option<i32>,
+    #[serde(default)]
+    spawn_potentials: Vec<SpawnPotential>,
+    #[serde(default)]
+    loot_tables_to_eject: Vec<WeightedLootTable>,
+    items_to_drop_when_ominous: Option<Identifier>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct WeightedLootTable {
+    data: Identifier,
+    weight: i32,
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Vault {
+    config: Option<VaultConfig>,
+    server_data: Option<VaultServerData>,
+    shared_data: Option<VaultSharedData>,
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+pub struct VaultConfig {
+    loot_table: Option<Identifier>,
+    override_loot_table_to_display: Option<Identifier>,
+    activation_range: Option<f64>,
+    deactivation_range: Option<f64>,
+    key_item: Option<Item>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct VaultServerData {
+    #[serde(default)]
+    rewarded_players: Vec<McUuid>,
+    #[serde(default)]
+    state_updating_resumes_at: i64,
+    #[serde(default)]
+    items_to_eject: Vec<Item>,
+    #[serde(default)]
+    total_ejections_needed: i32,
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+pub struct VaultSharedData {
+    display_item: Option<Item>,
+    #[serde(default)]
+    connected_players: Vec<McUuid>,
+    #[serde(default)]
+    connected_particles_range: f64,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Comparator {
+    #[serde(rename = "OutputSignal", default)]
+    output_signal: i32,
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+pub struct EnchantingTable {
+    #[serde(rename = "CustomName")]
+    custom_name: Option<TextComponent>,
+}
+
+#[serde_as]
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+pub struct EndGateway {
+    #[serde(rename = "Age", default)]
+    age: i64,
+    #[serde(rename = "ExactTeleport", default)]
+    #[serde_as(as = "FromInto<OneOf<bool, i8>>")]
+    exact_teleport: bool,
+    exit_portal: Option<[i32; 3]>,
+}
+
+/// A block being moved by a piston.
+#[serde_as]
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Piston {
+    #[serde(rename = "blockState")]
+    block_state: Block,
+    /// The direction being moved in, as a 3D data value
+    facing: i32,
+    progress: f32,
+    #[serde_as(as = "FromInto<OneOf<bool, i8>>")]
+    extending: bool,
+    /// Whether this is the piston head rather than a block it pushes
+    #[serde_as(as = "FromInto<OneOf<bool, i8>>")]
+    source: bool,
+}
+
+#[serde_as]
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct StructureBlock {
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    author: String,
+    #[serde(default)]
+    metadata: String,
+    #[serde(default)]
+    pos_x: i32,
+    #[serde(default)]
+    pos_y: i32,
+    #[serde(default)]
+    pos_z: i32,
+    #[serde(default)]
+    size_x: i32,
+    #[serde(default)]
+    size_y: i32,
+    #[serde(default)]
+    size_z: i32,
+    #[serde(default = "default_rotation")]
+    rotation: String,
+    #[serde(default = "default_mirror")]
+    mirror: String,
+    #[serde(default = "default_mode")]
+    mode: String,
+    #[serde(default)]
+    #[serde_as(as = "FromInto<OneOf<bool, i8>>")]
+    ignore_entities: bool,
+    #[serde(default)]
+    #[serde_as(as = "FromInto<OneOf<bool, i8>>")]
+    strict: bool,
+    #[serde(default)]
+    #[serde_as(as = "FromInto<OneOf<bool, i8>>")]
+    powered: bool,
+    #[serde(rename = "showair", default)]
+    #[serde_as(as = "FromInto<OneOf<bool, i8>>")]
+    show_air: bool,
+    #[serde(rename = "showboundingbox", default)]
+    #[serde_as(as = "FromInto<OneOf<bool, i8>>")]
+    show_bounding_box: bool,
+    #[serde(default = "default_integrity")]
+    integrity: f32,
+    #[serde(default)]
+    seed: i64,
+}
+
+fn default_rotation() -> String {
+    "NONE".to_string()
+}
+
+fn default_mirror() -> String {
+    "NONE".to_string()
+}
+
+fn default_mode() -> String {
+    "DATA".to_string()
+}
+
+const fn default_integrity() -> f32 {
+    1.0
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Jigsaw {
+    name: Identifier,
+    target: Identifier,
+    pool: Identifier,
+    final_state: String,
+    /// `rollable` or `aligned`
+    joint: String,
+    #[serde(default)]
+    placement_priority: i32,
+    #[serde(default)]
+    selection_priority: i32,
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Conduit {
+    /// The hostile mob being attacked
+    #[serde(rename = "Target")]
+    target: Option<McUuid>,
+}
+
+/// Sculk sensors and calibrated sculk sensors.
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SculkSensor {
+    #[serde(default)]
+    last_vibration_frequency: i32,
+    /// The vibration being listened to, if any
+    listener: Option<Value>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SculkCatalyst {
+    /// The charges spreading sculk from the catalyst
+    #[serde(default)]
+    cursors: Vec<Value>,
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SculkShrieker {
+    #[serde(default)]
+    warning_level: i32,
+    listener: Option<Value>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ChiseledBookshelf {
+    #[serde(rename = "Items", default)]
+    items: Vec<ItemWithSlot>,
+    /// The slot last taken from or put into, or -1
+    #[serde(default = "default_last_interacted_slot")]
+    last_interacted_slot: i32,
+}
+
+const fn default_last_interacted_slot() -> i32 {
+    -1
+}
+
+/// Suspicious sand and gravel.
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+pub struct BrushableBlock {
+    #[serde(rename = "LootTable")]
+    loot_table: Option<Identifier>,
+    #[serde(rename = "LootTableSeed")]
+    loot_table_seed: Option<i64>,
+    /// The face being brushed, as a 3D data value
+    hit_direction: Option<i32>,
+    item: Option<Item>,
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Crafter {
+    #[serde(flatten)]
+    container: ContainerBlock,
+    #[serde(default)]
+    crafting_ticks_remaining: i32,
+    /// 1 while powered, saved as an int rather than a boolean
+    #[serde(default)]
+    triggered: i32,
+    /// The slots toggled off so nothing can be put in them
+    disabled_slots: Option<IntArray>,
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+pub struct CreakingHeart {
+    /// The creaking the heart spawned, if it's still around
+    creaking: Option<McUuid>,
+}
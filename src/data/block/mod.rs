@@ -0,0 +1,129 @@
+//! Block states, generated by `build.rs` from the vanilla block report in `reports/blocks.json`.
+
+pub mod entity;
+
+pub use entity::BlockEntity;
+
+use crate::{
+    data::Identifier,
+    types::{AsVarInt, Codec, Type},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    io::{Read, Write},
+};
+use strum::FromRepr;
+
+/// The value types of block properties that aren't a boolean or a number. The values most blocks
+/// use for a property get a type named after it, e.g. [`property::Facing`], and any others one
+/// named after the first block with them as well, e.g. [`property::DispenserFacing`].
+pub mod property {
+    include!(concat!(env!("OUT_DIR"), "/block_properties.rs"));
+}
+
+include!(concat!(env!("OUT_DIR"), "/blocks.rs"));
+
+/// How a block state is written in NBT, with every property value as a string.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct BlockState {
+    name: Identifier,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    properties: HashMap<String, String>,
+}
+
+impl TryFrom<BlockState> for Block {
+    type Error = &'static str;
+
+    /// Properties that are left out take their default value, like in vanilla.
+    fn try_from(value: BlockState) -> Result<Self, Self::Error> {
+        let mut block = Self::default_state(&value.name.to_string()).ok_or("Unknown block")?;
+        for (name, value) in &value.properties {
+            block.set_property(name, value)?;
+        }
+
+        Ok(block)
+    }
+}
+
+impl From<Block> for BlockState {
+    fn from(value: Block) -> Self {
+        Self {
+            name: value.name().parse().unwrap(),
+            properties: value
+                .properties()
+                .into_iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+        }
+    }
+}
+
+impl Block {
+    /// Whether this is one of the air blocks, which don't count towards a section's blocks
+    pub fn is_air(&self) -> bool {
+        matches!(
+            self.name(),
+            "minecraft:air" | "minecraft:cave_air" | "minecraft:void_air"
+        )
+    }
+}
+
+impl std::fmt::Display for Block {
+    /// Formats the state the way commands take it, e.g. `minecraft:chest[facing=east,type=single]`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())?;
+        let properties = self.properties();
+        if !properties.is_empty() {
+            let properties: Vec<_> = properties
+                .into_iter()
+                .map(|(name, value)| format!("{name}={value}"))
+                .collect();
+            write!(f, "[{}]", properties.join(","))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Sent as its global state id.
+impl Type for Block {
+    fn write(&self, writer: impl Write) -> std::io::Result<()> {
+        AsVarInt::write(&(self.state_id() as i32), writer)
+    }
+
+    fn read(reader: impl Read) -> std::io::Result<Self> {
+        u32::try_from(AsVarInt::read(reader)?)
+            .ok()
+            .and_then(Self::from_state_id)
+            .ok_or_else(|| {
+                std::io::Error::new(std::io::ErrorKind::InvalidData, "Invalid block state id")
+            })
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, FromRepr)]
+#[serde(try_from = "i8", into = "i8")]
+pub enum Face {
+    Top,
+    Bottom,
+    North,
+    South,
+    West,
+    East,
+}
+
+impl TryFrom<i8> for Face {
+    type Error = &'static str;
+
+    fn try_from(value: i8) -> Result<Self, Self::Error> {
+        Self::from_repr(value as usize).ok_or("Invalid attach face")
+    }
+}
+
+impl From<Face> for i8 {
+    fn from(value: Face) -> Self {
+        value as i8
+    }
+}
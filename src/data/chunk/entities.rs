@@ -0,0 +1,72 @@
+use crate::data::{DATA_VERSION, entity::Entity};
+use fastnbt::{IntArray, Value};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// The entities of a chunk, saved in `entities/r.<x>.<z>.mca` apart from its terrain.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct EntityChunk {
+    #[serde(rename = "DataVersion")]
+    data_version: i32,
+    #[serde(rename = "Position")]
+    position: ChunkPos,
+    #[serde(rename = "Entities")]
+    entities: Vec<Entity>,
+    /// Fields that aren't modelled, kept as they were saved
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+impl EntityChunk {
+    pub fn new(x: i32, z: i32, entities: Vec<Entity>) -> Self {
+        Self {
+            data_version: DATA_VERSION,
+            position: ChunkPos(x, z),
+            entities,
+            extra: HashMap::new(),
+        }
+    }
+
+    /// The chunk's position in chunks
+    pub const fn position(&self) -> (i32, i32) {
+        (self.position.0, self.position.1)
+    }
+
+    pub const fn data_version(&self) -> i32 {
+        self.data_version
+    }
+
+    pub fn entities(&self) -> &[Entity] {
+        &self.entities
+    }
+
+    pub fn entities_mut(&mut self) -> &mut Vec<Entity> {
+        &mut self.entities
+    }
+
+    pub fn into_entities(self) -> Vec<Entity> {
+        self.entities
+    }
+}
+
+/// An int array of x and z.
+#[derive(Serialize, Deserialize, Clone, Copy)]
+#[serde(try_from = "IntArray", into = "IntArray")]
+struct ChunkPos(i32, i32);
+
+impl TryFrom<IntArray> for ChunkPos {
+    type Error = &'static str;
+
+    fn try_from(value: IntArray) -> Result<Self, Self::Error> {
+        match *value {
+            [x, z] => Ok(Self(x, z)),
+            _ => Err("Invalid chunk position"),
+        }
+    }
+}
+
+impl From<ChunkPos> for IntArray {
+    fn from(value: ChunkPos) -> Self {
+        Self::new(vec![value.0, value.1])
+    }
+}
@@ -0,0 +1,536 @@
+pub mod entities;
+pub mod palette;
+
+pub use entities::EntityChunk;
+pub use palette::PalettedContainer;
+
+use crate::{
+    OneOf,
+    data::{
+        DATA_VERSION, Identifier,
+        block::{Block, BlockEntity, STATE_COUNT},
+        registry::{Biome, DimensionType, SyncedRegistry},
+    },
+    protocol::play::{ChunkBlockEntity, ChunkDataAndUpdateLight, LightData},
+    types::{AsVarInt, Codec, Type},
+};
+use fastnbt::{ByteArray, LongArray, Value};
+use palette::ceil_log2;
+use serde::{Deserialize, Serialize};
+use serde_with::{FromInto, serde_as, skip_serializing_none};
+use std::{
+    collections::HashMap,
+    io::{Read, Write},
+};
+
+/// A chunk as saved in a region file.
+///
+/// Blocks are addressed by their x and z within the chunk and their absolute y.
+#[serde_as]
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Chunk {
+    #[serde(rename = "DataVersion")]
+    data_version: i32,
+    #[serde(rename = "xPos")]
+    x_pos: i32,
+    /// The lowest section
+    #[serde(rename = "yPos")]
+    y_pos: i32,
+    #[serde(rename = "zPos")]
+    z_pos: i32,
+    #[serde(rename = "Status")]
+    status: ChunkStatus,
+    #[serde(rename = "LastUpdate")]
+    last_update: i64,
+    #[serde(rename = "InhabitedTime")]
+    inhabited_time: i64,
+    #[serde(rename = "isLightOn")]
+    #[serde_as(as = "FromInto<OneOf<bool, i8>>")]
+    #[serde(default)]
+    is_light_on: bool,
+    #[serde(default)]
+    sections: Vec<ChunkSection>,
+    #[serde(default)]
+    block_entities: Vec<BlockEntity>,
+    #[serde(rename = "Heightmaps")]
+    #[serde(default)]
+    heightmaps: Heightmaps,
+    #[serde(default)]
+    block_ticks: Vec<ScheduledTick>,
+    #[serde(default)]
+    fluid_ticks: Vec<ScheduledTick>,
+    #[serde(rename = "PostProcessing")]
+    #[serde(default)]
+    post_processing: Vec<Vec<i16>>,
+    structures: Option<Value>,
+    blending_data: Option<Value>,
+    below_zero_retrogen: Option<Value>,
+    #[serde(rename = "UpgradeData")]
+    upgrade_data: Option<Value>,
+    /// Fields that aren't modelled, kept as they were saved
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+impl Chunk {
+    /// An empty, fully generated chunk of air and plains, spanning the dimension's height.
+    pub fn new(x: i32, z: i32, dimension: &DimensionType) -> Self {
+        let min_section = dimension.min_y >> 4;
+        Self {
+            data_version: DATA_VERSION,
+            x_pos: x,
+            y_pos: min_section,
+            z_pos: z,
+            status: ChunkStatus::Full,
+            last_update: 0,
+            inhabited_time: 0,
+            is_light_on: false,
+            sections: (min_section..min_section + dimension.height / 16)
+                .map(|y| ChunkSection::new(y as i8))
+                .collect(),
+            block_entities: vec![],
+            heightmaps: Heightmaps::default(),
+            block_ticks: vec![],
+            fluid_ticks: vec![],
+            post_processing: vec![],
+            structures: None,
+            blending_data: None,
+            below_zero_retrogen: None,
+            upgrade_data: None,
+            extra: HashMap::new(),
+        }
+    }
+
+    /// The chunk's position in chunks
+    pub const fn position(&self) -> (i32, i32) {
+        (self.x_pos, self.z_pos)
+    }
+
+    pub const fn data_version(&self) -> i32 {
+        self.data_version
+    }
+
+    pub const fn status(&self) -> ChunkStatus {
+        self.status
+    }
+
+    pub fn set_status(&mut self, status: ChunkStatus) {
+        self.status = status;
+    }
+
+    /// The section `y` sections up from y = 0
+    pub fn section(&self, y: i32) -> Option<&ChunkSection> {
+        self.sections.iter().find(|section| section.y as i32 == y)
+    }
+
+    pub fn section_mut(&mut self, y: i32) -> Option<&mut ChunkSection> {
+        self.sections
+            .iter_mut()
+            .find(|section| section.y as i32 == y)
+    }
+
+    pub fn sections(&self) -> &[ChunkSection] {
+        &self.sections
+    }
+
+    /// Air if the section isn't there.
+    pub fn block(&self, x: i32, y: i32, z: i32) -> Block {
+        self.section(y >> 4)
+            .map_or(Block::Air, |section| section.block(x, y, z))
+    }
+
+    /// Sets a block, adding its section if needed, and returns the block it replaced.
+    pub fn set_block(&mut self, x: i32, y: i32, z: i32, block: Block) -> Block {
+        self.section_or_insert(y >> 4).set_block(x, y, z, block)
+    }
+
+    /// The biome at a block, as biomes are stored for every 4 by 4 by 4 cell
+    pub fn biome(&self, x: i32, y: i32, z: i32) -> Option<&Identifier> {
+        self.section(y >> 4)?.biome(x, y, z)
+    }
+
+    pub fn set_biome(&mut self, x: i32, y: i32, z: i32, biome: Identifier) -> Option<Identifier> {
+        self.section_or_insert(y >> 4).set_biome(x, y, z, biome)
+    }
+
+    pub fn block_entities(&self) -> &[BlockEntity] {
+        &self.block_entities
+    }
+
+    pub fn block_entities_mut(&mut self) -> &mut Vec<BlockEntity> {
+        &mut self.block_entities
+    }
+
+    pub fn heightmaps(&self) -> &Heightmaps {
+        &self.heightmaps
+    }
+
+    pub fn heightmaps_mut(&mut self) -> &mut Heightmaps {
+        &mut self.heightmaps
+    }
+
+    pub fn block_ticks(&self) -> &[ScheduledTick] {
+        &self.block_ticks
+    }
+
+    pub fn fluid_ticks(&self) -> &[ScheduledTick] {
+        &self.fluid_ticks
+    }
+
+    /// Builds the packet that sends this chunk and its light to a client in `dimension`, with
+    /// biomes sent by their id in `biomes`.
+    pub fn to_packet(
+        &self,
+        dimension: &DimensionType,
+        biomes: &SyncedRegistry<Biome>,
+    ) -> std::io::Result<ChunkDataAndUpdateLight> {
+        let min_section = dimension.min_y >> 4;
+        let max_section = min_section + dimension.height / 16;
+        let block_bits = ceil_log2(STATE_COUNT as usize);
+        let biome_bits = ceil_log2(biomes.entries().len());
+        let air = PalettedContainer::new(Block::Air);
+        let plains = PalettedContainer::new(Identifier::minecraft("plains"));
+
+        let mut data = vec![];
+        for y in min_section..max_section {
+            let section = self.section(y);
+            let blocks = section
+                .and_then(|section| section.block_states.as_ref())
+                .unwrap_or(&air);
+            (blocks.count(|block| !block.is_air()) as i16).write(&mut data)?;
+            blocks.write_network(&mut data, block_bits, |block| Ok(block.state_id() as i32))?;
+            section
+                .and_then(|section| section.biomes.as_ref())
+                .unwrap_or(&plains)
+                .write_network(&mut data, biome_bits, |biome| {
+                    biomes.network_id(biome).ok_or_else(|| {
+                        std::io::Error::new(
+                            std::io::ErrorKind::InvalidInput,
+                            format!("Unknown biome {biome}"),
+                        )
+                    })
+                })?;
+        }
+
+        let block_entities = self
+            .block_entities
+            .iter()
+            .filter_map(|block_entity| {
+                let (x, y, z) = block_entity.position()?;
+                // Clients can't be told about block entities they don't know
+                let kind = block_entity.kind().network_id()?;
+                Some(
+                    block_entity
+                        .network_nbt()
+                        .map(|data| ChunkBlockEntity {
+                            packed_xz: (((x & 15) << 4) | (z & 15)) as u8,
+                            y: y as i16,
+                            kind,
+                            data,
+                        })
+                        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e)),
+                )
+            })
+            .collect::<std::io::Result<_>>()?;
+
+        // Light goes one section further both ways than blocks do
+        let mut light = LightData::default();
+        for (i, y) in (min_section - 1..=max_section).enumerate() {
+            let section = self.section(y);
+            for (array, mask, empty_mask, arrays) in [
+                (
+                    section.and_then(|section| section.sky_light.as_ref()),
+                    &mut light.sky_light_mask,
+                    &mut light.empty_sky_light_mask,
+                    &mut light.sky_light,
+                ),
+                (
+                    section.and_then(|section| section.block_light.as_ref()),
+                    &mut light.block_light_mask,
+                    &mut light.empty_block_light_mask,
+                    &mut light.block_light,
+                ),
+            ] {
+                match array {
+                    Some(array) if array.is_empty() => set_bit(empty_mask, i),
+                    Some(array) => {
+                        set_bit(mask, i);
+                        arrays.push(array.clone());
+                    }
+                    None => {}
+                }
+            }
+        }
+
+        Ok(ChunkDataAndUpdateLight {
+            x: self.x_pos,
+            z: self.z_pos,
+            heightmaps: Box::new(self.heightmaps.for_client()),
+            data,
+            block_entities,
+            light: Box::new(light),
+        })
+    }
+
+    fn section_or_insert(&mut self, y: i32) -> &mut ChunkSection {
+        // Kept sorted from the bottom up, like vanilla saves them
+        let i = match self
+            .sections
+            .binary_search_by_key(&y, |section| section.y as i32)
+        {
+            Ok(i) => i,
+            Err(i) => {
+                self.sections.insert(i, ChunkSection::new(y as i8));
+                i
+            }
+        };
+
+        &mut self.sections[i]
+    }
+}
+
+/// How far along generation a chunk is.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChunkStatus {
+    #[serde(rename = "minecraft:empty", alias = "empty")]
+    Empty,
+    #[serde(rename = "minecraft:structure_starts", alias = "structure_starts")]
+    StructureStarts,
+    #[serde(
+        rename = "minecraft:structure_references",
+        alias = "structure_references"
+    )]
+    StructureReferences,
+    #[serde(rename = "minecraft:biomes", alias = "biomes")]
+    Biomes,
+    #[serde(rename = "minecraft:noise", alias = "noise")]
+    Noise,
+    #[serde(rename = "minecraft:surface", alias = "surface")]
+    Surface,
+    #[serde(rename = "minecraft:carvers", alias = "carvers")]
+    Carvers,
+    #[serde(rename = "minecraft:features", alias = "features")]
+    Features,
+    #[serde(rename = "minecraft:initialize_light", alias = "initialize_light")]
+    InitializeLight,
+    #[serde(rename = "minecraft:light", alias = "light")]
+    Light,
+    #[serde(rename = "minecraft:spawn", alias = "spawn")]
+    Spawn,
+    #[serde(rename = "minecraft:full", alias = "full")]
+    Full,
+}
+
+/// Sixteen blocks of a chunk's height. The sections just outside the dimension only hold light.
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ChunkSection {
+    #[serde(rename = "Y")]
+    y: i8,
+    block_states: Option<PalettedContainer<Block>>,
+    biomes: Option<PalettedContainer<Identifier>>,
+    #[serde(rename = "BlockLight")]
+    block_light: Option<LightArray>,
+    #[serde(rename = "SkyLight")]
+    sky_light: Option<LightArray>,
+}
+
+impl ChunkSection {
+    /// A section of air and plains, without any light
+    pub fn new(y: i8) -> Self {
+        Self {
+            y,
+            block_states: Some(PalettedContainer::new(Block::Air)),
+            biomes: Some(PalettedContainer::new(Identifier::minecraft("plains"))),
+            block_light: None,
+            sky_light: None,
+        }
+    }
+
+    pub const fn y(&self) -> i8 {
+        self.y
+    }
+
+    /// Only the low four bits of each coordinate are used.
+    pub fn block(&self, x: i32, y: i32, z: i32) -> Block {
+        self.block_states
+            .as_ref()
+            .map_or(Block::Air, |blocks| *blocks.get(block_index(x, y, z)))
+    }
+
+    pub fn set_block(&mut self, x: i32, y: i32, z: i32, block: Block) -> Block {
+        self.block_states
+            .get_or_insert_with(|| PalettedContainer::new(Block::Air))
+            .set(block_index(x, y, z), block)
+    }
+
+    pub fn block_states(&self) -> Option<&PalettedContainer<Block>> {
+        self.block_states.as_ref()
+    }
+
+    /// Takes block coordinates, of which only the cell's are used.
+    pub fn biome(&self, x: i32, y: i32, z: i32) -> Option<&Identifier> {
+        Some(self.biomes.as_ref()?.get(biome_index(x, y, z)))
+    }
+
+    /// Sets a biome, returning the one it replaced unless the section had none.
+    pub fn set_biome(&mut self, x: i32, y: i32, z: i32, biome: Identifier) -> Option<Identifier> {
+        match &mut self.biomes {
+            Some(biomes) => Some(biomes.set(biome_index(x, y, z), biome)),
+            None => {
+                let mut biomes = PalettedContainer::new(Identifier::minecraft("plains"));
+                biomes.set(biome_index(x, y, z), biome);
+                self.biomes = Some(biomes);
+                None
+            }
+        }
+    }
+
+    pub fn biomes(&self) -> Option<&PalettedContainer<Identifier>> {
+        self.biomes.as_ref()
+    }
+
+    pub fn block_light(&self) -> Option<&LightArray> {
+        self.block_light.as_ref()
+    }
+
+    pub fn block_light_mut(&mut self) -> &mut Option<LightArray> {
+        &mut self.block_light
+    }
+
+    pub fn sky_light(&self) -> Option<&LightArray> {
+        self.sky_light.as_ref()
+    }
+
+    pub fn sky_light_mut(&mut self) -> &mut Option<LightArray> {
+        &mut self.sky_light
+    }
+}
+
+/// A light level from 0 to 15 for each block of a section, two to a byte.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(try_from = "ByteArray", into = "ByteArray")]
+pub struct LightArray(Box<[u8; LightArray::LENGTH]>);
+
+impl LightArray {
+    const LENGTH: usize = 16 * 16 * 16 / 2;
+
+    /// Every block at `level`
+    pub fn new(level: u8) -> Self {
+        Self(Box::new([(level & 15) * 0x11; Self::LENGTH]))
+    }
+
+    /// Only the low four bits of each coordinate are used.
+    pub fn get(&self, x: i32, y: i32, z: i32) -> u8 {
+        let i = block_index(x, y, z);
+        (self.0[i / 2] >> (i % 2 * 4)) & 15
+    }
+
+    pub fn set(&mut self, x: i32, y: i32, z: i32, level: u8) {
+        let i = block_index(x, y, z);
+        let shift = i % 2 * 4;
+        self.0[i / 2] = (self.0[i / 2] & !(15 << shift)) | ((level & 15) << shift);
+    }
+
+    /// Whether every level is 0
+    pub fn is_empty(&self) -> bool {
+        self.0.iter().all(|&byte| byte == 0)
+    }
+}
+
+impl TryFrom<ByteArray> for LightArray {
+    type Error = &'static str;
+
+    fn try_from(value: ByteArray) -> Result<Self, Self::Error> {
+        let bytes: Box<[u8]> = value.iter().map(|&byte| byte as u8).collect();
+        bytes
+            .try_into()
+            .map(Self)
+            .map_err(|_| "Light arrays are 2048 bytes")
+    }
+}
+
+impl From<LightArray> for ByteArray {
+    fn from(value: LightArray) -> Self {
+        Self::new(value.0.iter().map(|&byte| byte as i8).collect())
+    }
+}
+
+/// Sent prefixed with its length, which is always the same.
+impl Type for LightArray {
+    fn write(&self, mut writer: impl Write) -> std::io::Result<()> {
+        AsVarInt::write(&(Self::LENGTH as i32), &mut writer)?;
+        writer.write_all(self.0.as_slice())
+    }
+
+    fn read(mut reader: impl Read) -> std::io::Result<Self> {
+        if AsVarInt::read(&mut reader)? != Self::LENGTH as i32 {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "Light arrays are 2048 bytes",
+            ));
+        }
+        let mut array = Box::new([0; Self::LENGTH]);
+        reader.read_exact(array.as_mut_slice())?;
+
+        Ok(Self(array))
+    }
+}
+
+/// The highest blocks of each column by different criteria, packed like paletted containers.
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub struct Heightmaps {
+    pub world_surface_wg: Option<LongArray>,
+    pub world_surface: Option<LongArray>,
+    pub ocean_floor_wg: Option<LongArray>,
+    pub ocean_floor: Option<LongArray>,
+    pub motion_blocking: Option<LongArray>,
+    pub motion_blocking_no_leaves: Option<LongArray>,
+}
+
+impl Heightmaps {
+    /// Only the heightmaps clients use
+    fn for_client(&self) -> Self {
+        Self {
+            world_surface: self.world_surface.clone(),
+            motion_blocking: self.motion_blocking.clone(),
+            motion_blocking_no_leaves: self.motion_blocking_no_leaves.clone(),
+            ..Self::default()
+        }
+    }
+}
+
+/// A block or fluid update waiting to happen.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ScheduledTick {
+    /// The block or fluid
+    #[serde(rename = "i")]
+    pub id: Identifier,
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+    /// Ticks until it happens
+    #[serde(rename = "t")]
+    pub delay: i32,
+    /// Lower happens first among ticks on the same game tick
+    #[serde(rename = "p")]
+    pub priority: i32,
+}
+
+fn block_index(x: i32, y: i32, z: i32) -> usize {
+    (((y & 15) << 8) | ((z & 15) << 4) | (x & 15)) as usize
+}
+
+fn biome_index(x: i32, y: i32, z: i32) -> usize {
+    ((((y >> 2) & 3) << 4) | (((z >> 2) & 3) << 2) | ((x >> 2) & 3)) as usize
+}
+
+fn set_bit(words: &mut Vec<i64>, i: usize) {
+    if words.len() <= i / 64 {
+        words.resize(i / 64 + 1, 0);
+    }
+    words[i / 64] |= 1 << (i % 64);
+}
@@ -0,0 +1,371 @@
+use crate::{
+    data::{Identifier, block::Block},
+    types::{AsVarInt, Codec, Prefixed, Type},
+};
+use fastnbt::LongArray;
+use serde::{Deserialize, Serialize};
+use std::io::{Error, ErrorKind, Read, Write};
+
+/// Something stored in a [`PalettedContainer`]: block states, or biomes by id.
+pub trait Paletted: Clone + PartialEq {
+    /// How many entries a section holds
+    const ENTRIES: usize;
+    /// The fewest bits per entry an indirect palette uses
+    const MIN_BITS: u32;
+    /// The most bits per entry before the network form switches to global ids
+    const MAX_INDIRECT_BITS: u32;
+}
+
+impl Paletted for Block {
+    const ENTRIES: usize = 16 * 16 * 16;
+    const MIN_BITS: u32 = 4;
+    const MAX_INDIRECT_BITS: u32 = 8;
+}
+
+/// Biomes are stored by id, one for each 4 by 4 by 4 cell.
+impl Paletted for Identifier {
+    const ENTRIES: usize = 4 * 4 * 4;
+    const MIN_BITS: u32 = 1;
+    const MAX_INDIRECT_BITS: u32 = 3;
+}
+
+/// A section's worth of values, each an index into a palette of the distinct values present.
+///
+/// The palette grows as new values are set and drops values that are no longer used, so a
+/// section of a single value doesn't need any indices at all.
+#[derive(Clone, Debug)]
+pub struct PalettedContainer<T> {
+    palette: Vec<T>,
+    /// How many entries use each value of the palette
+    counts: Vec<u16>,
+    /// Left empty while the palette has a single value
+    indices: Vec<u16>,
+}
+
+impl<T: Paletted> PalettedContainer<T> {
+    /// A container holding `value` everywhere
+    pub fn new(value: T) -> Self {
+        Self {
+            palette: vec![value],
+            counts: vec![T::ENTRIES as u16],
+            indices: vec![],
+        }
+    }
+
+    /// # Panics
+    /// If `i` is out of range
+    pub fn get(&self, i: usize) -> &T {
+        &self.palette[self.index(i)]
+    }
+
+    /// Sets the `i`th entry, returning the value it replaced.
+    ///
+    /// # Panics
+    /// If `i` is out of range
+    pub fn set(&mut self, i: usize, value: T) -> T {
+        let old = self.index(i);
+        if self.palette[old] == value {
+            return value;
+        }
+
+        let new = match self.palette.iter().position(|entry| *entry == value) {
+            Some(new) => new,
+            None => {
+                self.palette.push(value);
+                self.counts.push(0);
+                self.palette.len() - 1
+            }
+        };
+        if self.indices.is_empty() {
+            self.indices = vec![0; T::ENTRIES];
+        }
+        self.indices[i] = new as u16;
+        self.counts[new] += 1;
+        self.counts[old] -= 1;
+
+        if self.counts[old] == 0 {
+            self.remove(old)
+        } else {
+            self.palette[old].clone()
+        }
+    }
+
+    pub fn fill(&mut self, value: T) {
+        *self = Self::new(value);
+    }
+
+    /// Every distinct value, in no particular order
+    pub fn palette(&self) -> &[T] {
+        &self.palette
+    }
+
+    /// How many entries hold a value matching `predicate`
+    pub fn count(&self, predicate: impl Fn(&T) -> bool) -> usize {
+        self.palette
+            .iter()
+            .zip(&self.counts)
+            .filter(|(value, _)| predicate(value))
+            .map(|(_, &count)| count as usize)
+            .sum()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        (0..T::ENTRIES).map(|i| self.get(i))
+    }
+
+    /// Writes the container in the network form: single valued, with its own palette, or with
+    /// the global ids given by `id` when the palette gets too big.
+    pub fn write_network(
+        &self,
+        mut writer: impl Write,
+        global_bits: u32,
+        id: impl Fn(&T) -> std::io::Result<i32>,
+    ) -> std::io::Result<()> {
+        if self.palette.len() == 1 {
+            0_u8.write(&mut writer)?;
+            AsVarInt::write(&id(&self.palette[0])?, &mut writer)?;
+            return AsVarInt::write(&0, writer);
+        }
+
+        let bits = self.bits();
+        let data = if bits <= T::MAX_INDIRECT_BITS {
+            (bits as u8).write(&mut writer)?;
+            let ids = self
+                .palette
+                .iter()
+                .map(|value| id(value).map(VarIntId))
+                .collect::<std::io::Result<Vec<_>>>()?;
+            Prefixed::write(&ids, &mut writer)?;
+            pack(self.indices.iter().map(|&i| i as u64), bits)
+        } else {
+            (global_bits as u8).write(&mut writer)?;
+            let ids = self
+                .palette
+                .iter()
+                .map(|value| Ok(id(value)? as u64))
+                .collect::<std::io::Result<Vec<_>>>()?;
+            pack(self.indices.iter().map(|&i| ids[i as usize]), global_bits)
+        };
+
+        Prefixed::write(&data, writer)
+    }
+
+    /// Reads a container written by [`Self::write_network`], looking up global ids with `value`.
+    pub fn read_network(
+        mut reader: impl Read,
+        global_bits: u32,
+        value: impl Fn(i32) -> std::io::Result<T>,
+    ) -> std::io::Result<Self> {
+        let bits = u8::read(&mut reader)? as u32;
+        if bits == 0 {
+            let single = value(AsVarInt::read(&mut reader)?)?;
+            // Nothing should follow, but skip it if anything does
+            let _: Vec<i64> = Prefixed::read(&mut reader)?;
+            return Ok(Self::new(single));
+        }
+
+        if bits <= T::MAX_INDIRECT_BITS {
+            let ids: Vec<VarIntId> = Prefixed::read(&mut reader)?;
+            let palette = ids
+                .into_iter()
+                .map(|VarIntId(id)| value(id))
+                .collect::<std::io::Result<Vec<_>>>()?;
+            let data: Vec<i64> = Prefixed::read(reader)?;
+            let indices = unpack(&data, bits.max(T::MIN_BITS), T::ENTRIES)
+                .ok_or_else(|| invalid("Invalid paletted container data"))?;
+            Self::from_indices(palette, indices).ok_or_else(|| invalid("Invalid palette index"))
+        } else {
+            let data: Vec<i64> = Prefixed::read(reader)?;
+            let ids = unpack(&data, global_bits, T::ENTRIES)
+                .ok_or_else(|| invalid("Invalid paletted container data"))?;
+
+            let mut palette = vec![];
+            let mut global = vec![];
+            let mut indices = Vec::with_capacity(T::ENTRIES);
+            for id in ids {
+                let i = match global.iter().position(|&other| other == id) {
+                    Some(i) => i,
+                    None => {
+                        palette.push(value(id as i32)?);
+                        global.push(id);
+                        global.len() - 1
+                    }
+                };
+                indices.push(i as u64);
+            }
+            Self::from_indices(palette, indices).ok_or_else(|| invalid("Invalid palette index"))
+        }
+    }
+
+    fn index(&self, i: usize) -> usize {
+        assert!(i < T::ENTRIES, "Index {i} out of range");
+        self.indices.get(i).map_or(0, |&index| index as usize)
+    }
+
+    /// Bits per entry for the palette as it is, in both the Anvil and network forms
+    fn bits(&self) -> u32 {
+        ceil_log2(self.palette.len()).max(T::MIN_BITS)
+    }
+
+    /// Swaps the last palette value into the place of the removed one.
+    fn remove(&mut self, removed: usize) -> T {
+        let last = self.palette.len() - 1;
+        let value = self.palette.swap_remove(removed);
+        self.counts.swap_remove(removed);
+        if removed != last {
+            for index in &mut self.indices {
+                if *index as usize == last {
+                    *index = removed as u16;
+                }
+            }
+        }
+        if self.palette.len() == 1 {
+            self.indices.clear();
+        }
+
+        value
+    }
+
+    /// Builds a container from palette indices, dropping palette values nothing uses.
+    fn from_indices(mut palette: Vec<T>, indices: Vec<u64>) -> Option<Self> {
+        let mut counts = vec![0_u16; palette.len()];
+        for &index in &indices {
+            *counts.get_mut(index as usize)? += 1;
+        }
+
+        let mut container = Self {
+            palette: vec![],
+            counts: vec![],
+            indices: indices.into_iter().map(|index| index as u16).collect(),
+        };
+        // Walked backwards so the indices being remapped are always past the ones removed
+        for i in (0..palette.len()).rev() {
+            if counts[i] == 0 {
+                palette.remove(i);
+                counts.remove(i);
+                for index in &mut container.indices {
+                    if *index as usize > i {
+                        *index -= 1;
+                    }
+                }
+            }
+        }
+        if palette.is_empty() {
+            return None;
+        }
+        if palette.len() == 1 {
+            container.indices.clear();
+        }
+        container.palette = palette;
+        container.counts = counts;
+
+        Some(container)
+    }
+}
+
+/// Equal when every entry is, whatever order the palettes are in.
+impl<T: Paletted> PartialEq for PalettedContainer<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct AnvilContainer<T> {
+    palette: Vec<T>,
+    data: Option<LongArray>,
+}
+
+/// The Anvil form: the palette, and the indices packed into longs unless there's a single value.
+impl<T: Paletted + Serialize> Serialize for PalettedContainer<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+
+        let mut state = serializer.serialize_struct("PalettedContainer", 2)?;
+        state.serialize_field("palette", &self.palette)?;
+        // Left out rather than `None`, which fastnbt can't write
+        if self.palette.len() > 1 {
+            let data = pack(self.indices.iter().map(|&i| i as u64), self.bits());
+            state.serialize_field("data", &LongArray::new(data))?;
+        }
+        state.end()
+    }
+}
+
+impl<'de, T: Paletted + Deserialize<'de>> Deserialize<'de> for PalettedContainer<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de::Error;
+
+        let AnvilContainer { mut palette, data } = AnvilContainer::<T>::deserialize(deserializer)?;
+        match (palette.len(), data) {
+            (0, _) => Err(D::Error::custom("Empty palette")),
+            (1, _) => Ok(Self::new(palette.remove(0))),
+            (_, None) => Err(D::Error::missing_field("data")),
+            (length, Some(data)) => {
+                let bits = ceil_log2(length).max(T::MIN_BITS);
+                let indices = unpack(&data, bits, T::ENTRIES)
+                    .ok_or_else(|| D::Error::custom("Invalid paletted container data"))?;
+                Self::from_indices(palette, indices)
+                    .ok_or_else(|| D::Error::custom("Invalid palette index"))
+            }
+        }
+    }
+}
+
+/// A palette entry's global id, as a VarInt.
+struct VarIntId(i32);
+
+impl Type for VarIntId {
+    fn write(&self, writer: impl Write) -> std::io::Result<()> {
+        AsVarInt::write(&self.0, writer)
+    }
+
+    fn read(reader: impl Read) -> std::io::Result<Self> {
+        AsVarInt::read(reader).map(Self)
+    }
+}
+
+/// Packs `bits` wide values into longs from the least significant bit up, without letting any
+/// value straddle two longs.
+pub(crate) fn pack(values: impl ExactSizeIterator<Item = u64>, bits: u32) -> Vec<i64> {
+    let per_long = (64 / bits) as usize;
+    let mut longs = vec![0_u64; values.len().div_ceil(per_long)];
+    for (i, value) in values.enumerate() {
+        longs[i / per_long] |= value << ((i % per_long) as u32 * bits);
+    }
+
+    longs.into_iter().map(|long| long as i64).collect()
+}
+
+/// The inverse of [`pack`], or `None` if there are too many or too few longs.
+pub(crate) fn unpack(longs: &[i64], bits: u32, length: usize) -> Option<Vec<u64>> {
+    let per_long = (64 / bits) as usize;
+    if longs.len() != length.div_ceil(per_long) {
+        return None;
+    }
+
+    let mask = (1 << bits) - 1;
+    Some(
+        (0..length)
+            .map(|i| (longs[i / per_long] as u64 >> ((i % per_long) as u32 * bits)) & mask)
+            .collect(),
+    )
+}
+
+pub(crate) const fn ceil_log2(n: usize) -> u32 {
+    if n <= 1 {
+        0
+    } else {
+        usize::BITS - (n - 1).leading_zeros()
+    }
+}
+
+fn invalid(message: &'static str) -> Error {
+    Error::new(ErrorKind::InvalidData, message)
+}
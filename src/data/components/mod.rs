@@ -0,0 +1,1136 @@
+mod network;
+
+use crate::{
+    OneOf,
+    data::{
+        Components, Identifier, Item, PositionInDimension,
+        block::BlockEntity,
+        entity::{
+            Entity,
+            mob::{PotionEffect, memories::EmptyCompound},
+        },
+        player::Profile,
+    },
+    text_component::TextComponent,
+    types::Type,
+};
+use derive_more::Deref;
+use fastnbt::{IntArray, Value};
+use serde::{Deserialize, Serialize};
+use serde_with::{FromInto, serde_as, skip_serializing_none};
+use std::collections::HashMap;
+
+/// The vanilla item components, as saved in 1.21.4. Those 1.21.5 saves differently are also read
+/// in their newer shape and written back in whichever shape they were read in.
+///
+/// A component is present if its field is `Some`, even if empty, like a bundle with nothing in it.
+#[serde_as]
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct MinecraftComponents {
+    attribute_modifiers: Option<OneOf<AttributeModifiers, Vec<AttributeModifier>>>,
+    #[serde(rename = "axolotl/variant")]
+    axolotl_variant: Option<String>,
+    banner_patterns: Option<Vec<BannerPattern>>,
+    base_color: Option<String>,
+    bees: Option<Vec<Bee>>,
+    // Boxed for indirection
+    block_entity_data: Option<Box<BlockEntity>>,
+    /// Block state properties, by name
+    block_state: Option<HashMap<String, String>>,
+    blocks_attacks: Option<BlocksAttacks>,
+    break_sound: Option<SoundEvent>,
+    bucket_entity_data: Option<BucketEntity>,
+    bundle_contents: Option<Vec<Item>>,
+    can_break: Option<AdventureModePredicate>,
+    can_place_on: Option<AdventureModePredicate>,
+    #[serde(rename = "cat/collar")]
+    cat_collar: Option<String>,
+    #[serde(rename = "cat/variant")]
+    cat_variant: Option<Identifier>,
+    charged_projectiles: Option<Vec<Item>>,
+    #[serde(rename = "chicken/variant")]
+    chicken_variant: Option<Identifier>,
+    consumable: Option<Consumable>,
+    /// Only ever sent to clients, never saved
+    #[serde(skip)]
+    creative_slot_lock: Option<EmptyCompound>,
+    container: Option<Vec<ContainerSlot>>,
+    container_loot: Option<Loot>,
+    #[serde(rename = "cow/variant")]
+    cow_variant: Option<Identifier>,
+    /// Anything at all, for commands and data packs to keep on the item
+    custom_data: Option<Value>,
+    custom_model_data: Option<CustomModelData>,
+    custom_name: Option<SavedText>,
+    damage: Option<i32>,
+    damage_resistant: Option<DamageResistant>,
+    death_protection: Option<DeathProtection>,
+    /// The property each block is set to change, by block id
+    debug_stick_state: Option<HashMap<Identifier, String>>,
+    dyed_color: Option<OneOf<DyedColor, i32>>,
+    enchantable: Option<Enchantable>,
+    #[serde_as(as = "Option<FromInto<OneOf<bool, i8>>>")]
+    enchantment_glint_override: Option<bool>,
+    enchantments: Option<Enchantments>,
+    entity_data: Option<EntityData>,
+    equippable: Option<Equippable>,
+    firework_explosion: Option<FireworkExplosion>,
+    fireworks: Option<Fireworks>,
+    food: Option<Food>,
+    #[serde(rename = "fox/variant")]
+    fox_variant: Option<String>,
+    #[serde(rename = "frog/variant")]
+    frog_variant: Option<Identifier>,
+    glider: Option<EmptyCompound>,
+    hide_additional_tooltip: Option<EmptyCompound>,
+    hide_tooltip: Option<EmptyCompound>,
+    #[serde(rename = "horse/variant")]
+    horse_variant: Option<String>,
+    instrument: Option<OneOf<Identifier, Instrument>>,
+    intangible_projectile: Option<EmptyCompound>,
+    item_model: Option<Identifier>,
+    item_name: Option<SavedText>,
+    jukebox_playable: Option<OneOf<JukeboxPlayable, Identifier>>,
+    #[serde(rename = "llama/variant")]
+    llama_variant: Option<String>,
+    // Boxed for indirection
+    lock: Option<Box<ItemPredicate>>,
+    lodestone_tracker: Option<LodestoneTracker>,
+    lore: Option<Vec<SavedText>>,
+    map_color: Option<i32>,
+    map_decorations: Option<HashMap<String, MapDecoration>>,
+    map_id: Option<i32>,
+    /// Only ever sent to clients, never saved
+    #[serde(skip)]
+    map_post_processing: Option<MapPostProcessing>,
+    max_damage: Option<i32>,
+    max_stack_size: Option<i32>,
+    #[serde(rename = "mooshroom/variant")]
+    mooshroom_variant: Option<String>,
+    note_block_sound: Option<Identifier>,
+    ominous_bottle_amplifier: Option<i32>,
+    #[serde(rename = "painting/variant")]
+    painting_variant: Option<Identifier>,
+    #[serde(rename = "parrot/variant")]
+    parrot_variant: Option<String>,
+    #[serde(rename = "pig/variant")]
+    pig_variant: Option<Identifier>,
+    pot_decorations: Option<Vec<Identifier>>,
+    potion_contents: Option<OneOf<Identifier, PotionContents>>,
+    potion_duration_scale: Option<f32>,
+    profile: Option<Profile>,
+    /// A banner pattern tag, starting with `#`
+    provides_banner_patterns: Option<String>,
+    provides_trim_material: Option<Identifier>,
+    #[serde(rename = "rabbit/variant")]
+    rabbit_variant: Option<String>,
+    rarity: Option<Rarity>,
+    recipes: Option<Vec<Identifier>>,
+    repair_cost: Option<i32>,
+    repairable: Option<Repairable>,
+    #[serde(rename = "salmon/size")]
+    salmon_size: Option<String>,
+    #[serde(rename = "sheep/color")]
+    sheep_color: Option<String>,
+    #[serde(rename = "shulker/color")]
+    shulker_color: Option<String>,
+    stored_enchantments: Option<Enchantments>,
+    suspicious_stew_effects: Option<Vec<SuspiciousStewEffect>>,
+    tool: Option<Tool>,
+    tooltip_display: Option<TooltipDisplay>,
+    tooltip_style: Option<Identifier>,
+    trim: Option<Trim>,
+    #[serde(rename = "tropical_fish/base_color")]
+    tropical_fish_base_color: Option<String>,
+    #[serde(rename = "tropical_fish/pattern")]
+    tropical_fish_pattern: Option<String>,
+    #[serde(rename = "tropical_fish/pattern_color")]
+    tropical_fish_pattern_color: Option<String>,
+    unbreakable: Option<Unbreakable>,
+    use_cooldown: Option<UseCooldown>,
+    // Boxed for indirection
+    use_remainder: Option<Box<Item>>,
+    #[serde(rename = "villager/variant")]
+    villager_variant: Option<Identifier>,
+    weapon: Option<Weapon>,
+    #[serde(rename = "wolf/collar")]
+    wolf_collar: Option<String>,
+    #[serde(rename = "wolf/sound_variant")]
+    wolf_sound_variant: Option<Identifier>,
+    #[serde(rename = "wolf/variant")]
+    wolf_variant: Option<Identifier>,
+    writable_book_content: Option<WritableBookContent>,
+    written_book_content: Option<WrittenBookContent>,
+}
+
+impl MinecraftComponents {
+    pub fn custom_name(&self) -> Option<&TextComponent> {
+        self.custom_name.as_deref()
+    }
+
+    pub fn set_custom_name(&mut self, custom_name: impl Into<Option<TextComponent>>) {
+        self.custom_name = custom_name.into().map(SavedText::from);
+    }
+
+    pub fn item_name(&self) -> Option<&TextComponent> {
+        self.item_name.as_deref()
+    }
+
+    pub fn set_item_name(&mut self, item_name: impl Into<Option<TextComponent>>) {
+        self.item_name = item_name.into().map(SavedText::from);
+    }
+
+    pub fn lore(&self) -> Option<&[SavedText]> {
+        self.lore.as_deref()
+    }
+
+    pub fn set_lore(&mut self, lore: impl Into<Option<Vec<TextComponent>>>) {
+        self.lore = lore
+            .into()
+            .map(|lines| lines.into_iter().map(SavedText::from).collect());
+    }
+
+    pub const fn damage(&self) -> Option<i32> {
+        self.damage
+    }
+
+    pub const fn max_damage(&self) -> Option<i32> {
+        self.max_damage
+    }
+
+    pub const fn max_stack_size(&self) -> Option<i32> {
+        self.max_stack_size
+    }
+
+    pub const fn unbreakable(&self) -> bool {
+        self.unbreakable.is_some()
+    }
+
+    pub fn enchantments(&self) -> Option<&Enchantments> {
+        self.enchantments.as_ref()
+    }
+
+    pub fn stored_enchantments(&self) -> Option<&Enchantments> {
+        self.stored_enchantments.as_ref()
+    }
+
+    pub fn food(&self) -> Option<&Food> {
+        self.food.as_ref()
+    }
+
+    pub fn tool(&self) -> Option<&Tool> {
+        self.tool.as_ref()
+    }
+
+    pub fn equippable(&self) -> Option<&Equippable> {
+        self.equippable.as_ref()
+    }
+
+    pub fn potion_contents(&self) -> Option<&OneOf<Identifier, PotionContents>> {
+        self.potion_contents.as_ref()
+    }
+
+    pub fn written_book_content(&self) -> Option<&WrittenBookContent> {
+        self.written_book_content.as_ref()
+    }
+
+    pub fn bundle_contents(&self) -> Option<&[Item]> {
+        self.bundle_contents.as_deref()
+    }
+
+    pub fn container(&self) -> Option<&[ContainerSlot]> {
+        self.container.as_deref()
+    }
+
+    pub fn custom_data(&self) -> Option<&Value> {
+        self.custom_data.as_ref()
+    }
+}
+
+/// Getters for the components that don't have one of their own, and setters for every component,
+/// which take `None` to leave the component out.
+macro_rules! accessors {
+    (
+        ref { $($ref:ident, $set_ref:ident: $ref_ty:ty;)* }
+        copy { $($copy:ident, $set_copy:ident: $copy_ty:ty;)* }
+        slice { $($slice:ident, $set_slice:ident: $slice_ty:ty;)* }
+        boxed { $($boxed:ident, $set_boxed:ident: $boxed_ty:ty;)* }
+        set { $($field:ident, $set:ident: $ty:ty;)* }
+    ) => {
+        impl MinecraftComponents {
+            $(
+                pub fn $ref(&self) -> Option<&$ref_ty> {
+                    self.$ref.as_ref()
+                }
+
+                pub fn $set_ref(&mut self, $ref: impl Into<Option<$ref_ty>>) {
+                    self.$ref = $ref.into();
+                }
+            )*
+            $(
+                pub const fn $copy(&self) -> Option<$copy_ty> {
+                    self.$copy
+                }
+
+                pub fn $set_copy(&mut self, $copy: impl Into<Option<$copy_ty>>) {
+                    self.$copy = $copy.into();
+                }
+            )*
+            $(
+                pub fn $slice(&self) -> Option<&[$slice_ty]> {
+                    self.$slice.as_deref()
+                }
+
+                pub fn $set_slice(&mut self, $slice: impl Into<Option<Vec<$slice_ty>>>) {
+                    self.$slice = $slice.into();
+                }
+            )*
+            $(
+                pub fn $boxed(&self) -> Option<&$boxed_ty> {
+                    self.$boxed.as_deref()
+                }
+
+                pub fn $set_boxed(&mut self, $boxed: impl Into<Option<$boxed_ty>>) {
+                    self.$boxed = $boxed.into().map(Box::new);
+                }
+            )*
+            $(
+                pub fn $set(&mut self, $field: impl Into<Option<$ty>>) {
+                    self.$field = $field.into();
+                }
+            )*
+        }
+    };
+}
+
+accessors! {
+    ref {
+        attribute_modifiers, set_attribute_modifiers:
+            OneOf<AttributeModifiers, Vec<AttributeModifier>>;
+        axolotl_variant, set_axolotl_variant: String;
+        base_color, set_base_color: String;
+        block_state, set_block_state: HashMap<String, String>;
+        blocks_attacks, set_blocks_attacks: BlocksAttacks;
+        break_sound, set_break_sound: SoundEvent;
+        bucket_entity_data, set_bucket_entity_data: BucketEntity;
+        can_break, set_can_break: AdventureModePredicate;
+        can_place_on, set_can_place_on: AdventureModePredicate;
+        cat_collar, set_cat_collar: String;
+        cat_variant, set_cat_variant: Identifier;
+        chicken_variant, set_chicken_variant: Identifier;
+        consumable, set_consumable: Consumable;
+        creative_slot_lock, set_creative_slot_lock: EmptyCompound;
+        container_loot, set_container_loot: Loot;
+        cow_variant, set_cow_variant: Identifier;
+        custom_model_data, set_custom_model_data: CustomModelData;
+        damage_resistant, set_damage_resistant: DamageResistant;
+        death_protection, set_death_protection: DeathProtection;
+        debug_stick_state, set_debug_stick_state: HashMap<Identifier, String>;
+        dyed_color, set_dyed_color: OneOf<DyedColor, i32>;
+        enchantable, set_enchantable: Enchantable;
+        entity_data, set_entity_data: EntityData;
+        firework_explosion, set_firework_explosion: FireworkExplosion;
+        fireworks, set_fireworks: Fireworks;
+        fox_variant, set_fox_variant: String;
+        frog_variant, set_frog_variant: Identifier;
+        glider, set_glider: EmptyCompound;
+        hide_additional_tooltip, set_hide_additional_tooltip: EmptyCompound;
+        hide_tooltip, set_hide_tooltip: EmptyCompound;
+        horse_variant, set_horse_variant: String;
+        instrument, set_instrument: OneOf<Identifier, Instrument>;
+        intangible_projectile, set_intangible_projectile: EmptyCompound;
+        item_model, set_item_model: Identifier;
+        jukebox_playable, set_jukebox_playable: OneOf<JukeboxPlayable, Identifier>;
+        llama_variant, set_llama_variant: String;
+        lodestone_tracker, set_lodestone_tracker: LodestoneTracker;
+        map_decorations, set_map_decorations: HashMap<String, MapDecoration>;
+        mooshroom_variant, set_mooshroom_variant: String;
+        note_block_sound, set_note_block_sound: Identifier;
+        painting_variant, set_painting_variant: Identifier;
+        parrot_variant, set_parrot_variant: String;
+        pig_variant, set_pig_variant: Identifier;
+        profile, set_profile: Profile;
+        provides_banner_patterns, set_provides_banner_patterns: String;
+        provides_trim_material, set_provides_trim_material: Identifier;
+        rabbit_variant, set_rabbit_variant: String;
+        repairable, set_repairable: Repairable;
+        salmon_size, set_salmon_size: String;
+        sheep_color, set_sheep_color: String;
+        shulker_color, set_shulker_color: String;
+        tooltip_display, set_tooltip_display: TooltipDisplay;
+        tooltip_style, set_tooltip_style: Identifier;
+        trim, set_trim: Trim;
+        tropical_fish_base_color, set_tropical_fish_base_color: String;
+        tropical_fish_pattern, set_tropical_fish_pattern: String;
+        tropical_fish_pattern_color, set_tropical_fish_pattern_color: String;
+        use_cooldown, set_use_cooldown: UseCooldown;
+        villager_variant, set_villager_variant: Identifier;
+        weapon, set_weapon: Weapon;
+        wolf_collar, set_wolf_collar: String;
+        wolf_sound_variant, set_wolf_sound_variant: Identifier;
+        wolf_variant, set_wolf_variant: Identifier;
+        writable_book_content, set_writable_book_content: WritableBookContent;
+    }
+    copy {
+        enchantment_glint_override, set_enchantment_glint_override: bool;
+        map_color, set_map_color: i32;
+        map_id, set_map_id: i32;
+        map_post_processing, set_map_post_processing: MapPostProcessing;
+        ominous_bottle_amplifier, set_ominous_bottle_amplifier: i32;
+        potion_duration_scale, set_potion_duration_scale: f32;
+        rarity, set_rarity: Rarity;
+        repair_cost, set_repair_cost: i32;
+    }
+    slice {
+        banner_patterns, set_banner_patterns: BannerPattern;
+        bees, set_bees: Bee;
+        charged_projectiles, set_charged_projectiles: Item;
+        pot_decorations, set_pot_decorations: Identifier;
+        recipes, set_recipes: Identifier;
+        suspicious_stew_effects, set_suspicious_stew_effects: SuspiciousStewEffect;
+    }
+    boxed {
+        block_entity_data, set_block_entity_data: BlockEntity;
+        lock, set_lock: ItemPredicate;
+        use_remainder, set_use_remainder: Item;
+    }
+    set {
+        bundle_contents, set_bundle_contents: Vec<Item>;
+        container, set_container: Vec<ContainerSlot>;
+        custom_data, set_custom_data: Value;
+        damage, set_damage: i32;
+        enchantments, set_enchantments: Enchantments;
+        equippable, set_equippable: Equippable;
+        food, set_food: Food;
+        max_damage, set_max_damage: i32;
+        max_stack_size, set_max_stack_size: i32;
+        potion_contents, set_potion_contents: OneOf<Identifier, PotionContents>;
+        stored_enchantments, set_stored_enchantments: Enchantments;
+        tool, set_tool: Tool;
+        unbreakable, set_unbreakable: Unbreakable;
+        written_book_content, set_written_book_content: WrittenBookContent;
+    }
+}
+
+/// An id, a list of them or a tag starting with `#`
+pub type HolderSet = OneOf<String, Vec<String>>;
+
+/// A sound event id, or a sound defined in place
+pub type SoundEvent = OneOf<Identifier, SoundEventDefinition>;
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SoundEventDefinition {
+    sound_id: Identifier,
+    range: Option<f32>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ContainerSlot {
+    slot: i32,
+    item: Item,
+}
+
+impl ContainerSlot {
+    pub fn new(slot: i32, item: Item) -> Self {
+        Self { slot, item }
+    }
+
+    pub const fn slot(&self) -> i32 {
+        self.slot
+    }
+
+    pub fn item(&self) -> &Item {
+        &self.item
+    }
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Loot {
+    loot_table: Identifier,
+    seed: Option<i64>,
+}
+
+#[serde_as]
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Consumable {
+    #[serde(default = "default_consume_seconds")]
+    consume_seconds: f32,
+    #[serde(default)]
+    animation: ConsumableAnimation,
+    sound: Option<SoundEvent>,
+    #[serde_as(as = "Option<FromInto<OneOf<bool, i8>>>")]
+    has_consume_particles: Option<bool>,
+    on_consume_effects: Option<Vec<ConsumeEffect>>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Default, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum ConsumableAnimation {
+    None,
+    #[default]
+    Eat,
+    Drink,
+    Block,
+    Bow,
+    Spear,
+    Crossbow,
+    Spyglass,
+    TootHorn,
+    Brush,
+    Bundle,
+}
+
+const fn default_consume_seconds() -> f32 {
+    1.6
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(tag = "type")]
+pub enum ConsumeEffect {
+    #[serde(rename = "minecraft:apply_effects")]
+    ApplyEffects {
+        effects: Vec<PotionEffect>,
+        probability: Option<f32>,
+    },
+    #[serde(rename = "minecraft:remove_effects")]
+    RemoveEffects { effects: HolderSet },
+    #[serde(rename = "minecraft:clear_all_effects")]
+    ClearAllEffects,
+    #[serde(rename = "minecraft:teleport_randomly")]
+    TeleportRandomly { diameter: Option<f32> },
+    #[serde(rename = "minecraft:play_sound")]
+    PlaySound { sound: SoundEvent },
+}
+
+/// How 1.21.4 saves whether a component is shown in the tooltip, which 1.21.5 moved to
+/// `tooltip_display`. Read as part of components with other shapes, so it may come as a byte.
+#[serde_as]
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct ShowInTooltip {
+    #[serde_as(as = "Option<FromInto<OneOf<bool, i8>>>")]
+    show_in_tooltip: Option<bool>,
+}
+
+impl ShowInTooltip {
+    pub const fn show_in_tooltip(&self) -> bool {
+        !matches!(self.show_in_tooltip, Some(false))
+    }
+}
+
+/// What `can_break` and `can_place_on` check blocks against: all the predicates with whether
+/// they're shown, a list of them or just one.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum AdventureModePredicate {
+    Full {
+        predicates: Vec<BlockPredicate>,
+        #[serde(flatten)]
+        tooltip: ShowInTooltip,
+    },
+    List(Vec<BlockPredicate>),
+    Single(BlockPredicate),
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+pub struct BlockPredicate {
+    blocks: Option<HolderSet>,
+    /// Block entity data the block's must contain
+    nbt: Option<Value>,
+    /// Exact values or ranges of block state properties, by name
+    state: Option<HashMap<String, OneOf<String, StateRange>>>,
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+pub struct StateRange {
+    min: Option<String>,
+    max: Option<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ItemPredicate {
+    items: Option<HolderSet>,
+    count: Option<OneOf<i32, IntRange>>,
+    /// Components the item must have exactly
+    components: Option<Components>,
+    /// Partial checks of components, by the kind of check
+    predicates: Option<HashMap<Identifier, Value>>,
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+pub struct IntRange {
+    min: Option<i32>,
+    max: Option<i32>,
+}
+
+/// The saved mob of a bucket, which only keeps a few of its fields
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct BucketEntity {
+    #[serde(rename = "NoAI")]
+    no_ai: Option<bool>,
+    silent: Option<bool>,
+    no_gravity: Option<bool>,
+    glowing: Option<bool>,
+    invulnerable: Option<bool>,
+    health: Option<f32>,
+    age: Option<i32>,
+    variant: Option<i32>,
+    hunting_cooldown: Option<i64>,
+    bucket_variant_tag: Option<i32>,
+    #[serde(rename = "type")]
+    kind: Option<String>,
+}
+
+/// The entity a spawn egg, armor stand or the like spawns, with just the fields it sets
+#[derive(Serialize, Deserialize, Clone)]
+pub struct EntityData {
+    id: Identifier,
+    #[serde(flatten)]
+    fields: HashMap<String, Value>,
+}
+
+impl EntityData {
+    pub fn id(&self) -> &Identifier {
+        &self.id
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Bee {
+    entity_data: Entity,
+    min_ticks_in_hive: i32,
+    ticks_in_hive: i32,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct AttributeModifiers {
+    modifiers: Vec<AttributeModifier>,
+    #[serde(flatten)]
+    tooltip: ShowInTooltip,
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+pub struct AttributeModifier {
+    // TODO: is this to be an enum?
+    #[serde(rename = "type")]
+    kind: Identifier,
+    #[serde(default)]
+    slot: ItemSlot,
+    id: Identifier,
+    amount: f64,
+    operation: AttributeModifierOperation,
+    display: Option<Display>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct BannerPattern {
+    color: String,
+    pattern: OneOf<BannerPatternInner, Identifier>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct BannerPatternInner {
+    asset_id: Identifier,
+    translation: String,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Display {
+    Default,
+    Hidden,
+    // Boxed at recommendation of compiler
+    Override { value: Box<TextComponent> },
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DisplayKind {
+    Default,
+    Hidden,
+    Override,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum AttributeModifierOperation {
+    AddValue,
+    AddMultipliedBase,
+    AddMultipliedTotal,
+}
+
+#[derive(Serialize, Deserialize, Default, Clone)]
+#[serde(rename_all = "lowercase")]
+pub enum ItemSlot {
+    #[default]
+    Any,
+    Hand,
+    Armor,
+    MainHand,
+    Offhand,
+    Head,
+    Chest,
+    Legs,
+    Feet,
+    Body,
+    Saddle,
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+pub struct BlocksAttacks {
+    block_delay_seconds: Option<f32>,
+    disable_cooldown_scale: Option<f32>,
+    damage_reductions: Option<Vec<DamageReduction>>,
+    item_damage: Option<ItemDamage>,
+    /// A damage type tag, starting with `#`
+    bypassed_by: Option<String>,
+    block_sound: Option<SoundEvent>,
+    disable_sound: Option<SoundEvent>,
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+pub struct DamageReduction {
+    #[serde(rename = "type")]
+    kind: Option<HolderSet>,
+    base: f32,
+    factor: f32,
+    horizontal_blocking_angle: Option<f32>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ItemDamage {
+    threshold: f32,
+    base: f32,
+    factor: f32,
+}
+
+/// Floats, flags, strings and colors for item models to pick from
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+pub struct CustomModelData {
+    floats: Option<Vec<f32>>,
+    flags: Option<Vec<bool>>,
+    strings: Option<Vec<String>>,
+    /// Packed RGB, or red, green and blue from 0 to 1
+    colors: Option<Vec<OneOf<i32, [f32; 3]>>>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct DamageResistant {
+    /// A damage type tag, starting with `#`
+    types: String,
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+pub struct DeathProtection {
+    death_effects: Option<Vec<ConsumeEffect>>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct DyedColor {
+    rgb: i32,
+    #[serde(flatten)]
+    tooltip: ShowInTooltip,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Enchantable {
+    value: i32,
+}
+
+/// Enchantment levels by enchantment id. 1.21.4 saves them under `levels`, alongside whether
+/// they're shown, and 1.21.5 saves just the levels.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum Enchantments {
+    Full {
+        levels: HashMap<Identifier, i32>,
+        #[serde(flatten)]
+        tooltip: ShowInTooltip,
+    },
+    Levels(HashMap<Identifier, i32>),
+}
+
+impl Enchantments {
+    pub fn levels(&self) -> &HashMap<Identifier, i32> {
+        match self {
+            Self::Full { levels, .. } | Self::Levels(levels) => levels,
+        }
+    }
+
+    pub fn level(&self, enchantment: &Identifier) -> Option<i32> {
+        self.levels().get(enchantment).copied()
+    }
+
+    pub fn levels_mut(&mut self) -> &mut HashMap<Identifier, i32> {
+        match self {
+            Self::Full { levels, .. } | Self::Levels(levels) => levels,
+        }
+    }
+
+    /// Sets the level of an enchantment, or takes it away if `level` is 0
+    pub fn set_level(&mut self, enchantment: Identifier, level: i32) {
+        if level == 0 {
+            self.levels_mut().remove(&enchantment);
+        } else {
+            self.levels_mut().insert(enchantment, level);
+        }
+    }
+}
+
+/// No enchantments, saved in 1.21.4's shape
+impl Default for Enchantments {
+    fn default() -> Self {
+        Self::Full {
+            levels: HashMap::new(),
+            tooltip: ShowInTooltip::default(),
+        }
+    }
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Equippable {
+    slot: EquipmentSlot,
+    equip_sound: Option<SoundEvent>,
+    asset_id: Option<Identifier>,
+    camera_overlay: Option<Identifier>,
+    allowed_entities: Option<HolderSet>,
+    dispensable: Option<bool>,
+    swappable: Option<bool>,
+    damage_on_hurt: Option<bool>,
+    equip_on_interact: Option<bool>,
+    can_be_sheared: Option<bool>,
+    shearing_sound: Option<SoundEvent>,
+}
+
+impl Equippable {
+    pub const fn slot(&self) -> EquipmentSlot {
+        self.slot
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum EquipmentSlot {
+    Head,
+    Chest,
+    Legs,
+    Feet,
+    Body,
+    Mainhand,
+    Offhand,
+    Saddle,
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+pub struct FireworkExplosion {
+    shape: FireworkShape,
+    colors: Option<IntArray>,
+    fade_colors: Option<IntArray>,
+    has_trail: Option<bool>,
+    has_twinkle: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum FireworkShape {
+    SmallBall,
+    LargeBall,
+    Star,
+    Creeper,
+    Burst,
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Fireworks {
+    explosions: Option<Vec<FireworkExplosion>>,
+    flight_duration: Option<i8>,
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Food {
+    nutrition: i32,
+    saturation: f32,
+    can_always_eat: Option<bool>,
+}
+
+impl Food {
+    pub const fn new(nutrition: i32, saturation: f32) -> Self {
+        Self {
+            nutrition,
+            saturation,
+            can_always_eat: None,
+        }
+    }
+
+    pub const fn can_always_eat(mut self) -> Self {
+        self.can_always_eat = Some(true);
+        self
+    }
+
+    pub const fn nutrition(&self) -> i32 {
+        self.nutrition
+    }
+
+    pub const fn saturation(&self) -> f32 {
+        self.saturation
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Instrument {
+    sound_event: SoundEvent,
+    use_duration: f32,
+    range: f32,
+    description: TextComponent,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct JukeboxPlayable {
+    song: Identifier,
+    #[serde(flatten)]
+    tooltip: ShowInTooltip,
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+pub struct LodestoneTracker {
+    target: Option<PositionInDimension>,
+    tracked: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct MapDecoration {
+    #[serde(rename = "type")]
+    kind: Identifier,
+    x: f64,
+    z: f64,
+    rotation: f32,
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+pub struct PotionContents {
+    potion: Option<Identifier>,
+    custom_color: Option<i32>,
+    custom_effects: Option<Vec<PotionEffect>>,
+    custom_name: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum Rarity {
+    Common,
+    Uncommon,
+    Rare,
+    Epic,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Repairable {
+    items: HolderSet,
+}
+
+/// What a filled map does to its contents when crafted
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Type)]
+pub enum MapPostProcessing {
+    Lock,
+    Scale,
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SuspiciousStewEffect {
+    id: Identifier,
+    duration: Option<i32>,
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Tool {
+    rules: Vec<ToolRule>,
+    default_mining_speed: Option<f32>,
+    damage_per_block: Option<i32>,
+    can_destroy_blocks_in_creative: Option<bool>,
+}
+
+impl Tool {
+    pub fn rules(&self) -> &[ToolRule] {
+        &self.rules
+    }
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ToolRule {
+    blocks: HolderSet,
+    speed: Option<f32>,
+    correct_for_drops: Option<bool>,
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+pub struct TooltipDisplay {
+    hide_tooltip: Option<bool>,
+    hidden_components: Option<Vec<Identifier>>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Trim {
+    material: Identifier,
+    pattern: Identifier,
+    #[serde(flatten)]
+    tooltip: ShowInTooltip,
+}
+
+/// Whether unbreakability is shown is all 1.21.4 saves, and 1.21.5 saves nothing at all
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct Unbreakable {
+    #[serde(flatten)]
+    tooltip: ShowInTooltip,
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone, Type)]
+pub struct UseCooldown {
+    seconds: f32,
+    cooldown_group: Option<Identifier>,
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Weapon {
+    item_damage_per_attack: Option<i32>,
+    disable_blocking_for_seconds: Option<f32>,
+}
+
+/// Text as items save it: a JSON string in 1.21.4, or NBT in 1.21.5. Text that was read is written
+/// back as it was saved, and new text is saved as JSON.
+#[derive(Deserialize, Deref, Clone, Debug, PartialEq)]
+#[serde(from = "OneOf<String, TextComponent>")]
+pub struct SavedText {
+    #[deref]
+    text: TextComponent,
+    /// The JSON it was saved as, or `None` if it was saved as NBT
+    json: Option<String>,
+}
+
+impl From<TextComponent> for SavedText {
+    fn from(text: TextComponent) -> Self {
+        let json = match text.as_unstyled_text() {
+            Some(unstyled) => serde_json::to_string(unstyled),
+            None => serde_json::to_string(&text),
+        };
+        Self {
+            json: json.ok(),
+            text,
+        }
+    }
+}
+
+impl From<OneOf<String, TextComponent>> for SavedText {
+    fn from(value: OneOf<String, TextComponent>) -> Self {
+        match value {
+            // Vanilla reads JSON leniently, so a bare word is taken as text
+            OneOf::Left(json) => Self {
+                text: serde_json::from_str(&json).unwrap_or_else(|_| json.as_str().into()),
+                json: Some(json),
+            },
+            OneOf::Right(text) => Self { text, json: None },
+        }
+    }
+}
+
+impl Serialize for SavedText {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match &self.json {
+            Some(json) => json.serialize(serializer),
+            None => self.text.serialize(serializer),
+        }
+    }
+}
+
+/// Text that may have a version with chat filtering applied, saved as just the text without one
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(from = "OneOf<FilterableInner<T>, T>")]
+pub struct Filterable<T> {
+    raw: T,
+    filtered: Option<T>,
+}
+
+#[derive(Deserialize)]
+pub struct FilterableInner<T> {
+    raw: T,
+    filtered: Option<T>,
+}
+
+impl<T> From<OneOf<FilterableInner<T>, T>> for Filterable<T> {
+    fn from(value: OneOf<FilterableInner<T>, T>) -> Self {
+        match value {
+            OneOf::Left(FilterableInner { raw, filtered }) => Self { raw, filtered },
+            OneOf::Right(raw) => Self {
+                raw,
+                filtered: None,
+            },
+        }
+    }
+}
+
+impl<T> Filterable<T> {
+    pub fn raw(&self) -> &T {
+        &self.raw
+    }
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+pub struct WritableBookContent {
+    pages: Option<Vec<Filterable<String>>>,
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+pub struct WrittenBookContent {
+    title: Filterable<String>,
+    author: String,
+    generation: Option<i32>,
+    pages: Option<Vec<Filterable<SavedText>>>,
+    resolved: Option<bool>,
+}
+
+impl WrittenBookContent {
+    pub fn title(&self) -> &str {
+        self.title.raw()
+    }
+
+    pub fn author(&self) -> &str {
+        &self.author
+    }
+
+    pub fn pages(&self) -> &[Filterable<SavedText>] {
+        self.pages.as_deref().unwrap_or_default()
+    }
+}
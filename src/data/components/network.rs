@@ -0,0 +1,1457 @@
+//! How components are sent to clients: an item's patch is the components it adds, each as its
+//! network id followed by its own encoding, then the ids of those it removes.
+//!
+//! Components of mods and those newer than this version have no network id, so they aren't sent,
+//! the way vanilla leaves out components it doesn't sync.
+
+use super::*;
+use crate::{
+    data::{
+        BlockPos, MinecraftNamespaceWrapper, RemovedComponents,
+        particle::Rgb,
+        registry::{BuiltinRegistry, Registries, SyncedCodec, SyncedRegistry, SyncedType, builtin},
+        slot::Slot,
+    },
+    types::{AsVarInt, Codec, Nbt, Prefixed, VarInt},
+};
+use serde::de::DeserializeOwned;
+use std::io::{Read, Write};
+
+fn invalid(error: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, error)
+}
+
+fn unsendable(error: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidInput, error)
+}
+
+fn write_length(length: usize, writer: impl Write) -> std::io::Result<()> {
+    VarInt::new(
+        length
+            .try_into()
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::FileTooLarge, e))?,
+    )
+    .write(writer)
+}
+
+/// Leaves out lists that are empty, as they are when saved.
+fn non_empty<T>(vec: Vec<T>) -> Option<Vec<T>> {
+    (!vec.is_empty()).then_some(vec)
+}
+
+fn read_length(reader: impl Read) -> std::io::Result<usize> {
+    usize::try_from(AsVarInt::read(reader)?).map_err(|_| invalid("Negative length"))
+}
+
+/// The network id of an entry of a synced registry, which is its index among the entries the
+/// client was sent.
+fn synced_id<T: Serialize>(registry: &SyncedRegistry<T>, id: &Identifier) -> std::io::Result<i32> {
+    registry
+        .network_id(id)
+        .ok_or_else(|| unsendable(format!("{id} isn't in {}", registry.id())))
+}
+
+fn synced_entry<T: Serialize>(
+    registry: &SyncedRegistry<T>,
+    network_id: i32,
+) -> std::io::Result<Identifier> {
+    registry
+        .by_network_id(network_id)
+        .map(|entry| entry.id.clone())
+        .ok_or_else(|| invalid(format!("Invalid {} id {network_id}", registry.id())))
+}
+
+/// A reference to an entry of a synced registry, sent as its id plus one. Zero would mean the
+/// entry is defined in place instead.
+fn write_holder<T: Serialize>(
+    registry: &SyncedRegistry<T>,
+    id: &Identifier,
+    writer: impl Write,
+) -> std::io::Result<()> {
+    AsVarInt::write(&(synced_id(registry, id)? + 1), writer)
+}
+
+fn read_holder<T: Serialize>(
+    registry: &SyncedRegistry<T>,
+    reader: impl Read,
+) -> std::io::Result<Identifier> {
+    match AsVarInt::read(reader)? {
+        0 => Err(invalid(format!(
+            "Inline {} definitions aren't supported",
+            registry.id()
+        ))),
+        id => synced_entry(registry, id - 1),
+    }
+}
+
+/// A tag as 0 followed by its id, or a list of entries as its length plus one followed by their
+/// network ids.
+fn write_holder_set(
+    set: &HolderSet,
+    registry: BuiltinRegistry,
+    mut writer: impl Write,
+) -> std::io::Result<()> {
+    let ids = match set {
+        OneOf::Left(tag) if tag.starts_with('#') => {
+            VarInt::new(0).write(&mut writer)?;
+            return tag[1..]
+                .parse::<Identifier>()
+                .map_err(unsendable)?
+                .write(writer);
+        }
+        OneOf::Left(id) => std::slice::from_ref(id),
+        OneOf::Right(ids) => ids.as_slice(),
+    };
+
+    write_length(ids.len() + 1, &mut writer)?;
+    for id in ids {
+        registry.write_entry(&id.parse().map_err(unsendable)?, &mut writer)?;
+    }
+
+    Ok(())
+}
+
+fn read_holder_set(registry: BuiltinRegistry, mut reader: impl Read) -> std::io::Result<HolderSet> {
+    match read_length(&mut reader)? {
+        0 => Ok(OneOf::Left(format!("#{}", Identifier::read(reader)?))),
+        length => (1..length)
+            .map(|_| Ok(registry.read_entry(&mut reader)?.to_string()))
+            .collect::<std::io::Result<_>>()
+            .map(OneOf::Right),
+    }
+}
+
+/// Encodes a value with its own [`Type`] implementation.
+struct Direct;
+
+/// Encodes a value with its own [`SyncedType`] implementation.
+struct Synced;
+
+impl<T: SyncedType> SyncedCodec<T> for Synced {
+    fn write_with(value: &T, registries: &Registries, writer: impl Write) -> std::io::Result<()> {
+        value.write_with(registries, writer)
+    }
+
+    fn read_with(registries: &Registries, reader: impl Read) -> std::io::Result<T> {
+        T::read_with(registries, reader)
+    }
+}
+
+/// Like [`Prefixed`], for elements with their own [`SyncedType`] implementation.
+struct SyncedPrefixed;
+
+impl<T: SyncedType> SyncedCodec<Vec<T>> for SyncedPrefixed {
+    fn write_with(
+        value: &Vec<T>,
+        registries: &Registries,
+        mut writer: impl Write,
+    ) -> std::io::Result<()> {
+        write_length(value.len(), &mut writer)?;
+        for element in value {
+            element.write_with(registries, &mut writer)?;
+        }
+
+        Ok(())
+    }
+
+    fn read_with(registries: &Registries, mut reader: impl Read) -> std::io::Result<Vec<T>> {
+        (0..read_length(&mut reader)?)
+            .map(|_| T::read_with(registries, &mut reader))
+            .collect()
+    }
+}
+
+impl<T: Type> Codec<T> for Direct {
+    fn write(value: &T, writer: impl Write) -> std::io::Result<()> {
+        value.write(writer)
+    }
+
+    fn read(reader: impl Read) -> std::io::Result<T> {
+        T::read(reader)
+    }
+}
+
+/// Encodes a component that's just there or not, which has no data.
+struct Unit;
+
+impl Codec<EmptyCompound> for Unit {
+    fn write(_value: &EmptyCompound, _writer: impl Write) -> std::io::Result<()> {
+        Ok(())
+    }
+
+    fn read(_reader: impl Read) -> std::io::Result<EmptyCompound> {
+        Ok(EmptyCompound)
+    }
+}
+
+/// Encodes any serde type as network NBT with whatever tag it's saved as at the root, for those
+/// that aren't compounds.
+struct AnyNbt;
+
+/// Holds a value so it can go through the serializer for compounds, which leaves out `None`s.
+#[derive(Serialize, Deserialize)]
+struct Root<T> {
+    value: T,
+}
+
+impl<T: Serialize + DeserializeOwned> Codec<T> for AnyNbt {
+    fn write(value: &T, writer: impl Write) -> std::io::Result<()> {
+        let root: HashMap<String, Value> =
+            fastnbt::from_bytes(&fastnbt::to_bytes(&Root { value }).map_err(unsendable)?)
+                .map_err(unsendable)?;
+        root.into_values()
+            .next()
+            .unwrap_or_else(|| unreachable!())
+            .write(writer)
+    }
+
+    fn read(reader: impl Read) -> std::io::Result<T> {
+        let root = HashMap::from([("value", Value::read(reader)?)]);
+        let root: Root<T> =
+            fastnbt::from_bytes(&fastnbt::to_bytes(&root).map_err(invalid)?).map_err(invalid)?;
+        Ok(root.value)
+    }
+}
+
+const DYE_COLORS: &[&str] = &[
+    "white",
+    "orange",
+    "magenta",
+    "light_blue",
+    "yellow",
+    "lime",
+    "pink",
+    "gray",
+    "light_gray",
+    "cyan",
+    "purple",
+    "blue",
+    "brown",
+    "green",
+    "red",
+    "black",
+];
+
+/// Encodes a dye color's name as its index.
+struct DyeColor;
+
+impl Codec<String> for DyeColor {
+    fn write(value: &String, writer: impl Write) -> std::io::Result<()> {
+        let id = DYE_COLORS
+            .iter()
+            .position(|color| color == value)
+            .ok_or_else(|| unsendable(format!("Unknown dye color {value}")))?;
+        AsVarInt::write(&(id as i32), writer)
+    }
+
+    fn read(reader: impl Read) -> std::io::Result<String> {
+        let id = AsVarInt::read(reader)?;
+        usize::try_from(id)
+            .ok()
+            .and_then(|id| DYE_COLORS.get(id))
+            .map(|color| color.to_string())
+            .ok_or_else(|| invalid(format!("Invalid dye color {id}")))
+    }
+}
+
+/// Always sent defined in place, so the client needn't know the sound event's network id.
+struct Sound;
+
+impl Codec<SoundEvent> for Sound {
+    fn write(value: &SoundEvent, mut writer: impl Write) -> std::io::Result<()> {
+        let (id, range) = match value {
+            OneOf::Left(id) => (id, None),
+            OneOf::Right(definition) => (&definition.sound_id, definition.range),
+        };
+
+        VarInt::new(0).write(&mut writer)?;
+        id.write(&mut writer)?;
+        range.write(writer)
+    }
+
+    fn read(mut reader: impl Read) -> std::io::Result<SoundEvent> {
+        match AsVarInt::read(&mut reader)? {
+            0 => {
+                let sound_id = Identifier::read(&mut reader)?;
+                Ok(match Option::<f32>::read(reader)? {
+                    Some(range) => OneOf::Right(SoundEventDefinition {
+                        sound_id,
+                        range: Some(range),
+                    }),
+                    None => OneOf::Left(sound_id),
+                })
+            }
+            id => builtin::SOUND_EVENT
+                .get(id - 1)
+                .map(OneOf::Left)
+                .ok_or_else(|| invalid(format!("Invalid sound event id {}", id - 1))),
+        }
+    }
+}
+
+fn sound_or(sound: &Option<SoundEvent>, default: &str) -> SoundEvent {
+    sound
+        .clone()
+        .unwrap_or_else(|| OneOf::Left(Identifier::minecraft(default)))
+}
+
+/// Sent in 1.21.4's shape, with the levels and then whether they're shown.
+impl SyncedType for Enchantments {
+    fn write_with(&self, registries: &Registries, mut writer: impl Write) -> std::io::Result<()> {
+        // Sorted so the same enchantments are always sent the same way
+        let mut levels = self
+            .levels()
+            .iter()
+            .map(|(id, level)| Ok((synced_id(&registries.enchantment, id)?, *level)))
+            .collect::<std::io::Result<Vec<_>>>()?;
+        levels.sort_unstable();
+
+        write_length(levels.len(), &mut writer)?;
+        for (id, level) in levels {
+            AsVarInt::write(&id, &mut writer)?;
+            AsVarInt::write(&level, &mut writer)?;
+        }
+
+        let shown = match self {
+            Self::Full { tooltip, .. } => tooltip.show_in_tooltip(),
+            Self::Levels(_) => true,
+        };
+        shown.write(writer)
+    }
+
+    fn read_with(registries: &Registries, mut reader: impl Read) -> std::io::Result<Self> {
+        let mut levels = HashMap::new();
+        for _ in 0..read_length(&mut reader)? {
+            let id = AsVarInt::read(&mut reader)?;
+            levels.insert(
+                synced_entry(&registries.enchantment, id)?,
+                AsVarInt::read(&mut reader)?,
+            );
+        }
+
+        Ok(Self::Full {
+            levels,
+            tooltip: ShowInTooltip::read(reader)?,
+        })
+    }
+}
+
+impl Type for ShowInTooltip {
+    fn write(&self, writer: impl Write) -> std::io::Result<()> {
+        self.show_in_tooltip().write(writer)
+    }
+
+    fn read(reader: impl Read) -> std::io::Result<Self> {
+        Ok(Self {
+            show_in_tooltip: Some(bool::read(reader)?),
+        })
+    }
+}
+
+impl Type for Unbreakable {
+    fn write(&self, writer: impl Write) -> std::io::Result<()> {
+        self.tooltip.write(writer)
+    }
+
+    fn read(reader: impl Read) -> std::io::Result<Self> {
+        Ok(Self {
+            tooltip: ShowInTooltip::read(reader)?,
+        })
+    }
+}
+
+impl Type for AdventureModePredicate {
+    fn write(&self, mut writer: impl Write) -> std::io::Result<()> {
+        let (predicates, shown) = match self {
+            Self::Full {
+                predicates,
+                tooltip,
+            } => (predicates.as_slice(), tooltip.show_in_tooltip()),
+            Self::List(predicates) => (predicates.as_slice(), true),
+            Self::Single(predicate) => (std::slice::from_ref(predicate), true),
+        };
+
+        write_length(predicates.len(), &mut writer)?;
+        for predicate in predicates {
+            predicate.write(&mut writer)?;
+        }
+        shown.write(writer)
+    }
+
+    fn read(mut reader: impl Read) -> std::io::Result<Self> {
+        Ok(Self::Full {
+            predicates: Prefixed::read(&mut reader)?,
+            tooltip: ShowInTooltip::read(reader)?,
+        })
+    }
+}
+
+/// The blocks, then the state properties, then the NBT, each optional.
+impl Type for BlockPredicate {
+    fn write(&self, mut writer: impl Write) -> std::io::Result<()> {
+        self.blocks.is_some().write(&mut writer)?;
+        if let Some(blocks) = &self.blocks {
+            write_holder_set(blocks, builtin::BLOCK, &mut writer)?;
+        }
+
+        self.state.is_some().write(&mut writer)?;
+        if let Some(state) = &self.state {
+            write_length(state.len(), &mut writer)?;
+            for (name, value) in state {
+                name.write(&mut writer)?;
+                match value {
+                    OneOf::Left(exact) => {
+                        true.write(&mut writer)?;
+                        exact.write(&mut writer)?;
+                    }
+                    OneOf::Right(range) => {
+                        false.write(&mut writer)?;
+                        range.min.clone().unwrap_or_default().write(&mut writer)?;
+                        range.max.clone().unwrap_or_default().write(&mut writer)?;
+                    }
+                }
+            }
+        }
+
+        self.nbt.write(writer)
+    }
+
+    fn read(mut reader: impl Read) -> std::io::Result<Self> {
+        let blocks = match bool::read(&mut reader)? {
+            true => Some(read_holder_set(builtin::BLOCK, &mut reader)?),
+            false => None,
+        };
+
+        let state = match bool::read(&mut reader)? {
+            true => {
+                let mut state = HashMap::new();
+                for _ in 0..read_length(&mut reader)? {
+                    let name = String::read(&mut reader)?;
+                    let value = if bool::read(&mut reader)? {
+                        OneOf::Left(String::read(&mut reader)?)
+                    } else {
+                        let bound = |bound: String| (!bound.is_empty()).then_some(bound);
+                        OneOf::Right(StateRange {
+                            min: bound(String::read(&mut reader)?),
+                            max: bound(String::read(&mut reader)?),
+                        })
+                    };
+                    state.insert(name, value);
+                }
+                Some(state)
+            }
+            false => None,
+        };
+
+        Ok(Self {
+            blocks,
+            nbt: Type::read(reader)?,
+            state,
+        })
+    }
+}
+
+/// Sent in 1.21.4's shape, with the modifiers and then whether they're shown.
+struct AttributeModifiersCodec;
+
+impl Codec<OneOf<AttributeModifiers, Vec<AttributeModifier>>> for AttributeModifiersCodec {
+    fn write(
+        value: &OneOf<AttributeModifiers, Vec<AttributeModifier>>,
+        mut writer: impl Write,
+    ) -> std::io::Result<()> {
+        let (modifiers, shown) = match value {
+            OneOf::Left(modifiers) => (&modifiers.modifiers, modifiers.tooltip.show_in_tooltip()),
+            OneOf::Right(modifiers) => (modifiers, true),
+        };
+
+        Prefixed::write(modifiers, &mut writer)?;
+        shown.write(writer)
+    }
+
+    fn read(
+        mut reader: impl Read,
+    ) -> std::io::Result<OneOf<AttributeModifiers, Vec<AttributeModifier>>> {
+        Ok(OneOf::Left(AttributeModifiers {
+            modifiers: Prefixed::read(&mut reader)?,
+            tooltip: ShowInTooltip::read(reader)?,
+        }))
+    }
+}
+
+/// How the modifier is displayed is newer than this version, so it isn't sent.
+impl Type for AttributeModifier {
+    fn write(&self, mut writer: impl Write) -> std::io::Result<()> {
+        builtin::ATTRIBUTE.write_entry(&self.kind, &mut writer)?;
+        self.id.write(&mut writer)?;
+        self.amount.write(&mut writer)?;
+        let operation = match self.operation {
+            AttributeModifierOperation::AddValue => 0,
+            AttributeModifierOperation::AddMultipliedBase => 1,
+            AttributeModifierOperation::AddMultipliedTotal => 2,
+        };
+        AsVarInt::write(&operation, &mut writer)?;
+        let slot = match self.slot {
+            ItemSlot::Any => 0,
+            ItemSlot::MainHand => 1,
+            ItemSlot::Offhand => 2,
+            ItemSlot::Hand => 3,
+            ItemSlot::Feet => 4,
+            ItemSlot::Legs => 5,
+            ItemSlot::Chest => 6,
+            ItemSlot::Head => 7,
+            ItemSlot::Armor => 8,
+            ItemSlot::Body => 9,
+            ItemSlot::Saddle => {
+                return Err(unsendable("Saddle slots are newer than this version"));
+            }
+        };
+        AsVarInt::write(&slot, writer)
+    }
+
+    fn read(mut reader: impl Read) -> std::io::Result<Self> {
+        let kind = builtin::ATTRIBUTE.read_entry(&mut reader)?;
+        let id = Identifier::read(&mut reader)?;
+        let amount = f64::read(&mut reader)?;
+        let operation = match AsVarInt::read(&mut reader)? {
+            0 => AttributeModifierOperation::AddValue,
+            1 => AttributeModifierOperation::AddMultipliedBase,
+            2 => AttributeModifierOperation::AddMultipliedTotal,
+            id => return Err(invalid(format!("Invalid attribute operation {id}"))),
+        };
+        let slot = match AsVarInt::read(reader)? {
+            0 => ItemSlot::Any,
+            1 => ItemSlot::MainHand,
+            2 => ItemSlot::Offhand,
+            3 => ItemSlot::Hand,
+            4 => ItemSlot::Feet,
+            5 => ItemSlot::Legs,
+            6 => ItemSlot::Chest,
+            7 => ItemSlot::Head,
+            8 => ItemSlot::Armor,
+            9 => ItemSlot::Body,
+            id => return Err(invalid(format!("Invalid equipment slot group {id}"))),
+        };
+
+        Ok(Self {
+            kind,
+            slot,
+            id,
+            amount,
+            operation,
+            display: None,
+        })
+    }
+}
+
+impl Type for CustomModelData {
+    fn write(&self, mut writer: impl Write) -> std::io::Result<()> {
+        Prefixed::write(&self.floats.clone().unwrap_or_default(), &mut writer)?;
+        Prefixed::write(&self.flags.clone().unwrap_or_default(), &mut writer)?;
+        Prefixed::write(&self.strings.clone().unwrap_or_default(), &mut writer)?;
+        let colors: Vec<_> = self
+            .colors
+            .iter()
+            .flatten()
+            .map(|color| Rgb::from(color.clone()).0)
+            .collect();
+        Prefixed::write(&colors, writer)
+    }
+
+    fn read(mut reader: impl Read) -> std::io::Result<Self> {
+        Ok(Self {
+            floats: non_empty(Prefixed::read(&mut reader)?),
+            flags: non_empty(Prefixed::read(&mut reader)?),
+            strings: non_empty(Prefixed::read(&mut reader)?),
+            colors: non_empty(
+                Prefixed::read(reader)?
+                    .into_iter()
+                    .map(OneOf::Left)
+                    .collect(),
+            ),
+        })
+    }
+}
+
+impl Type for Food {
+    fn write(&self, mut writer: impl Write) -> std::io::Result<()> {
+        AsVarInt::write(&self.nutrition, &mut writer)?;
+        self.saturation.write(&mut writer)?;
+        self.can_always_eat.unwrap_or(false).write(writer)
+    }
+
+    fn read(mut reader: impl Read) -> std::io::Result<Self> {
+        Ok(Self {
+            nutrition: AsVarInt::read(&mut reader)?,
+            saturation: f32::read(&mut reader)?,
+            can_always_eat: Some(bool::read(reader)?),
+        })
+    }
+}
+
+impl Type for Consumable {
+    fn write(&self, mut writer: impl Write) -> std::io::Result<()> {
+        self.consume_seconds.write(&mut writer)?;
+        self.animation.write(&mut writer)?;
+        Sound::write(&sound_or(&self.sound, "entity.generic.eat"), &mut writer)?;
+        self.has_consume_particles
+            .unwrap_or(true)
+            .write(&mut writer)?;
+        Prefixed::write(&self.on_consume_effects.clone().unwrap_or_default(), writer)
+    }
+
+    fn read(mut reader: impl Read) -> std::io::Result<Self> {
+        Ok(Self {
+            consume_seconds: f32::read(&mut reader)?,
+            animation: ConsumableAnimation::read(&mut reader)?,
+            sound: Some(Sound::read(&mut reader)?),
+            has_consume_particles: Some(bool::read(&mut reader)?),
+            on_consume_effects: Some(Prefixed::read(reader)?),
+        })
+    }
+}
+
+/// The effect's type as its network id, then its fields.
+impl Type for ConsumeEffect {
+    fn write(&self, mut writer: impl Write) -> std::io::Result<()> {
+        match self {
+            Self::ApplyEffects {
+                effects,
+                probability,
+            } => {
+                VarInt::new(0).write(&mut writer)?;
+                Prefixed::write(effects, &mut writer)?;
+                probability.unwrap_or(1.0).write(writer)
+            }
+            Self::RemoveEffects { effects } => {
+                VarInt::new(1).write(&mut writer)?;
+                write_holder_set(effects, builtin::MOB_EFFECT, writer)
+            }
+            Self::ClearAllEffects => VarInt::new(2).write(writer),
+            Self::TeleportRandomly { diameter } => {
+                VarInt::new(3).write(&mut writer)?;
+                diameter.unwrap_or(16.0).write(writer)
+            }
+            Self::PlaySound { sound } => {
+                VarInt::new(4).write(&mut writer)?;
+                Sound::write(sound, writer)
+            }
+        }
+    }
+
+    fn read(mut reader: impl Read) -> std::io::Result<Self> {
+        Ok(match AsVarInt::read(&mut reader)? {
+            0 => Self::ApplyEffects {
+                effects: Prefixed::read(&mut reader)?,
+                probability: Some(f32::read(reader)?),
+            },
+            1 => Self::RemoveEffects {
+                effects: read_holder_set(builtin::MOB_EFFECT, reader)?,
+            },
+            2 => Self::ClearAllEffects,
+            3 => Self::TeleportRandomly {
+                diameter: Some(f32::read(reader)?),
+            },
+            4 => Self::PlaySound {
+                sound: Sound::read(reader)?,
+            },
+            id => return Err(invalid(format!("Invalid consume effect type {id}"))),
+        })
+    }
+}
+
+/// The damage type tag, which is sent without its `#`.
+impl Type for DamageResistant {
+    fn write(&self, writer: impl Write) -> std::io::Result<()> {
+        self.types
+            .strip_prefix('#')
+            .unwrap_or(&self.types)
+            .parse::<Identifier>()
+            .map_err(unsendable)?
+            .write(writer)
+    }
+
+    fn read(reader: impl Read) -> std::io::Result<Self> {
+        Ok(Self {
+            types: format!("#{}", Identifier::read(reader)?),
+        })
+    }
+}
+
+/// Whether blocks can be destroyed in creative is newer than this version, so it isn't sent.
+impl Type for Tool {
+    fn write(&self, mut writer: impl Write) -> std::io::Result<()> {
+        Prefixed::write(&self.rules, &mut writer)?;
+        self.default_mining_speed
+            .unwrap_or(1.0)
+            .write(&mut writer)?;
+        AsVarInt::write(&self.damage_per_block.unwrap_or(1), writer)
+    }
+
+    fn read(mut reader: impl Read) -> std::io::Result<Self> {
+        Ok(Self {
+            rules: Prefixed::read(&mut reader)?,
+            default_mining_speed: Some(f32::read(&mut reader)?),
+            damage_per_block: Some(AsVarInt::read(reader)?),
+            can_destroy_blocks_in_creative: None,
+        })
+    }
+}
+
+impl Type for ToolRule {
+    fn write(&self, mut writer: impl Write) -> std::io::Result<()> {
+        write_holder_set(&self.blocks, builtin::BLOCK, &mut writer)?;
+        self.speed.write(&mut writer)?;
+        self.correct_for_drops.write(writer)
+    }
+
+    fn read(mut reader: impl Read) -> std::io::Result<Self> {
+        Ok(Self {
+            blocks: read_holder_set(builtin::BLOCK, &mut reader)?,
+            speed: Type::read(&mut reader)?,
+            correct_for_drops: Type::read(reader)?,
+        })
+    }
+}
+
+impl Type for Enchantable {
+    fn write(&self, writer: impl Write) -> std::io::Result<()> {
+        AsVarInt::write(&self.value, writer)
+    }
+
+    fn read(reader: impl Read) -> std::io::Result<Self> {
+        Ok(Self {
+            value: AsVarInt::read(reader)?,
+        })
+    }
+}
+
+/// The fields newer than this version, from equipping on interaction on, aren't sent.
+impl Type for Equippable {
+    fn write(&self, mut writer: impl Write) -> std::io::Result<()> {
+        let slot = match self.slot {
+            EquipmentSlot::Mainhand => 0,
+            EquipmentSlot::Feet => 1,
+            EquipmentSlot::Legs => 2,
+            EquipmentSlot::Chest => 3,
+            EquipmentSlot::Head => 4,
+            EquipmentSlot::Offhand => 5,
+            EquipmentSlot::Body => 6,
+            EquipmentSlot::Saddle => {
+                return Err(unsendable("Saddle slots are newer than this version"));
+            }
+        };
+        AsVarInt::write(&slot, &mut writer)?;
+        Sound::write(
+            &sound_or(&self.equip_sound, "item.armor.equip_generic"),
+            &mut writer,
+        )?;
+        self.asset_id.write(&mut writer)?;
+        self.camera_overlay.write(&mut writer)?;
+        self.allowed_entities.is_some().write(&mut writer)?;
+        if let Some(entities) = &self.allowed_entities {
+            write_holder_set(entities, builtin::ENTITY_TYPE, &mut writer)?;
+        }
+        self.dispensable.unwrap_or(true).write(&mut writer)?;
+        self.swappable.unwrap_or(true).write(&mut writer)?;
+        self.damage_on_hurt.unwrap_or(true).write(writer)
+    }
+
+    fn read(mut reader: impl Read) -> std::io::Result<Self> {
+        let slot = match AsVarInt::read(&mut reader)? {
+            0 => EquipmentSlot::Mainhand,
+            1 => EquipmentSlot::Feet,
+            2 => EquipmentSlot::Legs,
+            3 => EquipmentSlot::Chest,
+            4 => EquipmentSlot::Head,
+            5 => EquipmentSlot::Offhand,
+            6 => EquipmentSlot::Body,
+            id => return Err(invalid(format!("Invalid equipment slot {id}"))),
+        };
+
+        Ok(Self {
+            slot,
+            equip_sound: Some(Sound::read(&mut reader)?),
+            asset_id: Type::read(&mut reader)?,
+            camera_overlay: Type::read(&mut reader)?,
+            allowed_entities: match bool::read(&mut reader)? {
+                true => Some(read_holder_set(builtin::ENTITY_TYPE, &mut reader)?),
+                false => None,
+            },
+            dispensable: Some(bool::read(&mut reader)?),
+            swappable: Some(bool::read(&mut reader)?),
+            damage_on_hurt: Some(bool::read(reader)?),
+            equip_on_interact: None,
+            can_be_sheared: None,
+            shearing_sound: None,
+        })
+    }
+}
+
+impl Type for Repairable {
+    fn write(&self, writer: impl Write) -> std::io::Result<()> {
+        write_holder_set(&self.items, builtin::ITEM, writer)
+    }
+
+    fn read(reader: impl Read) -> std::io::Result<Self> {
+        Ok(Self {
+            items: read_holder_set(builtin::ITEM, reader)?,
+        })
+    }
+}
+
+impl Type for DeathProtection {
+    fn write(&self, writer: impl Write) -> std::io::Result<()> {
+        Prefixed::write(&self.death_effects.clone().unwrap_or_default(), writer)
+    }
+
+    fn read(reader: impl Read) -> std::io::Result<Self> {
+        Ok(Self {
+            death_effects: Some(Prefixed::read(reader)?),
+        })
+    }
+}
+
+/// Sent in 1.21.4's shape, with the color and then whether it's shown.
+struct DyedColorCodec;
+
+impl Codec<OneOf<DyedColor, i32>> for DyedColorCodec {
+    fn write(value: &OneOf<DyedColor, i32>, mut writer: impl Write) -> std::io::Result<()> {
+        let (rgb, shown) = match value {
+            OneOf::Left(color) => (color.rgb, color.tooltip.show_in_tooltip()),
+            OneOf::Right(rgb) => (*rgb, true),
+        };
+
+        rgb.write(&mut writer)?;
+        shown.write(writer)
+    }
+
+    fn read(mut reader: impl Read) -> std::io::Result<OneOf<DyedColor, i32>> {
+        Ok(OneOf::Left(DyedColor {
+            rgb: i32::read(&mut reader)?,
+            tooltip: ShowInTooltip::read(reader)?,
+        }))
+    }
+}
+
+/// A potion by id is sent like contents with nothing but the potion.
+struct PotionContentsCodec;
+
+impl Codec<OneOf<Identifier, PotionContents>> for PotionContentsCodec {
+    fn write(
+        value: &OneOf<Identifier, PotionContents>,
+        mut writer: impl Write,
+    ) -> std::io::Result<()> {
+        let (potion, contents) = match value {
+            OneOf::Left(potion) => (Some(potion), None),
+            OneOf::Right(contents) => (contents.potion.as_ref(), Some(contents)),
+        };
+
+        potion.is_some().write(&mut writer)?;
+        if let Some(potion) = potion {
+            builtin::POTION.write_entry(potion, &mut writer)?;
+        }
+        contents
+            .and_then(|contents| contents.custom_color)
+            .write(&mut writer)?;
+        Prefixed::write(
+            &contents
+                .and_then(|contents| contents.custom_effects.clone())
+                .unwrap_or_default(),
+            &mut writer,
+        )?;
+        contents
+            .and_then(|contents| contents.custom_name.clone())
+            .write(writer)
+    }
+
+    fn read(mut reader: impl Read) -> std::io::Result<OneOf<Identifier, PotionContents>> {
+        let potion = match bool::read(&mut reader)? {
+            true => Some(builtin::POTION.read_entry(&mut reader)?),
+            false => None,
+        };
+        let custom_color = Type::read(&mut reader)?;
+        let custom_effects = Prefixed::read(&mut reader)?;
+
+        Ok(OneOf::Right(PotionContents {
+            potion,
+            custom_color,
+            custom_effects: non_empty(custom_effects),
+            custom_name: Type::read(reader)?,
+        }))
+    }
+}
+
+impl Type for SuspiciousStewEffect {
+    fn write(&self, mut writer: impl Write) -> std::io::Result<()> {
+        builtin::MOB_EFFECT.write_entry(&self.id, &mut writer)?;
+        AsVarInt::write(&self.duration.unwrap_or(160), writer)
+    }
+
+    fn read(mut reader: impl Read) -> std::io::Result<Self> {
+        Ok(Self {
+            id: builtin::MOB_EFFECT.read_entry(&mut reader)?,
+            duration: Some(AsVarInt::read(reader)?),
+        })
+    }
+}
+
+/// Sent as a text component, whichever way it's saved.
+impl Type for SavedText {
+    fn write(&self, writer: impl Write) -> std::io::Result<()> {
+        self.text.write(writer)
+    }
+
+    fn read(reader: impl Read) -> std::io::Result<Self> {
+        TextComponent::read(reader).map(Self::from)
+    }
+}
+
+/// The raw text, then the filtered text if there is any.
+impl<T: Type> Type for Filterable<T> {
+    fn write(&self, mut writer: impl Write) -> std::io::Result<()> {
+        self.raw.write(&mut writer)?;
+        self.filtered.write(writer)
+    }
+
+    fn read(mut reader: impl Read) -> std::io::Result<Self> {
+        Ok(Self {
+            raw: T::read(&mut reader)?,
+            filtered: Type::read(reader)?,
+        })
+    }
+}
+
+impl Type for WritableBookContent {
+    fn write(&self, writer: impl Write) -> std::io::Result<()> {
+        Prefixed::write(&self.pages.clone().unwrap_or_default(), writer)
+    }
+
+    fn read(reader: impl Read) -> std::io::Result<Self> {
+        Ok(Self {
+            pages: Some(Prefixed::read(reader)?),
+        })
+    }
+}
+
+impl Type for WrittenBookContent {
+    fn write(&self, mut writer: impl Write) -> std::io::Result<()> {
+        self.title.write(&mut writer)?;
+        self.author.write(&mut writer)?;
+        AsVarInt::write(&self.generation.unwrap_or(0), &mut writer)?;
+        Prefixed::write(&self.pages.clone().unwrap_or_default(), &mut writer)?;
+        self.resolved.unwrap_or(false).write(writer)
+    }
+
+    fn read(mut reader: impl Read) -> std::io::Result<Self> {
+        Ok(Self {
+            title: Filterable::read(&mut reader)?,
+            author: String::read(&mut reader)?,
+            generation: Some(AsVarInt::read(&mut reader)?),
+            pages: Some(Prefixed::read(&mut reader)?),
+            resolved: Some(bool::read(reader)?),
+        })
+    }
+}
+
+impl SyncedType for Trim {
+    fn write_with(&self, registries: &Registries, mut writer: impl Write) -> std::io::Result<()> {
+        write_holder(&registries.trim_material, &self.material, &mut writer)?;
+        write_holder(&registries.trim_pattern, &self.pattern, &mut writer)?;
+        self.tooltip.write(writer)
+    }
+
+    fn read_with(registries: &Registries, mut reader: impl Read) -> std::io::Result<Self> {
+        Ok(Self {
+            material: read_holder(&registries.trim_material, &mut reader)?,
+            pattern: read_holder(&registries.trim_pattern, &mut reader)?,
+            tooltip: ShowInTooltip::read(reader)?,
+        })
+    }
+}
+
+/// An instrument by network id, or one defined in place.
+struct InstrumentCodec;
+
+impl SyncedCodec<OneOf<Identifier, Instrument>> for InstrumentCodec {
+    fn write_with(
+        value: &OneOf<Identifier, Instrument>,
+        registries: &Registries,
+        mut writer: impl Write,
+    ) -> std::io::Result<()> {
+        match value {
+            OneOf::Left(id) => write_holder(&registries.instrument, id, writer),
+            OneOf::Right(instrument) => {
+                VarInt::new(0).write(&mut writer)?;
+                Sound::write(&instrument.sound_event, &mut writer)?;
+                instrument.use_duration.write(&mut writer)?;
+                instrument.range.write(&mut writer)?;
+                instrument.description.write(writer)
+            }
+        }
+    }
+
+    fn read_with(
+        registries: &Registries,
+        mut reader: impl Read,
+    ) -> std::io::Result<OneOf<Identifier, Instrument>> {
+        match AsVarInt::read(&mut reader)? {
+            0 => Ok(OneOf::Right(Instrument {
+                sound_event: Sound::read(&mut reader)?,
+                use_duration: f32::read(&mut reader)?,
+                range: f32::read(&mut reader)?,
+                description: TextComponent::read(reader)?,
+            })),
+            id => synced_entry(&registries.instrument, id - 1).map(OneOf::Left),
+        }
+    }
+}
+
+/// Whether the song is given by network id, then the song, then whether it's shown. Songs are
+/// sent by name, which any client can look up.
+struct JukeboxPlayableCodec;
+
+impl SyncedCodec<OneOf<JukeboxPlayable, Identifier>> for JukeboxPlayableCodec {
+    fn write_with(
+        value: &OneOf<JukeboxPlayable, Identifier>,
+        _registries: &Registries,
+        mut writer: impl Write,
+    ) -> std::io::Result<()> {
+        let (song, shown) = match value {
+            OneOf::Left(playable) => (&playable.song, playable.tooltip.show_in_tooltip()),
+            OneOf::Right(song) => (song, true),
+        };
+
+        false.write(&mut writer)?;
+        song.write(&mut writer)?;
+        shown.write(writer)
+    }
+
+    fn read_with(
+        registries: &Registries,
+        mut reader: impl Read,
+    ) -> std::io::Result<OneOf<JukeboxPlayable, Identifier>> {
+        let song = match bool::read(&mut reader)? {
+            true => read_holder(&registries.jukebox_song, &mut reader)?,
+            false => Identifier::read(&mut reader)?,
+        };
+
+        Ok(OneOf::Left(JukeboxPlayable {
+            song,
+            tooltip: ShowInTooltip::read(reader)?,
+        }))
+    }
+}
+
+impl Type for LodestoneTracker {
+    fn write(&self, mut writer: impl Write) -> std::io::Result<()> {
+        self.target.is_some().write(&mut writer)?;
+        if let Some(target) = &self.target {
+            target.dimension().write(&mut writer)?;
+            target.pos().write(&mut writer)?;
+        }
+        self.tracked.unwrap_or(true).write(writer)
+    }
+
+    fn read(mut reader: impl Read) -> std::io::Result<Self> {
+        let target = match bool::read(&mut reader)? {
+            true => Some(PositionInDimension::new(
+                Identifier::read(&mut reader)?,
+                BlockPos::read(&mut reader)?,
+            )),
+            false => None,
+        };
+
+        Ok(Self {
+            target,
+            tracked: Some(bool::read(reader)?),
+        })
+    }
+}
+
+impl Type for FireworkExplosion {
+    fn write(&self, mut writer: impl Write) -> std::io::Result<()> {
+        self.shape.write(&mut writer)?;
+        let ints = |colors: &Option<IntArray>| colors.as_deref().unwrap_or_default().to_vec();
+        Prefixed::write(&ints(&self.colors), &mut writer)?;
+        Prefixed::write(&ints(&self.fade_colors), &mut writer)?;
+        self.has_trail.unwrap_or(false).write(&mut writer)?;
+        self.has_twinkle.unwrap_or(false).write(writer)
+    }
+
+    fn read(mut reader: impl Read) -> std::io::Result<Self> {
+        Ok(Self {
+            shape: FireworkShape::read(&mut reader)?,
+            colors: Some(IntArray::new(Prefixed::read(&mut reader)?)),
+            fade_colors: Some(IntArray::new(Prefixed::read(&mut reader)?)),
+            has_trail: Some(bool::read(&mut reader)?),
+            has_twinkle: Some(bool::read(reader)?),
+        })
+    }
+}
+
+impl Type for Fireworks {
+    fn write(&self, mut writer: impl Write) -> std::io::Result<()> {
+        AsVarInt::write(&i32::from(self.flight_duration.unwrap_or(0)), &mut writer)?;
+        Prefixed::write(&self.explosions.clone().unwrap_or_default(), writer)
+    }
+
+    fn read(mut reader: impl Read) -> std::io::Result<Self> {
+        let flight_duration = i8::try_from(AsVarInt::read(&mut reader)?)
+            .map_err(|_| invalid("Invalid flight duration"))?;
+
+        Ok(Self {
+            explosions: Some(Prefixed::read(reader)?),
+            flight_duration: Some(flight_duration),
+        })
+    }
+}
+
+impl SyncedType for BannerPattern {
+    fn write_with(&self, registries: &Registries, mut writer: impl Write) -> std::io::Result<()> {
+        match &self.pattern {
+            OneOf::Left(pattern) => {
+                VarInt::new(0).write(&mut writer)?;
+                pattern.asset_id.write(&mut writer)?;
+                pattern.translation.write(&mut writer)?;
+            }
+            OneOf::Right(id) => {
+                write_holder(&registries.banner_pattern, id, &mut writer)?;
+            }
+        }
+        DyeColor::write(&self.color, writer)
+    }
+
+    fn read_with(registries: &Registries, mut reader: impl Read) -> std::io::Result<Self> {
+        let pattern = match AsVarInt::read(&mut reader)? {
+            0 => OneOf::Left(BannerPatternInner {
+                asset_id: Identifier::read(&mut reader)?,
+                translation: String::read(&mut reader)?,
+            }),
+            id => OneOf::Right(synced_entry(&registries.banner_pattern, id - 1)?),
+        };
+
+        Ok(Self {
+            color: DyeColor::read(reader)?,
+            pattern,
+        })
+    }
+}
+
+/// Each decoration as its item's network id.
+struct PotDecorations;
+
+impl Codec<Vec<Identifier>> for PotDecorations {
+    fn write(value: &Vec<Identifier>, mut writer: impl Write) -> std::io::Result<()> {
+        write_length(value.len(), &mut writer)?;
+        for item in value {
+            builtin::ITEM.write_entry(item, &mut writer)?;
+        }
+
+        Ok(())
+    }
+
+    fn read(mut reader: impl Read) -> std::io::Result<Vec<Identifier>> {
+        (0..read_length(&mut reader)?)
+            .map(|_| builtin::ITEM.read_entry(&mut reader))
+            .collect()
+    }
+}
+
+/// Every slot up to the last full one, with empty slots in between.
+struct ContainerCodec;
+
+impl SyncedCodec<Vec<ContainerSlot>> for ContainerCodec {
+    fn write_with(
+        value: &Vec<ContainerSlot>,
+        registries: &Registries,
+        mut writer: impl Write,
+    ) -> std::io::Result<()> {
+        let mut slots: Vec<Option<&Item>> = vec![];
+        for slot in value {
+            let index = usize::try_from(slot.slot).map_err(|_| unsendable("Negative slot"))?;
+            if slots.len() <= index {
+                slots.resize(index + 1, None);
+            }
+            slots[index] = Some(&slot.item);
+        }
+
+        write_length(slots.len(), &mut writer)?;
+        for item in slots {
+            match item {
+                Some(item) => item.write_with(registries, &mut writer)?,
+                None => VarInt::new(0).write(&mut writer)?,
+            }
+        }
+
+        Ok(())
+    }
+
+    fn read_with(
+        registries: &Registries,
+        mut reader: impl Read,
+    ) -> std::io::Result<Vec<ContainerSlot>> {
+        let mut out = vec![];
+        for slot in 0..read_length(&mut reader)? {
+            if let Some(item) = Slot::read_with(registries, &mut reader)? {
+                out.push(ContainerSlot {
+                    slot: slot as i32,
+                    item,
+                });
+            }
+        }
+
+        Ok(out)
+    }
+}
+
+/// Each property's name and value.
+struct BlockStateCodec;
+
+impl Codec<HashMap<String, String>> for BlockStateCodec {
+    fn write(value: &HashMap<String, String>, mut writer: impl Write) -> std::io::Result<()> {
+        write_length(value.len(), &mut writer)?;
+        for (name, value) in value {
+            name.write(&mut writer)?;
+            value.write(&mut writer)?;
+        }
+
+        Ok(())
+    }
+
+    fn read(mut reader: impl Read) -> std::io::Result<HashMap<String, String>> {
+        (0..read_length(&mut reader)?)
+            .map(|_| Ok((String::read(&mut reader)?, String::read(&mut reader)?)))
+            .collect()
+    }
+}
+
+impl Type for Bee {
+    fn write(&self, mut writer: impl Write) -> std::io::Result<()> {
+        Nbt::write(&self.entity_data, &mut writer)?;
+        AsVarInt::write(&self.ticks_in_hive, &mut writer)?;
+        AsVarInt::write(&self.min_ticks_in_hive, writer)
+    }
+
+    fn read(mut reader: impl Read) -> std::io::Result<Self> {
+        Ok(Self {
+            entity_data: Nbt::read(&mut reader)?,
+            ticks_in_hive: AsVarInt::read(&mut reader)?,
+            min_ticks_in_hive: AsVarInt::read(reader)?,
+        })
+    }
+}
+
+/// Lets the listed codecs, which don't depend on the registries, be used where one that does could
+/// be.
+macro_rules! unsynced {
+    ($($codec:ty),* $(,)?) => {
+        $(impl<T> SyncedCodec<T> for $codec
+        where
+            $codec: Codec<T>,
+        {
+            fn write_with(
+                value: &T,
+                _registries: &Registries,
+                writer: impl Write,
+            ) -> std::io::Result<()> {
+                <$codec as Codec<T>>::write(value, writer)
+            }
+
+            fn read_with(_registries: &Registries, reader: impl Read) -> std::io::Result<T> {
+                <$codec as Codec<T>>::read(reader)
+            }
+        })*
+    };
+}
+
+unsynced! {
+    AnyNbt,
+    AsVarInt,
+    AttributeModifiersCodec,
+    BlockStateCodec,
+    Direct,
+    DyeColor,
+    DyedColorCodec,
+    Nbt,
+    PotDecorations,
+    PotionContentsCodec,
+    Prefixed,
+    Unit,
+}
+
+/// Lists every component this version sends, with the codec of its value. The name is the
+/// component's path, and defaults to the field's name.
+macro_rules! components {
+    ($($field:ident $(as $name:literal)?: $codec:ty),* $(,)?) => {
+        /// The network id and encoding of every component that's sent.
+        fn write_added(
+            components: &MinecraftComponents,
+            registries: &Registries,
+        ) -> std::io::Result<Vec<(i32, Vec<u8>)>> {
+            let mut out = vec![];
+            $(if let Some(value) = &components.$field {
+                let id = Identifier::minecraft(components!(@name $field $($name)?));
+                if let Some(id) = builtin::DATA_COMPONENT_TYPE.network_id(&id) {
+                    let mut data = vec![];
+                    <$codec as SyncedCodec<_>>::write_with(value, registries, &mut data)?;
+                    out.push((id, data));
+                }
+            })*
+
+            Ok(out)
+        }
+
+        /// Reads the component called `name` into `components`, or returns `false` if it isn't
+        /// one that's sent.
+        fn read_added(
+            components: &mut MinecraftComponents,
+            name: &str,
+            registries: &Registries,
+            reader: impl Read,
+        ) -> std::io::Result<bool> {
+            match name {
+                $(components!(@name $field $($name)?) => {
+                    components.$field =
+                        Some(<$codec as SyncedCodec<_>>::read_with(registries, reader)?);
+                })*
+                _ => return Ok(false),
+            }
+
+            Ok(true)
+        }
+    };
+    (@name $field:ident $name:literal) => { $name };
+    (@name $field:ident) => { stringify!($field) };
+}
+
+components! {
+    attribute_modifiers: AttributeModifiersCodec,
+    banner_patterns: SyncedPrefixed,
+    base_color: DyeColor,
+    bees: Prefixed,
+    block_entity_data: Nbt,
+    block_state: BlockStateCodec,
+    bucket_entity_data: Nbt,
+    bundle_contents: SyncedPrefixed,
+    can_break: Direct,
+    can_place_on: Direct,
+    charged_projectiles: SyncedPrefixed,
+    consumable: Direct,
+    container: ContainerCodec,
+    container_loot: Nbt,
+    creative_slot_lock: Unit,
+    custom_data: Direct,
+    custom_model_data: Direct,
+    custom_name: Direct,
+    damage: AsVarInt,
+    damage_resistant: Direct,
+    death_protection: Direct,
+    debug_stick_state: Nbt,
+    dyed_color: DyedColorCodec,
+    enchantable: Direct,
+    enchantment_glint_override: Direct,
+    enchantments: Synced,
+    entity_data: Nbt,
+    equippable: Direct,
+    firework_explosion: Direct,
+    fireworks: Direct,
+    food: Direct,
+    glider: Unit,
+    hide_additional_tooltip: Unit,
+    hide_tooltip: Unit,
+    instrument: InstrumentCodec,
+    intangible_projectile: Unit,
+    item_model: Direct,
+    item_name: Direct,
+    jukebox_playable: JukeboxPlayableCodec,
+    lock: Nbt,
+    lodestone_tracker: Direct,
+    lore: Prefixed,
+    map_color: Direct,
+    map_decorations: Nbt,
+    map_id: AsVarInt,
+    map_post_processing: Direct,
+    max_damage: AsVarInt,
+    max_stack_size: AsVarInt,
+    note_block_sound: Direct,
+    ominous_bottle_amplifier: AsVarInt,
+    pot_decorations: PotDecorations,
+    potion_contents: PotionContentsCodec,
+    profile: Direct,
+    rarity: Direct,
+    recipes: AnyNbt,
+    repair_cost: AsVarInt,
+    repairable: Direct,
+    stored_enchantments: Synced,
+    suspicious_stew_effects: Prefixed,
+    tool: Direct,
+    tooltip_style: Direct,
+    trim: Synced,
+    unbreakable: Direct,
+    use_cooldown: Direct,
+    use_remainder: Synced,
+    writable_book_content: Direct,
+    written_book_content: Direct,
+}
+
+impl Components {
+    /// Whether none of the components are sent, so the patch is sent as if there were none
+    pub(crate) fn sends_nothing(&self, registries: &Registries) -> bool {
+        self.removed
+            .iter()
+            .all(|id| builtin::DATA_COMPONENT_TYPE.network_id(id).is_none())
+            && write_added(&self.minecraft, registries).is_ok_and(|added| added.is_empty())
+    }
+}
+
+/// The number of components added and removed, then the added components, then the network ids
+/// of the removed ones.
+impl SyncedType for Components {
+    fn write_with(&self, registries: &Registries, mut writer: impl Write) -> std::io::Result<()> {
+        let added = write_added(&self.minecraft, registries)?;
+        let mut removed: Vec<_> = self
+            .removed
+            .iter()
+            .filter_map(|id| builtin::DATA_COMPONENT_TYPE.network_id(id))
+            .collect();
+        removed.sort_unstable();
+
+        write_length(added.len(), &mut writer)?;
+        write_length(removed.len(), &mut writer)?;
+        for (id, data) in added {
+            AsVarInt::write(&id, &mut writer)?;
+            writer.write_all(&data)?;
+        }
+        for id in removed {
+            AsVarInt::write(&id, &mut writer)?;
+        }
+
+        Ok(())
+    }
+
+    fn read_with(registries: &Registries, mut reader: impl Read) -> std::io::Result<Self> {
+        // Components hold items, so the reader is made a trait object to keep from instantiating
+        // ever more nested readers
+        let mut reader: &mut dyn Read = &mut reader;
+        let added = read_length(&mut reader)?;
+        let removed = read_length(&mut reader)?;
+
+        let mut minecraft = MinecraftComponents::default();
+        for _ in 0..added {
+            let id = builtin::DATA_COMPONENT_TYPE.read_entry(&mut reader)?;
+            if !read_added(&mut minecraft, &id.path, registries, &mut reader)? {
+                return Err(invalid(format!("Unsupported component {id}")));
+            }
+        }
+
+        let removed = (0..removed)
+            .map(|_| builtin::DATA_COMPONENT_TYPE.read_entry(&mut reader))
+            .collect::<std::io::Result<_>>()?;
+
+        Ok(Self {
+            minecraft: MinecraftNamespaceWrapper(Box::new(minecraft)),
+            other: HashMap::new(),
+            removed: RemovedComponents(removed),
+        })
+    }
+}
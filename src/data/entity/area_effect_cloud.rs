@@ -7,7 +7,9 @@ use crate::{
     text_component::ARGB,
 };
 use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
 
+#[skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(rename_all = "PascalCase")]
 pub struct AreaEffectCloud {
@@ -15,10 +17,12 @@ pub struct AreaEffectCloud {
     color: ARGB,
     #[serde(rename = "potion_contents")]
     potion_contents: PotionContents,
-    owner: McUuid,
+    owner: Option<McUuid>,
+    /// Left out to use the effect particle, colored by the potion
     #[serde(rename = "custom_particle")]
-    custom_particle: Particle,
-    potion: Identifier,
+    custom_particle: Option<Particle>,
+    /// Left out by versions that save it in `potion_contents`
+    potion: Option<Identifier>,
     #[serde(rename = "potion_duration_scale")]
     potion_duration_scale: f32,
     radius: f32,
@@ -28,9 +32,48 @@ pub struct AreaEffectCloud {
     wait_time: i32,
 }
 
+fields! {
+    AreaEffectCloud {
+        copy {
+            age, set_age: i32;
+            potion_duration_scale, set_potion_duration_scale: f32;
+            radius, set_radius: f32;
+            radius_on_use, set_radius_on_use: f32;
+            radius_per_tick, set_radius_per_tick: f32;
+            reapplication_delay, set_reapplication_delay: i32;
+            wait_time, set_wait_time: i32;
+        }
+        reference {
+            color, set_color: ARGB;
+        }
+        opt {
+            owner, set_owner: McUuid;
+            custom_particle, set_custom_particle: Particle;
+            potion, set_potion: Identifier;
+        }
+        part {
+            potion_contents, potion_contents_mut: PotionContents;
+        }
+    }
+}
+
+#[skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone)]
 pub struct PotionContents {
     potion: Option<Identifier>,
-    custom_color: ARGB,
+    custom_color: Option<ARGB>,
+    #[serde(default)]
     custom_effects: Vec<PotionEffect>,
 }
+
+fields! {
+    PotionContents {
+        opt {
+            potion, set_potion: Identifier;
+            custom_color, set_custom_color: ARGB;
+        }
+        list {
+            custom_effects, custom_effects_mut: PotionEffect;
+        }
+    }
+}
@@ -1,15 +1,142 @@
 use crate::{
     OneOf,
-    data::{Item, block::Block, entity::one_f32},
+    data::{
+        Item,
+        block::Block,
+        entity::{EntityKind, one_f32},
+    },
     text_component::{ARGB, TextComponent},
 };
 use glam::Mat4;
 use serde::{Deserialize, Serialize};
-use serde_with::{FromInto, serde_as};
+use serde_with::{FromInto, serde_as, skip_serializing_none};
+
+#[derive(Serialize, Clone)]
+pub struct Display {
+    #[serde(flatten)]
+    base: DisplayBase,
+    #[serde(flatten)]
+    unique: UniqueDisplay,
+}
+
+impl Display {
+    pub(crate) fn from_nbt(kind: EntityKind, nbt: &[u8]) -> fastnbt::error::Result<Self> {
+        Ok(Self {
+            base: fastnbt::from_bytes(nbt)?,
+            unique: match kind {
+                EntityKind::BlockDisplay => UniqueDisplay::BlockDisplay(fastnbt::from_bytes(nbt)?),
+                EntityKind::ItemDisplay => UniqueDisplay::ItemDisplay(fastnbt::from_bytes(nbt)?),
+                _ => UniqueDisplay::TextDisplay(fastnbt::from_bytes(nbt)?),
+            },
+        })
+    }
+
+    /// How it turns to face the camera
+    pub fn billboard(&self) -> &Pivot {
+        &self.base.billboard
+    }
+
+    pub fn set_billboard(&mut self, billboard: Pivot) {
+        self.base.billboard = billboard;
+    }
+
+    /// The light it's drawn with, in place of that where it is
+    pub fn brightness(&self) -> Option<&Brightness> {
+        self.base.brightness.as_ref()
+    }
+
+    pub fn set_brightness(&mut self, brightness: impl Into<Option<Brightness>>) {
+        self.base.brightness = brightness.into();
+    }
+
+    /// The color of its outline when glowing, or -1 for that of its team
+    pub const fn glow_color_override(&self) -> i32 {
+        self.base.glow_color_override
+    }
+
+    pub fn set_glow_color_override(&mut self, color: i32) {
+        self.base.glow_color_override = color;
+    }
+
+    /// The size of the box it's culled by, which is never culled when either is 0
+    pub const fn size(&self) -> [f32; 2] {
+        [self.base.width, self.base.height]
+    }
+
+    pub fn set_size(&mut self, [width, height]: [f32; 2]) {
+        self.base.width = width;
+        self.base.height = height;
+    }
+
+    pub const fn interpolation_duration(&self) -> i32 {
+        self.base.interpolation_duration
+    }
+
+    pub fn set_interpolation_duration(&mut self, ticks: i32) {
+        self.base.interpolation_duration = ticks;
+    }
+
+    pub const fn teleport_duration(&self) -> i32 {
+        self.base.teleport_duration
+    }
+
+    pub fn set_teleport_duration(&mut self, ticks: i32) {
+        self.base.teleport_duration = ticks;
+    }
+
+    pub const fn start_interpolation(&self) -> i32 {
+        self.base.start_interpolation
+    }
+
+    pub fn set_start_interpolation(&mut self, ticks: i32) {
+        self.base.start_interpolation = ticks;
+    }
+
+    pub const fn shadow_radius(&self) -> f32 {
+        self.base.shadow_radius
+    }
+
+    pub fn set_shadow_radius(&mut self, radius: f32) {
+        self.base.shadow_radius = radius;
+    }
+
+    pub const fn shadow_strength(&self) -> f32 {
+        self.base.shadow_strength
+    }
+
+    pub fn set_shadow_strength(&mut self, strength: f32) {
+        self.base.shadow_strength = strength;
+    }
+
+    pub const fn view_range(&self) -> f32 {
+        self.base.view_range
+    }
+
+    pub fn set_view_range(&mut self, view_range: f32) {
+        self.base.view_range = view_range;
+    }
+
+    pub fn transformation(&self) -> &Transformation {
+        &self.base.transformation
+    }
+
+    pub fn set_transformation(&mut self, transformation: Transformation) {
+        self.base.transformation = transformation;
+    }
+
+    pub fn unique(&self) -> &UniqueDisplay {
+        &self.unique
+    }
+
+    pub fn unique_mut(&mut self) -> &mut UniqueDisplay {
+        &mut self.unique
+    }
+}
 
+#[skip_serializing_none]
 #[serde_as]
 #[derive(Serialize, Deserialize, Clone)]
-pub struct Display {
+struct DisplayBase {
     #[serde(default)]
     billboard: Pivot,
     brightness: Option<Brightness>,
@@ -31,41 +158,85 @@ pub struct Display {
     view_range: f32,
     #[serde_as(as = "FromInto<OneOf<[f32; 16], Transformation>>")]
     transformation: Transformation,
-    #[serde(flatten)]
-    unique: Option<UniqueDisplay>,
 }
 
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Serialize, Clone)]
 #[serde(untagged)]
 pub enum UniqueDisplay {
+    BlockDisplay(BlockDisplay),
+    // Boxed at recommendation of compiler
+    ItemDisplay(Box<ItemDisplay>),
+    TextDisplay(Box<TextDisplay>),
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct BlockDisplay {
+    block_state: Block,
+}
+
+fields! {
     BlockDisplay {
-        block_state: Block,
-    },
+        reference {
+            block_state, set_block_state: Block;
+        }
+    }
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ItemDisplay {
+    /// Left out when it shows nothing
+    item: Option<Item>,
+    #[serde(default)]
+    item_display: ItemDisplayContext,
+}
+
+fields! {
     ItemDisplay {
-        // Boxed at recommendation of compiler
-        item: Box<Item>,
-        #[serde(default)]
-        item_display: ItemDisplay,
-    },
+        reference {
+            item_display, set_item_display: ItemDisplayContext;
+        }
+        opt {
+            item, set_item: Item;
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct TextDisplay {
+    #[serde(default)]
+    alignment: TextAlignment,
+    background: ARGB,
+    #[serde(default)]
+    default_background: bool,
+    #[serde(default = "default_line_width")]
+    line_width: i32,
+    #[serde(default)]
+    see_through: bool,
+    #[serde(default)]
+    shadow: bool,
+    // TODO: wiki says this is stored as raw JSON text. I doubt it though...
+    text: TextComponent,
+    // TODO: test how this gets (de)serialized... the wiki is confusing about how unsigned is
+    // handled in NBT
+    text_opacity: u8,
+}
+
+fields! {
     TextDisplay {
-        #[serde(default)]
-        alignment: TextAlignment,
-        background: ARGB,
-        #[serde(default)]
-        default_background: bool,
-        #[serde(default = "default_line_width")]
-        line_width: i32,
-        #[serde(default)]
-        see_through: bool,
-        #[serde(default)]
-        shadow: bool,
-        // TODO: wiki says this is stored as raw JSON text. I doubt it though...
-        // Boxed at recommendation of compiler
-        text: Box<TextComponent>,
-        // TODO: test how this gets (de)serialized... the wiki is confusing about how unsigned is
-        // handled in NBT
-        text_opacity: u8,
-    },
+        copy {
+            default_background, set_default_background: bool;
+            line_width, set_line_width: i32;
+            see_through, set_see_through: bool;
+            shadow, set_shadow: bool;
+            text_opacity, set_text_opacity: u8;
+        }
+        reference {
+            alignment, set_alignment: TextAlignment;
+            background, set_background: ARGB;
+            text, set_text: TextComponent;
+        }
+    }
 }
 
 const fn default_line_width() -> i32 {
@@ -83,7 +254,7 @@ pub enum TextAlignment {
 
 #[derive(Serialize, Deserialize, Clone, Default)]
 #[serde(rename_all = "snake_case")]
-pub enum ItemDisplay {
+pub enum ItemDisplayContext {
     #[default]
     None,
     ThirdpersonLefthand,
@@ -116,6 +287,21 @@ pub struct Brightness {
     sky: i32,
 }
 
+fields! {
+    Brightness {
+        copy {
+            block, set_block: i32;
+            sky, set_sky: i32;
+        }
+    }
+}
+
+impl Brightness {
+    pub const fn new(block: i32, sky: i32) -> Self {
+        Self { block, sky }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 pub struct Transformation {
     right_rotation: Rotation,
@@ -124,10 +310,34 @@ pub struct Transformation {
     translation: [f32; 3],
 }
 
-#[derive(Serialize, Deserialize, Clone)]
+fields! {
+    Transformation {
+        copy {
+            right_rotation, set_right_rotation: Rotation;
+            scale, set_scale: [f32; 3];
+            left_rotation, set_left_rotation: Rotation;
+            translation, set_translation: [f32; 3];
+        }
+    }
+}
+
+/// A quaternion
+#[derive(Serialize, Deserialize, Clone, Copy)]
 #[serde(from = "OneOf<[f32; 4], AngleAxisRotation>")]
 pub struct Rotation([f32; 4]);
 
+impl Rotation {
+    pub const IDENTITY: Self = Self([0.0, 0.0, 0.0, 1.0]);
+
+    pub const fn new(quaternion: [f32; 4]) -> Self {
+        Self(quaternion)
+    }
+
+    pub const fn quaternion(self) -> [f32; 4] {
+        self.0
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 pub struct AngleAxisRotation {
     angle: f32,
@@ -155,6 +365,18 @@ impl From<OneOf<[f32; 4], AngleAxisRotation>> for Rotation {
     }
 }
 
+/// Leaves the display as it is
+impl Default for Transformation {
+    fn default() -> Self {
+        Self {
+            right_rotation: Rotation::IDENTITY,
+            scale: [1.0; 3],
+            left_rotation: Rotation::IDENTITY,
+            translation: [0.0; 3],
+        }
+    }
+}
+
 // TODO: is there shear? figure out how to test
 impl From<[f32; 16]> for Transformation {
     fn from(value: [f32; 16]) -> Self {
@@ -169,7 +391,7 @@ impl From<[f32; 16]> for Transformation {
         Self {
             right_rotation: Rotation(rotation.to_array()),
             scale: scale.to_array(),
-            left_rotation: Rotation([0.0, 0.0, 0.0, 1.0]),
+            left_rotation: Rotation::IDENTITY,
             translation: translation.to_array(),
         }
     }
@@ -1,18 +1,64 @@
 use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
 
-use crate::{
-    OneOf,
-    data::{Identifier, Item, block::Face, entity::one_f32},
+use crate::data::{
+    BlockPos, Identifier, Item,
+    block::Face,
+    entity::{EntityKind, one_f32},
 };
 
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Serialize, Clone)]
 pub struct Hanging {
-    // TODO: wiki claims this is i32 but this feels more accurate
-    block_pos: [i32; 3],
     #[serde(flatten)]
-    unique: Option<OneOf<ItemFrame, Painting>>,
+    base: HangingBase,
+    #[serde(flatten)]
+    unique: UniqueHanging,
+}
+
+impl Hanging {
+    pub(crate) fn from_nbt(kind: EntityKind, nbt: &[u8]) -> fastnbt::error::Result<Self> {
+        Ok(Self {
+            base: fastnbt::from_bytes(nbt)?,
+            unique: match kind {
+                EntityKind::Painting => UniqueHanging::Painting(fastnbt::from_bytes(nbt)?),
+                // Glow item frames save the same as any other
+                _ => UniqueHanging::ItemFrame(fastnbt::from_bytes(nbt)?),
+            },
+        })
+    }
+
+    /// The block it hangs on
+    pub const fn block_pos(&self) -> BlockPos {
+        self.base.block_pos
+    }
+
+    pub fn set_block_pos(&mut self, block_pos: BlockPos) {
+        self.base.block_pos = block_pos;
+    }
+
+    pub fn unique(&self) -> &UniqueHanging {
+        &self.unique
+    }
+
+    pub fn unique_mut(&mut self) -> &mut UniqueHanging {
+        &mut self.unique
+    }
 }
 
+#[derive(Serialize, Deserialize, Clone)]
+struct HangingBase {
+    block_pos: BlockPos,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(untagged)]
+pub enum UniqueHanging {
+    // Boxed at recommendation of compiler
+    ItemFrame(Box<ItemFrame>),
+    Painting(Painting),
+}
+
+#[skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(rename_all = "PascalCase")]
 pub struct ItemFrame {
@@ -26,9 +72,34 @@ pub struct ItemFrame {
     item_rotation: i8,
 }
 
+fields! {
+    ItemFrame {
+        copy {
+            fixed, set_fixed: bool;
+            invisible, set_invisible: bool;
+            item_drop_chance, set_item_drop_chance: f32;
+            item_rotation, set_item_rotation: i8;
+        }
+        reference {
+            facing, set_facing: Face;
+        }
+        opt {
+            item, set_item: Item;
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 pub struct Painting {
     facing: Face,
     variant: Identifier,
 }
 
+fields! {
+    Painting {
+        reference {
+            facing, set_facing: Face;
+            variant, set_variant: Identifier;
+        }
+    }
+}
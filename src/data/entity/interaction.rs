@@ -1,7 +1,10 @@
 use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use uuid::Uuid;
 
 use super::{McUuid, one_f32};
 
+#[skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone)]
 pub struct Interaction {
     #[serde(default = "one_f32")]
@@ -10,8 +13,24 @@ pub struct Interaction {
     height: f32,
     #[serde(default)]
     response: bool,
-    attack: Hit,
-    interaction: Hit,
+    /// The last player to attack it, if any
+    attack: Option<Hit>,
+    /// The last player to use it, if any
+    interaction: Option<Hit>,
+}
+
+fields! {
+    Interaction {
+        copy {
+            width, set_width: f32;
+            height, set_height: f32;
+            response, set_response: bool;
+        }
+        opt {
+            attack, set_attack: Hit;
+            interaction, set_interaction: Hit;
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -19,3 +38,23 @@ pub struct Hit {
     player: McUuid,
     timestamp: i64,
 }
+
+fields! {
+    Hit {
+        copy {
+            timestamp, set_timestamp: i64;
+        }
+        reference {
+            player, set_player: McUuid;
+        }
+    }
+}
+
+impl Hit {
+    pub fn new(player: Uuid, timestamp: i64) -> Self {
+        Self {
+            player: player.into(),
+            timestamp,
+        }
+    }
+}
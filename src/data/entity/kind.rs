@@ -0,0 +1,313 @@
+use crate::data::Identifier;
+use strum::{EnumIter, EnumString, IntoStaticStr};
+
+/// The type of an entity, saved as its `id`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, EnumString, IntoStaticStr, EnumIter)]
+#[strum(serialize_all = "snake_case")]
+pub enum EntityKind {
+    AcaciaBoat,
+    AcaciaChestBoat,
+    Allay,
+    AreaEffectCloud,
+    Armadillo,
+    ArmorStand,
+    Arrow,
+    Axolotl,
+    BambooChestRaft,
+    BambooRaft,
+    Bat,
+    Bee,
+    BirchBoat,
+    BirchChestBoat,
+    Blaze,
+    BlockDisplay,
+    Bogged,
+    Breeze,
+    BreezeWindCharge,
+    Camel,
+    Cat,
+    CaveSpider,
+    CherryBoat,
+    CherryChestBoat,
+    ChestMinecart,
+    Chicken,
+    Cod,
+    CommandBlockMinecart,
+    CopperGolem,
+    Cow,
+    Creaking,
+    Creeper,
+    DarkOakBoat,
+    DarkOakChestBoat,
+    Dolphin,
+    Donkey,
+    DragonFireball,
+    Drowned,
+    Egg,
+    ElderGuardian,
+    EndCrystal,
+    EnderDragon,
+    EnderPearl,
+    Enderman,
+    Endermite,
+    Evoker,
+    EvokerFangs,
+    ExperienceBottle,
+    ExperienceOrb,
+    EyeOfEnder,
+    FallingBlock,
+    Fireball,
+    FireworkRocket,
+    FishingBobber,
+    Fox,
+    Frog,
+    FurnaceMinecart,
+    Ghast,
+    Giant,
+    GlowItemFrame,
+    GlowSquid,
+    Goat,
+    Guardian,
+    HappyGhast,
+    Hoglin,
+    HopperMinecart,
+    Horse,
+    Husk,
+    Illusioner,
+    Interaction,
+    IronGolem,
+    Item,
+    ItemDisplay,
+    ItemFrame,
+    JungleBoat,
+    JungleChestBoat,
+    LeashKnot,
+    LightningBolt,
+    LingeringPotion,
+    Llama,
+    LlamaSpit,
+    MagmaCube,
+    MangroveBoat,
+    MangroveChestBoat,
+    Mannequin,
+    Marker,
+    Minecart,
+    Mooshroom,
+    Mule,
+    OakBoat,
+    OakChestBoat,
+    Ocelot,
+    OminousItemSpawner,
+    Painting,
+    PaleOakBoat,
+    PaleOakChestBoat,
+    Panda,
+    Parrot,
+    Phantom,
+    Pig,
+    Piglin,
+    PiglinBrute,
+    Pillager,
+    Player,
+    PolarBear,
+    Pufferfish,
+    Rabbit,
+    Ravager,
+    Salmon,
+    Sheep,
+    Shulker,
+    ShulkerBullet,
+    Silverfish,
+    Skeleton,
+    SkeletonHorse,
+    Slime,
+    SmallFireball,
+    Sniffer,
+    SnowGolem,
+    Snowball,
+    SpawnerMinecart,
+    SpectralArrow,
+    Spider,
+    SplashPotion,
+    SpruceBoat,
+    SpruceChestBoat,
+    Squid,
+    Stray,
+    Strider,
+    Tadpole,
+    TextDisplay,
+    Tnt,
+    TntMinecart,
+    TraderLlama,
+    Trident,
+    TropicalFish,
+    Turtle,
+    Vex,
+    Villager,
+    Vindicator,
+    WanderingTrader,
+    Warden,
+    WindCharge,
+    Witch,
+    Wither,
+    WitherSkeleton,
+    WitherSkull,
+    Wolf,
+    Zoglin,
+    Zombie,
+    ZombieHorse,
+    ZombieVillager,
+    ZombifiedPiglin,
+}
+
+impl EntityKind {
+    /// The kind of entity an id names, or `None` if it isn't a vanilla entity
+    pub fn from_id(id: &Identifier) -> Option<Self> {
+        if id.namespace != "minecraft" {
+            return None;
+        }
+
+        id.path.parse().ok()
+    }
+
+    pub fn id(self) -> Identifier {
+        Identifier::minecraft(<&'static str>::from(self))
+    }
+
+    /// Whether it has health and effects, and saves them along with its brain
+    pub const fn is_living(self) -> bool {
+        use EntityKind::*;
+        matches!(
+            self,
+            Allay
+                | Armadillo
+                | ArmorStand
+                | Axolotl
+                | Bat
+                | Bee
+                | Blaze
+                | Bogged
+                | Breeze
+                | Camel
+                | Cat
+                | CaveSpider
+                | Chicken
+                | Cod
+                | CopperGolem
+                | Cow
+                | Creaking
+                | Creeper
+                | Dolphin
+                | Donkey
+                | Drowned
+                | ElderGuardian
+                | EnderDragon
+                | Enderman
+                | Endermite
+                | Evoker
+                | Fox
+                | Frog
+                | Ghast
+                | Giant
+                | GlowSquid
+                | Goat
+                | Guardian
+                | HappyGhast
+                | Hoglin
+                | Horse
+                | Husk
+                | Illusioner
+                | IronGolem
+                | Llama
+                | MagmaCube
+                | Mannequin
+                | Mooshroom
+                | Mule
+                | Ocelot
+                | Panda
+                | Parrot
+                | Phantom
+                | Pig
+                | Piglin
+                | PiglinBrute
+                | Pillager
+                | Player
+                | PolarBear
+                | Pufferfish
+                | Rabbit
+                | Ravager
+                | Salmon
+                | Sheep
+                | Shulker
+                | Silverfish
+                | Skeleton
+                | SkeletonHorse
+                | Slime
+                | Sniffer
+                | SnowGolem
+                | Spider
+                | Squid
+                | Stray
+                | Strider
+                | Tadpole
+                | TraderLlama
+                | TropicalFish
+                | Turtle
+                | Vex
+                | Villager
+                | Vindicator
+                | WanderingTrader
+                | Warden
+                | Witch
+                | Wither
+                | WitherSkeleton
+                | Wolf
+                | Zoglin
+                | Zombie
+                | ZombieHorse
+                | ZombieVillager
+                | ZombifiedPiglin
+        )
+    }
+
+    /// The health it spawns with, or `None` if it isn't living. Horses and llamas, whose health
+    /// is random, get the least they can have, and slimes and magma cubes that of the smallest
+    /// size.
+    pub const fn max_health(self) -> Option<f32> {
+        use EntityKind::*;
+        Some(match self {
+            Creaking | MagmaCube | Slime => 1.0,
+            Cod | Pufferfish | Rabbit | Salmon | TropicalFish => 3.0,
+            Chicken | SnowGolem => 4.0,
+            Bat | Parrot | Tadpole => 6.0,
+            Endermite | Sheep | Silverfish | Wolf => 8.0,
+            Bee | Cat | Cow | Dolphin | Fox | Frog | Ghast | GlowSquid | Goat | Mooshroom
+            | Ocelot | Pig | Squid => 10.0,
+            Armadillo | CaveSpider | CopperGolem => 12.0,
+            Axolotl | Sniffer | Vex => 14.0,
+            Donkey | Horse | Llama | Mule | SkeletonHorse | TraderLlama | ZombieHorse => 15.0,
+            Bogged | Piglin | Spider => 16.0,
+            Allay | ArmorStand | Blaze | Creeper | Drowned | HappyGhast | Husk | Mannequin
+            | Panda | Phantom | Player | Skeleton | Stray | Strider | Villager
+            | WanderingTrader | WitherSkeleton | Zombie | ZombieVillager | ZombifiedPiglin => 20.0,
+            Evoker | Pillager | Vindicator => 24.0,
+            Witch => 26.0,
+            Breeze | Guardian | PolarBear | Shulker | Turtle => 30.0,
+            Camel | Illusioner => 32.0,
+            Enderman | Hoglin | Zoglin => 40.0,
+            PiglinBrute => 50.0,
+            ElderGuardian => 80.0,
+            Giant | IronGolem | Ravager => 100.0,
+            EnderDragon => 200.0,
+            Wither => 300.0,
+            Warden => 500.0,
+            _ => return None,
+        })
+    }
+
+    /// Whether it's a living entity with AI, which everything living but armor stands, mannequins
+    /// and players is
+    pub const fn is_mob(self) -> bool {
+        self.is_living() && !matches!(self, Self::ArmorStand | Self::Mannequin | Self::Player)
+    }
+}
@@ -1,5 +1,6 @@
 use crate::data::{Identifier, Item, entity::McUuid};
 use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
 
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(rename_all = "PascalCase")]
@@ -11,6 +12,21 @@ pub struct Allay {
     listener: VibrationListener,
 }
 
+fields! {
+    Allay {
+        copy {
+            duplication_cooldown, set_duplication_cooldown: i64;
+        }
+        list {
+            inventory, inventory_mut: Item;
+        }
+        part {
+            listener, listener_mut: VibrationListener;
+        }
+    }
+}
+
+#[skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone)]
 pub struct VibrationListener {
     distance: i32,
@@ -21,6 +37,23 @@ pub struct VibrationListener {
     source: VibrationSource,
 }
 
+fields! {
+    VibrationListener {
+        copy {
+            distance, set_distance: i32;
+            event_delay, set_event_delay: i32;
+            event_distance, set_event_distance: i32;
+            range, set_range: i32;
+        }
+        reference {
+            source, set_source: VibrationSource;
+        }
+        opt {
+            event, set_event: VibrationEvent;
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(rename_all = "snake_case", tag = "type")]
 pub enum VibrationSource {
@@ -36,3 +69,19 @@ pub struct VibrationEvent {
     projectile_owner: Option<McUuid>,
     source: Option<McUuid>,
 }
+
+fields! {
+    VibrationEvent {
+        copy {
+            distance, set_distance: i32;
+            pos, set_pos: [f64; 3];
+        }
+        reference {
+            game_event, set_game_event: Identifier;
+        }
+        opt {
+            projectile_owner, set_projectile_owner: McUuid;
+            source, set_source: McUuid;
+        }
+    }
+}
@@ -9,6 +9,20 @@ pub struct Armadillo {
     breedable: Breedable,
 }
 
+fields! {
+    Armadillo {
+        copy {
+            scute_time, set_scute_time: i32;
+        }
+        reference {
+            state, set_state: ArmadilloState;
+        }
+        part {
+            breedable, breedable_mut: Breedable;
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(rename_all = "snake_case")]
 pub enum ArmadilloState {
@@ -1,6 +1,8 @@
 use bitflags::bitflags;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_with::skip_serializing_none;
 
+#[skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(rename_all = "PascalCase")]
 pub struct ArmorStand {
@@ -13,9 +15,26 @@ pub struct ArmorStand {
     small: bool,
 }
 
+fields! {
+    ArmorStand {
+        copy {
+            disabled_slots, set_disabled_slots: ArmorStandSlots;
+            invisible, set_invisible: bool;
+            no_base_plate, set_no_base_plate: bool;
+            show_arms, set_show_arms: bool;
+            small, set_small: bool;
+        }
+        opt_copy {
+            marker, set_marker: bool;
+        }
+        part {
+            pose, pose_mut: ArmorStandPose;
+        }
+    }
+}
+
 bitflags! {
-    #[derive(Serialize, Deserialize, Clone)]
-    #[serde(transparent)]
+    #[derive(Clone, Copy)]
     pub struct ArmorStandSlots: i32 {
         const ADD_CHANGE_MAINHAND = 1 << 0;
         const ADD_CHANGE_BOOTS = 1 << 1;
@@ -38,6 +57,19 @@ bitflags! {
     }
 }
 
+// Saved as the bits themselves, where bitflags' own impls would write their names
+impl Serialize for ArmorStandSlots {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.bits().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ArmorStandSlots {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        i32::deserialize(deserializer).map(Self::from_bits_retain)
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(rename_all = "PascalCase")]
 pub struct ArmorStandPose {
@@ -48,3 +80,16 @@ pub struct ArmorStandPose {
     right_arm: [f32; 3],
     right_leg: [f32; 3],
 }
+
+fields! {
+    ArmorStandPose {
+        copy {
+            body, set_body: [f32; 3];
+            head, set_head: [f32; 3];
+            left_arm, set_left_arm: [f32; 3];
+            left_leg, set_left_leg: [f32; 3];
+            right_arm, set_right_arm: [f32; 3];
+            right_leg, set_right_leg: [f32; 3];
+        }
+    }
+}
@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 use strum::FromRepr;
 
 #[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
 pub struct Axolotl {
     from_bucket: bool,
     variant: Variant,
@@ -10,6 +11,20 @@ pub struct Axolotl {
     breedable: Breedable,
 }
 
+fields! {
+    Axolotl {
+        copy {
+            from_bucket, set_from_bucket: bool;
+        }
+        reference {
+            variant, set_variant: Variant;
+        }
+        part {
+            breedable, breedable_mut: Breedable;
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, FromRepr)]
 #[serde(try_from = "i32", into = "i32")]
 pub enum Variant {
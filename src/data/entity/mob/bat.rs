@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct Bat {
+    bat_flags: bool,
+}
+
+fields! {
+    Bat {
+        copy {
+            bat_flags, set_bat_flags: bool;
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use super::{Angerable, Breedable};
+use crate::data::BlockPos;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct Bee {
+    cannot_enter_hive_ticks: i32,
+    crops_grown_since_pollination: i32,
+    #[serde(rename = "flower_pos")]
+    flower_pos: Option<BlockPos>,
+    has_nectar: bool,
+    has_stung: bool,
+    #[serde(rename = "hive_pos")]
+    hive_pos: Option<BlockPos>,
+    ticks_since_pollination: i32,
+    #[serde(flatten)]
+    breedable: Breedable,
+    #[serde(flatten)]
+    angerable: Angerable,
+}
+
+fields! {
+    Bee {
+        copy {
+            cannot_enter_hive_ticks, set_cannot_enter_hive_ticks: i32;
+            crops_grown_since_pollination, set_crops_grown_since_pollination: i32;
+            has_nectar, set_has_nectar: bool;
+            has_stung, set_has_stung: bool;
+            ticks_since_pollination, set_ticks_since_pollination: i32;
+        }
+        opt_copy {
+            flower_pos, set_flower_pos: BlockPos;
+            hive_pos, set_hive_pos: BlockPos;
+        }
+        part {
+            breedable, breedable_mut: Breedable;
+            angerable, angerable_mut: Angerable;
+        }
+    }
+}
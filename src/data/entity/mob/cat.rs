@@ -6,10 +6,11 @@ use crate::{
     },
 };
 use serde::{Deserialize, Serialize};
+use strum::{EnumIter, IntoStaticStr};
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct Cat {
-    #[serde(default = "default_collar_color")]
+    #[serde(rename = "CollarColor", default = "default_collar_color")]
     collar_color: Color,
     #[serde(rename = "variant")]
     variant: OneOf<MinecraftNamespaceWrapper<Variant>, Identifier>,
@@ -19,8 +20,22 @@ pub struct Cat {
     tameable: Tameable,
 }
 
-#[derive(Serialize, Deserialize, Clone)]
+fields! {
+    Cat {
+        reference {
+            collar_color, set_collar_color: Color;
+            variant, set_variant: OneOf<MinecraftNamespaceWrapper<Variant>, Identifier>;
+        }
+        part {
+            breedable, breedable_mut: Breedable;
+            tameable, tameable_mut: Tameable;
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, IntoStaticStr, EnumIter)]
 #[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
 pub enum Variant {
     White,
     Black,
@@ -0,0 +1,31 @@
+use super::Breedable;
+use crate::data::Identifier;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct Chicken {
+    egg_lay_time: i32,
+    is_chicken_jockey: bool,
+    #[serde(rename = "variant")]
+    variant: Option<Identifier>,
+    #[serde(flatten)]
+    breedable: Breedable,
+}
+
+fields! {
+    Chicken {
+        copy {
+            egg_lay_time, set_egg_lay_time: i32;
+            is_chicken_jockey, set_is_chicken_jockey: bool;
+        }
+        opt {
+            variant, set_variant: Identifier;
+        }
+        part {
+            breedable, breedable_mut: Breedable;
+        }
+    }
+}
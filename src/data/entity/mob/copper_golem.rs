@@ -7,6 +7,17 @@ pub struct CopperGolem {
     next_weather_age: i64,
 }
 
+fields! {
+    CopperGolem {
+        copy {
+            next_weather_age, set_next_weather_age: i64;
+        }
+        reference {
+            weather_state, set_weather_state: WeatherState;
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, FromRepr)]
 #[serde(into = "i32", try_from = "i32")]
 pub enum WeatherState {
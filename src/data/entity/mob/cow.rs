@@ -0,0 +1,23 @@
+use super::Breedable;
+use crate::data::Identifier;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Cow {
+    variant: Option<Identifier>,
+    #[serde(flatten)]
+    breedable: Breedable,
+}
+
+fields! {
+    Cow {
+        opt {
+            variant, set_variant: Identifier;
+        }
+        part {
+            breedable, breedable_mut: Breedable;
+        }
+    }
+}
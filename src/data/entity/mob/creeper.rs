@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Creeper {
+    #[serde(rename = "ExplosionRadius")]
+    explosion_radius: i8,
+    #[serde(rename = "Fuse")]
+    fuse: i16,
+    ignited: bool,
+    #[serde(default)]
+    powered: bool,
+}
+
+fields! {
+    Creeper {
+        copy {
+            explosion_radius, set_explosion_radius: i8;
+            fuse, set_fuse: i16;
+            ignited, set_ignited: bool;
+            powered, set_powered: bool;
+        }
+    }
+}
@@ -0,0 +1,23 @@
+use super::Breedable;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct Dolphin {
+    moistness: i32,
+    got_fish: bool,
+    #[serde(flatten)]
+    breedable: Breedable,
+}
+
+fields! {
+    Dolphin {
+        copy {
+            moistness, set_moistness: i32;
+            got_fish, set_got_fish: bool;
+        }
+        part {
+            breedable, breedable_mut: Breedable;
+        }
+    }
+}
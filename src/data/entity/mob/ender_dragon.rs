@@ -7,6 +7,14 @@ pub struct EnderDragon {
     dragon_phase: DragonPhase,
 }
 
+fields! {
+    EnderDragon {
+        reference {
+            dragon_phase, set_dragon_phase: DragonPhase;
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, FromRepr)]
 #[serde(into = "i32", try_from = "i32")]
 pub enum DragonPhase {
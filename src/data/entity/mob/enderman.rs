@@ -1,8 +1,10 @@
 use super::Angerable;
 use crate::data::Identifier;
 use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
 use std::collections::HashMap;
 
+#[skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Enderman {
@@ -18,3 +20,14 @@ pub struct CarriedBlock {
     // TODO: how is this actually represented
     properties: HashMap<String, String>,
 }
+
+fields! {
+    Enderman {
+        opt {
+            carried_block_state, set_carried_block_state: CarriedBlock;
+        }
+        part {
+            angerable, angerable_mut: Angerable;
+        }
+    }
+}
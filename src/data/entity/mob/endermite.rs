@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct Endermite {
+    lifetime: i32,
+}
+
+fields! {
+    Endermite {
+        copy {
+            lifetime, set_lifetime: i32;
+        }
+    }
+}
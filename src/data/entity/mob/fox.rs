@@ -0,0 +1,36 @@
+use super::Breedable;
+use crate::data::{Identifier, entity::McUuid};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct Fox {
+    crouching: bool,
+    sitting: bool,
+    sleeping: bool,
+    #[serde(default)]
+    trusted: Vec<McUuid>,
+    #[serde(rename = "Type")]
+    kind: Identifier,
+    #[serde(flatten)]
+    breedable: Breedable,
+}
+
+fields! {
+    Fox {
+        copy {
+            crouching, set_crouching: bool;
+            sitting, set_sitting: bool;
+            sleeping, set_sleeping: bool;
+        }
+        reference {
+            kind, set_kind: Identifier;
+        }
+        list {
+            trusted, trusted_mut: McUuid;
+        }
+        part {
+            breedable, breedable_mut: Breedable;
+        }
+    }
+}
@@ -3,7 +3,7 @@ use crate::{
     data::{Identifier, entity::mob::Breedable},
 };
 use serde::{Deserialize, Serialize};
-use strum::{EnumString, IntoStaticStr};
+use strum::{EnumIter, EnumString, IntoStaticStr};
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct Frog {
@@ -12,7 +12,18 @@ pub struct Frog {
     breedable: Breedable,
 }
 
-#[derive(Serialize, Deserialize, Clone, IntoStaticStr, EnumString)]
+fields! {
+    Frog {
+        reference {
+            variant, set_variant: OneOf<Variant, Identifier>;
+        }
+        part {
+            breedable, breedable_mut: Breedable;
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, IntoStaticStr, EnumString, EnumIter)]
 #[strum(serialize_all = "snake_case")]
 #[serde(try_from = "Identifier", into = "Identifier")]
 pub enum Variant {
@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct Ghast {
+    explosion_power: i8,
+}
+
+fields! {
+    Ghast {
+        copy {
+            explosion_power, set_explosion_power: i8;
+        }
+    }
+}
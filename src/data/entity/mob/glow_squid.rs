@@ -0,0 +1,21 @@
+use super::Breedable;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct GlowSquid {
+    dark_ticks_remaining: i32,
+    #[serde(flatten)]
+    breedable: Breedable,
+}
+
+fields! {
+    GlowSquid {
+        copy {
+            dark_ticks_remaining, set_dark_ticks_remaining: i32;
+        }
+        part {
+            breedable, breedable_mut: Breedable;
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use super::Breedable;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct Goat {
+    has_left_horn: bool,
+    has_right_horn: bool,
+    is_screaming_goat: bool,
+    #[serde(flatten)]
+    breedable: Breedable,
+}
+
+fields! {
+    Goat {
+        copy {
+            has_left_horn, set_has_left_horn: bool;
+            has_right_horn, set_has_right_horn: bool;
+            is_screaming_goat, set_is_screaming_goat: bool;
+        }
+        part {
+            breedable, breedable_mut: Breedable;
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use super::Breedable;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct Hoglin {
+    #[serde(default)]
+    cannot_be_hunted: bool,
+    #[serde(default)]
+    is_immune_to_zombification: bool,
+    time_in_overworld: i32,
+    #[serde(flatten)]
+    breedable: Breedable,
+}
+
+fields! {
+    Hoglin {
+        copy {
+            cannot_be_hunted, set_cannot_be_hunted: bool;
+            is_immune_to_zombification, set_is_immune_to_zombification: bool;
+            time_in_overworld, set_time_in_overworld: i32;
+        }
+        part {
+            breedable, breedable_mut: Breedable;
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct Zoglin {
+    #[serde(default)]
+    is_baby: bool,
+}
+
+fields! {
+    Zoglin {
+        copy {
+            is_baby, set_is_baby: bool;
+        }
+    }
+}
@@ -1,37 +1,164 @@
 use crate::data::{
     ItemWithSlot,
-    entity::{McUuid, mob::Breedable},
+    entity::{
+        McUuid,
+        mob::{Breedable, I8Bool},
+    },
 };
 use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
 use strum::{EnumIter, IntoEnumIterator};
 
+/// What every kind of horse saves, flattened into each of them
+#[skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(rename_all = "PascalCase")]
-pub struct Horse {
-    bred: bool,
-    eating_haystack: bool,
+pub struct AbstractHorse {
+    bred: I8Bool,
+    eating_haystack: I8Bool,
     owner: Option<McUuid>,
-    tame: bool,
+    tame: I8Bool,
     temper: i32,
     #[serde(flatten)]
     breedable: Breedable,
 }
 
+fields! {
+    AbstractHorse {
+        copy {
+            temper, set_temper: i32;
+        }
+        flag {
+            bred, set_bred;
+            eating_haystack, set_eating_haystack;
+            tame, set_tame;
+        }
+        opt {
+            owner, set_owner: McUuid;
+        }
+        part {
+            breedable, breedable_mut: Breedable;
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(rename_all = "PascalCase")]
 pub struct ChestedHorse {
-    chested_horse: bool,
+    chested_horse: I8Bool,
     #[serde(default)]
     items: Vec<ItemWithSlot>,
 }
 
+impl ChestedHorse {
+    pub fn items(&self) -> &[ItemWithSlot] {
+        &self.items
+    }
+
+    pub fn items_mut(&mut self) -> &mut Vec<ItemWithSlot> {
+        &mut self.items
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone)]
+pub struct Horse {
+    #[serde(flatten)]
+    horse: AbstractHorse,
+    #[serde(rename = "Variant")]
+    variant: HorseColors,
+}
+
+fields! {
+    Horse {
+        copy {
+            variant, set_variant: HorseColors;
+        }
+        part {
+            horse, horse_mut: AbstractHorse;
+        }
+    }
+}
+
+/// Donkeys and mules
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Donkey {
+    #[serde(flatten)]
+    horse: AbstractHorse,
+    #[serde(flatten)]
+    chested_horse: ChestedHorse,
+}
+
+fields! {
+    Donkey {
+        part {
+            horse, horse_mut: AbstractHorse;
+            chested_horse, chested_horse_mut: ChestedHorse;
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Camel {
+    #[serde(flatten)]
+    horse: AbstractHorse,
+    #[serde(rename = "LastPoseTick")]
+    last_pose_tick: i64,
+}
+
+fields! {
+    Camel {
+        copy {
+            last_pose_tick, set_last_pose_tick: i64;
+        }
+        part {
+            horse, horse_mut: AbstractHorse;
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct SkeletonHorse {
+    #[serde(flatten)]
+    horse: AbstractHorse,
+    skeleton_trap: bool,
+    skeleton_trap_time: i32,
+}
+
+fields! {
+    SkeletonHorse {
+        copy {
+            skeleton_trap, set_skeleton_trap: bool;
+            skeleton_trap_time, set_skeleton_trap_time: i32;
+        }
+        part {
+            horse, horse_mut: AbstractHorse;
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy)]
 #[serde(into = "i32", try_from = "i32")]
 pub struct HorseColors {
     color: Color,
     markings: Markings,
 }
 
+impl HorseColors {
+    pub const fn new(color: Color, markings: Markings) -> Self {
+        Self { color, markings }
+    }
+}
+
+fields! {
+    HorseColors {
+        copy {
+            color, set_color: Color;
+            markings, set_markings: Markings;
+        }
+    }
+}
+
 #[derive(EnumIter, Clone, Copy)]
 pub enum Color {
     White,
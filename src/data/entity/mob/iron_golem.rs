@@ -0,0 +1,21 @@
+use super::Angerable;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct IronGolem {
+    player_created: bool,
+    #[serde(flatten)]
+    angerable: Angerable,
+}
+
+fields! {
+    IronGolem {
+        copy {
+            player_created, set_player_created: bool;
+        }
+        part {
+            angerable, angerable_mut: Angerable;
+        }
+    }
+}
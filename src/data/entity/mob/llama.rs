@@ -1,4 +1,4 @@
-use super::{ChestedHorse, Horse};
+use super::horse::{AbstractHorse, ChestedHorse};
 use serde::{Deserialize, Serialize};
 use strum::FromRepr;
 
@@ -6,13 +6,28 @@ use strum::FromRepr;
 #[serde(rename_all = "PascalCase")]
 pub struct Llama {
     #[serde(flatten)]
-    horse: Horse,
+    horse: AbstractHorse,
     #[serde(flatten)]
     chested_horse: ChestedHorse,
     strength: i32,
     variant: Variant,
 }
 
+fields! {
+    Llama {
+        copy {
+            strength, set_strength: i32;
+        }
+        reference {
+            variant, set_variant: Variant;
+        }
+        part {
+            horse, horse_mut: AbstractHorse;
+            chested_horse, chested_horse_mut: ChestedHorse;
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, FromRepr)]
 #[serde(try_from = "i32", into = "i32")]
 pub enum Variant {
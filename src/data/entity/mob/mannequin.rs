@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
 
 use crate::{
     OneOf,
@@ -9,6 +10,7 @@ use crate::{
     text_component::TextComponent,
 };
 
+#[skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone)]
 pub struct Mannequin {
     profile: OneOf<MannequinProfile, Profile>,
@@ -22,6 +24,28 @@ pub struct Mannequin {
     hide_description: Option<bool>,
 }
 
+fields! {
+    Mannequin {
+        copy {
+            immovable, set_immovable: bool;
+        }
+        reference {
+            profile, set_profile: OneOf<MannequinProfile, Profile>;
+            main_hand, set_main_hand: Hand;
+            pose, set_pose: Pose;
+        }
+        opt {
+            description, set_description: TextComponent;
+        }
+        opt_copy {
+            hide_description, set_hide_description: bool;
+        }
+        list {
+            hidden_layers, hidden_layers_mut: SkinLayer;
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 pub struct MannequinProfile {
     texture: Identifier,
@@ -31,6 +55,19 @@ pub struct MannequinProfile {
     model: Model,
 }
 
+fields! {
+    MannequinProfile {
+        reference {
+            texture, set_texture: Identifier;
+            model, set_model: Model;
+        }
+        opt {
+            cape, set_cape: Identifier;
+            elytra, set_elytra: Identifier;
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(rename_all = "snake_case")]
 pub enum Hand {
@@ -1,79 +1,172 @@
-use crate::data::{PositionInDimension, entity::McUuid};
-use serde::{Deserialize, Serialize};
+use crate::data::{
+    PositionInDimension,
+    entity::{EntityKind, McUuid, mob::I8Bool},
+    prefix_minecraft_namespace,
+};
+use fastnbt::Value;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use serde_with::skip_serializing_none;
 use std::collections::HashMap;
 
-#[derive(Serialize, Deserialize, Clone)]
+/// The memories a mob's brain saves, which depend on the kind of mob
+#[derive(Serialize, Clone)]
 #[serde(untagged)]
 pub enum Memories {
-    Allay {
-        item_pickup_cooldown_ticks: Option<ValueHolder<i32>>,
-        liked_noteblock: Option<ValueHolder<PositionInDimension>>,
-        liked_noteblock_cooldown_ticks: Option<ValueHolder<i32>>,
-        liked_player: Option<ValueHolder<McUuid>>,
-    },
-    Axolotl {
-        has_hunting_cooldown: Option<EphemeralValueHolder<bool>>,
-        play_dead_ticks: Option<ValueHolder<i32>>,
-        #[serde(flatten)]
-        temptation: Temptation,
-    },
-    Camel {
-        gaze_cooldown_ticks: Option<ValueHolder<i32>>,
-        #[serde(flatten)]
-        temptation: Temptation,
-    },
-    CopperGolem {
-        is_panicking: Option<ValueHolder<bool>>,
-        visited_block_positions: Option<EphemeralValueHolder<Vec<PositionInDimension>>>,
-        gaze_cooldown_ticks: Option<ValueHolder<i32>>,
-    },
-    Frog {
-        is_in_water: Option<ValueHolder<EmptyCompound>>,
-        is_pregnant: Option<ValueHolder<EmptyCompound>>,
-    },
-    Goat {
-        long_jump_cooling_down: Option<ValueHolder<i32>>,
-        ram_cooldown_ticcks: Option<ValueHolder<i32>>,
-        #[serde(flatten)]
-        temptation: Temptation,
-    },
-    Piglin {
-        admiring_disabled: Option<EphemeralValueHolder<bool>>,
-        admiring_item: Option<EphemeralValueHolder<bool>>,
-        angry_at: Option<EphemeralValueHolder<McUuid>>,
-        hunted_reccently: Option<EphemeralValueHolder<bool>>,
-        universal_anger: Option<EphemeralValueHolder<bool>>,
-    },
-    PiglinBrute {
-        angry_at: Option<EphemeralValueHolder<McUuid>>,
-        home: Option<ValueHolder<PositionInDimension>>,
-    },
-    Sniffer {
-        sniffer_explored_positions: Option<ValueHolder<Vec<PositionInDimension>>>,
-        sniff_cooldown: Option<EphemeralValueHolder<EmptyCompound>>,
-    },
-    Villager {
-        home: Option<ValueHolder<PositionInDimension>>,
-        job_site: Option<ValueHolder<PositionInDimension>>,
-        last_slept: Option<ValueHolder<i64>>,
-        last_woken: Option<ValueHolder<i64>>,
-        last_worked_at_poi: Option<ValueHolder<i64>>,
-        meeting_point: Option<ValueHolder<PositionInDimension>>,
-        // Boxed at recommendation of compiler
-        potential_job_site: Option<Box<ValueHolder<PositionInDimension>>>,
-        golem_detected_recently: Option<EphemeralValueHolder<bool>>,
-    },
-    Warden {
-        is_emerging: Option<ValueHolder<EmptyCompound>>,
-        dig_cooldown: Option<EphemeralValueHolder<EmptyCompound>>,
-        is_sniffing: Option<ValueHolder<EmptyCompound>>,
-        recent_projectile: Option<EphemeralValueHolder<EmptyCompound>>,
-        roar_sound_cooldown: Option<EphemeralValueHolder<EmptyCompound>>,
-        roar_sound_delay: Option<EphemeralValueHolder<EmptyCompound>>,
-        touch_cooldown: Option<EphemeralValueHolder<EmptyCompound>>,
-        vibration_cooldown: Option<EphemeralValueHolder<EmptyCompound>>,
-        sniff_cooldown: Option<EphemeralValueHolder<EmptyCompound>>,
-    },
+    Allay(AllayMemories),
+    Axolotl(AxolotlMemories),
+    Camel(CamelMemories),
+    CopperGolem(CopperGolemMemories),
+    Frog(FrogMemories),
+    Goat(GoatMemories),
+    Piglin(PiglinMemories),
+    PiglinBrute(PiglinBruteMemories),
+    Sniffer(SnifferMemories),
+    // Boxed at recommendation of compiler
+    Villager(Box<VillagerMemories>),
+    Warden(WardenMemories),
+    /// Those of mobs that don't save any we know of, kept as they are
+    Other(HashMap<String, Value>),
+}
+
+impl Memories {
+    pub(crate) fn from_nbt(kind: EntityKind, nbt: &[u8]) -> fastnbt::error::Result<Self> {
+        use EntityKind as K;
+        Ok(match kind {
+            K::Allay => Self::Allay(read(nbt)?),
+            K::Axolotl => Self::Axolotl(read(nbt)?),
+            K::Camel => Self::Camel(read(nbt)?),
+            K::CopperGolem => Self::CopperGolem(read(nbt)?),
+            K::Frog => Self::Frog(read(nbt)?),
+            K::Goat => Self::Goat(read(nbt)?),
+            K::Piglin => Self::Piglin(read(nbt)?),
+            K::PiglinBrute => Self::PiglinBrute(read(nbt)?),
+            K::Sniffer => Self::Sniffer(read(nbt)?),
+            K::Villager => Self::Villager(Box::new(read(nbt)?)),
+            K::Warden => Self::Warden(read(nbt)?),
+            _ => Self::Other(read(nbt)?),
+        })
+    }
+}
+
+/// Reads the memories out of a whole mob, which may not have a brain at all
+fn read<T: DeserializeOwned + Default>(nbt: &[u8]) -> fastnbt::error::Result<T> {
+    #[derive(Deserialize)]
+    #[serde(bound = "T: DeserializeOwned + Default")]
+    struct Mob<T> {
+        #[serde(rename = "Brain")]
+        brain: Option<Brain<T>>,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(bound = "T: DeserializeOwned + Default")]
+    struct Brain<T> {
+        #[serde(default, with = "prefix_minecraft_namespace")]
+        memories: T,
+    }
+
+    let mob: Mob<T> = fastnbt::from_bytes(nbt)?;
+    Ok(mob.brain.map_or_else(T::default, |brain| brain.memories))
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct AllayMemories {
+    item_pickup_cooldown_ticks: Option<ValueHolder<i32>>,
+    liked_noteblock: Option<ValueHolder<PositionInDimension>>,
+    liked_noteblock_cooldown_ticks: Option<ValueHolder<i32>>,
+    liked_player: Option<ValueHolder<McUuid>>,
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct AxolotlMemories {
+    has_hunting_cooldown: Option<EphemeralValueHolder<bool>>,
+    play_dead_ticks: Option<ValueHolder<i32>>,
+    #[serde(flatten)]
+    temptation: Temptation,
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct CamelMemories {
+    gaze_cooldown_ticks: Option<ValueHolder<i32>>,
+    #[serde(flatten)]
+    temptation: Temptation,
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct CopperGolemMemories {
+    is_panicking: Option<ValueHolder<bool>>,
+    visited_block_positions: Option<EphemeralValueHolder<Vec<PositionInDimension>>>,
+    gaze_cooldown_ticks: Option<ValueHolder<i32>>,
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct FrogMemories {
+    is_in_water: Option<ValueHolder<EmptyCompound>>,
+    is_pregnant: Option<ValueHolder<EmptyCompound>>,
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct GoatMemories {
+    long_jump_cooling_down: Option<ValueHolder<i32>>,
+    ram_cooldown_ticks: Option<ValueHolder<i32>>,
+    #[serde(flatten)]
+    temptation: Temptation,
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct PiglinMemories {
+    admiring_disabled: Option<EphemeralValueHolder<bool>>,
+    admiring_item: Option<EphemeralValueHolder<bool>>,
+    angry_at: Option<EphemeralValueHolder<McUuid>>,
+    hunted_recently: Option<EphemeralValueHolder<bool>>,
+    universal_anger: Option<EphemeralValueHolder<bool>>,
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct PiglinBruteMemories {
+    angry_at: Option<EphemeralValueHolder<McUuid>>,
+    home: Option<ValueHolder<PositionInDimension>>,
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct SnifferMemories {
+    sniffer_explored_positions: Option<ValueHolder<Vec<PositionInDimension>>>,
+    sniff_cooldown: Option<EphemeralValueHolder<EmptyCompound>>,
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct VillagerMemories {
+    home: Option<ValueHolder<PositionInDimension>>,
+    job_site: Option<ValueHolder<PositionInDimension>>,
+    last_slept: Option<ValueHolder<i64>>,
+    last_woken: Option<ValueHolder<i64>>,
+    last_worked_at_poi: Option<ValueHolder<i64>>,
+    meeting_point: Option<ValueHolder<PositionInDimension>>,
+    potential_job_site: Option<ValueHolder<PositionInDimension>>,
+    golem_detected_recently: Option<EphemeralValueHolder<bool>>,
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct WardenMemories {
+    is_emerging: Option<ValueHolder<EmptyCompound>>,
+    dig_cooldown: Option<EphemeralValueHolder<EmptyCompound>>,
+    is_sniffing: Option<ValueHolder<EmptyCompound>>,
+    recent_projectile: Option<EphemeralValueHolder<EmptyCompound>>,
+    roar_sound_cooldown: Option<EphemeralValueHolder<EmptyCompound>>,
+    roar_sound_delay: Option<EphemeralValueHolder<EmptyCompound>>,
+    touch_cooldown: Option<EphemeralValueHolder<EmptyCompound>>,
+    vibration_cooldown: Option<EphemeralValueHolder<EmptyCompound>>,
+    sniff_cooldown: Option<EphemeralValueHolder<EmptyCompound>>,
 }
 
 #[derive(Clone)]
@@ -102,9 +195,11 @@ impl<'de> Deserialize<'de> for EmptyCompound {
     }
 }
 
-#[derive(Serialize, Deserialize, Clone)]
+// Flattened into the memories that save it, which won't read a bool from a byte
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone, Default)]
 pub struct Temptation {
-    is_tempted: Option<ValueHolder<bool>>,
+    is_tempted: Option<ValueHolder<I8Bool>>,
     temptation_cooldown_ticks: Option<ValueHolder<i32>>,
 }
 
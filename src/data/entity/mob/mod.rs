@@ -2,135 +2,372 @@ pub mod allay;
 pub mod armadillo;
 pub mod armor_stand;
 pub mod axolotl;
+pub mod bat;
+pub mod bee;
 pub mod cat;
+pub mod chicken;
 pub mod copper_golem;
+pub mod cow;
+pub mod creeper;
+pub mod dolphin;
 pub mod ender_dragon;
 pub mod enderman;
+pub mod endermite;
+pub mod fox;
 pub mod frog;
+pub mod ghast;
+pub mod glow_squid;
+pub mod goat;
+pub mod hoglin;
 pub mod horse;
+pub mod iron_golem;
 pub mod llama;
 pub mod mannequin;
 pub mod memories;
 pub mod mooshroom;
+pub mod ocelot;
 pub mod panda;
 pub mod parrot;
+pub mod phantom;
+pub mod pig;
+pub mod piglin;
+pub mod polar_bear;
 pub mod pufferfish;
 pub mod rabbit;
 pub mod raider;
 pub mod salmon;
+pub mod sheep;
 pub mod shulker;
+pub mod skeleton;
+pub mod slime;
+pub mod snow_golem;
+pub mod tadpole;
 pub mod tropical_fish;
+pub mod turtle;
+pub mod vex;
 pub mod villager;
 pub mod warden;
+pub mod wither;
 pub mod wolf;
 pub mod zombie;
 
 use crate::{
     OneOf,
     data::{
-        Identifier, Item, MinecraftNamespaceWrapper,
+        BlockPos, Identifier, Item,
         components::AttributeModifierOperation,
         entity::{
-            McUuid,
+            EntityKind, McUuid,
             mob::{
                 allay::Allay,
                 armadillo::Armadillo,
                 armor_stand::ArmorStand,
+                axolotl::Axolotl,
+                bat::Bat,
+                bee::Bee,
+                cat::Cat,
+                chicken::Chicken,
                 copper_golem::CopperGolem,
+                cow::Cow,
+                creeper::Creeper,
+                dolphin::Dolphin,
                 ender_dragon::EnderDragon,
                 enderman::Enderman,
-                horse::{ChestedHorse, Horse},
+                endermite::Endermite,
+                fox::Fox,
+                frog::Frog,
+                ghast::Ghast,
+                glow_squid::GlowSquid,
+                goat::Goat,
+                hoglin::{Hoglin, Zoglin},
+                horse::{AbstractHorse, Camel, Donkey, Horse, SkeletonHorse},
+                iron_golem::IronGolem,
                 llama::Llama,
                 mannequin::Mannequin,
-                memories::Memories,
+                memories::{EmptyCompound, Memories},
                 mooshroom::Mooshroom,
+                ocelot::Ocelot,
                 panda::Panda,
                 parrot::Parrot,
+                phantom::Phantom,
+                pig::Pig,
+                piglin::{Piglin, PiglinBrute},
+                polar_bear::PolarBear,
                 pufferfish::Pufferfish,
                 rabbit::Rabbit,
-                raider::Raider,
+                raider::{Pillager, Raider, Ravager, Spellcaster, Vindicator},
                 salmon::Salmon,
+                sheep::Sheep,
                 shulker::Shulker,
+                skeleton::Skeleton,
+                slime::Slime,
+                snow_golem::SnowGolem,
+                tadpole::Tadpole,
                 tropical_fish::TropicalFish,
-                villager::{Offers, Villager},
+                turtle::Turtle,
+                vex::Vex,
+                villager::{Villager, WanderingTrader},
                 warden::Warden,
+                wither::Wither,
                 wolf::Wolf,
-                zombie::Zombie,
+                zombie::{Zombie, ZombieVillager, ZombifiedPiglin},
             },
         },
-        player::Player,
+        prefix_minecraft_namespace,
+        registry::builtin,
     },
+    types::{AsVarInt, Codec, Type},
 };
 use derive_more::{AsMut, AsRef, Deref, DerefMut};
 use serde::{Deserialize, Serialize};
+use serde_with::{FromInto, serde_as, skip_serializing_none};
+use std::io::{Read, Write};
 use strum::FromRepr;
+use uuid::Uuid;
 
+/// A living entity. What it saves beyond that depends on whether it has AI, which armor stands and
+/// mannequins don't, and on what kind of mob it is.
+#[derive(Serialize, Clone)]
+pub struct Mob {
+    #[serde(flatten)]
+    living: Living,
+    #[serde(flatten)]
+    equipment: Equipment,
+    #[serde(rename = "Brain")]
+    brain: Brain,
+    #[serde(flatten)]
+    mob: Option<LivingMob>,
+    #[serde(flatten)]
+    unique: Option<UniqueMob>,
+}
+
+impl Mob {
+    pub(crate) fn from_nbt(kind: EntityKind, nbt: &[u8]) -> fastnbt::error::Result<Self> {
+        Ok(Self {
+            living: fastnbt::from_bytes(nbt)?,
+            equipment: fastnbt::from_bytes(nbt)?,
+            brain: Brain::from_nbt(kind, nbt)?,
+            mob: if kind.is_mob() {
+                Some(fastnbt::from_bytes(nbt)?)
+            } else {
+                None
+            },
+            unique: UniqueMob::from_nbt(kind, nbt)?,
+        })
+    }
+
+    pub const fn health(&self) -> f32 {
+        self.living.health
+    }
+
+    /// Health above the most this kind of mob can have is lowered to that when it's loaded
+    pub fn set_health(&mut self, health: f32) {
+        self.living.health = health;
+    }
+
+    pub const fn absorption_amount(&self) -> f32 {
+        self.living.absorption_amount
+    }
+
+    pub fn set_absorption_amount(&mut self, absorption_amount: f32) {
+        self.living.absorption_amount = absorption_amount;
+    }
+
+    pub fn active_effects(&self) -> &[PotionEffect] {
+        self.living.active_effects.as_deref().unwrap_or_default()
+    }
+
+    pub fn active_effects_mut(&mut self) -> &mut Vec<PotionEffect> {
+        self.living.active_effects.get_or_insert_default()
+    }
+
+    /// Attributes whose base differs from the default for this kind of mob, or that are modified
+    pub fn attributes(&self) -> &[MobAttribute] {
+        &self.living.attributes
+    }
+
+    pub fn attributes_mut(&mut self) -> &mut Vec<MobAttribute> {
+        &mut self.living.attributes
+    }
+
+    pub fn armor_items(&self) -> &ArmorItems {
+        &self.equipment.armor_items
+    }
+
+    pub fn armor_items_mut(&mut self) -> &mut ArmorItems {
+        &mut self.equipment.armor_items
+    }
+
+    pub fn hand_items(&self) -> &HandItems {
+        &self.equipment.hand_items
+    }
+
+    pub fn hand_items_mut(&mut self) -> &mut HandItems {
+        &mut self.equipment.hand_items
+    }
+
+    pub const fn death_time(&self) -> i16 {
+        self.living.death_time
+    }
+
+    pub fn set_death_time(&mut self, death_time: i16) {
+        self.living.death_time = death_time;
+    }
+
+    pub const fn fall_flying(&self) -> bool {
+        self.living.fall_flying
+    }
+
+    pub fn set_fall_flying(&mut self, fall_flying: bool) {
+        self.living.fall_flying = fall_flying;
+    }
+
+    pub const fn hurt_time(&self) -> i16 {
+        self.living.hurt_time
+    }
+
+    pub fn set_hurt_time(&mut self, hurt_time: i16) {
+        self.living.hurt_time = hurt_time;
+    }
+
+    pub const fn hurt_by_timestamp(&self) -> i32 {
+        self.living.hurt_by_timestamp
+    }
+
+    pub fn set_hurt_by_timestamp(&mut self, hurt_by_timestamp: i32) {
+        self.living.hurt_by_timestamp = hurt_by_timestamp;
+    }
+
+    /// The bed it's sleeping in, if it's asleep
+    pub fn sleeping_pos(&self) -> Option<BlockPos> {
+        let living = &self.living;
+        Some(BlockPos::new(
+            living.sleeping_x?,
+            living.sleeping_y?,
+            living.sleeping_z?,
+        ))
+    }
+
+    pub fn set_sleeping_pos(&mut self, pos: impl Into<Option<BlockPos>>) {
+        let pos = pos.into();
+        self.living.sleeping_x = pos.map(|pos| pos.x);
+        self.living.sleeping_y = pos.map(|pos| pos.y);
+        self.living.sleeping_z = pos.map(|pos| pos.z);
+    }
+
+    pub fn brain(&self) -> &Brain {
+        &self.brain
+    }
+
+    /// What only mobs with AI save, which is `None` for armor stands and mannequins
+    pub fn mob(&self) -> Option<&LivingMob> {
+        self.mob.as_ref()
+    }
+
+    pub fn mob_mut(&mut self) -> Option<&mut LivingMob> {
+        self.mob.as_mut()
+    }
+
+    /// What only this kind of mob saves, if anything
+    pub fn unique(&self) -> Option<&UniqueMob> {
+        self.unique.as_ref()
+    }
+
+    pub fn unique_mut(&mut self) -> Option<&mut UniqueMob> {
+        self.unique.as_mut()
+    }
+}
+
+/// What every living entity saves, players included.
+#[skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(rename_all = "PascalCase")]
-pub struct Mob {
+pub(crate) struct Living {
     absorption_amount: f32,
-    #[serde(rename = "active_effects", default)]
-    active_effects: Vec<PotionEffect>,
+    #[serde(rename = "active_effects")]
+    active_effects: Option<Vec<PotionEffect>>,
     #[serde(rename = "attributes", default)]
     attributes: Vec<MobAttribute>,
-    brain: Brain,
-    can_pick_up_loot: bool,
     death_time: i16,
-    #[serde(default)]
-    armor_items: ArmorItems,
-    #[serde(default)]
-    hand_items: HandItems,
-    #[serde(rename = "body_armor_item")]
-    body_armor_item: Option<Item>,
-    fall_flying: I8Bool,
-    health: f32,
+    fall_flying: bool,
+    pub(crate) health: f32,
     hurt_by_timestamp: i32,
     hurt_time: i16,
-    #[serde(rename = "leash")]
-    leash: Option<OneOf<[i32; 3], UuidHolder>>,
-    left_handed: bool,
-    #[serde(rename = "NoAI")]
-    no_ai: bool,
-    persistence_required: bool,
-    sleeping_x: i32,
-    sleeping_y: i32,
-    sleeping_z: i32,
+    sleeping_x: Option<i32>,
+    sleeping_y: Option<i32>,
+    sleeping_z: Option<i32>,
     // FIXME: only used when spawning a mob!! :<
     team: Option<String>,
-    #[serde(flatten)]
-    unique: Option<OneOf<LivingMob, DummyMob>>,
 }
 
+/// The armor and held items of a mob or armor stand. Players keep theirs in their inventory.
 #[derive(Serialize, Deserialize, Clone)]
-#[serde(untagged)]
-pub enum DummyMob {
-    ArmorStand(ArmorStand),
-    // Boxed at recommendation of compiler
-    Mannequin(Box<Mannequin>),
-    // Funny to describe a player as a "dummy", but it is missing the same fields
-    // Boxed at recommendation of compiler
-    Player(Box<Player>),
+#[serde(rename_all = "PascalCase")]
+struct Equipment {
+    #[serde(default)]
+    armor_items: ArmorItems,
+    #[serde(default)]
+    hand_items: HandItems,
 }
 
+#[skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
 pub struct LivingMob {
-    #[serde(rename = "CanPickUpLoot")]
     can_pick_up_loot: bool,
-    #[serde(rename = "LeftHanded")]
     left_handed: bool,
     persistence_required: bool,
-    leash: Option<OneOf<[i32; 3], UuidHolder>>,
+    #[serde(rename = "leash")]
+    leash: Option<OneOf<BlockPos, UuidHolder>>,
+    #[serde(default)]
     armor_drop_chances: ArmorDropChances,
+    #[serde(default)]
     hand_drop_chances: HandDropChances,
+    #[serde(rename = "body_armor_item")]
+    body_armor_item: Option<Item>,
     #[serde(rename = "body_armor_drop_chance")]
-    body_armor_drop_chance: i8,
+    body_armor_drop_chance: Option<f32>,
+    death_loot_table: Option<Identifier>,
+    death_loot_table_seed: Option<i64>,
     #[serde(rename = "NoAI")]
-    no_ai: bool,
-    #[serde(flatten)]
-    unique: Option<UniqueMob>,
+    no_ai: Option<bool>,
 }
+
+impl LivingMob {
+    pub const fn no_ai(&self) -> bool {
+        matches!(self.no_ai, Some(true))
+    }
+
+    pub fn set_no_ai(&mut self, no_ai: bool) {
+        self.no_ai = no_ai.then_some(true);
+    }
+}
+
+fields! {
+    LivingMob {
+        copy {
+            can_pick_up_loot, set_can_pick_up_loot: bool;
+            left_handed, set_left_handed: bool;
+            persistence_required, set_persistence_required: bool;
+        }
+        opt {
+            leash, set_leash: OneOf<BlockPos, UuidHolder>;
+            body_armor_item, set_body_armor_item: Item;
+            death_loot_table, set_death_loot_table: Identifier;
+        }
+        opt_copy {
+            body_armor_drop_chance, set_body_armor_drop_chance: f32;
+            death_loot_table_seed, set_death_loot_table_seed: i64;
+        }
+        part {
+            armor_drop_chances, armor_drop_chances_mut: ArmorDropChances;
+            hand_drop_chances, hand_drop_chances_mut: HandDropChances;
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Default)]
-#[serde(into = "[Option<Item>; 4]", from = "[Option<Item>; 4]")]
+#[serde(into = "[EquipmentSlot; 4]", from = "[EquipmentSlot; 4]")]
 pub struct ArmorItems {
     feet: Option<Item>,
     legs: Option<Item>,
@@ -138,9 +375,20 @@ pub struct ArmorItems {
     head: Option<Item>,
 }
 
-impl From<[Option<Item>; 4]> for ArmorItems {
-    fn from(value: [Option<Item>; 4]) -> Self {
-        let [feet, legs, chest, head] = value;
+fields! {
+    ArmorItems {
+        opt {
+            feet, set_feet: Item;
+            legs, set_legs: Item;
+            chest, set_chest: Item;
+            head, set_head: Item;
+        }
+    }
+}
+
+impl From<[EquipmentSlot; 4]> for ArmorItems {
+    fn from(value: [EquipmentSlot; 4]) -> Self {
+        let [feet, legs, chest, head] = value.map(|slot| slot.0);
         Self {
             feet,
             legs,
@@ -150,29 +398,67 @@ impl From<[Option<Item>; 4]> for ArmorItems {
     }
 }
 
-impl From<ArmorItems> for [Option<Item>; 4] {
+impl From<ArmorItems> for [EquipmentSlot; 4] {
     fn from(value: ArmorItems) -> Self {
-        [value.feet, value.legs, value.chest, value.head]
+        [value.feet, value.legs, value.chest, value.head].map(EquipmentSlot)
     }
 }
 
 #[derive(Serialize, Deserialize, Clone, Default)]
-#[serde(into = "[Option<Item>; 2]", from = "[Option<Item>; 2]")]
+#[serde(into = "[EquipmentSlot; 2]", from = "[EquipmentSlot; 2]")]
 pub struct HandItems {
     mainhand: Option<Item>,
     offhand: Option<Item>,
 }
 
-impl From<[Option<Item>; 2]> for HandItems {
-    fn from(value: [Option<Item>; 2]) -> Self {
-        let [mainhand, offhand] = value;
+fields! {
+    HandItems {
+        opt {
+            mainhand, set_mainhand: Item;
+            offhand, set_offhand: Item;
+        }
+    }
+}
+
+impl From<[EquipmentSlot; 2]> for HandItems {
+    fn from(value: [EquipmentSlot; 2]) -> Self {
+        let [mainhand, offhand] = value.map(|slot| slot.0);
         Self { mainhand, offhand }
     }
 }
 
-impl From<HandItems> for [Option<Item>; 2] {
+impl From<HandItems> for [EquipmentSlot; 2] {
     fn from(value: HandItems) -> Self {
-        [value.mainhand, value.offhand]
+        [value.mainhand, value.offhand].map(EquipmentSlot)
+    }
+}
+
+/// A slot of `ArmorItems` or `HandItems`, which is an empty compound when there's nothing in it.
+pub struct EquipmentSlot(Option<Item>);
+
+impl Serialize for EquipmentSlot {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match &self.0 {
+            Some(item) => item.serialize(serializer),
+            None => EmptyCompound.serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for EquipmentSlot {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Ok(Self(
+            match OneOf::<EmptyCompound, Item>::deserialize(deserializer)? {
+                OneOf::Left(_) => None,
+                OneOf::Right(item) => Some(item),
+            },
+        ))
     }
 }
 
@@ -185,6 +471,17 @@ pub struct ArmorDropChances {
     head: f32,
 }
 
+fields! {
+    ArmorDropChances {
+        copy {
+            feet, set_feet: f32;
+            legs, set_legs: f32;
+            chest, set_chest: f32;
+            head, set_head: f32;
+        }
+    }
+}
+
 impl From<[f32; 4]> for ArmorDropChances {
     fn from(value: [f32; 4]) -> Self {
         Self {
@@ -209,6 +506,15 @@ pub struct HandDropChances {
     offhand: f32,
 }
 
+fields! {
+    HandDropChances {
+        copy {
+            mainhand, set_mainhand: f32;
+            offhand, set_offhand: f32;
+        }
+    }
+}
+
 impl From<[f32; 2]> for HandDropChances {
     fn from(value: [f32; 2]) -> Self {
         Self {
@@ -224,356 +530,248 @@ impl From<HandDropChances> for [f32; 2] {
     }
 }
 
-#[derive(Serialize, Deserialize, Clone)]
+/// What only one kind of mob saves, picked by its `id`. Mobs that save nothing of their own, like
+/// spiders, have none.
+#[derive(Serialize, Clone)]
 #[serde(untagged)]
 pub enum UniqueMob {
     Allay(Allay),
     Armadillo(Armadillo),
     ArmorStand(ArmorStand),
-    #[serde(rename_all = "PascalCase")]
-    Axolotl {
-        from_bucket: I8Bool,
-        variant: i32,
-        #[serde(flatten)]
-        breedable: Breedable,
-    },
-    #[serde(rename_all = "PascalCase")]
-    Bat {
-        bat_flags: I8Bool,
-    },
-    #[serde(rename_all = "PascalCase")]
-    Bee {
-        cannot_enter_hive_ticks: i32,
-        crops_grown_since_pollination: i32,
-        #[serde(rename = "flower_pos")]
-        flower_pos: [i32; 3],
-        has_nectar: bool,
-        #[serde(rename = "hive_pos")]
-        hive_pos: [i32; 3],
-        ticks_since_pollination: i32,
-        #[serde(flatten)]
-        breedable: Breedable,
-        #[serde(flatten)]
-        angerable: Angerable,
-    },
-    #[serde(rename_all = "PascalCase")]
-    Camel {
-        #[serde(flatten)]
-        horse: Horse,
-        last_pose_tick: i64,
-    },
-    #[serde(rename_all = "PascalCase")]
-    Cat {
-        collar_color: i8,
-        #[serde(rename = "variant")]
-        variant: Identifier,
-        #[serde(flatten)]
-        breedable: Breedable,
-        #[serde(flatten)]
-        tameable: Tameable,
-    },
-    #[serde(rename_all = "PascalCase")]
-    Chicken {
-        egg_lay_time: i32,
-        is_chicken_jockey: bool,
-        #[serde(rename = "variant")]
-        variant: Identifier,
-        #[serde(flatten)]
-        breedable: Breedable,
-    },
-    #[serde(rename_all = "PascalCase")]
-    Cod {
-        from_bucket: I8Bool,
-    },
+    Axolotl(Axolotl),
+    Bat(Bat),
+    Bee(Bee),
+    Camel(Camel),
+    Cat(Cat),
+    Chicken(Chicken),
+    Cod(Bucketable),
     CopperGolem(CopperGolem),
-    Cow {
-        variant: Identifier,
-        #[serde(flatten)]
-        breedable: Breedable,
-    },
-    Creeper {
-        #[serde(rename = "ExplosionRadius")]
-        explosion_radius: i8,
-        #[serde(rename = "Fuse")]
-        fuse: i16,
-        ignited: I8Bool,
-        powered: I8Bool,
-    },
-    #[serde(rename_all = "PascalCase")]
-    Dolphin {
-        moistness: i32,
-        got_fish: I8Bool,
-        #[serde(flatten)]
-        breedable: Breedable,
-    },
-    #[serde(rename_all = "PascalCase")]
-    Donkey {
-        #[serde(flatten)]
-        horse: Horse,
-        #[serde(flatten)]
-        chested_horse: ChestedHorse,
-    },
+    Cow(Cow),
+    Creeper(Creeper),
+    Dolphin(Dolphin),
+    Donkey(Donkey),
     Drowned(Zombie),
     EnderDragon(EnderDragon),
     Enderman(Enderman),
-    #[serde(rename_all = "PascalCase")]
-    Endermite {
-        lifetime: i32,
-    },
-    #[serde(rename_all = "PascalCase")]
-    Evoker {
-        spell_ticks: i32,
-        #[serde(flatten)]
-        raider: Raider,
-    },
-    #[serde(rename_all = "PascalCase")]
-    Fox {
-        crouching: I8Bool,
-        sitting: I8Bool,
-        sleeping: I8Bool,
-        #[serde(default)]
-        trusted: Vec<McUuid>,
-        #[serde(rename = "Type")]
-        kind: Identifier,
-        #[serde(flatten)]
-        breedable: Breedable,
-    },
-    Frog {
-        variant: Identifier,
-        #[serde(flatten)]
-        breedable: Breedable,
-    },
-    #[serde(rename_all = "PascalCase")]
-    Ghast {
-        explosion_power: i8,
-    },
-    #[serde(rename_all = "PascalCase")]
-    GlowSquid {
-        dark_ticks_remaining: i32,
-        #[serde(flatten)]
-        breedable: Breedable,
-    },
-    #[serde(rename_all = "PascalCase")]
-    Goat {
-        has_left_horn: I8Bool,
-        has_right_horn: I8Bool,
-        is_screaming_goat: I8Bool,
-        #[serde(flatten)]
-        breedable: Breedable,
-    },
-    #[serde(rename_all = "PascalCase")]
-    Hoglin {
-        cannot_be_hunted: bool,
-        is_immune_to_zombification: bool,
-        time_in_overworld: i32,
-    },
-    #[serde(rename_all = "PascalCase")]
-    Horse {
-        #[serde(flatten)]
-        horse: Horse,
-        variant: i32,
-    },
+    Endermite(Endermite),
+    Evoker(Spellcaster),
+    Fox(Fox),
+    Frog(Frog),
+    Ghast(Ghast),
+    GlowSquid(GlowSquid),
+    Goat(Goat),
+    Hoglin(Hoglin),
+    Horse(Horse),
     Husk(Zombie),
-    #[serde(rename_all = "PascalCase")]
-    Illusioner {
-        spell_ticks: i32,
-        #[serde(flatten)]
-        raider: Raider,
-    },
-    #[serde(rename_all = "PascalCase")]
-    IronGolem {
-        player_created: I8Bool,
-        #[serde(flatten)]
-        angerable: Angerable,
-    },
+    Illusioner(Spellcaster),
+    IronGolem(IronGolem),
     Llama(Llama),
-    #[serde(rename_all = "PascalCase")]
-    MagmaCube {
-        size: i32,
-        #[serde(rename = "wasOnGround")]
-        was_on_ground: I8Bool,
-    },
+    MagmaCube(Slime),
+    // Boxed at recommendation of compiler
+    Mannequin(Box<Mannequin>),
     Mooshroom(Mooshroom),
-    Mule {
-        #[serde(flatten)]
-        horse: Horse,
-        #[serde(flatten)]
-        chested_horse: ChestedHorse,
-    },
-    #[serde(rename_all = "PascalCase")]
-    Ocelot {
-        trusting: bool,
-        #[serde(flatten)]
-        breedable: Breedable,
-    },
+    Mule(Donkey),
+    Ocelot(Ocelot),
     Panda(Panda),
     Parrot(Parrot),
-    Phantom {
-        size: i32,
-        anchor_pos: Option<[i32; 3]>,
-    },
-    Pig {
-        // TODO: this is probably an identifier?
-        variant: String,
-        #[serde(flatten)]
-        breedable: Breedable,
-    },
-    #[serde(rename_all = "PascalCase")]
-    Piglin {
-        cannot_hunt: bool,
-        #[serde(default)]
-        inventory: Vec<Item>,
-        #[serde(default)]
-        is_baby: bool,
-        is_immune_to_zombification: bool,
-        time_in_overworld: i32,
-    },
-    #[serde(rename_all = "PascalCase")]
-    PiglinBrute {
-        is_immune_to_zombification: bool,
-        time_in_overworld: i32,
-    },
-    #[serde(rename_all = "PascalCase")]
-    Pillager {
-        #[serde(default)]
-        inventory: Vec<Item>,
-        #[serde(flatten)]
-        raider: Raider,
-    },
-    PolarBear {
-        #[serde(flatten)]
-        angerable: Angerable,
-        #[serde(flatten)]
-        breedable: Breedable,
-    },
+    Phantom(Phantom),
+    Pig(Pig),
+    Piglin(Piglin),
+    PiglinBrute(PiglinBrute),
+    Pillager(Pillager),
+    PolarBear(PolarBear),
     Pufferfish(Pufferfish),
     Rabbit(Rabbit),
-    #[serde(rename_all = "PascalCase")]
-    Ravager {
-        #[serde(flatten)]
-        raider: Raider,
-        attack_tick: i32,
-        roar_tick: i32,
-        stun_tick: i32,
-    },
+    Ravager(Ravager),
     Salmon(Salmon),
-    #[serde(rename_all = "PascalCase")]
-    Sheep {
-        #[serde(default = "default_sheep_color")]
-        color: Color,
-        sheared: bool,
-    },
+    Sheep(Sheep),
     Shulker(Shulker),
-    #[serde(rename_all = "PascalCase")]
-    Skeleton {
-        stray_conversion_time: i32,
-    },
-    #[serde(rename_all = "PascalCase")]
-    SkeletonHorse {
-        #[serde(flatten)]
-        horse: Horse,
-        skeleton_trap: bool,
-        skeleton_trap_time: i32,
-    },
-    #[serde(rename_all = "camelCase")]
-    Slime {
-        #[serde(rename = "Size")]
-        size: i32,
-        was_on_ground: bool,
-    },
+    Skeleton(Skeleton),
+    SkeletonHorse(SkeletonHorse),
+    Slime(Slime),
     Sniffer(Breedable),
-    #[serde(rename_all = "PascalCase")]
-    SnowGolem {
-        pumpkin: bool,
-    },
-    Strider(Breedable),
+    SnowGolem(SnowGolem),
     Squid(Breedable),
-    #[serde(rename_all = "PascalCase")]
-    Tadpole {
-        age: i32,
-        from_bucket: bool,
-    },
+    Strider(Breedable),
+    Tadpole(Tadpole),
     TraderLlama(Llama),
     TropicalFish(TropicalFish),
-    Turtle {
-        #[serde(flatten)]
-        breedable: Breedable,
-        has_egg: bool,
-    },
-    Vex {
-        bound_pos: Option<[i32; 3]>,
-        life_ticks: i32,
-    },
+    Turtle(Turtle),
+    Vex(Vex),
     Villager(Villager),
-    #[serde(rename_all = "PascalCase")]
-    Vindicator {
-        #[serde(flatten)]
-        raider: Raider,
-        johnny: bool,
-    },
-    #[serde(rename_all = "PascalCase")]
-    WanderingTrader {
-        despawn_delay: i32,
-        offers: Offers,
-        #[serde(rename = "wander_target")]
-        wander_target: [i32; 3],
-        inventory: Vec<Item>,
-    },
+    Vindicator(Vindicator),
+    WanderingTrader(WanderingTrader),
     Warden(Warden),
     Witch(Raider),
-    #[serde(rename_all = "PascalCase")]
-    Wither {
-        invul: i32,
-    },
+    Wither(Wither),
     Wolf(Wolf),
-    #[serde(rename_all = "PascalCase")]
-    Zoglin {
-        is_baby: bool,
-    },
+    Zoglin(Zoglin),
     Zombie(Zombie),
-    ZombieHorse(Horse),
-    #[serde(rename_all = "PascalCase")]
-    ZombieVillager {
-        #[serde(flatten)]
-        villager: Villager,
-        #[serde(flatten)]
-        zombie: Zombie,
-        conversion_time: i32,
-        conversion_player: McUuid,
-    },
-    ZombifiedPiglin {
-        #[serde(flatten)]
-        angerable: Angerable,
-        #[serde(flatten)]
-        zombie: Zombie,
-    },
+    ZombieHorse(AbstractHorse),
+    ZombieVillager(ZombieVillager),
+    ZombifiedPiglin(ZombifiedPiglin),
 }
 
-const fn default_sheep_color() -> Color {
-    Color::White
+impl UniqueMob {
+    fn from_nbt(kind: EntityKind, nbt: &[u8]) -> fastnbt::error::Result<Option<Self>> {
+        use EntityKind as K;
+        Ok(Some(match kind {
+            K::Allay => Self::Allay(fastnbt::from_bytes(nbt)?),
+            K::Armadillo => Self::Armadillo(fastnbt::from_bytes(nbt)?),
+            K::ArmorStand => Self::ArmorStand(fastnbt::from_bytes(nbt)?),
+            K::Axolotl => Self::Axolotl(fastnbt::from_bytes(nbt)?),
+            K::Bat => Self::Bat(fastnbt::from_bytes(nbt)?),
+            K::Bee => Self::Bee(fastnbt::from_bytes(nbt)?),
+            K::Camel => Self::Camel(fastnbt::from_bytes(nbt)?),
+            K::Cat => Self::Cat(fastnbt::from_bytes(nbt)?),
+            K::Chicken => Self::Chicken(fastnbt::from_bytes(nbt)?),
+            K::Cod => Self::Cod(fastnbt::from_bytes(nbt)?),
+            K::CopperGolem => Self::CopperGolem(fastnbt::from_bytes(nbt)?),
+            K::Cow => Self::Cow(fastnbt::from_bytes(nbt)?),
+            K::Creeper => Self::Creeper(fastnbt::from_bytes(nbt)?),
+            K::Dolphin => Self::Dolphin(fastnbt::from_bytes(nbt)?),
+            K::Donkey => Self::Donkey(fastnbt::from_bytes(nbt)?),
+            K::Drowned => Self::Drowned(fastnbt::from_bytes(nbt)?),
+            K::EnderDragon => Self::EnderDragon(fastnbt::from_bytes(nbt)?),
+            K::Enderman => Self::Enderman(fastnbt::from_bytes(nbt)?),
+            K::Endermite => Self::Endermite(fastnbt::from_bytes(nbt)?),
+            K::Evoker => Self::Evoker(fastnbt::from_bytes(nbt)?),
+            K::Fox => Self::Fox(fastnbt::from_bytes(nbt)?),
+            K::Frog => Self::Frog(fastnbt::from_bytes(nbt)?),
+            K::Ghast => Self::Ghast(fastnbt::from_bytes(nbt)?),
+            K::GlowSquid => Self::GlowSquid(fastnbt::from_bytes(nbt)?),
+            K::Goat => Self::Goat(fastnbt::from_bytes(nbt)?),
+            K::Hoglin => Self::Hoglin(fastnbt::from_bytes(nbt)?),
+            K::Horse => Self::Horse(fastnbt::from_bytes(nbt)?),
+            K::Husk => Self::Husk(fastnbt::from_bytes(nbt)?),
+            K::Illusioner => Self::Illusioner(fastnbt::from_bytes(nbt)?),
+            K::IronGolem => Self::IronGolem(fastnbt::from_bytes(nbt)?),
+            K::Llama => Self::Llama(fastnbt::from_bytes(nbt)?),
+            K::MagmaCube => Self::MagmaCube(fastnbt::from_bytes(nbt)?),
+            K::Mannequin => Self::Mannequin(fastnbt::from_bytes(nbt)?),
+            K::Mooshroom => Self::Mooshroom(fastnbt::from_bytes(nbt)?),
+            K::Mule => Self::Mule(fastnbt::from_bytes(nbt)?),
+            K::Ocelot => Self::Ocelot(fastnbt::from_bytes(nbt)?),
+            K::Panda => Self::Panda(fastnbt::from_bytes(nbt)?),
+            K::Parrot => Self::Parrot(fastnbt::from_bytes(nbt)?),
+            K::Phantom => Self::Phantom(fastnbt::from_bytes(nbt)?),
+            K::Pig => Self::Pig(fastnbt::from_bytes(nbt)?),
+            K::Piglin => Self::Piglin(fastnbt::from_bytes(nbt)?),
+            K::PiglinBrute => Self::PiglinBrute(fastnbt::from_bytes(nbt)?),
+            K::Pillager => Self::Pillager(fastnbt::from_bytes(nbt)?),
+            K::PolarBear => Self::PolarBear(fastnbt::from_bytes(nbt)?),
+            K::Pufferfish => Self::Pufferfish(fastnbt::from_bytes(nbt)?),
+            K::Rabbit => Self::Rabbit(fastnbt::from_bytes(nbt)?),
+            K::Ravager => Self::Ravager(fastnbt::from_bytes(nbt)?),
+            K::Salmon => Self::Salmon(fastnbt::from_bytes(nbt)?),
+            K::Sheep => Self::Sheep(fastnbt::from_bytes(nbt)?),
+            K::Shulker => Self::Shulker(fastnbt::from_bytes(nbt)?),
+            K::Skeleton => Self::Skeleton(fastnbt::from_bytes(nbt)?),
+            K::SkeletonHorse => Self::SkeletonHorse(fastnbt::from_bytes(nbt)?),
+            K::Slime => Self::Slime(fastnbt::from_bytes(nbt)?),
+            K::Sniffer => Self::Sniffer(fastnbt::from_bytes(nbt)?),
+            K::SnowGolem => Self::SnowGolem(fastnbt::from_bytes(nbt)?),
+            K::Squid => Self::Squid(fastnbt::from_bytes(nbt)?),
+            K::Strider => Self::Strider(fastnbt::from_bytes(nbt)?),
+            K::Tadpole => Self::Tadpole(fastnbt::from_bytes(nbt)?),
+            K::TraderLlama => Self::TraderLlama(fastnbt::from_bytes(nbt)?),
+            K::TropicalFish => Self::TropicalFish(fastnbt::from_bytes(nbt)?),
+            K::Turtle => Self::Turtle(fastnbt::from_bytes(nbt)?),
+            K::Vex => Self::Vex(fastnbt::from_bytes(nbt)?),
+            K::Villager => Self::Villager(fastnbt::from_bytes(nbt)?),
+            K::Vindicator => Self::Vindicator(fastnbt::from_bytes(nbt)?),
+            K::WanderingTrader => Self::WanderingTrader(fastnbt::from_bytes(nbt)?),
+            K::Warden => Self::Warden(fastnbt::from_bytes(nbt)?),
+            K::Witch => Self::Witch(fastnbt::from_bytes(nbt)?),
+            K::Wither => Self::Wither(fastnbt::from_bytes(nbt)?),
+            K::Wolf => Self::Wolf(fastnbt::from_bytes(nbt)?),
+            K::Zoglin => Self::Zoglin(fastnbt::from_bytes(nbt)?),
+            K::Zombie => Self::Zombie(fastnbt::from_bytes(nbt)?),
+            K::ZombieHorse => Self::ZombieHorse(fastnbt::from_bytes(nbt)?),
+            K::ZombieVillager => Self::ZombieVillager(fastnbt::from_bytes(nbt)?),
+            K::ZombifiedPiglin => Self::ZombifiedPiglin(fastnbt::from_bytes(nbt)?),
+            _ => return Ok(None),
+        }))
+    }
 }
 
+// The structs below are flattened into those of the mobs that save them, which won't read a bool
+// from a byte, so they use `I8Bool` instead
+
+#[skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(rename_all = "PascalCase")]
 pub struct Tameable {
     owner: Option<McUuid>,
-    sitting: bool,
+    sitting: I8Bool,
 }
 
+fields! {
+    Tameable {
+        flag {
+            sitting, set_sitting;
+        }
+        opt {
+            owner, set_owner: McUuid;
+        }
+    }
+}
+
+#[skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(rename_all = "PascalCase")]
 pub struct Breedable {
     age: i32,
     forced_age: i32,
     in_love: i32,
-    love_cause: McUuid,
+    love_cause: Option<McUuid>,
+}
+
+fields! {
+    Breedable {
+        copy {
+            age, set_age: i32;
+            forced_age, set_forced_age: i32;
+            in_love, set_in_love: i32;
+        }
+        opt {
+            love_cause, set_love_cause: McUuid;
+        }
+    }
 }
 
+#[skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(rename_all = "PascalCase")]
 pub struct Angerable {
     anger_time: i32,
-    angry_at: McUuid,
+    angry_at: Option<McUuid>,
+}
+
+fields! {
+    Angerable {
+        copy {
+            anger_time, set_anger_time: i32;
+        }
+        opt {
+            angry_at, set_angry_at: McUuid;
+        }
+    }
+}
+
+/// Fish and other mobs that can be picked up in a bucket
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct Bucketable {
+    from_bucket: I8Bool,
+}
+
+fields! {
+    Bucketable {
+        flag {
+            from_bucket, set_from_bucket;
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -582,9 +780,25 @@ pub struct UuidHolder {
     uuid: McUuid,
 }
 
+impl UuidHolder {
+    pub fn new(uuid: Uuid) -> Self {
+        Self { uuid: uuid.into() }
+    }
+
+    pub fn uuid(&self) -> &McUuid {
+        &self.uuid
+    }
+}
+
 #[derive(Clone, Copy, Deref, DerefMut, AsRef, AsMut)]
 pub struct I8Bool(bool);
 
+impl From<bool> for I8Bool {
+    fn from(value: bool) -> Self {
+        Self(value)
+    }
+}
+
 impl Serialize for I8Bool {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -604,19 +818,57 @@ impl<'de> Deserialize<'de> for I8Bool {
     }
 }
 
-#[derive(Serialize, Deserialize, Clone)]
+/// A mob's memories. Which it has depends on the kind of mob, so they're read for that kind.
+#[derive(Serialize, Clone)]
 pub struct Brain {
-    memories: Option<MinecraftNamespaceWrapper<Memories>>,
+    #[serde(with = "prefix_minecraft_namespace")]
+    memories: Memories,
+}
+
+impl Brain {
+    fn from_nbt(kind: EntityKind, nbt: &[u8]) -> fastnbt::error::Result<Self> {
+        Ok(Self {
+            memories: Memories::from_nbt(kind, nbt)?,
+        })
+    }
+
+    pub fn memories(&self) -> &Memories {
+        &self.memories
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct MobAttribute {
     id: String,
     base: f64,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     modifiers: Vec<MobAttributeModifier>,
 }
 
+impl MobAttribute {
+    pub fn new(id: Identifier, base: f64) -> Self {
+        Self {
+            id: id.to_string(),
+            base,
+            modifiers: vec![],
+        }
+    }
+}
+
+fields! {
+    MobAttribute {
+        copy {
+            base, set_base: f64;
+        }
+        reference {
+            id, set_id: String;
+        }
+        list {
+            modifiers, modifiers_mut: MobAttributeModifier;
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 pub struct MobAttributeModifier {
     amount: Option<f64>,
@@ -624,17 +876,145 @@ pub struct MobAttributeModifier {
     operation: Option<AttributeModifierOperation>,
 }
 
+impl MobAttributeModifier {
+    pub fn new(id: Identifier, amount: f64, operation: AttributeModifierOperation) -> Self {
+        Self {
+            amount: Some(amount),
+            id: Some(id),
+            operation: Some(operation),
+        }
+    }
+}
+
+fields! {
+    MobAttributeModifier {
+        opt {
+            id, set_id: Identifier;
+            operation, set_operation: AttributeModifierOperation;
+        }
+        opt_copy {
+            amount, set_amount: f64;
+        }
+    }
+}
+
+/// Also read as part of item components that are tagged or have other shapes, so its booleans
+/// may come as bytes
+#[serde_as]
+#[skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone)]
 pub struct PotionEffect {
+    #[serde_as(as = "Option<FromInto<OneOf<bool, i8>>>")]
     ambient: Option<bool>,
     amplifier: Option<i8>,
     duration: Option<i32>,
     hidden_effect: Option<Box<Self>>,
     id: Option<Identifier>,
+    #[serde_as(as = "Option<FromInto<OneOf<bool, i8>>>")]
     show_icon: Option<bool>,
+    #[serde_as(as = "Option<FromInto<OneOf<bool, i8>>>")]
     show_particles: Option<bool>,
 }
 
+fields! {
+    PotionEffect {
+        opt {
+            id, set_id: Identifier;
+        }
+        opt_copy {
+            ambient, set_ambient: bool;
+            amplifier, set_amplifier: i8;
+            duration, set_duration: i32;
+            show_icon, set_show_icon: bool;
+            show_particles, set_show_particles: bool;
+        }
+    }
+}
+
+/// The effect's id, then its details. A hidden effect has the same id, so only its details are
+/// nested in those of the effect hiding it.
+impl Type for PotionEffect {
+    fn write(&self, mut writer: impl Write) -> std::io::Result<()> {
+        let id = self.id.as_ref().ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "Potion effects are sent with their id",
+            )
+        })?;
+        builtin::MOB_EFFECT.write_entry(id, &mut writer)?;
+        self.write_details(&mut writer)
+    }
+
+    fn read(mut reader: impl Read) -> std::io::Result<Self> {
+        let id = builtin::MOB_EFFECT.read_entry(&mut reader)?;
+        Ok(Self {
+            id: Some(id),
+            ..Self::read_details(&mut reader)?
+        })
+    }
+}
+
+impl PotionEffect {
+    /// An effect of level 1 lasting `duration` ticks, or forever if that's -1
+    pub fn new(id: Identifier, duration: i32) -> Self {
+        Self {
+            ambient: None,
+            amplifier: None,
+            duration: Some(duration),
+            hidden_effect: None,
+            id: Some(id),
+            show_icon: None,
+            show_particles: None,
+        }
+    }
+
+    /// A weaker or shorter effect of the same kind, which takes over when this one ends
+    pub fn hidden_effect(&self) -> Option<&Self> {
+        self.hidden_effect.as_deref()
+    }
+
+    pub fn set_hidden_effect(&mut self, hidden_effect: impl Into<Option<Self>>) {
+        self.hidden_effect = hidden_effect.into().map(Box::new);
+    }
+
+    fn write_details<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        AsVarInt::write(&self.amplifier.unwrap_or(0).into(), &mut *writer)?;
+        AsVarInt::write(&self.duration.unwrap_or(0), &mut *writer)?;
+        self.ambient.unwrap_or(false).write(&mut *writer)?;
+        let show_particles = self.show_particles.unwrap_or(true);
+        show_particles.write(&mut *writer)?;
+        self.show_icon
+            .unwrap_or(show_particles)
+            .write(&mut *writer)?;
+
+        self.hidden_effect.is_some().write(&mut *writer)?;
+        match &self.hidden_effect {
+            Some(hidden) => hidden.write_details(writer),
+            None => Ok(()),
+        }
+    }
+
+    fn read_details<R: Read>(reader: &mut R) -> std::io::Result<Self> {
+        let amplifier = i8::try_from(AsVarInt::read(&mut *reader)?).map_err(|_| {
+            std::io::Error::new(std::io::ErrorKind::InvalidData, "Invalid effect amplifier")
+        })?;
+
+        Ok(Self {
+            amplifier: Some(amplifier),
+            duration: Some(AsVarInt::read(&mut *reader)?),
+            ambient: Some(bool::read(&mut *reader)?),
+            show_particles: Some(bool::read(&mut *reader)?),
+            show_icon: Some(bool::read(&mut *reader)?),
+            hidden_effect: if bool::read(&mut *reader)? {
+                Some(Box::new(Self::read_details(reader)?))
+            } else {
+                None
+            },
+            id: None,
+        })
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, FromRepr)]
 #[serde(into = "i8", try_from = "i8")]
 pub enum Color {
@@ -11,8 +11,42 @@ pub struct Mooshroom {
     breedable: Breedable,
 }
 
+fields! {
+    Mooshroom {
+        reference {
+            kind, set_kind: Identifier;
+        }
+        list {
+            stew_effects, stew_effects_mut: StewEffect;
+        }
+        part {
+            breedable, breedable_mut: Breedable;
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 pub struct StewEffect {
     id: Option<Identifier>,
     duration: Option<i32>,
 }
+
+impl StewEffect {
+    pub fn new(id: Identifier, duration: i32) -> Self {
+        Self {
+            id: Some(id),
+            duration: Some(duration),
+        }
+    }
+}
+
+fields! {
+    StewEffect {
+        opt {
+            id, set_id: Identifier;
+        }
+        opt_copy {
+            duration, set_duration: i32;
+        }
+    }
+}
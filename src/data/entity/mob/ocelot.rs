@@ -0,0 +1,21 @@
+use super::Breedable;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct Ocelot {
+    trusting: bool,
+    #[serde(flatten)]
+    breedable: Breedable,
+}
+
+fields! {
+    Ocelot {
+        copy {
+            trusting, set_trusting: bool;
+        }
+        part {
+            breedable, breedable_mut: Breedable;
+        }
+    }
+}
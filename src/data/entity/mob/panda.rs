@@ -7,6 +7,15 @@ pub struct Panda {
     main_gene: Gene,
 }
 
+fields! {
+    Panda {
+        reference {
+            hidden_gene, set_hidden_gene: Gene;
+            main_gene, set_main_gene: Gene;
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(rename_all = "snake_case")]
 pub enum Gene {
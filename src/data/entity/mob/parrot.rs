@@ -11,6 +11,17 @@ pub struct Parrot {
     tameable: Tameable,
 }
 
+fields! {
+    Parrot {
+        reference {
+            variant, set_variant: Variant;
+        }
+        part {
+            tameable, tameable_mut: Tameable;
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, FromRepr)]
 #[serde(try_from = "i32", into = "i32")]
 pub enum Variant {
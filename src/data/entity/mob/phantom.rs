@@ -0,0 +1,21 @@
+use crate::data::BlockPos;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Phantom {
+    size: i32,
+    anchor_pos: Option<BlockPos>,
+}
+
+fields! {
+    Phantom {
+        copy {
+            size, set_size: i32;
+        }
+        opt_copy {
+            anchor_pos, set_anchor_pos: BlockPos;
+        }
+    }
+}
@@ -0,0 +1,23 @@
+use super::Breedable;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Pig {
+    // TODO: this is probably an identifier?
+    variant: Option<String>,
+    #[serde(flatten)]
+    breedable: Breedable,
+}
+
+fields! {
+    Pig {
+        opt {
+            variant, set_variant: String;
+        }
+        part {
+            breedable, breedable_mut: Breedable;
+        }
+    }
+}
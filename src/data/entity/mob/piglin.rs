@@ -0,0 +1,47 @@
+use crate::data::Item;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct Piglin {
+    #[serde(default)]
+    cannot_hunt: bool,
+    #[serde(default)]
+    inventory: Vec<Item>,
+    #[serde(default)]
+    is_baby: bool,
+    #[serde(default)]
+    is_immune_to_zombification: bool,
+    time_in_overworld: i32,
+}
+
+fields! {
+    Piglin {
+        copy {
+            cannot_hunt, set_cannot_hunt: bool;
+            is_baby, set_is_baby: bool;
+            is_immune_to_zombification, set_is_immune_to_zombification: bool;
+            time_in_overworld, set_time_in_overworld: i32;
+        }
+        list {
+            inventory, inventory_mut: Item;
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct PiglinBrute {
+    #[serde(default)]
+    is_immune_to_zombification: bool,
+    time_in_overworld: i32,
+}
+
+fields! {
+    PiglinBrute {
+        copy {
+            is_immune_to_zombification, set_is_immune_to_zombification: bool;
+            time_in_overworld, set_time_in_overworld: i32;
+        }
+    }
+}
@@ -0,0 +1,19 @@
+use super::{Angerable, Breedable};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct PolarBear {
+    #[serde(flatten)]
+    angerable: Angerable,
+    #[serde(flatten)]
+    breedable: Breedable,
+}
+
+fields! {
+    PolarBear {
+        part {
+            angerable, angerable_mut: Angerable;
+            breedable, breedable_mut: Breedable;
+        }
+    }
+}
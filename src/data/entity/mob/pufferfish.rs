@@ -8,6 +8,17 @@ pub struct Pufferfish {
     puff_state: PuffState,
 }
 
+fields! {
+    Pufferfish {
+        copy {
+            from_bucket, set_from_bucket: bool;
+        }
+        reference {
+            puff_state, set_puff_state: PuffState;
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, FromRepr)]
 #[serde(try_from = "i32", into = "i32")]
 pub enum PuffState {
@@ -9,6 +9,17 @@ pub struct Rabbit {
     rabbit_type: RabbitType,
 }
 
+fields! {
+    Rabbit {
+        reference {
+            rabbit_type, set_rabbit_type: RabbitType;
+        }
+        part {
+            breedable, breedable_mut: Breedable;
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, FromRepr)]
 #[serde(try_from = "Option<i32>", into = "Option<i32>")]
 pub enum RabbitType {
@@ -1,15 +1,122 @@
-use crate::data::entity::McUuid;
+use crate::data::{
+    Item,
+    entity::{McUuid, mob::I8Bool},
+};
 use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
 
+/// What every illager and witch saves, flattened into each of them
+#[skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(rename_all = "PascalCase")]
 pub struct Raider {
-    can_join_raid: bool,
-    patrol_leader: bool,
-    pratrolling: bool,
+    can_join_raid: I8Bool,
+    patrol_leader: I8Bool,
+    patrolling: I8Bool,
     #[serde(rename = "patrol_target")]
     patrol_target: Option<McUuid>,
-    raid_id: i32,
+    raid_id: Option<i32>,
     wave: i32,
+}
+
+fields! {
+    Raider {
+        copy {
+            wave, set_wave: i32;
+        }
+        flag {
+            can_join_raid, set_can_join_raid;
+            patrol_leader, set_patrol_leader;
+            patrolling, set_patrolling;
+        }
+        opt {
+            patrol_target, set_patrol_target: McUuid;
+        }
+        opt_copy {
+            raid_id, set_raid_id: i32;
+        }
+    }
+}
+
+/// Evokers and illusioners
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct Spellcaster {
     spell_ticks: i32,
+    #[serde(flatten)]
+    raider: Raider,
+}
+
+fields! {
+    Spellcaster {
+        copy {
+            spell_ticks, set_spell_ticks: i32;
+        }
+        part {
+            raider, raider_mut: Raider;
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct Pillager {
+    #[serde(default)]
+    inventory: Vec<Item>,
+    #[serde(flatten)]
+    raider: Raider,
+}
+
+fields! {
+    Pillager {
+        list {
+            inventory, inventory_mut: Item;
+        }
+        part {
+            raider, raider_mut: Raider;
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct Ravager {
+    #[serde(flatten)]
+    raider: Raider,
+    attack_tick: i32,
+    roar_tick: i32,
+    stun_tick: i32,
+}
+
+fields! {
+    Ravager {
+        copy {
+            attack_tick, set_attack_tick: i32;
+            roar_tick, set_roar_tick: i32;
+            stun_tick, set_stun_tick: i32;
+        }
+        part {
+            raider, raider_mut: Raider;
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct Vindicator {
+    #[serde(flatten)]
+    raider: Raider,
+    #[serde(default)]
+    johnny: bool,
+}
+
+fields! {
+    Vindicator {
+        copy {
+            johnny, set_johnny: bool;
+        }
+        part {
+            raider, raider_mut: Raider;
+        }
+    }
 }
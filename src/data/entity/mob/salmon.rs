@@ -8,6 +8,17 @@ pub struct Salmon {
     size: SalmonSize,
 }
 
+fields! {
+    Salmon {
+        copy {
+            from_bucket, set_from_bucket: bool;
+        }
+        reference {
+            size, set_size: SalmonSize;
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(rename_all = "snake_case")]
 pub enum SalmonSize {
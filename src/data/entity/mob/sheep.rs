@@ -0,0 +1,30 @@
+use super::{Breedable, Color};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct Sheep {
+    #[serde(default = "default_color")]
+    color: Color,
+    sheared: bool,
+    #[serde(flatten)]
+    breedable: Breedable,
+}
+
+fields! {
+    Sheep {
+        copy {
+            sheared, set_sheared: bool;
+        }
+        reference {
+            color, set_color: Color;
+        }
+        part {
+            breedable, breedable_mut: Breedable;
+        }
+    }
+}
+
+const fn default_color() -> Color {
+    Color::White
+}
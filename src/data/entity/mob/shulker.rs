@@ -13,6 +13,18 @@ pub struct Shulker {
     peek: i8,
 }
 
+fields! {
+    Shulker {
+        copy {
+            peek, set_peek: i8;
+        }
+        reference {
+            attach_face, set_attach_face: Face;
+            color, set_color: OneOf<Color, ShulkerPurple>;
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(into = "i8", try_from = "i8")]
 pub struct ShulkerPurple;
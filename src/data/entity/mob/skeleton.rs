@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct Skeleton {
+    stray_conversion_time: i32,
+}
+
+fields! {
+    Skeleton {
+        copy {
+            stray_conversion_time, set_stray_conversion_time: i32;
+        }
+    }
+}
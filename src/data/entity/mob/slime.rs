@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+
+/// Slimes and magma cubes
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Slime {
+    #[serde(rename = "Size")]
+    size: i32,
+    was_on_ground: bool,
+}
+
+fields! {
+    Slime {
+        copy {
+            size, set_size: i32;
+            was_on_ground, set_was_on_ground: bool;
+        }
+    }
+}
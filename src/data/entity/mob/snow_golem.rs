@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct SnowGolem {
+    pumpkin: bool,
+}
+
+fields! {
+    SnowGolem {
+        copy {
+            pumpkin, set_pumpkin: bool;
+        }
+    }
+}
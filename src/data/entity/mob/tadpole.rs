@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct Tadpole {
+    age: i32,
+    from_bucket: bool,
+}
+
+fields! {
+    Tadpole {
+        copy {
+            age, set_age: i32;
+            from_bucket, set_from_bucket: bool;
+        }
+    }
+}
@@ -1,5 +1,5 @@
 use serde::{Deserialize, Serialize};
-use strum::FromRepr;
+use strum::EnumIter;
 
 use crate::data::entity::mob::Color;
 
@@ -10,6 +10,17 @@ pub struct TropicalFish {
     variant: Variant,
 }
 
+fields! {
+    TropicalFish {
+        copy {
+            from_bucket, set_from_bucket: bool;
+        }
+        reference {
+            variant, set_variant: Variant;
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(try_from = "i32", into = "i32")]
 pub struct Variant {
@@ -18,8 +29,29 @@ pub struct Variant {
     pattern_color: Color,
 }
 
-#[derive(Clone, FromRepr)]
-// TODO: test conversion Self <-> i16
+impl Variant {
+    pub const fn new(pattern: Option<Pattern>, base_color: Color, pattern_color: Color) -> Self {
+        Self {
+            pattern,
+            base_color,
+            pattern_color,
+        }
+    }
+}
+
+fields! {
+    Variant {
+        reference {
+            base_color, set_base_color: Color;
+            pattern_color, set_pattern_color: Color;
+        }
+        opt {
+            pattern, set_pattern: Pattern;
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, EnumIter)]
 pub enum Pattern {
     Flopper,
     Kob,
@@ -56,12 +88,27 @@ impl From<Pattern> for i16 {
 }
 
 impl Pattern {
+    /// The inverse of the `i16` conversion, with `None` for patterns vanilla doesn't have
     pub fn from_i16(value: i16) -> Result<Option<Self>, &'static str> {
+        use Pattern::*;
         if value < 0 {
-            Err("Invalid pattern")
-        } else {
-            Ok(Self::from_repr(value as usize))
+            return Err("Invalid pattern");
         }
+        Ok(Some(match (value & 0xff, value >> 8) {
+            (0, 0) => Kob,
+            (0, 1) => Sunstreak,
+            (0, 2) => Snooper,
+            (0, 3) => Dasher,
+            (0, 4) => Brinely,
+            (0, 5) => Spotty,
+            (1, 0) => Flopper,
+            (1, 1) => Stripey,
+            (1, 2) => Glitter,
+            (1, 3) => Blockfish,
+            (1, 4) => Betty,
+            (1, 5) => Clayfish,
+            _ => return Ok(None),
+        }))
     }
 }
 
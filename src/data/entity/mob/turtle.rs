@@ -0,0 +1,24 @@
+use super::Breedable;
+use crate::data::BlockPos;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Turtle {
+    #[serde(flatten)]
+    breedable: Breedable,
+    #[serde(rename = "HasEgg")]
+    has_egg: bool,
+    home_pos: BlockPos,
+}
+
+fields! {
+    Turtle {
+        copy {
+            has_egg, set_has_egg: bool;
+            home_pos, set_home_pos: BlockPos;
+        }
+        part {
+            breedable, breedable_mut: Breedable;
+        }
+    }
+}
@@ -0,0 +1,20 @@
+use crate::data::BlockPos;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Vex {
+    bound_pos: Option<BlockPos>,
+    #[serde(rename = "LifeTicks")]
+    life_ticks: Option<i32>,
+}
+
+fields! {
+    Vex {
+        opt_copy {
+            bound_pos, set_bound_pos: BlockPos;
+            life_ticks, set_life_ticks: i32;
+        }
+    }
+}
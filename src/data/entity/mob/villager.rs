@@ -1,7 +1,8 @@
 use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
 use strum::FromRepr;
 
-use crate::data::{Identifier, Item, entity::McUuid};
+use crate::data::{BlockPos, Identifier, Item, entity::McUuid};
 
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(rename_all = "PascalCase")]
@@ -19,6 +20,55 @@ pub struct Villager {
     willing: bool,
 }
 
+fields! {
+    Villager {
+        copy {
+            xp, set_xp: i32;
+            last_restock, set_last_restock: i64;
+            last_gossip_decay, set_last_gossip_decay: i64;
+            restocks_today, set_restocks_today: i32;
+            willing, set_willing: bool;
+        }
+        list {
+            gossips, gossips_mut: Gossip;
+            inventory, inventory_mut: Item;
+        }
+        part {
+            offers, offers_mut: Offers;
+            villager_data, villager_data_mut: VillagerData;
+        }
+    }
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct WanderingTrader {
+    despawn_delay: i32,
+    offers: Option<Offers>,
+    #[serde(rename = "wander_target")]
+    wander_target: Option<BlockPos>,
+    #[serde(default)]
+    inventory: Vec<Item>,
+}
+
+fields! {
+    WanderingTrader {
+        copy {
+            despawn_delay, set_despawn_delay: i32;
+        }
+        opt {
+            offers, set_offers: Offers;
+        }
+        opt_copy {
+            wander_target, set_wander_target: BlockPos;
+        }
+        list {
+            inventory, inventory_mut: Item;
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(rename_all = "PascalCase")]
 pub struct Gossip {
@@ -28,6 +78,18 @@ pub struct Gossip {
     kind: GossipKind,
 }
 
+fields! {
+    Gossip {
+        copy {
+            value, set_value: i32;
+        }
+        reference {
+            target, set_target: McUuid;
+            kind, set_kind: GossipKind;
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(rename_all = "snake_case")]
 pub enum GossipKind {
@@ -45,6 +107,14 @@ pub struct Offers {
     recipes: Vec<Recipe>,
 }
 
+fields! {
+    Offers {
+        list {
+            recipes, recipes_mut: Recipe;
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Recipe {
@@ -60,6 +130,25 @@ pub struct Recipe {
     xp: i32,
 }
 
+fields! {
+    Recipe {
+        copy {
+            demand, set_demand: i32;
+            max_uses, set_max_uses: i32;
+            price_multiplier, set_price_multiplier: f32;
+            reward_exp, set_reward_exp: bool;
+            special_price, set_special_price: i32;
+            uses, set_uses: i32;
+            xp, set_xp: i32;
+        }
+        reference {
+            buy, set_buy: Item;
+            buy_b, set_buy_b: Item;
+            sell, set_sell: Item;
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 pub struct VillagerData {
     level: Level,
@@ -69,6 +158,26 @@ pub struct VillagerData {
     kind: Identifier,
 }
 
+impl VillagerData {
+    pub fn new(profession: Identifier, kind: Identifier, level: Level) -> Self {
+        Self {
+            level,
+            profession,
+            kind,
+        }
+    }
+}
+
+fields! {
+    VillagerData {
+        reference {
+            level, set_level: Level;
+            profession, set_profession: Identifier;
+            kind, set_kind: Identifier;
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, FromRepr)]
 #[serde(try_from = "i32", into = "i32")]
 pub enum Level {
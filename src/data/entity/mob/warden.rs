@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
 
 use crate::data::entity::{McUuid, mob::allay::VibrationEvent};
 
@@ -9,27 +10,84 @@ pub struct Warden {
     listener: Listener,
 }
 
+fields! {
+    Warden {
+        part {
+            anger, anger_mut: Anger;
+            listener, listener_mut: Listener;
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Default)]
 pub struct Anger {
     #[serde(default)]
     suspects: Vec<Suspect>,
 }
 
+fields! {
+    Anger {
+        list {
+            suspects, suspects_mut: Suspect;
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 pub struct Suspect {
     anger: i32,
     uuid: McUuid,
 }
 
+fields! {
+    Suspect {
+        copy {
+            anger, set_anger: i32;
+        }
+        reference {
+            uuid, set_uuid: McUuid;
+        }
+    }
+}
+
+#[skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone)]
 pub struct Listener {
-    event: VibrationEvent,
+    /// The vibration it's about to react to, if any
+    event: Option<VibrationEvent>,
     event_delay: i32,
     selector: Selector,
 }
 
+fields! {
+    Listener {
+        copy {
+            event_delay, set_event_delay: i32;
+        }
+        opt {
+            event, set_event: VibrationEvent;
+        }
+        part {
+            selector, selector_mut: Selector;
+        }
+    }
+}
+
+#[skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone)]
 pub struct Selector {
     tick: i64,
-    event: VibrationEvent,
+    /// The closest vibration heard this tick, if any
+    event: Option<VibrationEvent>,
+}
+
+fields! {
+    Selector {
+        copy {
+            tick, set_tick: i64;
+        }
+        opt {
+            event, set_event: VibrationEvent;
+        }
+    }
 }
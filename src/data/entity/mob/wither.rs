@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct Wither {
+    invul: i32,
+}
+
+fields! {
+    Wither {
+        copy {
+            invul, set_invul: i32;
+        }
+    }
+}
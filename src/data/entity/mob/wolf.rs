@@ -3,8 +3,10 @@ use crate::data::{
     entity::mob::{Angerable, Breedable, Color, Tameable},
 };
 use serde::{Deserialize, Serialize};
-use strum::{EnumString, IntoStaticStr};
+use serde_with::skip_serializing_none;
+use strum::{EnumIter, EnumString, IntoStaticStr};
 
+#[skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone)]
 pub struct Wolf {
     #[serde(flatten)]
@@ -18,14 +20,33 @@ pub struct Wolf {
     #[serde(default)]
     variant: Variant,
     // TODO: is this an identifier?
-    sound_variant: SoundVariant,
+    sound_variant: Option<SoundVariant>,
+}
+
+fields! {
+    Wolf {
+        copy {
+            variant, set_variant: Variant;
+        }
+        reference {
+            collar_color, set_collar_color: Color;
+        }
+        opt {
+            sound_variant, set_sound_variant: SoundVariant;
+        }
+        part {
+            angerable, angerable_mut: Angerable;
+            tameable, tameable_mut: Tameable;
+            breedable, breedable_mut: Breedable;
+        }
+    }
 }
 
 const fn default_color() -> Color {
     Color::Red
 }
 
-#[derive(Serialize, Deserialize, Clone, Default, IntoStaticStr, EnumString)]
+#[derive(Serialize, Deserialize, Clone, Copy, Default, IntoStaticStr, EnumString, EnumIter)]
 #[strum(serialize_all = "snake_case")]
 #[serde(into = "Identifier", try_from = "Identifier")]
 pub enum Variant {
@@ -1,10 +1,94 @@
+use crate::data::entity::{
+    McUuid,
+    mob::{
+        Angerable, I8Bool,
+        villager::{Gossip, Offers, VillagerData},
+    },
+};
 use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
 
+/// Zombies, husks and drowned, and flattened into the other zombies
+#[skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(rename_all = "PascalCase")]
 pub struct Zombie {
-    can_break_doors: bool,
+    can_break_doors: I8Bool,
     drowned_conversion_time: i32,
     in_water_time: i32,
-    is_baby: Option<bool>,
+    is_baby: Option<I8Bool>,
+}
+
+fields! {
+    Zombie {
+        copy {
+            drowned_conversion_time, set_drowned_conversion_time: i32;
+            in_water_time, set_in_water_time: i32;
+        }
+        flag {
+            can_break_doors, set_can_break_doors;
+        }
+    }
+}
+
+impl Zombie {
+    pub fn is_baby(&self) -> bool {
+        self.is_baby.is_some_and(|is_baby| *is_baby)
+    }
+
+    pub fn set_is_baby(&mut self, is_baby: bool) {
+        self.is_baby = Some(is_baby.into());
+    }
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct ZombieVillager {
+    #[serde(default)]
+    gossips: Vec<Gossip>,
+    offers: Option<Offers>,
+    villager_data: VillagerData,
+    xp: i32,
+    #[serde(flatten)]
+    zombie: Zombie,
+    conversion_time: i32,
+    conversion_player: Option<McUuid>,
+}
+
+fields! {
+    ZombieVillager {
+        copy {
+            xp, set_xp: i32;
+            conversion_time, set_conversion_time: i32;
+        }
+        opt {
+            offers, set_offers: Offers;
+            conversion_player, set_conversion_player: McUuid;
+        }
+        list {
+            gossips, gossips_mut: Gossip;
+        }
+        part {
+            villager_data, villager_data_mut: VillagerData;
+            zombie, zombie_mut: Zombie;
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ZombifiedPiglin {
+    #[serde(flatten)]
+    angerable: Angerable,
+    #[serde(flatten)]
+    zombie: Zombie,
+}
+
+fields! {
+    ZombifiedPiglin {
+        part {
+            angerable, angerable_mut: Angerable;
+            zombie, zombie_mut: Zombie;
+        }
+    }
 }
@@ -1,14 +1,106 @@
+/// Getters and setters for the fields of an entity or one of its parts, each given as the getter,
+/// then the setter or mutable getter, then the type. Sections are:
+/// - `copy`, returned by value
+/// - `flag`, bools saved as bytes in flattened parts
+/// - `reference`, returned by reference and replaced whole
+/// - `opt` and `opt_copy`, left out when set to `None`
+/// - `list`, read as a slice and changed in place
+/// - `part`, parts shared with other entities, changed in place
+macro_rules! fields {
+    (
+        $ty:ident {
+            $(copy { $($copy:ident, $set_copy:ident: $copy_ty:ty;)* })?
+            $(flag { $($flag:ident, $set_flag:ident;)* })?
+            $(reference { $($ref:ident, $set_ref:ident: $ref_ty:ty;)* })?
+            $(opt { $($opt:ident, $set_opt:ident: $opt_ty:ty;)* })?
+            $(opt_copy { $($opt_copy:ident, $set_opt_copy:ident: $opt_copy_ty:ty;)* })?
+            $(list { $($list:ident, $list_mut:ident: $list_ty:ty;)* })?
+            $(part { $($part:ident, $part_mut:ident: $part_ty:ty;)* })?
+        }
+    ) => {
+        impl $ty {
+            $($(
+                pub const fn $copy(&self) -> $copy_ty {
+                    self.$copy
+                }
+
+                pub fn $set_copy(&mut self, $copy: $copy_ty) {
+                    self.$copy = $copy;
+                }
+            )*)?
+            $($(
+                pub fn $flag(&self) -> bool {
+                    *self.$flag
+                }
+
+                pub fn $set_flag(&mut self, $flag: bool) {
+                    self.$flag = $flag.into();
+                }
+            )*)?
+            $($(
+                pub fn $ref(&self) -> &$ref_ty {
+                    &self.$ref
+                }
+
+                pub fn $set_ref(&mut self, $ref: $ref_ty) {
+                    self.$ref = $ref;
+                }
+            )*)?
+            $($(
+                pub fn $opt(&self) -> Option<&$opt_ty> {
+                    self.$opt.as_ref()
+                }
+
+                pub fn $set_opt(&mut self, $opt: impl Into<Option<$opt_ty>>) {
+                    self.$opt = $opt.into();
+                }
+            )*)?
+            $($(
+                pub const fn $opt_copy(&self) -> Option<$opt_copy_ty> {
+                    self.$opt_copy
+                }
+
+                pub fn $set_opt_copy(&mut self, $opt_copy: impl Into<Option<$opt_copy_ty>>) {
+                    self.$opt_copy = $opt_copy.into();
+                }
+            )*)?
+            $($(
+                pub fn $list(&self) -> &[$list_ty] {
+                    &self.$list
+                }
+
+                pub fn $list_mut(&mut self) -> &mut Vec<$list_ty> {
+                    &mut self.$list
+                }
+            )*)?
+            $($(
+                pub fn $part(&self) -> &$part_ty {
+                    &self.$part
+                }
+
+                pub fn $part_mut(&mut self) -> &mut $part_ty {
+                    &mut self.$part
+                }
+            )*)?
+        }
+    };
+}
+
 pub mod area_effect_cloud;
 pub mod display;
 pub mod hanging;
 pub mod interaction;
+pub mod kind;
 pub mod mob;
 pub mod projectile;
+pub mod spawn;
 pub mod vehicle;
 
+pub use kind::EntityKind;
+
 use crate::{
     data::{
-        Identifier, Item,
+        BlockPos, Identifier, Item,
         block::{Block, BlockEntity},
         entity::{
             area_effect_cloud::AreaEffectCloud,
@@ -19,110 +111,536 @@ use crate::{
             projectile::{Projectile, RenderAs},
             vehicle::Vehicle,
         },
+        unknown_fields,
     },
     text_component::TextComponent,
 };
 use derive_more::{AsMut, AsRef, Deref, DerefMut};
+use fastnbt::{IntArray, Value};
 use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use std::collections::HashMap;
 use uuid::Uuid;
 
+/// An entity as saved in entity chunks, spawners and vehicles. What it saves beyond the fields every
+/// entity has depends on its `id`, which is what it's read as.
+#[derive(Serialize, Clone)]
+pub struct Entity {
+    #[serde(flatten)]
+    base: EntityBase,
+    #[serde(flatten)]
+    subtype: EntitySubtype,
+    /// Fields that aren't modelled, kept as they were saved
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+#[skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(rename_all = "PascalCase")]
-pub struct Entity {
+pub(crate) struct EntityBase {
     air: Option<i16>,
     custom_name: Option<TextComponent>,
-    #[serde(default)]
-    custom_name_visible: bool,
-    #[serde(rename = "fall_distance")]
-    fall_distance: f64,
+    custom_name_visible: Option<bool>,
+    fall_distance: f32,
     fire: i16,
-    #[serde(default)]
-    glowing: bool,
-    has_visual_fire: bool,
+    glowing: Option<bool>,
+    has_visual_fire: Option<bool>,
     #[serde(rename = "id")]
     id: Option<Identifier>,
     invulnerable: bool,
     motion: Motion,
-    no_gravity: bool,
+    no_gravity: Option<bool>,
     on_ground: bool,
-    #[serde(default)]
-    passengers: Vec<Self>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    passengers: Vec<Entity>,
     portal_cooldown: i32,
-    pos: [f64; 3],
+    pub(crate) pos: [f64; 3],
     rotation: Rotation,
     silent: Option<bool>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     tags: Vec<String>,
-    ticks_frozen: i32,
+    ticks_frozen: Option<i32>,
     #[serde(rename = "UUID")]
-    uuid: McUuid,
-    #[serde(flatten)]
-    subtype: Option<EntitySubtype>,
+    pub(crate) uuid: McUuid,
 }
 
-#[derive(Serialize, Deserialize, Clone)]
+impl Entity {
+    /// The entity type, which every saved entity but a player has
+    pub fn id(&self) -> Option<&Identifier> {
+        self.base.id.as_ref()
+    }
+
+    /// The entity type, if it's one of the vanilla ones
+    pub fn kind(&self) -> Option<EntityKind> {
+        self.id().and_then(EntityKind::from_id)
+    }
+
+    pub fn uuid(&self) -> &McUuid {
+        &self.base.uuid
+    }
+
+    pub fn set_uuid(&mut self, uuid: Uuid) {
+        self.base.uuid = uuid.into();
+    }
+
+    pub const fn pos(&self) -> [f64; 3] {
+        self.base.pos
+    }
+
+    pub fn set_pos(&mut self, pos: [f64; 3]) {
+        self.base.pos = pos;
+    }
+
+    pub fn motion(&self) -> [f64; 3] {
+        self.base.motion.into()
+    }
+
+    /// Motion faster than 10 blocks a tick on any axis is dropped, as it is when loaded
+    pub fn set_motion(&mut self, motion: [f64; 3]) {
+        self.base.motion = motion.into();
+    }
+
+    /// The yaw, then the pitch
+    pub fn rotation(&self) -> [f32; 2] {
+        self.base.rotation.into()
+    }
+
+    /// The yaw is wrapped to between -180 and 180 degrees, and the pitch clamped to between -90
+    /// and 90
+    pub fn set_rotation(&mut self, rotation: [f32; 2]) {
+        self.base.rotation = rotation.into();
+    }
+
+    /// Ticks of breath left, which vanilla saves for every entity
+    pub const fn air(&self) -> Option<i16> {
+        self.base.air
+    }
+
+    pub fn set_air(&mut self, air: impl Into<Option<i16>>) {
+        self.base.air = air.into();
+    }
+
+    pub fn custom_name(&self) -> Option<&TextComponent> {
+        self.base.custom_name.as_ref()
+    }
+
+    pub fn set_custom_name(&mut self, custom_name: impl Into<Option<TextComponent>>) {
+        self.base.custom_name = custom_name.into();
+    }
+
+    pub const fn custom_name_visible(&self) -> bool {
+        matches!(self.base.custom_name_visible, Some(true))
+    }
+
+    pub fn set_custom_name_visible(&mut self, visible: bool) {
+        self.base.custom_name_visible = visible.then_some(true);
+    }
+
+    pub const fn fall_distance(&self) -> f32 {
+        self.base.fall_distance
+    }
+
+    pub fn set_fall_distance(&mut self, fall_distance: f32) {
+        self.base.fall_distance = fall_distance;
+    }
+
+    /// Ticks left on fire, or if negative, ticks until it can be set on fire
+    pub const fn fire(&self) -> i16 {
+        self.base.fire
+    }
+
+    pub fn set_fire(&mut self, fire: i16) {
+        self.base.fire = fire;
+    }
+
+    pub const fn glowing(&self) -> bool {
+        matches!(self.base.glowing, Some(true))
+    }
+
+    pub fn set_glowing(&mut self, glowing: bool) {
+        self.base.glowing = glowing.then_some(true);
+    }
+
+    pub const fn has_visual_fire(&self) -> bool {
+        matches!(self.base.has_visual_fire, Some(true))
+    }
+
+    pub fn set_has_visual_fire(&mut self, has_visual_fire: bool) {
+        self.base.has_visual_fire = has_visual_fire.then_some(true);
+    }
+
+    pub const fn invulnerable(&self) -> bool {
+        self.base.invulnerable
+    }
+
+    pub fn set_invulnerable(&mut self, invulnerable: bool) {
+        self.base.invulnerable = invulnerable;
+    }
+
+    pub const fn no_gravity(&self) -> bool {
+        matches!(self.base.no_gravity, Some(true))
+    }
+
+    pub fn set_no_gravity(&mut self, no_gravity: bool) {
+        self.base.no_gravity = no_gravity.then_some(true);
+    }
+
+    pub const fn on_ground(&self) -> bool {
+        self.base.on_ground
+    }
+
+    pub fn set_on_ground(&mut self, on_ground: bool) {
+        self.base.on_ground = on_ground;
+    }
+
+    pub const fn portal_cooldown(&self) -> i32 {
+        self.base.portal_cooldown
+    }
+
+    pub fn set_portal_cooldown(&mut self, portal_cooldown: i32) {
+        self.base.portal_cooldown = portal_cooldown;
+    }
+
+    pub const fn silent(&self) -> bool {
+        matches!(self.base.silent, Some(true))
+    }
+
+    pub fn set_silent(&mut self, silent: bool) {
+        self.base.silent = silent.then_some(true);
+    }
+
+    pub const fn ticks_frozen(&self) -> i32 {
+        match self.base.ticks_frozen {
+            Some(ticks_frozen) => ticks_frozen,
+            None => 0,
+        }
+    }
+
+    pub fn set_ticks_frozen(&mut self, ticks_frozen: i32) {
+        self.base.ticks_frozen = (ticks_frozen > 0).then_some(ticks_frozen);
+    }
+
+    pub fn tags(&self) -> &[String] {
+        &self.base.tags
+    }
+
+    pub fn tags_mut(&mut self) -> &mut Vec<String> {
+        &mut self.base.tags
+    }
+
+    pub fn passengers(&self) -> &[Self] {
+        &self.base.passengers
+    }
+
+    pub fn passengers_mut(&mut self) -> &mut Vec<Self> {
+        &mut self.base.passengers
+    }
+
+    pub fn subtype(&self) -> &EntitySubtype {
+        &self.subtype
+    }
+
+    pub fn subtype_mut(&mut self) -> &mut EntitySubtype {
+        &mut self.subtype
+    }
+
+    /// What a living entity saves, or `None` if this isn't one
+    pub fn mob(&self) -> Option<&Mob> {
+        match &self.subtype {
+            EntitySubtype::Mob(mob) => Some(mob),
+            _ => None,
+        }
+    }
+
+    pub fn mob_mut(&mut self) -> Option<&mut Mob> {
+        match &mut self.subtype {
+            EntitySubtype::Mob(mob) => Some(mob),
+            _ => None,
+        }
+    }
+
+    /// What a block, item or text display saves, or `None` if this isn't one
+    pub fn display(&self) -> Option<&Display> {
+        match &self.subtype {
+            EntitySubtype::Display(display) => Some(display),
+            _ => None,
+        }
+    }
+
+    pub fn display_mut(&mut self) -> Option<&mut Display> {
+        match &mut self.subtype {
+            EntitySubtype::Display(display) => Some(display),
+            _ => None,
+        }
+    }
+
+    /// What a minecart or chest boat saves, or `None` if this isn't one
+    pub fn vehicle(&self) -> Option<&Vehicle> {
+        match &self.subtype {
+            EntitySubtype::Vehicle(vehicle) => Some(vehicle),
+            _ => None,
+        }
+    }
+
+    pub fn vehicle_mut(&mut self) -> Option<&mut Vehicle> {
+        match &mut self.subtype {
+            EntitySubtype::Vehicle(vehicle) => Some(vehicle),
+            _ => None,
+        }
+    }
+
+    /// Fields that aren't modelled, like those of newer versions and mods, kept as they were saved
+    pub fn extra(&self) -> &HashMap<String, Value> {
+        &self.extra
+    }
+
+    pub fn extra_mut(&mut self) -> &mut HashMap<String, Value> {
+        &mut self.extra
+    }
+
+    fn from_fields(fields: HashMap<String, Value>) -> fastnbt::error::Result<Self> {
+        // Each part is read from the encoded entity rather than from `fields`, since flattening
+        // doesn't get along with reading from a `Value`
+        let nbt = fastnbt::to_bytes(&fields)?;
+        let base: EntityBase = fastnbt::from_bytes(&nbt)?;
+        if let Some(kind) = base.id.as_ref().and_then(EntityKind::from_id)
+            && let Some(subtype) = EntitySubtype::from_nbt(kind, &nbt)?
+        {
+            let mut entity = Self {
+                base,
+                subtype,
+                extra: HashMap::new(),
+            };
+            entity.extra = unknown_fields(&entity, fields)?;
+            return Ok(entity);
+        }
+
+        // Everything else is kept as it was saved, less what's already in `base`
+        let fields = unknown_fields(&base, fields)?;
+        Ok(Self {
+            base,
+            subtype: EntitySubtype::Unknown(Value::Compound(fields)),
+            extra: HashMap::new(),
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for Entity {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let Value::Compound(fields) = Value::deserialize(deserializer)? else {
+            return Err(serde::de::Error::custom("Expected an entity compound"));
+        };
+        let id = match fields.get("id") {
+            Some(Value::String(id)) => id.clone(),
+            _ => "untyped".to_string(),
+        };
+
+        Self::from_fields(fields)
+            .map_err(|e| serde::de::Error::custom(format!("Invalid {id} entity: {e}")))
+    }
+}
+
+/// What an entity saves beyond the fields every entity has, picked by its `id`.
+#[derive(Serialize, Clone)]
 #[serde(untagged)]
 // Boxes from compiler recommendation
 pub enum EntitySubtype {
     Mob(Box<Mob>),
     Projectile(Box<Projectile>),
-    #[serde(rename_all = "PascalCase")]
+    ExperienceOrb(ExperienceOrb),
+    Item(Box<ItemEntity>),
+    Vehicle(Box<Vehicle>),
+    FallingBlock(Box<FallingBlock>),
+    Tnt(Tnt),
+    Display(Box<Display>),
+    AreaEffectCloud(Box<AreaEffectCloud>),
+    EndCrystal(EndCrystal),
+    EvokerFangs(EvokerFangs),
+    EyeOfEnder(Box<RenderAs>),
+    Hanging(Box<Hanging>),
+    Interaction(Interaction),
+    OminousItemSpawner(Box<OminousItemSpawner>),
+    /// An entity of a type that isn't modelled, or isn't vanilla at all, with everything but the
+    /// common fields as it was saved
+    Unknown(Value),
+}
+
+impl EntitySubtype {
+    /// Reads the part of an entity that depends on its kind, or `None` if that kind isn't
+    /// modelled.
+    fn from_nbt(kind: EntityKind, nbt: &[u8]) -> fastnbt::error::Result<Option<Self>> {
+        use EntityKind as K;
+        Ok(Some(match kind {
+            K::AreaEffectCloud => Self::AreaEffectCloud(fastnbt::from_bytes(nbt)?),
+            K::BlockDisplay | K::ItemDisplay | K::TextDisplay => {
+                Self::Display(Box::new(Display::from_nbt(kind, nbt)?))
+            }
+            K::EndCrystal => Self::EndCrystal(fastnbt::from_bytes(nbt)?),
+            K::EvokerFangs => Self::EvokerFangs(fastnbt::from_bytes(nbt)?),
+            K::ExperienceOrb => Self::ExperienceOrb(fastnbt::from_bytes(nbt)?),
+            K::EyeOfEnder => Self::EyeOfEnder(fastnbt::from_bytes(nbt)?),
+            K::FallingBlock => Self::FallingBlock(fastnbt::from_bytes(nbt)?),
+            K::GlowItemFrame | K::ItemFrame | K::Painting => {
+                Self::Hanging(Box::new(Hanging::from_nbt(kind, nbt)?))
+            }
+            K::Interaction => Self::Interaction(fastnbt::from_bytes(nbt)?),
+            K::Item => Self::Item(fastnbt::from_bytes(nbt)?),
+            K::OminousItemSpawner => Self::OminousItemSpawner(fastnbt::from_bytes(nbt)?),
+            K::Tnt => Self::Tnt(fastnbt::from_bytes(nbt)?),
+            K::Arrow
+            | K::BreezeWindCharge
+            | K::DragonFireball
+            | K::Egg
+            | K::EnderPearl
+            | K::ExperienceBottle
+            | K::Fireball
+            | K::FireworkRocket
+            | K::LingeringPotion
+            | K::LlamaSpit
+            | K::ShulkerBullet
+            | K::SmallFireball
+            | K::Snowball
+            | K::SpectralArrow
+            | K::SplashPotion
+            | K::Trident
+            | K::WindCharge
+            | K::WitherSkull => Self::Projectile(Box::new(Projectile::from_nbt(kind, nbt)?)),
+            K::AcaciaChestBoat
+            | K::BambooChestRaft
+            | K::BirchChestBoat
+            | K::CherryChestBoat
+            | K::ChestMinecart
+            | K::CommandBlockMinecart
+            | K::DarkOakChestBoat
+            | K::FurnaceMinecart
+            | K::HopperMinecart
+            | K::JungleChestBoat
+            | K::MangroveChestBoat
+            | K::Minecart
+            | K::OakChestBoat
+            | K::PaleOakChestBoat
+            | K::SpawnerMinecart
+            | K::SpruceChestBoat
+            | K::TntMinecart => Self::Vehicle(Box::new(Vehicle::from_nbt(kind, nbt)?)),
+            // Players are only ever saved on their own
+            K::Player => return Ok(None),
+            _ if kind.is_living() => Self::Mob(Box::new(Mob::from_nbt(kind, nbt)?)),
+            _ => return Ok(None),
+        }))
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct ExperienceOrb {
+    age: i16,
+    count: i32,
+    health: i16,
+    value: i16,
+}
+
+fields! {
     ExperienceOrb {
-        age: i16,
-        count: i32,
-        health: i16,
-        value: i16,
-    },
-    #[serde(rename_all = "PascalCase")]
-    Item {
-        age: i16,
-        health: i16,
-        item: Box<Item>,
-        owner: Option<McUuid>,
-        pickup_delay: i16,
-        thrower: McUuid,
-    },
-    Vehicle(Vehicle),
-    #[serde(rename_all = "PascalCase")]
+        copy {
+            age, set_age: i16;
+            count, set_count: i32;
+            health, set_health: i16;
+            value, set_value: i16;
+        }
+    }
+}
+
+/// A dropped item, named apart from the [`Item`] it holds.
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct ItemEntity {
+    age: i16,
+    health: i16,
+    /// Left out when the stack is empty, which only lasts until the item is next ticked
+    item: Option<Item>,
+    owner: Option<McUuid>,
+    pickup_delay: i16,
+    thrower: Option<McUuid>,
+}
+
+fields! {
+    ItemEntity {
+        copy {
+            age, set_age: i16;
+            health, set_health: i16;
+            pickup_delay, set_pickup_delay: i16;
+        }
+        opt {
+            item, set_item: Item;
+            owner, set_owner: McUuid;
+            thrower, set_thrower: McUuid;
+        }
+    }
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct FallingBlock {
+    block_state: Block,
+    cancel_drop: bool,
+    drop_item: bool,
+    fall_hurt_amount: f32,
+    fall_hurt_max: i32,
+    hurt_entities: bool,
+    tile_entity_data: Option<BlockEntity>,
+    time: i32,
+}
+
+fields! {
     FallingBlock {
-        block_state: Block,
-        cancel_drop: bool,
-        drop_item: bool,
-        fall_hurt_amount: f32,
-        fall_hurt_max: i32,
-        hurt_enemies: bool,
-        tile_entity_data: Option<BlockEntity>,
-        time: i32,
-    },
+        copy {
+            cancel_drop, set_cancel_drop: bool;
+            drop_item, set_drop_item: bool;
+            fall_hurt_amount, set_fall_hurt_amount: f32;
+            fall_hurt_max, set_fall_hurt_max: i32;
+            hurt_entities, set_hurt_entities: bool;
+            time, set_time: i32;
+        }
+        reference {
+            block_state, set_block_state: Block;
+        }
+        opt {
+            tile_entity_data, set_tile_entity_data: BlockEntity;
+        }
+    }
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Tnt {
+    #[serde(default = "default_tnt_fuse")]
+    fuse: i16,
+    #[serde(default = "tnt_block")]
+    block_state: Block,
+    #[serde(default = "default_tnt_explotion_power")]
+    explosion_power: f32,
+    owner: Option<McUuid>,
+}
+
+fields! {
     Tnt {
-        #[serde(default = "default_tnt_fuse")]
-        fuse: i16,
-        #[serde(default = "tnt_block")]
-        block_state: Block,
-        #[serde(default = "default_tnt_explotion_power")]
-        explosion_power: f32,
-        owner: Option<McUuid>,
-    },
-    // Boxed at recommendation of compiler
-    Display(Box<Display>),
-    AreaEffectCloud(AreaEffectCloud),
-    EndCrystal {
-        beam_target: [i32; 3],
-        #[serde(rename = "ShowBottom")]
-        show_bottom: bool,
-    },
-    #[serde(rename_all = "PascalCase")]
-    EvokerFangs {
-        owner: McUuid,
-        warmup: i32,
-    },
-    EyeOfEnder(RenderAs),
-    Hanging(Hanging),
-    Interaction(Interaction),
-    OminousItemSpawner {
-        item: Item,
-        spawn_item_after_ticks: i64,
-    },
+        copy {
+            fuse, set_fuse: i16;
+            explosion_power, set_explosion_power: f32;
+        }
+        reference {
+            block_state, set_block_state: Block;
+        }
+        opt {
+            owner, set_owner: McUuid;
+        }
+    }
 }
 
 const fn default_tnt_fuse() -> i16 {
@@ -133,26 +651,87 @@ const fn default_tnt_explotion_power() -> f32 {
     4.0
 }
 
-fn tnt_block() -> Block {
-    todo!("TNT block value")
+const fn tnt_block() -> Block {
+    Block::Tnt { unstable: false }
 }
 
-#[derive(Clone, Deref, DerefMut, AsRef, AsMut)]
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+pub struct EndCrystal {
+    beam_target: Option<BlockPos>,
+    #[serde(rename = "ShowBottom")]
+    show_bottom: bool,
+}
+
+fields! {
+    EndCrystal {
+        copy {
+            show_bottom, set_show_bottom: bool;
+        }
+        opt_copy {
+            beam_target, set_beam_target: BlockPos;
+        }
+    }
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct EvokerFangs {
+    owner: Option<McUuid>,
+    warmup: i32,
+}
+
+fields! {
+    EvokerFangs {
+        copy {
+            warmup, set_warmup: i32;
+        }
+        opt {
+            owner, set_owner: McUuid;
+        }
+    }
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+pub struct OminousItemSpawner {
+    /// Left out until the spawner picks what to spawn
+    item: Option<Item>,
+    spawn_item_after_ticks: i64,
+}
+
+fields! {
+    OminousItemSpawner {
+        copy {
+            spawn_item_after_ticks, set_spawn_item_after_ticks: i64;
+        }
+        opt {
+            item, set_item: Item;
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deref, DerefMut, AsRef, AsMut)]
 pub struct McUuid(Uuid);
 
+impl From<Uuid> for McUuid {
+    fn from(value: Uuid) -> Self {
+        Self(value)
+    }
+}
+
 impl Serialize for McUuid {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
-        use serde::ser::SerializeSeq;
-
-        let bytes = self.as_bytes();
-        let mut seq = serializer.serialize_seq(Some(4))?;
-        for bytes in bytes.chunks(4) {
-            seq.serialize_element(&i32::from_be_bytes(bytes.try_into().unwrap()))?;
-        }
-        seq.end()
+        let ints = self
+            .as_bytes()
+            .chunks(4)
+            .map(|bytes| i32::from_be_bytes(bytes.try_into().unwrap()))
+            .collect();
+        IntArray::new(ints).serialize(serializer)
     }
 }
 
@@ -161,7 +740,11 @@ impl<'de> Deserialize<'de> for McUuid {
     where
         D: serde::Deserializer<'de>,
     {
-        let ints = <[i32; 4]>::deserialize(deserializer)?;
+        // An int array rather than a list, which `[i32; 4]` won't take
+        let ints = IntArray::deserialize(deserializer)?;
+        let ints: [i32; 4] = (*ints)
+            .try_into()
+            .map_err(|_| serde::de::Error::invalid_length(ints.len(), &"4 ints"))?;
         let mut bytes = [0; 16];
         for (i, int) in ints.iter().enumerate() {
             bytes[i * 4..(i + 1) * 4].copy_from_slice(&int.to_be_bytes());
@@ -170,7 +753,7 @@ impl<'de> Deserialize<'de> for McUuid {
     }
 }
 
-#[derive(Serialize, Deserialize, Clone, Default)]
+#[derive(Serialize, Deserialize, Clone, Copy, Default)]
 #[serde(from = "[f64; 3]", into = "[f64; 3]")]
 pub struct Motion {
     x: f64,
@@ -201,7 +784,7 @@ impl From<Motion> for [f64; 3] {
     }
 }
 
-#[derive(Serialize, Deserialize, Clone, Default)]
+#[derive(Serialize, Deserialize, Clone, Copy, Default)]
 #[serde(from = "[f32; 2]", into = "[f32; 2]")]
 pub struct Rotation {
     yaw: f32,
@@ -210,7 +793,10 @@ pub struct Rotation {
 
 impl From<[f32; 2]> for Rotation {
     fn from(value: [f32; 2]) -> Self {
-        let yaw = {
+        // Left alone when already in range, so that saving doesn't change it
+        let yaw = if (-180.0..=180.0).contains(&value[0]) {
+            value[0]
+        } else {
             let mut yaw = value[0].rem_euclid(360.0);
             if yaw > 180.0 {
                 yaw -= 360.0;
@@ -1,96 +1,314 @@
 use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
 use strum::FromRepr;
 
-use crate::data::{Identifier, Item, block::Block, entity::McUuid};
+use crate::data::{
+    Identifier, Item,
+    block::Block,
+    entity::{EntityKind, McUuid, mob::I8Bool},
+};
 
+#[derive(Serialize, Clone)]
+pub struct Projectile {
+    #[serde(flatten)]
+    base: ProjectileBase,
+    #[serde(flatten)]
+    unique: Option<UniqueProjectile>,
+}
+
+impl Projectile {
+    pub(crate) fn from_nbt(kind: EntityKind, nbt: &[u8]) -> fastnbt::error::Result<Self> {
+        Ok(Self {
+            base: fastnbt::from_bytes(nbt)?,
+            unique: UniqueProjectile::from_nbt(kind, nbt)?,
+        })
+    }
+
+    pub fn owner(&self) -> Option<&McUuid> {
+        self.base.owner.as_ref()
+    }
+
+    pub fn set_owner(&mut self, owner: impl Into<Option<McUuid>>) {
+        self.base.owner = owner.into();
+    }
+
+    pub const fn has_been_shot(&self) -> bool {
+        self.base.has_been_shot
+    }
+
+    pub fn set_has_been_shot(&mut self, has_been_shot: bool) {
+        self.base.has_been_shot = has_been_shot;
+    }
+
+    /// Whether it's moved clear of its owner, which it can't hit until it has
+    pub const fn left_owner(&self) -> bool {
+        self.base.left_owner
+    }
+
+    pub fn set_left_owner(&mut self, left_owner: bool) {
+        self.base.left_owner = left_owner;
+    }
+
+    pub fn unique(&self) -> Option<&UniqueProjectile> {
+        self.unique.as_ref()
+    }
+
+    pub fn unique_mut(&mut self) -> Option<&mut UniqueProjectile> {
+        self.unique.as_mut()
+    }
+}
+
+#[skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(rename_all = "PascalCase")]
-pub struct Projectile {
+struct ProjectileBase {
     has_been_shot: bool,
     #[serde(default)]
     left_owner: bool,
     owner: Option<McUuid>,
-    #[serde(flatten)]
-    unique: Option<UniqueProjectile>,
 }
 
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Serialize, Clone)]
 #[serde(untagged)]
 pub enum UniqueProjectile {
-    Arrow(Arrow),
+    Arrow(Box<Arrow>),
     BreezeWindCharge(Hurting),
     DragonFireball(Hurting),
     Egg(RenderAs),
     EnderPearl(RenderAs),
     ExperienceBottle(RenderAs),
-    #[serde(rename_all = "PascalCase")]
+    Fireball(Fireball),
+    FireworkRocket(FireworkRocket),
+    LingeringPotion(RenderAs),
+    ShulkerBullet(ShulkerBullet),
+    SmallFireball(SmallFireball),
+    Snowball(RenderAs),
+    SpectralArrow(Box<SpectralArrow>),
+    SplashPotion(RenderAs),
+    Trident(Box<Trident>),
+    WindCharge(Hurting),
+    WitherSkull(WitherSkull),
+}
+
+impl UniqueProjectile {
+    fn from_nbt(kind: EntityKind, nbt: &[u8]) -> fastnbt::error::Result<Option<Self>> {
+        use EntityKind as K;
+        Ok(Some(match kind {
+            K::Arrow => Self::Arrow(fastnbt::from_bytes(nbt)?),
+            K::BreezeWindCharge => Self::BreezeWindCharge(fastnbt::from_bytes(nbt)?),
+            K::DragonFireball => Self::DragonFireball(fastnbt::from_bytes(nbt)?),
+            K::Egg => Self::Egg(fastnbt::from_bytes(nbt)?),
+            K::EnderPearl => Self::EnderPearl(fastnbt::from_bytes(nbt)?),
+            K::ExperienceBottle => Self::ExperienceBottle(fastnbt::from_bytes(nbt)?),
+            K::Fireball => Self::Fireball(fastnbt::from_bytes(nbt)?),
+            K::FireworkRocket => Self::FireworkRocket(fastnbt::from_bytes(nbt)?),
+            K::LingeringPotion => Self::LingeringPotion(fastnbt::from_bytes(nbt)?),
+            K::ShulkerBullet => Self::ShulkerBullet(fastnbt::from_bytes(nbt)?),
+            K::SmallFireball => Self::SmallFireball(fastnbt::from_bytes(nbt)?),
+            K::Snowball => Self::Snowball(fastnbt::from_bytes(nbt)?),
+            K::SpectralArrow => Self::SpectralArrow(fastnbt::from_bytes(nbt)?),
+            K::SplashPotion => Self::SplashPotion(fastnbt::from_bytes(nbt)?),
+            K::Trident => Self::Trident(fastnbt::from_bytes(nbt)?),
+            K::WindCharge => Self::WindCharge(fastnbt::from_bytes(nbt)?),
+            K::WitherSkull => Self::WitherSkull(fastnbt::from_bytes(nbt)?),
+            _ => return Ok(None),
+        }))
+    }
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct Fireball {
+    explosion_power: i8,
+    item: Option<Item>,
+}
+
+fields! {
     Fireball {
-        explosion_power: i8,
-        item: Option<Item>,
-    },
-    #[serde(rename_all = "PascalCase")]
+        copy {
+            explosion_power, set_explosion_power: i8;
+        }
+        opt {
+            item, set_item: Item;
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct FireworkRocket {
+    fireworks_item: Item,
+    life: i32,
+    life_time: i32,
+    shot_at_angle: bool,
+}
+
+fields! {
     FireworkRocket {
-        fireworks_item: Item,
-        life: i32,
-        life_time: i32,
-        shot_at_angle: bool,
-    },
-    LingeringPotion(RenderAs),
-    #[serde(rename_all = "PascalCase")]
+        copy {
+            life, set_life: i32;
+            life_time, set_life_time: i32;
+            shot_at_angle, set_shot_at_angle: bool;
+        }
+        reference {
+            fireworks_item, set_fireworks_item: Item;
+        }
+    }
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct ShulkerBullet {
+    steps: i32,
+    /// Left out until it's fired at something
+    target: Option<McUuid>,
+    t_x_d: f64,
+    t_y_d: f64,
+    t_z_d: f64,
+}
+
+fields! {
     ShulkerBullet {
-        steps: i32,
-        target: McUuid,
-        t_x_d: f64,
-        t_y_d: f64,
-        t_z_d: f64,
-    },
+        copy {
+            steps, set_steps: i32;
+            t_x_d, set_t_x_d: f64;
+            t_y_d, set_t_y_d: f64;
+            t_z_d, set_t_z_d: f64;
+        }
+        opt {
+            target, set_target: McUuid;
+        }
+    }
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SmallFireball {
+    acceleration_power: f64,
+    #[serde(rename = "Item")]
+    item: Option<Item>,
+}
+
+fields! {
     SmallFireball {
-        acceleration_power: f64,
-        #[serde(rename = "Item")]
-        item: Option<Item>,
-    },
-    Snowball(RenderAs),
-    #[serde(rename_all = "PascalCase")]
+        copy {
+            acceleration_power, set_acceleration_power: f64;
+        }
+        opt {
+            item, set_item: Item;
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct SpectralArrow {
+    #[serde(flatten)]
+    arrow: Arrow,
+    duration: i32,
+}
+
+fields! {
     SpectralArrow {
-        #[serde(flatten)]
-        arrow: Arrow,
-        duration: i32,
-    },
-    SplashPotion(RenderAs),
-    #[serde(rename_all = "PascalCase")]
+        copy {
+            duration, set_duration: i32;
+        }
+        part {
+            arrow, arrow_mut: Arrow;
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Trident {
+    #[serde(flatten)]
+    arrow: Arrow,
+    #[serde(rename = "DealtDamage")]
+    dealt_damage: bool,
+}
+
+fields! {
     Trident {
-        #[serde(flatten)]
-        // Boxed at recommendation of compiler
-        arrow: Box<Arrow>,
-        dealt_damage: bool,
-        #[serde(rename = "item")]
-        item: Item,
-    },
-    WindCharge(Hurting),
+        copy {
+            dealt_damage, set_dealt_damage: bool;
+        }
+        part {
+            arrow, arrow_mut: Arrow;
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct WitherSkull {
+    acceleration_power: f64,
+    dangerous: bool,
+}
+
+fields! {
     WitherSkull {
-        acceleration_power: f64,
-        dangerous: bool,
-    },
+        copy {
+            acceleration_power, set_acceleration_power: f64;
+            dangerous, set_dangerous: bool;
+        }
+    }
 }
 
+#[skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Arrow {
-    crit: bool,
+    crit: I8Bool,
     damage: f64,
     in_block_state: Option<Block>,
-    in_groud: bool,
+    in_ground: I8Bool,
     life: i16,
     pickup: PickupGamemode,
     #[serde(rename = "PierceLevel")]
     pierce_level: i8,
     shake: i8,
     #[serde(rename = "ShotFromCrossbow")]
-    shot_from_crossbow: bool,
+    shot_from_crossbow: I8Bool,
     #[serde(rename = "SoundEvent")]
     sount_event: Option<Identifier>,
     item: Item,
-    weapon: Item,
+    weapon: Option<Item>,
+}
+
+fields! {
+    Arrow {
+        copy {
+            damage, set_damage: f64;
+            life, set_life: i16;
+            pierce_level, set_pierce_level: i8;
+            shake, set_shake: i8;
+        }
+        flag {
+            crit, set_crit;
+            in_ground, set_in_ground;
+            shot_from_crossbow, set_shot_from_crossbow;
+        }
+        reference {
+            pickup, set_pickup: PickupGamemode;
+            item, set_item: Item;
+        }
+        opt {
+            in_block_state, set_in_block_state: Block;
+            weapon, set_weapon: Item;
+        }
+    }
+}
+
+impl Arrow {
+    /// What it sounds like when it hits something
+    pub fn sound_event(&self) -> Option<&Identifier> {
+        self.sount_event.as_ref()
+    }
+
+    pub fn set_sound_event(&mut self, sound_event: impl Into<Option<Identifier>>) {
+        self.sount_event = sound_event.into();
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, FromRepr)]
@@ -120,8 +338,25 @@ pub struct Hurting {
     acceleration_power: f64,
 }
 
+fields! {
+    Hurting {
+        copy {
+            acceleration_power, set_acceleration_power: f64;
+        }
+    }
+}
+
+#[skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(rename_all = "PascalCase")]
 pub struct RenderAs {
     item: Option<Item>,
 }
+
+fields! {
+    RenderAs {
+        opt {
+            item, set_item: Item;
+        }
+    }
+}
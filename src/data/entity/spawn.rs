@@ -0,0 +1,441 @@
+//! Making entities from scratch, with what vanilla gives them when they're spawned.
+
+use crate::{
+    data::{
+        BlockPos,
+        entity::{Entity, EntityKind, McUuid, mob::PotionEffect, mob::UniqueMob},
+    },
+    nbt::snbt,
+    text_component::TextComponent,
+};
+use fastnbt::Value;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// What every entity saves, less its id, UUID and position
+const ENTITY: &str = "{
+    Air: 300s, FallDistance: 0.0f, Fire: -1s, Invulnerable: 0b, Motion: [0.0d, 0.0d, 0.0d],
+    OnGround: 0b, PortalCooldown: 0, Pos: [0.0d, 0.0d, 0.0d], Rotation: [0.0f, 0.0f]
+}";
+
+/// What every living entity saves, less its health
+const LIVING: &str = "{
+    AbsorptionAmount: 0.0f, Brain: {memories: {}}, DeathTime: 0s, FallFlying: 0b,
+    HurtByTimestamp: 0, HurtTime: 0s
+}";
+
+const MOB: &str = "{
+    ArmorDropChances: [0.085f, 0.085f, 0.085f, 0.085f], CanPickUpLoot: 0b,
+    HandDropChances: [0.085f, 0.085f], LeftHanded: 0b, PersistenceRequired: 0b
+}";
+
+const AGEABLE: &str = "{Age: 0, ForcedAge: 0}";
+const BREEDABLE: &str = "{Age: 0, ForcedAge: 0, InLove: 0}";
+const ANGERABLE: &str = "{AngerTime: 0}";
+const TAMEABLE: &str = "{Sitting: 0b}";
+const BUCKETABLE: &str = "{FromBucket: 0b}";
+const HORSE: &str = "{Bred: 0b, EatingHaystack: 0b, Tame: 0b, Temper: 0}";
+const CHESTED_HORSE: &str = "{ChestedHorse: 0b}";
+const RAIDER: &str = "{CanJoinRaid: 0b, PatrolLeader: 0b, Patrolling: 0b, Wave: 0}";
+const ZOMBIE: &str = "{CanBreakDoors: 0b, DrownedConversionTime: -1, InWaterTime: 0, IsBaby: 0b}";
+const VILLAGER_DATA: &str =
+    "{VillagerData: {level: 1, profession: \"minecraft:none\", type: \"minecraft:plains\"}, Xp: 0}";
+const PROJECTILE: &str = "{HasBeenShot: 0b, LeftOwner: 0b}";
+const ARROW: &str = "{
+    crit: 0b, damage: 2.0d, inGround: 0b, life: 0s, pickup: 0b, PierceLevel: 0b, shake: 0b,
+    ShotFromCrossbow: 0b
+}";
+const HURTING: &str = "{acceleration_power: 0.1d}";
+const DISPLAY: &str = "{
+    interpolation_duration: 0, teleport_duration: 0, start_interpolation: 0,
+    transformation: {
+        left_rotation: [0.0f, 0.0f, 0.0f, 1.0f], right_rotation: [0.0f, 0.0f, 0.0f, 1.0f],
+        scale: [1.0f, 1.0f, 1.0f], translation: [0.0f, 0.0f, 0.0f]
+    }
+}";
+
+/// What only this kind of entity saves, as vanilla first saves it. Where vanilla picks at random,
+/// the first or least of what it picks from is used.
+fn spawn_defaults(kind: EntityKind) -> &'static [&'static str] {
+    use EntityKind as K;
+    match kind {
+        K::Allay => &["{
+            DuplicationCooldown: 0L,
+            listener: {
+                distance: 0, event_delay: 0, event_distance: 0, range: 16,
+                source: {type: \"entity\", uuid: [I; 0, 0, 0, 0], y_offset: 0.36f}
+            }
+        }"],
+        K::AreaEffectCloud => &["{
+            Age: 0, Color: -1, Duration: 600, DurationOnUse: 0, potion_contents: {},
+            potion_duration_scale: 1.0f, Radius: 3.0f, RadiusOnUse: 0.0f, RadiusPerTick: 0.0f,
+            ReapplicationDelay: 20, WaitTime: 10
+        }"],
+        K::Armadillo => &[BREEDABLE, "{scute_time: 6000, state: \"idle\"}"],
+        K::ArmorStand => &["{
+            DisabledSlots: 0, Invisible: 0b, NoBasePlate: 0b, ShowArms: 0b, Small: 0b,
+            Pose: {
+                Body: [0.0f, 0.0f, 0.0f], Head: [0.0f, 0.0f, 0.0f],
+                LeftArm: [-10.0f, 0.0f, -10.0f], LeftLeg: [-1.0f, 0.0f, -1.0f],
+                RightArm: [-15.0f, 0.0f, 10.0f], RightLeg: [1.0f, 0.0f, 1.0f]
+            }
+        }"],
+        K::Arrow => &[
+            PROJECTILE,
+            ARROW,
+            "{item: {id: \"minecraft:arrow\", count: 1}}",
+        ],
+        K::Axolotl => &[BREEDABLE, BUCKETABLE, "{Variant: 0}"],
+        K::Bat => &["{BatFlags: 1b}"],
+        K::Bee => &[
+            BREEDABLE,
+            ANGERABLE,
+            "{
+                CannotEnterHiveTicks: 0, CropsGrownSincePollination: 0, HasNectar: 0b,
+                HasStung: 0b, TicksSincePollination: 0
+            }",
+        ],
+        K::BlockDisplay => &[DISPLAY, "{block_state: {Name: \"minecraft:air\"}}"],
+        K::BreezeWindCharge | K::DragonFireball | K::SmallFireball | K::WindCharge => {
+            &[PROJECTILE, HURTING]
+        }
+        K::Camel => &[BREEDABLE, HORSE, "{LastPoseTick: 0L}"],
+        K::Cat => &[BREEDABLE, TAMEABLE, "{variant: \"minecraft:tabby\"}"],
+        K::Chicken => &[
+            BREEDABLE,
+            "{EggLayTime: 6000, IsChickenJockey: 0b, variant: \"minecraft:temperate\"}",
+        ],
+        K::Cod => &[BUCKETABLE],
+        K::CommandBlockMinecart => {
+            &["{Command: \"\", LastOutput: \"\", SuccessCount: 0, TrackOutput: 1b}"]
+        }
+        K::CopperGolem => &["{next_weather_age: -1L, weather_state: 0}"],
+        K::Cow | K::Frog | K::Pig => &[BREEDABLE, "{variant: \"minecraft:temperate\"}"],
+        K::Creeper => &["{ExplosionRadius: 3b, Fuse: 30s, ignited: 0b}"],
+        K::Dolphin => &[BREEDABLE, "{GotFish: 0b, Moistness: 2400}"],
+        K::Donkey | K::Mule => &[BREEDABLE, HORSE, CHESTED_HORSE],
+        K::Drowned | K::Husk | K::Zombie => &[ZOMBIE],
+        K::Egg
+        | K::EnderPearl
+        | K::ExperienceBottle
+        | K::LingeringPotion
+        | K::LlamaSpit
+        | K::Snowball
+        | K::SplashPotion => &[PROJECTILE],
+        K::EndCrystal => &["{ShowBottom: 1b}"],
+        K::EnderDragon => &["{DragonPhase: 10}"],
+        K::Enderman => &[ANGERABLE],
+        K::Endermite => &["{Lifetime: 0}"],
+        K::Evoker | K::Illusioner => &[RAIDER, "{SpellTicks: 0}"],
+        K::EvokerFangs => &["{Warmup: 0}"],
+        K::ExperienceOrb => &["{Age: 0s, Count: 1, Health: 5s, Value: 0s}"],
+        K::FallingBlock => &["{
+            BlockState: {Name: \"minecraft:sand\"}, CancelDrop: 0b, DropItem: 1b,
+            FallHurtAmount: 0.0f, FallHurtMax: 40, HurtEntities: 0b, Time: 0
+        }"],
+        K::Fireball => &[PROJECTILE, HURTING, "{ExplosionPower: 1b}"],
+        K::FireworkRocket => &[
+            PROJECTILE,
+            "{
+                FireworksItem: {id: \"minecraft:firework_rocket\", count: 1}, Life: 0,
+                LifeTime: 20, ShotAtAngle: 0b
+            }",
+        ],
+        K::Fox => &[
+            BREEDABLE,
+            "{Crouching: 0b, Sitting: 0b, Sleeping: 0b, Type: \"red\"}",
+        ],
+        K::FurnaceMinecart => &["{Fuel: 0s, PushX: 0.0d, PushZ: 0.0d}"],
+        K::Ghast => &["{ExplosionPower: 1b}"],
+        K::GlowItemFrame | K::ItemFrame => &["{
+            block_pos: [I; 0, 0, 0], Facing: 3b, Fixed: 0b, Invisible: 0b, ItemDropChance: 1.0f,
+            ItemRotation: 0b
+        }"],
+        K::GlowSquid => &[BREEDABLE, "{DarkTicksRemaining: 0}"],
+        K::Goat => &[
+            BREEDABLE,
+            "{HasLeftHorn: 1b, HasRightHorn: 1b, IsScreamingGoat: 0b}",
+        ],
+        K::Hoglin => &[BREEDABLE, "{TimeInOverworld: 0}"],
+        K::HopperMinecart => &["{Enabled: 1b}"],
+        K::Horse => &[BREEDABLE, HORSE, "{Variant: 0}"],
+        K::IronGolem => &[ANGERABLE, "{PlayerCreated: 0b}"],
+        K::Item => &["{Age: 0s, Health: 5s, PickupDelay: 0s}"],
+        K::ItemDisplay => &[DISPLAY],
+        K::Llama | K::TraderLlama => {
+            &[BREEDABLE, HORSE, CHESTED_HORSE, "{Strength: 1, Variant: 0}"]
+        }
+        K::MagmaCube | K::Slime => &["{Size: 0, wasOnGround: 0b}"],
+        K::Mannequin => &["{main_hand: \"right\", pose: \"standing\", profile: {}}"],
+        K::Mooshroom => &[BREEDABLE, "{Type: \"red\"}"],
+        K::Ocelot => &[BREEDABLE, "{Trusting: 0b}"],
+        K::OminousItemSpawner => &["{spawn_item_after_ticks: 60L}"],
+        K::Painting => &["{block_pos: [I; 0, 0, 0], facing: 0b, variant: \"minecraft:kebab\"}"],
+        K::Panda => &[BREEDABLE, "{HiddenGene: \"normal\", MainGene: \"normal\"}"],
+        K::Parrot => &[BREEDABLE, TAMEABLE, "{Variant: 0}"],
+        K::Phantom => &["{size: 0}"],
+        K::Piglin | K::PiglinBrute => &["{TimeInOverworld: 0}"],
+        K::Pillager | K::Vindicator | K::Witch => &[RAIDER],
+        K::PolarBear => &[BREEDABLE, ANGERABLE],
+        K::Pufferfish => &[BUCKETABLE, "{PuffState: 0}"],
+        K::Rabbit => &[BREEDABLE, "{rabbit_type: 0}"],
+        K::Ravager => &[RAIDER, "{AttackTick: 0, RoarTick: 0, StunTick: 0}"],
+        K::Salmon => &[BUCKETABLE, "{type: \"medium\"}"],
+        K::Sheep => &[BREEDABLE, "{Sheared: 0b}"],
+        K::Shulker => &["{AttachFace: 0b, Color: 16b, Peek: 0b}"],
+        K::ShulkerBullet => &[PROJECTILE, "{Steps: 0, TXD: 0.0d, TYD: 0.0d, TZD: 0.0d}"],
+        K::Skeleton => &["{StrayConversionTime: -1}"],
+        K::SkeletonHorse => &[BREEDABLE, HORSE, "{SkeletonTrap: 0b, SkeletonTrapTime: 0}"],
+        K::Sniffer | K::Squid | K::Strider => &[BREEDABLE],
+        K::SnowGolem => &["{Pumpkin: 1b}"],
+        K::SpawnerMinecart => &["{
+            Delay: 20s, MaxNearbyEntities: 6s, MaxSpawnDelay: 800s, MinSpawnDelay: 200s,
+            RequiredPlayerRange: 16s, SpawnCount: 4s, SpawnPotentials: [], SpawnRange: 4s
+        }"],
+        K::SpectralArrow => &[
+            PROJECTILE,
+            ARROW,
+            "{Duration: 200, item: {id: \"minecraft:spectral_arrow\", count: 1}}",
+        ],
+        K::Tadpole => &["{Age: 0, FromBucket: 0b}"],
+        K::TextDisplay => &[
+            DISPLAY,
+            "{background: 1073741824, text: \"\", text_opacity: 127b}",
+        ],
+        K::TntMinecart => &["{explosion_power: 4.0f, explosion_speed_factor: 1.0f, fuse: -1}"],
+        K::Trident => &[
+            PROJECTILE,
+            ARROW,
+            "{DealtDamage: 0b, item: {id: \"minecraft:trident\", count: 1}}",
+        ],
+        K::TropicalFish => &[BUCKETABLE, "{Variant: 0}"],
+        K::Turtle => &[BREEDABLE, "{HasEgg: 0b, home_pos: [I; 0, 0, 0]}"],
+        K::Villager => &[
+            AGEABLE,
+            VILLAGER_DATA,
+            "{
+                LastGossipDecay: 0L, LastRestock: 0L, Offers: {Recipes: []}, RestocksToday: 0,
+                Willing: 0b
+            }",
+        ],
+        K::WanderingTrader => &[AGEABLE, "{DespawnDelay: 0}"],
+        K::Warden => &["{listener: {event_delay: 0, selector: {tick: -1L}}}"],
+        K::Wither => &["{Invul: 0}"],
+        K::WitherSkull => &[PROJECTILE, HURTING, "{dangerous: 0b}"],
+        K::Wolf => &[BREEDABLE, ANGERABLE, TAMEABLE],
+        K::ZombieHorse => &[BREEDABLE, HORSE],
+        K::ZombieVillager => &[ZOMBIE, VILLAGER_DATA, "{ConversionTime: -1}"],
+        K::ZombifiedPiglin => &[ZOMBIE, ANGERABLE],
+        _ => &[],
+    }
+}
+
+fn compound(snbt: &str) -> HashMap<String, Value> {
+    match snbt::parse(snbt) {
+        Ok(Value::Compound(compound)) => compound,
+        _ => unreachable!("Spawn defaults are all compounds"),
+    }
+}
+
+impl Entity {
+    /// An entity of `kind` at the origin, with a random UUID and what vanilla spawns it with.
+    /// Entities that aren't modelled are given the fields every entity has, and those of living
+    /// entities if they're living.
+    pub fn new(kind: EntityKind) -> Self {
+        let uuid = fastnbt::to_value(McUuid::from(Uuid::new_v4())).expect("UUIDs are int arrays");
+        let mut fields = compound(ENTITY);
+        fields.insert("id".to_string(), Value::String(kind.id().to_string()));
+        fields.insert("UUID".to_string(), uuid.clone());
+        if let Some(health) = kind.max_health() {
+            fields.extend(compound(LIVING));
+            fields.insert("Health".to_string(), Value::Float(health));
+        }
+        if kind.is_mob() {
+            fields.extend(compound(MOB));
+        }
+        for snbt in spawn_defaults(kind) {
+            fields.extend(compound(snbt));
+        }
+
+        match kind {
+            // Allays listen for vibrations from where they are
+            EntityKind::Allay => {
+                if let Some(Value::Compound(listener)) = fields.get_mut("listener")
+                    && let Some(Value::Compound(source)) = listener.get_mut("source")
+                {
+                    source.insert("uuid".to_string(), uuid);
+                }
+            }
+            // Spawners spawn pigs until they're told otherwise
+            EntityKind::SpawnerMinecart => {
+                let pig = fastnbt::to_value(Self::new(EntityKind::Pig)).expect("Pigs can be saved");
+                fields.insert("SpawnData".to_string(), pig);
+            }
+            _ => {}
+        }
+
+        Self::from_fields(fields).expect("Spawn defaults are read as the kind they're for")
+    }
+
+    pub fn builder(kind: EntityKind) -> EntityBuilder {
+        EntityBuilder {
+            entity: Self::new(kind),
+        }
+    }
+}
+
+/// Makes an entity starting from what vanilla spawns it with. What can only be set on some kinds
+/// of entity is set through [`Entity::subtype_mut`] and the like once it's built.
+#[derive(Clone)]
+pub struct EntityBuilder {
+    entity: Entity,
+}
+
+impl EntityBuilder {
+    pub fn pos(mut self, pos: [f64; 3]) -> Self {
+        self.entity.set_pos(pos);
+        self
+    }
+
+    pub fn motion(mut self, motion: [f64; 3]) -> Self {
+        self.entity.set_motion(motion);
+        self
+    }
+
+    /// The yaw, then the pitch
+    pub fn rotation(mut self, rotation: [f32; 2]) -> Self {
+        self.entity.set_rotation(rotation);
+        self
+    }
+
+    pub fn uuid(mut self, uuid: Uuid) -> Self {
+        self.entity.set_uuid(uuid);
+        self
+    }
+
+    pub fn custom_name(mut self, name: impl Into<TextComponent>) -> Self {
+        self.entity.set_custom_name(name.into());
+        self
+    }
+
+    /// Shows the custom name even when the entity isn't looked at
+    pub fn custom_name_visible(mut self) -> Self {
+        self.entity.set_custom_name_visible(true);
+        self
+    }
+
+    pub fn glowing(mut self) -> Self {
+        self.entity.set_glowing(true);
+        self
+    }
+
+    pub fn invulnerable(mut self) -> Self {
+        self.entity.set_invulnerable(true);
+        self
+    }
+
+    pub fn no_gravity(mut self) -> Self {
+        self.entity.set_no_gravity(true);
+        self
+    }
+
+    pub fn silent(mut self) -> Self {
+        self.entity.set_silent(true);
+        self
+    }
+
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        self.entity.tags_mut().push(tag.into());
+        self
+    }
+
+    pub fn passenger(mut self, passenger: Entity) -> Self {
+        self.entity.passengers_mut().push(passenger);
+        self
+    }
+
+    /// Ignored unless the entity is living
+    pub fn health(mut self, health: f32) -> Self {
+        if let Some(mob) = self.entity.mob_mut() {
+            mob.set_health(health);
+        }
+        self
+    }
+
+    /// Ignored unless the entity is living
+    pub fn effect(mut self, effect: PotionEffect) -> Self {
+        if let Some(mob) = self.entity.mob_mut() {
+            mob.active_effects_mut().push(effect);
+        }
+        self
+    }
+
+    /// Ignored unless the entity is a mob with AI
+    pub fn no_ai(mut self) -> Self {
+        if let Some(mob) = self.entity.mob_mut().and_then(|mob| mob.mob_mut()) {
+            mob.set_no_ai(true);
+        }
+        self
+    }
+
+    /// Keeps the mob from despawning. Ignored unless the entity is a mob with AI.
+    pub fn persistent(mut self) -> Self {
+        if let Some(mob) = self.entity.mob_mut().and_then(|mob| mob.mob_mut()) {
+            mob.set_persistence_required(true);
+        }
+        self
+    }
+
+    /// A field by its saved name. Fields that aren't modelled are saved as they're given, and
+    /// modelled ones are set as if the entity had been read with them, failing if `value` doesn't
+    /// fit them.
+    pub fn extra(mut self, key: impl Into<String>, value: Value) -> Result<Self, EntityError> {
+        let key = key.into();
+        let Ok(Value::Compound(mut fields)) = fastnbt::to_value(&self.entity) else {
+            unreachable!("Entities are saved as compounds")
+        };
+        fields.insert(key.clone(), value);
+        self.entity = Entity::from_fields(fields)
+            .map_err(|error| EntityError::InvalidField { key, error })?;
+        Ok(self)
+    }
+
+    pub fn build(mut self) -> Entity {
+        // Turtles are home wherever they're spawned
+        let [x, y, z] = self.entity.pos().map(|axis| axis.floor() as i32);
+        if let Some(UniqueMob::Turtle(turtle)) =
+            self.entity.mob_mut().and_then(|mob| mob.unique_mut())
+        {
+            turtle.set_home_pos(BlockPos::new(x, y, z));
+        }
+
+        self.entity
+    }
+}
+
+#[derive(Debug)]
+pub enum EntityError {
+    /// The field is modelled, and the value given for it doesn't fit
+    InvalidField {
+        key: String,
+        error: fastnbt::error::Error,
+    },
+}
+
+impl std::fmt::Display for EntityError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidField { key, error } => {
+                write!(f, "{key} doesn't fit this entity: {error}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for EntityError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InvalidField { error, .. } => Some(error),
+        }
+    }
+}
@@ -1,68 +1,248 @@
 use serde::{Deserialize, Serialize};
-use serde_with::{FromInto, serde_as};
+use serde_with::{FromInto, serde_as, skip_serializing_none};
 
 use crate::{
     OneOf,
-    data::{Identifier, ItemWithSlot, block::Block, entity::Entity},
+    data::{
+        Identifier, ItemWithSlot,
+        block::Block,
+        entity::{Entity, EntityKind},
+    },
 };
 
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Serialize, Clone)]
+#[serde(untagged)]
 pub enum Vehicle {
     ChestBoat(Container),
     Minecart(Minecart),
+    ChestMinecart(ChestMinecart),
+    CommandBlockMinecart(CommandBlockMinecart),
+    FurnaceMinecart(FurnaceMinecart),
+    HopperMinecart(HopperMinecart),
+    SpawnerMinecart(SpawnerMinecart),
+    TntMinecart(TntMinecart),
+}
+
+impl Vehicle {
+    pub(crate) fn from_nbt(kind: EntityKind, nbt: &[u8]) -> fastnbt::error::Result<Self> {
+        use EntityKind as K;
+        Ok(match kind {
+            K::ChestMinecart => Self::ChestMinecart(fastnbt::from_bytes(nbt)?),
+            K::CommandBlockMinecart => Self::CommandBlockMinecart(fastnbt::from_bytes(nbt)?),
+            K::FurnaceMinecart => Self::FurnaceMinecart(fastnbt::from_bytes(nbt)?),
+            K::HopperMinecart => Self::HopperMinecart(fastnbt::from_bytes(nbt)?),
+            K::Minecart => Self::Minecart(fastnbt::from_bytes(nbt)?),
+            K::SpawnerMinecart => Self::SpawnerMinecart(fastnbt::from_bytes(nbt)?),
+            K::TntMinecart => Self::TntMinecart(fastnbt::from_bytes(nbt)?),
+            // Every kind of chest boat and raft
+            _ => Self::ChestBoat(fastnbt::from_bytes(nbt)?),
+        })
+    }
+
+    /// What every minecart saves, or `None` for chest boats
+    pub fn minecart(&self) -> Option<&Minecart> {
+        Some(match self {
+            Self::ChestBoat(_) => return None,
+            Self::Minecart(minecart) => minecart,
+            Self::ChestMinecart(cart) => &cart.minecart,
+            Self::CommandBlockMinecart(cart) => &cart.minecart,
+            Self::FurnaceMinecart(cart) => &cart.minecart,
+            Self::HopperMinecart(cart) => &cart.minecart,
+            Self::SpawnerMinecart(cart) => &cart.minecart,
+            Self::TntMinecart(cart) => &cart.minecart,
+        })
+    }
+
+    pub fn minecart_mut(&mut self) -> Option<&mut Minecart> {
+        Some(match self {
+            Self::ChestBoat(_) => return None,
+            Self::Minecart(minecart) => minecart,
+            Self::ChestMinecart(cart) => &mut cart.minecart,
+            Self::CommandBlockMinecart(cart) => &mut cart.minecart,
+            Self::FurnaceMinecart(cart) => &mut cart.minecart,
+            Self::HopperMinecart(cart) => &mut cart.minecart,
+            Self::SpawnerMinecart(cart) => &mut cart.minecart,
+            Self::TntMinecart(cart) => &mut cart.minecart,
+        })
+    }
+
+    /// The items of chest boats and chest and hopper minecarts
+    pub fn container(&self) -> Option<&Container> {
+        match self {
+            Self::ChestBoat(container) => Some(container),
+            Self::ChestMinecart(cart) => Some(&cart.container),
+            Self::HopperMinecart(cart) => Some(&cart.container),
+            _ => None,
+        }
+    }
+
+    pub fn container_mut(&mut self) -> Option<&mut Container> {
+        match self {
+            Self::ChestBoat(container) => Some(container),
+            Self::ChestMinecart(cart) => Some(&mut cart.container),
+            Self::HopperMinecart(cart) => Some(&mut cart.container),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ChestMinecart {
+    #[serde(flatten)]
+    container: Container,
+    #[serde(flatten)]
+    minecart: Minecart,
+}
+
+fields! {
     ChestMinecart {
-        #[serde(flatten)]
-        container: Container,
-        #[serde(flatten)]
-        minecart: Minecart,
-    },
-    #[serde(rename_all = "PascalCase")]
+        part {
+            container, container_mut: Container;
+            minecart, minecart_mut: Minecart;
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct CommandBlockMinecart {
+    #[serde(flatten)]
+    minecart: Minecart,
+    command: String,
+    last_output: String,
+    success_count: i32,
+    track_output: bool,
+}
+
+fields! {
     CommandBlockMinecart {
-        #[serde(flatten)]
-        minecart: Minecart,
-        command: String,
-        last_output: String,
-        success_count: i32,
-        track_output: bool,
-    },
-    #[serde(rename_all = "PascalCase")]
+        copy {
+            success_count, set_success_count: i32;
+            track_output, set_track_output: bool;
+        }
+        reference {
+            command, set_command: String;
+            last_output, set_last_output: String;
+        }
+        part {
+            minecart, minecart_mut: Minecart;
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct FurnaceMinecart {
+    #[serde(flatten)]
+    minecart: Minecart,
+    fuel: i16,
+    push_x: f64,
+    push_z: f64,
+}
+
+fields! {
     FurnaceMinecart {
-        #[serde(flatten)]
-        minecart: Minecart,
-        fuel: i16,
-        push_x: f64,
-        push_z: f64,
-    },
-    #[serde(rename_all = "PascalCase")]
+        copy {
+            fuel, set_fuel: i16;
+            push_x, set_push_x: f64;
+            push_z, set_push_z: f64;
+        }
+        part {
+            minecart, minecart_mut: Minecart;
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct HopperMinecart {
+    #[serde(flatten)]
+    minecart: Minecart,
+    #[serde(flatten)]
+    container: Container,
+    enabled: bool,
+}
+
+fields! {
     HopperMinecart {
-        #[serde(flatten)]
-        minecart: Minecart,
-        #[serde(flatten)]
-        container: Container,
-        enabled: bool,
-    },
-    #[serde(rename_all = "PascalCase")]
+        copy {
+            enabled, set_enabled: bool;
+        }
+        part {
+            minecart, minecart_mut: Minecart;
+            container, container_mut: Container;
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct SpawnerMinecart {
+    #[serde(flatten)]
+    minecart: Minecart,
+    delay: i16,
+    max_nearby_entities: i16,
+    max_spawn_delay: i16,
+    min_spawn_delay: i16,
+    required_player_range: i16,
+    spawn_count: i16,
+    // Boxed for indirection
+    spawn_data: Box<Entity>,
+    spawn_potentials: Vec<SpawnPotential>,
+    spawn_range: i16,
+}
+
+fields! {
     SpawnerMinecart {
-        #[serde(flatten)]
-        minecart: Minecart,
-        delay: i16,
-        max_nearby_entities: i16,
-        max_spawn_delay: i16,
-        min_spawn_delay: i16,
-        required_player_range: i16,
-        spawn_count: i16,
-        // Boxed for indirection
-        spawn_data: Box<Entity>,
-        spawn_potentials: Vec<SpawnPotential>,
-        spawn_range: i16,
-    },
+        copy {
+            delay, set_delay: i16;
+            max_nearby_entities, set_max_nearby_entities: i16;
+            max_spawn_delay, set_max_spawn_delay: i16;
+            min_spawn_delay, set_min_spawn_delay: i16;
+            required_player_range, set_required_player_range: i16;
+            spawn_count, set_spawn_count: i16;
+            spawn_range, set_spawn_range: i16;
+        }
+        list {
+            spawn_potentials, spawn_potentials_mut: SpawnPotential;
+        }
+        part {
+            minecart, minecart_mut: Minecart;
+        }
+    }
+}
+
+impl SpawnerMinecart {
+    /// The entity it spawns next
+    pub fn spawn_data(&self) -> &Entity {
+        &self.spawn_data
+    }
+
+    pub fn set_spawn_data(&mut self, entity: Entity) {
+        *self.spawn_data = entity;
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct TntMinecart {
+    #[serde(flatten)]
+    minecart: Minecart,
+    fuse: i32,
+    explosion_power: f32,
+    explosion_speed_factor: f32,
+}
+
+fields! {
     TntMinecart {
-        #[serde(flatten)]
-        minecart: Minecart,
-        fuse: i32,
-        explosion_power: f32,
-        explosion_speed_factor: f32,
-    },
+        copy {
+            fuse, set_fuse: i32;
+            explosion_power, set_explosion_power: f32;
+            explosion_speed_factor, set_explosion_speed_factor: f32;
+        }
+        part {
+            minecart, minecart_mut: Minecart;
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -134,18 +314,52 @@ pub struct CustomSpawnRules {
     sky_light_limit: Option<i32>,
 }
 
+#[skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(rename_all = "PascalCase")]
 pub struct Container {
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     items: Vec<ItemWithSlot>,
     loot_table: Option<Identifier>,
-    loot_table_seed: Option<i32>,
+    loot_table_seed: Option<i64>,
 }
 
+fields! {
+    Container {
+        opt {
+            loot_table, set_loot_table: Identifier;
+        }
+        opt_copy {
+            loot_table_seed, set_loot_table_seed: i64;
+        }
+    }
+}
+
+impl Container {
+    pub fn items(&self) -> &[ItemWithSlot] {
+        &self.items
+    }
+
+    pub fn items_mut(&mut self) -> &mut Vec<ItemWithSlot> {
+        &mut self.items
+    }
+}
+
+#[skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(rename_all = "PascalCase")]
 pub struct Minecart {
     display_offset: Option<i32>,
     display_state: Option<Block>,
 }
+
+fields! {
+    Minecart {
+        opt {
+            display_state, set_display_state: Block;
+        }
+        opt_copy {
+            display_offset, set_display_offset: i32;
+        }
+    }
+}
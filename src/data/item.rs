@@ -0,0 +1,382 @@
+//! Making items from scratch, and checking items hold together the way vanilla requires before
+//! they're saved or sent.
+
+use crate::{
+    data::{
+        Components, Identifier, Item, ItemWithSlot,
+        components::{Enchantments, MinecraftComponents, Rarity, Unbreakable},
+        registry::builtin,
+    },
+    text_component::TextComponent,
+};
+use fastnbt::Value;
+use num_traits::{PrimInt, Signed};
+use std::collections::{HashMap, HashSet};
+
+/// The most of anything that fits in a slot, whatever its `max_stack_size`
+pub const MAX_STACK_SIZE: i32 = 99;
+
+/// How many of each vanilla item fit in a stack without a `max_stack_size` component, by network
+/// id
+const DEFAULT_MAX_STACK_SIZES: &[u8] = &include!(concat!(env!("OUT_DIR"), "/max_stack_sizes.rs"));
+
+/// The highest level an enchantment can be
+pub const MAX_ENCHANTMENT_LEVEL: i32 = 255;
+
+#[derive(Debug)]
+pub enum ItemError {
+    Nbt(fastnbt::error::Error),
+    /// Air is the absence of an item, so there can't be any of it
+    Air,
+    InvalidCount {
+        count: i32,
+        max: i32,
+    },
+    InvalidMaxStackSize(i32),
+    InvalidDamage(i32),
+    InvalidMaxDamage(i32),
+    InvalidEnchantmentLevel {
+        enchantment: Identifier,
+        level: i32,
+    },
+    /// Two components that can't both be on an item
+    Conflict(Identifier, Identifier),
+    /// A component that's both added and taken away
+    AddedAndRemoved(Identifier),
+    InvalidSlot {
+        slot: i32,
+        container: ContainerKind,
+    },
+    DuplicateSlot(i32),
+}
+
+impl std::fmt::Display for ItemError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Nbt(e) => e.fmt(f),
+            Self::Air => write!(f, "Items can't be air"),
+            Self::InvalidCount { count, max } => {
+                write!(f, "Invalid count {count}, expected 1 to {max}")
+            }
+            Self::InvalidMaxStackSize(size) => write!(
+                f,
+                "Invalid max stack size {size}, expected 1 to {MAX_STACK_SIZE}"
+            ),
+            Self::InvalidDamage(damage) => write!(f, "Invalid damage {damage}"),
+            Self::InvalidMaxDamage(max_damage) => write!(f, "Invalid max damage {max_damage}"),
+            Self::InvalidEnchantmentLevel { enchantment, level } => write!(
+                f,
+                "Invalid level {level} of {enchantment}, expected 1 to {MAX_ENCHANTMENT_LEVEL}"
+            ),
+            Self::Conflict(first, second) => {
+                write!(f, "Items can't have both {first} and {second}")
+            }
+            Self::AddedAndRemoved(component) => {
+                write!(f, "{component} is both added and removed")
+            }
+            Self::InvalidSlot { slot, container } => {
+                write!(f, "Invalid slot {slot} of {container:?}")
+            }
+            Self::DuplicateSlot(slot) => write!(f, "More than one item in slot {slot}"),
+        }
+    }
+}
+
+impl std::error::Error for ItemError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Nbt(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<fastnbt::error::Error> for ItemError {
+    fn from(value: fastnbt::error::Error) -> Self {
+        Self::Nbt(value)
+    }
+}
+
+/// What holds a list of items, which decides the slots they can be in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ContainerKind {
+    /// A player's inventory: the hotbar and main inventory, then the armor from the feet up, then
+    /// the offhand
+    Inventory,
+    /// Chests, barrels, shulker boxes, ender chests and chest minecarts and boats
+    Chest,
+    /// Dispensers, droppers and crafters
+    Dispenser,
+    /// Hoppers and hopper minecarts
+    Hopper,
+    /// Furnaces, blast furnaces and smokers: the input, then the fuel, then the result
+    Furnace,
+    /// The three bottles, then the ingredient, then the fuel
+    BrewingStand,
+    Campfire,
+    ChiseledBookshelf,
+    /// The chest of a donkey, mule or llama, which starts after its saddle and armor slots
+    ChestedHorse,
+    /// The `container` component of an item
+    Item,
+}
+
+impl ContainerKind {
+    pub fn contains(self, slot: i32) -> bool {
+        match self {
+            Self::Inventory => matches!(slot, 0..=35 | 100..=103 | -106),
+            Self::Chest => (0..27).contains(&slot),
+            Self::Dispenser => (0..9).contains(&slot),
+            Self::Hopper | Self::BrewingStand => (0..5).contains(&slot),
+            Self::Furnace => (0..3).contains(&slot),
+            Self::Campfire => (0..4).contains(&slot),
+            Self::ChiseledBookshelf => (0..6).contains(&slot),
+            Self::ChestedHorse => (2..17).contains(&slot),
+            Self::Item => (0..256).contains(&slot),
+        }
+    }
+
+    /// Checks every item, and that each is in its own slot of this container.
+    pub fn validate<I: PrimInt + Signed>(self, items: &[ItemWithSlot<I>]) -> Result<(), ItemError> {
+        self.validate_slots(items.iter().map(|item| {
+            // Every slot type fits in an i32
+            (item.slot().to_i32().unwrap_or(i32::MAX), item.item())
+        }))
+    }
+
+    fn validate_slots<'a>(
+        self,
+        items: impl IntoIterator<Item = (i32, &'a Item)>,
+    ) -> Result<(), ItemError> {
+        let mut slots = HashSet::new();
+        for (slot, item) in items {
+            if !self.contains(slot) {
+                return Err(ItemError::InvalidSlot {
+                    slot,
+                    container: self,
+                });
+            }
+            if !slots.insert(slot) {
+                return Err(ItemError::DuplicateSlot(slot));
+            }
+            item.validate()?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Item {
+    /// One of an item, with the defaults of its type
+    pub fn new(id: Identifier) -> Self {
+        Self {
+            id,
+            count: None,
+            components: None,
+        }
+    }
+
+    pub fn builder(id: Identifier) -> ItemBuilder {
+        ItemBuilder {
+            item: Self::new(id),
+        }
+    }
+
+    /// Checks the count and components, and those of any items inside this one. Without a
+    /// `max_stack_size` component, an item stacks as far as vanilla's item of the same id does,
+    /// and an item that isn't vanilla as far as any item can.
+    pub fn validate(&self) -> Result<(), ItemError> {
+        if self.id == Identifier::minecraft("air") {
+            return Err(ItemError::Air);
+        }
+
+        let default = builtin::ITEM
+            .network_id(&self.id)
+            .map_or(MAX_STACK_SIZE, |id| {
+                i32::from(DEFAULT_MAX_STACK_SIZES[id as usize])
+            });
+        let max = match &self.components {
+            Some(components) => validate_components(components, default)?,
+            None => default,
+        };
+        if !(1..=max).contains(&self.count()) {
+            return Err(ItemError::InvalidCount {
+                count: self.count(),
+                max,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+impl<I: PrimInt + Signed> ItemWithSlot<I> {
+    /// Checks the item, and that it's in a slot `container` has.
+    pub fn validate(&self, container: ContainerKind) -> Result<(), ItemError> {
+        container.validate(std::slice::from_ref(self))
+    }
+}
+
+/// Returns the most the item can be stacked to, which is `default` unless a component changes it.
+fn validate_components(components: &Components, default: i32) -> Result<i32, ItemError> {
+    let minecraft = components.minecraft();
+
+    let max_stack_size = match minecraft.max_stack_size() {
+        Some(max_stack_size) => max_stack_size,
+        // Items that have had the component taken away don't stack
+        None if components
+            .removed()
+            .contains(&Identifier::minecraft("max_stack_size")) =>
+        {
+            1
+        }
+        None => default,
+    };
+    if !(1..=MAX_STACK_SIZE).contains(&max_stack_size) {
+        return Err(ItemError::InvalidMaxStackSize(max_stack_size));
+    }
+    if let Some(damage) = minecraft.damage()
+        && damage < 0
+    {
+        return Err(ItemError::InvalidDamage(damage));
+    }
+    if let Some(max_damage) = minecraft.max_damage() {
+        if max_damage < 1 {
+            return Err(ItemError::InvalidMaxDamage(max_damage));
+        }
+        // Stacked items would share their damage
+        if max_stack_size > 1 {
+            return Err(ItemError::Conflict(
+                Identifier::minecraft("max_damage"),
+                Identifier::minecraft("max_stack_size"),
+            ));
+        }
+    }
+
+    for enchantments in [minecraft.enchantments(), minecraft.stored_enchantments()]
+        .into_iter()
+        .flatten()
+    {
+        for (enchantment, level) in enchantments.levels() {
+            if !(1..=MAX_ENCHANTMENT_LEVEL).contains(level) {
+                return Err(ItemError::InvalidEnchantmentLevel {
+                    enchantment: enchantment.clone(),
+                    level: *level,
+                });
+            }
+        }
+    }
+
+    // Removed components are saved under their id with a `!` in front
+    let saved: HashMap<String, Value> = fastnbt::from_bytes(&fastnbt::to_bytes(components)?)?;
+    for removed in components.removed() {
+        if saved.contains_key(&removed.to_string()) {
+            return Err(ItemError::AddedAndRemoved(removed.clone()));
+        }
+    }
+
+    let items = minecraft.bundle_contents().into_iter().flatten();
+    let items = items.chain(minecraft.charged_projectiles().into_iter().flatten());
+    for item in items.chain(minecraft.use_remainder()) {
+        item.validate()?;
+    }
+    if let Some(container) = minecraft.container() {
+        ContainerKind::Item
+            .validate_slots(container.iter().map(|slot| (slot.slot(), slot.item())))?;
+    }
+
+    Ok(max_stack_size)
+}
+
+/// Makes an item a component at a time, then checks it with [`Item::validate`].
+#[derive(Clone)]
+pub struct ItemBuilder {
+    item: Item,
+}
+
+impl ItemBuilder {
+    pub fn count(mut self, count: i32) -> Self {
+        self.item.set_count(count);
+        self
+    }
+
+    /// Replaces every component set so far
+    pub fn components(mut self, components: Components) -> Self {
+        self.item.set_components(components);
+        self
+    }
+
+    pub fn custom_name(mut self, name: impl Into<TextComponent>) -> Self {
+        self.minecraft().set_custom_name(name.into());
+        self
+    }
+
+    pub fn item_name(mut self, name: impl Into<TextComponent>) -> Self {
+        self.minecraft().set_item_name(name.into());
+        self
+    }
+
+    pub fn lore(mut self, lines: impl IntoIterator<Item = impl Into<TextComponent>>) -> Self {
+        self.minecraft()
+            .set_lore(lines.into_iter().map(Into::into).collect::<Vec<_>>());
+        self
+    }
+
+    pub fn damage(mut self, damage: i32) -> Self {
+        self.minecraft().set_damage(damage);
+        self
+    }
+
+    pub fn max_damage(mut self, max_damage: i32) -> Self {
+        self.minecraft().set_max_damage(max_damage);
+        self
+    }
+
+    pub fn max_stack_size(mut self, max_stack_size: i32) -> Self {
+        self.minecraft().set_max_stack_size(max_stack_size);
+        self
+    }
+
+    pub fn unbreakable(mut self) -> Self {
+        self.minecraft().set_unbreakable(Unbreakable::default());
+        self
+    }
+
+    /// Adds an enchantment, or changes its level if the item already has it
+    pub fn enchant(mut self, enchantment: Identifier, level: i32) -> Self {
+        let mut enchantments = self.minecraft().enchantments().cloned().unwrap_or_default();
+        enchantments.set_level(enchantment, level);
+        self.minecraft().set_enchantments(enchantments);
+        self
+    }
+
+    pub fn enchantments(mut self, enchantments: Enchantments) -> Self {
+        self.minecraft().set_enchantments(enchantments);
+        self
+    }
+
+    pub fn rarity(mut self, rarity: Rarity) -> Self {
+        self.minecraft().set_rarity(rarity);
+        self
+    }
+
+    pub fn custom_data(mut self, data: Value) -> Self {
+        self.minecraft().set_custom_data(data);
+        self
+    }
+
+    /// Takes away a default component of the item's type
+    pub fn remove(mut self, component: Identifier) -> Self {
+        self.item.components_mut().remove(component);
+        self
+    }
+
+    pub fn build(self) -> Result<Item, ItemError> {
+        self.item.validate()?;
+        Ok(self.item)
+    }
+
+    fn minecraft(&mut self) -> &mut MinecraftComponents {
+        self.item.components_mut().minecraft_mut()
+    }
+}
@@ -0,0 +1,622 @@
+use crate::{
+    OneOf,
+    data::{
+        BlockPos, DATA_VERSION, Identifier,
+        entity::McUuid,
+        player::{GameType, Player},
+    },
+    protocol::VERSION_NAME,
+    text_component::TextComponent,
+};
+use fastnbt::Value;
+use serde::{Deserialize, Serialize};
+use serde_with::{FromInto, serde_as, skip_serializing_none};
+use std::collections::HashMap;
+use strum::{EnumString, FromRepr, IntoStaticStr};
+
+/// The version of the Anvil format saved in `level.dat`
+const ANVIL_VERSION: i32 = 19133;
+
+/// The world settings and state saved in `level.dat`, under its `Data` compound.
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+pub struct LevelData {
+    #[serde(rename = "allowCommands")]
+    allow_commands: bool,
+    #[serde(flatten)]
+    border: WorldBorder,
+    #[serde(rename = "clearWeatherTime")]
+    clear_weather_time: i32,
+    #[serde(rename = "CustomBossEvents")]
+    #[serde(default)]
+    custom_boss_events: HashMap<Identifier, BossEvent>,
+    #[serde(rename = "DataPacks")]
+    data_packs: DataPacks,
+    #[serde(rename = "DataVersion")]
+    data_version: i32,
+    #[serde(rename = "DayTime")]
+    day_time: i64,
+    #[serde(rename = "Difficulty")]
+    difficulty: Difficulty,
+    #[serde(rename = "DifficultyLocked")]
+    difficulty_locked: bool,
+    #[serde(rename = "DragonFight")]
+    dragon_fight: Option<DragonFight>,
+    #[serde(default)]
+    enabled_features: Vec<Identifier>,
+    #[serde(rename = "GameRules")]
+    game_rules: GameRules,
+    #[serde(rename = "GameType")]
+    game_type: GameType,
+    hardcore: bool,
+    initialized: bool,
+    #[serde(rename = "LastPlayed")]
+    last_played: i64,
+    #[serde(rename = "LevelName")]
+    level_name: String,
+    /// The player of a singleplayer world
+    // Boxed at recommendation of compiler
+    #[serde(rename = "Player")]
+    player: Option<Box<Player>>,
+    raining: bool,
+    #[serde(rename = "rainTime")]
+    rain_time: i32,
+    #[serde(rename = "ScheduledEvents")]
+    #[serde(default)]
+    scheduled_events: Vec<Value>,
+    #[serde(rename = "ServerBrands")]
+    #[serde(default)]
+    server_brands: Vec<String>,
+    #[serde(rename = "SpawnAngle")]
+    spawn_angle: f32,
+    #[serde(rename = "SpawnX")]
+    spawn_x: i32,
+    #[serde(rename = "SpawnY")]
+    spawn_y: i32,
+    #[serde(rename = "SpawnZ")]
+    spawn_z: i32,
+    thundering: bool,
+    #[serde(rename = "thunderTime")]
+    thunder_time: i32,
+    #[serde(rename = "Time")]
+    time: i64,
+    /// The Anvil format version, always [`ANVIL_VERSION`]
+    version: i32,
+    #[serde(rename = "Version")]
+    game_version: Option<GameVersion>,
+    #[serde(rename = "WanderingTraderId")]
+    wandering_trader_id: Option<McUuid>,
+    #[serde(rename = "WanderingTraderSpawnChance")]
+    wandering_trader_spawn_chance: i32,
+    #[serde(rename = "WanderingTraderSpawnDelay")]
+    wandering_trader_spawn_delay: i32,
+    #[serde(rename = "WasModded")]
+    was_modded: bool,
+    #[serde(rename = "WorldGenSettings")]
+    world_gen_settings: WorldGenSettings,
+    /// Fields that aren't modelled, kept as they were saved
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+impl LevelData {
+    /// The `level.dat` vanilla makes for a new survival world with default settings, before the
+    /// spawn has been found. The dimensions are the vanilla overworld, nether and end.
+    pub fn new(level_name: impl Into<String>, seed: i64) -> Self {
+        Self {
+            allow_commands: false,
+            border: WorldBorder::default(),
+            clear_weather_time: 0,
+            custom_boss_events: HashMap::new(),
+            data_packs: DataPacks {
+                enabled: vec!["vanilla".to_string()],
+                disabled: vec![],
+            },
+            data_version: DATA_VERSION,
+            day_time: 0,
+            difficulty: Difficulty::Normal,
+            difficulty_locked: false,
+            dragon_fight: None,
+            enabled_features: vec![Identifier::minecraft("vanilla")],
+            game_rules: GameRules::default(),
+            game_type: GameType::Survival,
+            hardcore: false,
+            initialized: false,
+            last_played: 0,
+            level_name: level_name.into(),
+            player: None,
+            raining: false,
+            rain_time: 0,
+            scheduled_events: vec![],
+            server_brands: vec![],
+            spawn_angle: 0.0,
+            spawn_x: 0,
+            spawn_y: 64,
+            spawn_z: 0,
+            thundering: false,
+            thunder_time: 0,
+            time: 0,
+            version: ANVIL_VERSION,
+            game_version: Some(GameVersion {
+                id: DATA_VERSION,
+                name: VERSION_NAME.to_string(),
+                series: "main".to_string(),
+                snapshot: false,
+            }),
+            wandering_trader_id: None,
+            wandering_trader_spawn_chance: 25,
+            wandering_trader_spawn_delay: 24000,
+            was_modded: false,
+            world_gen_settings: WorldGenSettings::new(seed),
+            extra: HashMap::new(),
+        }
+    }
+
+    pub fn level_name(&self) -> &str {
+        &self.level_name
+    }
+
+    pub const fn data_version(&self) -> i32 {
+        self.data_version
+    }
+
+    pub const fn game_type(&self) -> GameType {
+        self.game_type
+    }
+
+    pub const fn difficulty(&self) -> Difficulty {
+        self.difficulty
+    }
+
+    pub const fn hardcore(&self) -> bool {
+        self.hardcore
+    }
+
+    /// The world spawn and the angle players face there
+    pub const fn spawn(&self) -> (BlockPos, f32) {
+        (
+            BlockPos::new(self.spawn_x, self.spawn_y, self.spawn_z),
+            self.spawn_angle,
+        )
+    }
+
+    pub fn set_spawn(&mut self, pos: BlockPos, angle: f32) {
+        self.spawn_x = pos.x;
+        self.spawn_y = pos.y;
+        self.spawn_z = pos.z;
+        self.spawn_angle = angle;
+    }
+
+    /// Ticks since the world was created
+    pub const fn time(&self) -> i64 {
+        self.time
+    }
+
+    /// The time of day, which unlike [`Self::time`] can be changed by commands and sleeping
+    pub const fn day_time(&self) -> i64 {
+        self.day_time
+    }
+
+    pub fn set_day_time(&mut self, day_time: i64) {
+        self.day_time = day_time;
+    }
+
+    pub const fn weather(&self) -> Weather {
+        Weather {
+            clear_time: self.clear_weather_time,
+            raining: self.raining,
+            rain_time: self.rain_time,
+            thundering: self.thundering,
+            thunder_time: self.thunder_time,
+        }
+    }
+
+    pub fn set_weather(&mut self, weather: Weather) {
+        self.clear_weather_time = weather.clear_time;
+        self.raining = weather.raining;
+        self.rain_time = weather.rain_time;
+        self.thundering = weather.thundering;
+        self.thunder_time = weather.thunder_time;
+    }
+
+    pub fn game_rules(&self) -> &GameRules {
+        &self.game_rules
+    }
+
+    pub fn game_rules_mut(&mut self) -> &mut GameRules {
+        &mut self.game_rules
+    }
+
+    pub fn world_gen_settings(&self) -> &WorldGenSettings {
+        &self.world_gen_settings
+    }
+
+    pub fn data_packs(&self) -> &DataPacks {
+        &self.data_packs
+    }
+
+    pub fn data_packs_mut(&mut self) -> &mut DataPacks {
+        &mut self.data_packs
+    }
+
+    pub fn custom_boss_events(&self) -> &HashMap<Identifier, BossEvent> {
+        &self.custom_boss_events
+    }
+
+    pub fn dragon_fight(&self) -> Option<&DragonFight> {
+        self.dragon_fight.as_ref()
+    }
+
+    /// Ticks until the next attempt to spawn a wandering trader
+    pub const fn wandering_trader_spawn_delay(&self) -> i32 {
+        self.wandering_trader_spawn_delay
+    }
+
+    pub fn player(&self) -> Option<&Player> {
+        self.player.as_deref()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Weather {
+    /// Ticks of clear weather left after `/weather clear`
+    pub clear_time: i32,
+    pub raining: bool,
+    /// Ticks until it starts or stops raining
+    pub rain_time: i32,
+    pub thundering: bool,
+    /// Ticks until it starts or stops thundering
+    pub thunder_time: i32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct WorldBorder {
+    #[serde(rename = "BorderCenterX")]
+    center_x: f64,
+    #[serde(rename = "BorderCenterZ")]
+    center_z: f64,
+    #[serde(rename = "BorderDamagePerBlock")]
+    damage_per_block: f64,
+    #[serde(rename = "BorderSafeZone")]
+    safe_zone: f64,
+    #[serde(rename = "BorderSize")]
+    size: f64,
+    #[serde(rename = "BorderSizeLerpTarget")]
+    size_lerp_target: f64,
+    #[serde(rename = "BorderSizeLerpTime")]
+    size_lerp_time: i64,
+    #[serde(rename = "BorderWarningBlocks")]
+    warning_blocks: f64,
+    #[serde(rename = "BorderWarningTime")]
+    warning_time: f64,
+}
+
+impl Default for WorldBorder {
+    /// The border of a new world, as far out as it can go
+    fn default() -> Self {
+        Self {
+            center_x: 0.0,
+            center_z: 0.0,
+            damage_per_block: 0.2,
+            safe_zone: 5.0,
+            size: 59_999_968.0,
+            size_lerp_target: 59_999_968.0,
+            size_lerp_time: 0,
+            warning_blocks: 5.0,
+            warning_time: 15.0,
+        }
+    }
+}
+
+#[derive(
+    Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, FromRepr, EnumString, IntoStaticStr,
+)]
+#[serde(try_from = "i8", into = "i8")]
+#[strum(serialize_all = "lowercase")]
+pub enum Difficulty {
+    Peaceful,
+    Easy,
+    Normal,
+    Hard,
+}
+
+impl TryFrom<i8> for Difficulty {
+    type Error = &'static str;
+
+    fn try_from(value: i8) -> Result<Self, Self::Error> {
+        Self::from_repr(value as usize).ok_or("Invalid difficulty")
+    }
+}
+
+impl From<Difficulty> for i8 {
+    fn from(value: Difficulty) -> Self {
+        value as i8
+    }
+}
+
+/// Game rules by name. Every value is saved as a string, whatever the rule's type.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct GameRules(HashMap<String, String>);
+
+impl GameRules {
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.0.get(name).map(String::as_str)
+    }
+
+    /// `None` if the rule isn't set or isn't a boolean
+    pub fn get_bool(&self, name: &str) -> Option<bool> {
+        self.get(name)?.parse().ok()
+    }
+
+    /// `None` if the rule isn't set or isn't an integer
+    pub fn get_int(&self, name: &str) -> Option<i32> {
+        self.get(name)?.parse().ok()
+    }
+
+    pub fn set(&mut self, name: impl Into<String>, value: impl ToString) {
+        self.0.insert(name.into(), value.to_string());
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+#[serde(rename_all = "PascalCase")]
+pub struct DataPacks {
+    /// Pack ids like `vanilla` or `file/example.zip`, from lowest to highest priority
+    #[serde(default)]
+    pub enabled: Vec<String>,
+    #[serde(default)]
+    pub disabled: Vec<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct DragonFight {
+    #[serde(default = "default_true")]
+    needs_state_scanning: bool,
+    #[serde(default)]
+    dragon_killed: bool,
+    #[serde(default)]
+    previously_killed: bool,
+    #[serde(default)]
+    is_respawning: bool,
+    dragon: Option<McUuid>,
+    exit_portal_location: Option<BlockPos>,
+    /// Angles of the end gateways still to be spawned
+    gateways: Option<Vec<i32>>,
+}
+
+impl DragonFight {
+    pub const fn dragon_killed(&self) -> bool {
+        self.dragon_killed
+    }
+
+    pub const fn previously_killed(&self) -> bool {
+        self.previously_killed
+    }
+
+    pub const fn exit_portal_location(&self) -> Option<BlockPos> {
+        self.exit_portal_location
+    }
+}
+
+const fn default_true() -> bool {
+    true
+}
+
+/// A boss bar made with `/bossbar`.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct BossEvent {
+    /// A text component as JSON
+    name: String,
+    #[serde(default)]
+    players: Vec<McUuid>,
+    color: BossBarColor,
+    overlay: BossBarOverlay,
+    create_world_fog: bool,
+    darken_screen: bool,
+    play_boss_music: bool,
+    value: i32,
+    max: i32,
+    visible: bool,
+}
+
+impl BossEvent {
+    pub fn name(&self) -> Result<TextComponent, serde_json::Error> {
+        serde_json::from_str(&self.name)
+    }
+
+    pub const fn color(&self) -> BossBarColor {
+        self.color
+    }
+
+    pub const fn overlay(&self) -> BossBarOverlay {
+        self.overlay
+    }
+
+    /// The progress and what it's out of
+    pub const fn value(&self) -> (i32, i32) {
+        (self.value, self.max)
+    }
+
+    pub const fn visible(&self) -> bool {
+        self.visible
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BossBarColor {
+    Pink,
+    Blue,
+    Red,
+    Green,
+    Yellow,
+    Purple,
+    White,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BossBarOverlay {
+    #[serde(rename = "progress")]
+    Progress,
+    #[serde(rename = "notched_6")]
+    Notched6,
+    #[serde(rename = "notched_10")]
+    Notched10,
+    #[serde(rename = "notched_12")]
+    Notched12,
+    #[serde(rename = "notched_20")]
+    Notched20,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "PascalCase")]
+pub struct GameVersion {
+    pub id: i32,
+    pub name: String,
+    /// `main` unless the world was made by an experimental version
+    pub series: String,
+    pub snapshot: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct WorldGenSettings {
+    bonus_chest: bool,
+    generate_features: bool,
+    seed: i64,
+    dimensions: HashMap<Identifier, Dimension>,
+}
+
+impl WorldGenSettings {
+    /// Vanilla's default settings: the overworld, nether and end as vanilla generates them
+    pub fn new(seed: i64) -> Self {
+        let noise = |settings: &str, biome_source| Generator::Noise {
+            settings: OneOf::Left(Identifier::minecraft(settings)),
+            biome_source,
+        };
+        let multi_noise = |preset: &str| BiomeSource::MultiNoise {
+            preset: Some(Identifier::minecraft(preset)),
+            biomes: None,
+        };
+
+        let dimensions = [
+            ("overworld", noise("overworld", multi_noise("overworld"))),
+            ("the_nether", noise("nether", multi_noise("nether"))),
+            ("the_end", noise("end", BiomeSource::TheEnd)),
+        ]
+        .into_iter()
+        .map(|(name, generator)| {
+            let id = Identifier::minecraft(name);
+            let dimension = Dimension {
+                kind: id.clone(),
+                generator,
+            };
+            (id, dimension)
+        })
+        .collect();
+
+        Self {
+            bonus_chest: false,
+            generate_features: true,
+            seed,
+            dimensions,
+        }
+    }
+
+    pub const fn seed(&self) -> i64 {
+        self.seed
+    }
+
+    pub const fn generate_features(&self) -> bool {
+        self.generate_features
+    }
+
+    pub const fn bonus_chest(&self) -> bool {
+        self.bonus_chest
+    }
+
+    pub fn dimensions(&self) -> &HashMap<Identifier, Dimension> {
+        &self.dimensions
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Dimension {
+    /// An entry of the `dimension_type` registry
+    #[serde(rename = "type")]
+    pub kind: Identifier,
+    pub generator: Generator,
+}
+
+#[serde_as]
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(tag = "type")]
+pub enum Generator {
+    #[serde(rename = "minecraft:noise", alias = "noise")]
+    Noise {
+        /// A noise settings id, or inline noise settings
+        settings: OneOf<Identifier, Value>,
+        biome_source: BiomeSource,
+    },
+    #[serde(rename = "minecraft:flat", alias = "flat")]
+    Flat { settings: FlatSettings },
+    #[serde(rename = "minecraft:debug", alias = "debug")]
+    Debug,
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(tag = "type")]
+pub enum BiomeSource {
+    #[serde(rename = "minecraft:multi_noise", alias = "multi_noise")]
+    MultiNoise {
+        /// A multi noise preset, or the biomes and their climates inline
+        preset: Option<Identifier>,
+        biomes: Option<Value>,
+    },
+    #[serde(rename = "minecraft:the_end", alias = "the_end")]
+    TheEnd,
+    #[serde(rename = "minecraft:fixed", alias = "fixed")]
+    Fixed { biome: Identifier },
+    #[serde(rename = "minecraft:checkerboard", alias = "checkerboard")]
+    Checkerboard {
+        /// A biome, a list of biomes or a biome tag
+        biomes: Value,
+        scale: Option<i32>,
+    },
+}
+
+#[serde_as]
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+pub struct FlatSettings {
+    /// From the bottom up
+    layers: Vec<FlatLayer>,
+    biome: Identifier,
+    #[serde_as(as = "FromInto<OneOf<bool, i8>>")]
+    #[serde(default)]
+    lakes: bool,
+    #[serde_as(as = "FromInto<OneOf<bool, i8>>")]
+    #[serde(default)]
+    features: bool,
+    /// A structure set, a list of them or a tag
+    structure_overrides: Option<Value>,
+}
+
+impl FlatSettings {
+    pub fn layers(&self) -> &[FlatLayer] {
+        &self.layers
+    }
+
+    pub fn biome(&self) -> &Identifier {
+        &self.biome
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct FlatLayer {
+    pub block: Identifier,
+    pub height: i32,
+}
@@ -1,16 +1,38 @@
 pub mod block;
+pub mod chunk;
 pub mod components;
 pub mod entity;
+pub mod item;
+pub mod level;
+pub mod nbt_path;
 pub mod particle;
 pub mod player;
+pub mod region;
+pub mod registry;
+pub mod selector;
+pub mod slot;
+pub mod world;
 
+pub use nbt_path::NbtPath;
+pub use selector::Selector;
+
+use crate::types::Type;
 use components::MinecraftComponents;
 use derive_more::{AsMut, AsRef, Deref, DerefMut};
+use fastnbt::IntArray;
 use num_traits::{PrimInt, Signed};
 use serde::{Deserialize, Serialize};
-use serde_with::with_prefix;
-use std::{collections::HashMap, str::FromStr};
+use serde_with::{skip_serializing_none, with_prefix};
+use std::{
+    collections::HashMap,
+    io::{Read, Write},
+    str::FromStr,
+};
+
+/// The `DataVersion` of 1.21.4, saved alongside chunks, entities and players
+pub const DATA_VERSION: i32 = 4189;
 
+#[skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone)]
 pub struct Item {
     id: Identifier,
@@ -18,28 +40,196 @@ pub struct Item {
     components: Option<Components>,
 }
 
-#[derive(Serialize, Deserialize, Clone)]
+impl Item {
+    pub fn id(&self) -> &Identifier {
+        &self.id
+    }
+
+    /// How many there are, which is left out when it's one
+    pub fn count(&self) -> i32 {
+        self.count.unwrap_or(1)
+    }
+
+    /// What differs from the defaults of the item's type
+    pub fn components(&self) -> Option<&Components> {
+        self.components.as_ref()
+    }
+
+    pub fn set_count(&mut self, count: i32) {
+        self.count = Some(count);
+    }
+
+    /// The item's components, which are added if it has none
+    pub fn components_mut(&mut self) -> &mut Components {
+        self.components.get_or_insert_default()
+    }
+
+    pub fn set_components(&mut self, components: impl Into<Option<Components>>) {
+        self.components = components.into();
+    }
+}
+
+/// The components an item adds to or changes from the defaults of its type, and the defaults it
+/// removes, saved as `!<id>`.
+#[derive(Serialize, Clone, Default)]
 pub struct Components {
+    // Boxed at recommendation of compiler
     #[serde(flatten)]
-    minecraft: MinecraftNamespaceWrapper<MinecraftComponents>,
+    minecraft: MinecraftNamespaceWrapper<Box<MinecraftComponents>>,
+    /// Components of mods, and vanilla ones that aren't modelled
     #[serde(flatten)]
     other: HashMap<Identifier, fastnbt::Value>,
+    #[serde(flatten)]
+    removed: RemovedComponents,
+}
+
+impl Components {
+    pub fn minecraft(&self) -> &MinecraftComponents {
+        &self.minecraft
+    }
+
+    pub fn other(&self) -> &HashMap<Identifier, fastnbt::Value> {
+        &self.other
+    }
+
+    /// Default components of the item's type that it doesn't have
+    pub fn removed(&self) -> &[Identifier] {
+        &self.removed
+    }
+
+    pub fn minecraft_mut(&mut self) -> &mut MinecraftComponents {
+        &mut self.minecraft
+    }
+
+    pub fn other_mut(&mut self) -> &mut HashMap<Identifier, fastnbt::Value> {
+        &mut self.other
+    }
+
+    /// Takes away a default component of the item's type
+    pub fn remove(&mut self, component: Identifier) {
+        if !self.removed.contains(&component) {
+            self.removed.push(component);
+        }
+    }
+
+    /// Gives back a default component of the item's type that was taken away
+    pub fn restore(&mut self, component: &Identifier) {
+        self.removed.retain(|removed| removed != component);
+    }
+}
+
+impl<'de> Deserialize<'de> for Components {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de::Error;
+
+        let mut minecraft = HashMap::new();
+        let mut other = HashMap::new();
+        let mut removed = vec![];
+        for (key, value) in HashMap::<String, fastnbt::Value>::deserialize(deserializer)? {
+            if let Some(id) = key.strip_prefix('!') {
+                removed.push(id.parse::<Identifier>().map_err(D::Error::custom)?);
+                continue;
+            }
+
+            let id = key.parse::<Identifier>().map_err(D::Error::custom)?;
+            if id.namespace == "minecraft" {
+                minecraft.insert(id.path, value);
+            } else {
+                other.insert(id, value);
+            }
+        }
+
+        // Read from the encoded components rather than from the values, which only works for
+        // structs without flattened fields
+        let nbt = fastnbt::to_bytes(&minecraft).map_err(D::Error::custom)?;
+        let known: MinecraftComponents = fastnbt::from_bytes(&nbt)
+            .map_err(|e| D::Error::custom(format!("Invalid item components: {e}")))?;
+        for (path, value) in unknown_fields(&known, minecraft).map_err(D::Error::custom)? {
+            other.insert(Identifier::minecraft(path), value);
+        }
+
+        Ok(Self {
+            minecraft: MinecraftNamespaceWrapper(Box::new(known)),
+            other,
+            removed: RemovedComponents(removed),
+        })
+    }
+}
+
+/// Saved as a compound of `!<id>` to empty compounds
+#[derive(Clone, Default, Deref, DerefMut)]
+struct RemovedComponents(Vec<Identifier>);
+
+impl Serialize for RemovedComponents {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_map(
+            self.iter()
+                .map(|id| (format!("!{id}"), HashMap::<String, fastnbt::Value>::new())),
+        )
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct ItemWithSlot<I: PrimInt + Signed = i8> {
+    #[serde(rename = "Slot")]
     slot: I,
     #[serde(flatten)]
     item: Item,
 }
 
-#[derive(Serialize, Deserialize, Clone, Hash, PartialEq, Eq)]
+impl<I: PrimInt + Signed> ItemWithSlot<I> {
+    pub fn new(slot: I, item: Item) -> Self {
+        Self { slot, item }
+    }
+
+    pub fn slot(&self) -> I {
+        self.slot
+    }
+
+    pub fn item(&self) -> &Item {
+        &self.item
+    }
+
+    pub fn set_slot(&mut self, slot: I) {
+        self.slot = slot;
+    }
+
+    pub fn item_mut(&mut self) -> &mut Item {
+        &mut self.item
+    }
+
+    pub fn into_item(self) -> Item {
+        self.item
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Hash, PartialEq, Eq)]
 #[serde(try_from = "String", into = "String")]
 pub struct Identifier {
     pub namespace: String,
     pub path: String,
 }
 
+impl Identifier {
+    pub fn new(namespace: impl Into<String>, path: impl Into<String>) -> Self {
+        Self {
+            namespace: namespace.into(),
+            path: path.into(),
+        }
+    }
+
+    /// An identifier in the `minecraft` namespace
+    pub fn minecraft(path: impl Into<String>) -> Self {
+        Self::new("minecraft", path)
+    }
+}
+
 impl From<Identifier> for String {
     fn from(value: Identifier) -> Self {
         format!("{}:{}", value.namespace, value.path)
@@ -53,6 +243,24 @@ impl TryFrom<String> for Identifier {
     }
 }
 
+impl std::fmt::Display for Identifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.namespace, self.path)
+    }
+}
+
+impl Type for Identifier {
+    fn write(&self, writer: impl Write) -> std::io::Result<()> {
+        self.to_string().write(writer)
+    }
+
+    fn read(reader: impl Read) -> std::io::Result<Self> {
+        String::read(reader)?
+            .parse()
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+    }
+}
+
 impl FromStr for Identifier {
     type Err = &'static str;
 
@@ -74,66 +282,7 @@ impl FromStr for Identifier {
     }
 }
 
-// TODO: arguments
-#[derive(Serialize, Deserialize, Clone)]
-#[serde(try_from = "String", into = "String")]
-pub enum Selector {
-    NearestPlayer,
-    RandomPlayer,
-    AllPlayers,
-    AllEntities,
-    Executor,
-    NearestEntity,
-}
-
-impl std::fmt::Display for Selector {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Self::NearestPlayer => write!(f, "@p"),
-            Self::RandomPlayer => write!(f, "@r"),
-            Self::AllPlayers => write!(f, "@a"),
-            Self::AllEntities => write!(f, "@e"),
-            Self::Executor => write!(f, "@s"),
-            Self::NearestEntity => write!(f, "@n"),
-        }
-    }
-}
-
-impl From<Selector> for String {
-    fn from(value: Selector) -> Self {
-        value.to_string()
-    }
-}
-
-impl FromStr for Selector {
-    type Err = &'static str;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "@p" => Ok(Self::NearestPlayer),
-            "@r" => Ok(Self::RandomPlayer),
-            "@a" => Ok(Self::AllPlayers),
-            "@e" => Ok(Self::AllEntities),
-            "@s" => Ok(Self::Executor),
-            "@n" => Ok(Self::NearestEntity),
-            _ => Err("Invalid selector"),
-        }
-    }
-}
-
-impl TryFrom<String> for Selector {
-    type Error = &'static str;
-
-    fn try_from(s: String) -> Result<Self, Self::Error> {
-        s.parse()
-    }
-}
-
-// TODO
-#[derive(Serialize, Deserialize, Clone)]
-pub struct NbtPath;
-
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 #[serde(try_from = "String", into = "String")]
 pub struct Coordinates {
     x: Coordinate,
@@ -157,15 +306,15 @@ impl FromStr for Coordinates {
     type Err = &'static str;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut parts = s.split(' ').collect::<Vec<_>>();
-        if parts.len() != 3 {
+        let parts = s.split(' ').collect::<Vec<_>>();
+        let [x, y, z] = parts.as_slice() else {
             return Err("Invalid coordinates");
-        }
+        };
 
         Ok(Self {
-            x: parts.pop().unwrap().parse()?,
-            y: parts.pop().unwrap().parse()?,
-            z: parts.pop().unwrap().parse()?,
+            x: x.parse()?,
+            y: y.parse()?,
+            z: z.parse()?,
         })
     }
 }
@@ -178,7 +327,7 @@ impl TryFrom<String> for Coordinates {
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Coordinate {
     value: f64,
     kind: CoordinateKind,
@@ -186,7 +335,11 @@ pub struct Coordinate {
 
 impl std::fmt::Display for Coordinate {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}{}", self.kind.prefix(), self.value)
+        if self.kind != CoordinateKind::Absolute && self.value == 0.0 {
+            f.write_str(self.kind.prefix())
+        } else {
+            write!(f, "{}{}", self.kind.prefix(), self.value)
+        }
     }
 }
 
@@ -201,10 +354,11 @@ impl FromStr for Coordinate {
             None => return Err("Invalid coordinate"),
         };
 
-        let value = if kind == CoordinateKind::Absolute {
-            s.parse::<f64>()
-        } else {
-            s[1..].parse::<f64>()
+        let value = match kind {
+            CoordinateKind::Absolute => s.parse::<f64>(),
+            // A bare `~` or `^` is no offset at all
+            _ if s.len() == 1 => Ok(0.0),
+            _ => s[1..].parse::<f64>(),
         }
         .map_err(|_| "Invalid coordinate")?;
 
@@ -212,7 +366,7 @@ impl FromStr for Coordinate {
     }
 }
 
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum CoordinateKind {
     Absolute,
     Relative,
@@ -236,8 +390,86 @@ pub struct MinecraftNamespaceWrapper<T: Serialize + for<'a> Deserialize<'a>>(
 
 with_prefix!(prefix_minecraft_namespace "minecraft:");
 
+/// What of a saved compound isn't written back by what was read from it, such as the fields of
+/// newer versions and mods, so it can be kept alongside.
+pub(crate) fn unknown_fields<T: Serialize>(
+    known: &T,
+    mut fields: HashMap<String, fastnbt::Value>,
+) -> fastnbt::error::Result<HashMap<String, fastnbt::Value>> {
+    if let fastnbt::Value::Compound(known) = fastnbt::to_value(known)? {
+        fields.retain(|key, _| !known.contains_key(key));
+    }
+    Ok(fields)
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 pub struct PositionInDimension {
     dimension: Identifier,
-    pos: [i32; 3],
+    pos: BlockPos,
+}
+
+impl PositionInDimension {
+    pub fn new(dimension: Identifier, pos: BlockPos) -> Self {
+        Self { dimension, pos }
+    }
+
+    pub fn dimension(&self) -> &Identifier {
+        &self.dimension
+    }
+
+    pub const fn pos(&self) -> BlockPos {
+        self.pos
+    }
+}
+
+/// A block position: an int array in NBT and a single packed long on the network.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[serde(try_from = "IntArray", into = "IntArray")]
+pub struct BlockPos {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+impl BlockPos {
+    pub const fn new(x: i32, y: i32, z: i32) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl TryFrom<IntArray> for BlockPos {
+    type Error = &'static str;
+
+    fn try_from(value: IntArray) -> Result<Self, Self::Error> {
+        match *value {
+            [x, y, z] => Ok(Self { x, y, z }),
+            _ => Err("Invalid block position"),
+        }
+    }
+}
+
+impl From<BlockPos> for IntArray {
+    fn from(value: BlockPos) -> Self {
+        Self::new(vec![value.x, value.y, value.z])
+    }
+}
+
+/// 26 bits of x, then 26 of z, then 12 of y.
+impl Type for BlockPos {
+    fn write(&self, writer: impl Write) -> std::io::Result<()> {
+        (((self.x as i64 & 0x3ff_ffff) << 38)
+            | ((self.z as i64 & 0x3ff_ffff) << 12)
+            | (self.y as i64 & 0xfff))
+            .write(writer)
+    }
+
+    fn read(reader: impl Read) -> std::io::Result<Self> {
+        let packed = i64::read(reader)?;
+
+        Ok(Self {
+            x: (packed >> 38) as i32,
+            y: (packed << 52 >> 52) as i32,
+            z: (packed << 26 >> 38) as i32,
+        })
+    }
 }
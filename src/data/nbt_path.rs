@@ -0,0 +1,711 @@
+use crate::nbt::snbt;
+use fastnbt::{ByteArray, IntArray, LongArray, Value};
+use serde::{Deserialize, Serialize};
+use std::{borrow::Cow, collections::HashMap, str::FromStr};
+
+/// A path into NBT like `Inventory[{Slot:0b}].components."minecraft:custom_name"`, as used by
+/// `/data` and NBT text components.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(try_from = "String", into = "String")]
+pub struct NbtPath {
+    nodes: Vec<NbtPathNode>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum NbtPathNode {
+    /// `{...}` at the very start, matching the root itself
+    MatchRoot(NbtFilter),
+    /// `name`
+    Child(String),
+    /// `name{...}`
+    MatchChild(String, NbtFilter),
+    /// `[]`
+    AllElements,
+    /// `[i]`, counting back from the end when negative
+    Index(i32),
+    /// `[{...}]`
+    MatchElements(NbtFilter),
+}
+
+/// A compound that matches any tag containing at least what it does, as written in SNBT.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NbtFilter {
+    snbt: String,
+    compound: HashMap<String, Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NbtPathError {
+    /// Nothing matched the path
+    NotFound,
+    /// Values can only be inserted into lists and arrays
+    NotACollection,
+    InvalidIndex(i32),
+}
+
+impl std::fmt::Display for NbtPathError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotFound => write!(f, "Found no elements matching path"),
+            Self::NotACollection => write!(f, "Expected a list or array"),
+            Self::InvalidIndex(index) => write!(f, "Invalid index {index}"),
+        }
+    }
+}
+
+impl std::error::Error for NbtPathError {}
+
+impl NbtPath {
+    pub fn nodes(&self) -> &[NbtPathNode] {
+        &self.nodes
+    }
+
+    /// Everything the path points to, failing if that's nothing.
+    pub fn get<'a>(&self, root: &'a Value) -> Result<Vec<Cow<'a, Value>>, NbtPathError> {
+        let mut found = vec![Cow::Borrowed(root)];
+        for node in &self.nodes {
+            let mut next = vec![];
+            for parent in &found {
+                // Elements of arrays are numbers, which have no children anyway
+                if let Cow::Borrowed(parent) = parent {
+                    node.get(parent, &mut next);
+                }
+            }
+            found = next;
+        }
+
+        if found.is_empty() {
+            Err(NbtPathError::NotFound)
+        } else {
+            Ok(found)
+        }
+    }
+
+    /// Sets everything the path points to, creating any missing parents along the way. Returns
+    /// how many tags changed.
+    pub fn set(&self, root: &mut Value, value: &Value) -> usize {
+        let (last, parents) = self.split_last();
+        follow(parents, Some(last), root, true)
+            .into_iter()
+            .map(|parent| last.set(parent, value))
+            .sum()
+    }
+
+    /// Removes everything the path points to, returning how many tags were removed.
+    pub fn remove(&self, root: &mut Value) -> usize {
+        let (last, parents) = self.split_last();
+        follow(parents, Some(last), root, false)
+            .into_iter()
+            .map(|parent| last.remove(parent))
+            .sum()
+    }
+
+    /// Inserts `values` into every list or array the path points to, creating an empty list if
+    /// there isn't one. A negative `index` counts back from the end, so `-1` appends. Returns how
+    /// many lists changed.
+    pub fn insert(
+        &self,
+        index: i32,
+        root: &mut Value,
+        values: &[Value],
+    ) -> Result<usize, NbtPathError> {
+        let targets = follow(&self.nodes, None, root, true);
+        if targets.is_empty() {
+            return Err(NbtPathError::NotFound);
+        }
+
+        let mut changed = 0;
+        for target in targets {
+            let length = collection_len(target).ok_or(NbtPathError::NotACollection)?;
+            let mut index = if index < 0 {
+                length as i64 + index as i64 + 1
+            } else {
+                index as i64
+            };
+
+            let mut inserted = false;
+            for value in values {
+                let position = usize::try_from(index)
+                    .ok()
+                    .filter(|&position| position <= collection_len(target).unwrap_or(0))
+                    .ok_or(NbtPathError::InvalidIndex(index as i32))?;
+                if insert_element(target, position, value) {
+                    index += 1;
+                    inserted = true;
+                }
+            }
+            changed += usize::from(inserted);
+        }
+
+        Ok(changed)
+    }
+
+    pub fn append(&self, root: &mut Value, values: &[Value]) -> Result<usize, NbtPathError> {
+        self.insert(-1, root, values)
+    }
+
+    pub fn prepend(&self, root: &mut Value, values: &[Value]) -> Result<usize, NbtPathError> {
+        self.insert(0, root, values)
+    }
+
+    fn split_last(&self) -> (&NbtPathNode, &[NbtPathNode]) {
+        // Parsing never produces an empty path
+        self.nodes.split_last().unwrap()
+    }
+}
+
+/// Follows `nodes` from `root`, optionally creating whatever the node after each one expects to
+/// find where there's nothing. With no node after the last, lists are created to insert into.
+fn follow<'v>(
+    nodes: &[NbtPathNode],
+    after: Option<&NbtPathNode>,
+    root: &'v mut Value,
+    create: bool,
+) -> Vec<&'v mut Value> {
+    let mut found = vec![root];
+    for (i, node) in nodes.iter().enumerate() {
+        let parent = nodes
+            .get(i + 1)
+            .or(after)
+            .map_or(Value::List(vec![]), NbtPathNode::preferred_parent);
+        let mut next = vec![];
+        for value in found {
+            node.get_mut(value, create.then_some(&parent), &mut next);
+        }
+        found = next;
+    }
+    found
+}
+
+impl NbtPathNode {
+    /// What to create for this node to look into, if there's nothing there.
+    fn preferred_parent(&self) -> Value {
+        match self {
+            Self::MatchRoot(_) | Self::Child(_) | Self::MatchChild(..) => {
+                Value::Compound(HashMap::new())
+            }
+            Self::AllElements | Self::Index(_) | Self::MatchElements(_) => Value::List(vec![]),
+        }
+    }
+
+    fn get<'a>(&self, parent: &'a Value, out: &mut Vec<Cow<'a, Value>>) {
+        match (self, parent) {
+            (Self::MatchRoot(filter), _) if filter.matches(parent) => {
+                out.push(Cow::Borrowed(parent))
+            }
+            (Self::Child(name), Value::Compound(compound)) => {
+                out.extend(compound.get(name).map(Cow::Borrowed));
+            }
+            (Self::MatchChild(name, filter), Value::Compound(compound)) => out.extend(
+                compound
+                    .get(name)
+                    .filter(|child| filter.matches(child))
+                    .map(Cow::Borrowed),
+            ),
+            (Self::AllElements, _) => {
+                let length = collection_len(parent).unwrap_or(0);
+                out.extend((0..length).filter_map(|i| element(parent, i)));
+            }
+            (Self::Index(index), _) => {
+                let length = collection_len(parent).unwrap_or(0);
+                out.extend(resolve(*index, length).and_then(|i| element(parent, i)));
+            }
+            (Self::MatchElements(filter), Value::List(list)) => out.extend(
+                list.iter()
+                    .filter(|element| filter.matches(element))
+                    .map(Cow::Borrowed),
+            ),
+            _ => {}
+        }
+    }
+
+    fn get_mut<'a>(
+        &self,
+        parent: &'a mut Value,
+        create: Option<&Value>,
+        out: &mut Vec<&'a mut Value>,
+    ) {
+        match (self, parent) {
+            (Self::MatchRoot(filter), parent) if filter.matches(parent) => out.push(parent),
+            (Self::Child(name), Value::Compound(compound)) => match create {
+                Some(create) => out.push(
+                    compound
+                        .entry(name.clone())
+                        .or_insert_with(|| create.clone()),
+                ),
+                None => out.extend(compound.get_mut(name)),
+            },
+            (Self::MatchChild(name, filter), Value::Compound(compound)) => {
+                if create.is_some() && !compound.contains_key(name) {
+                    compound.insert(name.clone(), filter.to_value());
+                }
+                out.extend(compound.get_mut(name).filter(|child| filter.matches(child)));
+            }
+            (Self::AllElements, Value::List(list)) => {
+                if list.is_empty()
+                    && let Some(create) = create
+                {
+                    list.push(create.clone());
+                }
+                out.extend(list.iter_mut());
+            }
+            (Self::Index(index), Value::List(list)) => {
+                if let Some(i) = resolve(*index, list.len()) {
+                    out.push(&mut list[i]);
+                }
+            }
+            (Self::MatchElements(filter), Value::List(list)) => {
+                if create.is_some() && !list.iter().any(|element| filter.matches(element)) {
+                    list.push(filter.to_value());
+                }
+                out.extend(list.iter_mut().filter(|element| filter.matches(element)));
+            }
+            _ => {}
+        }
+    }
+
+    /// Sets what this node points to in `parent`, returning how many tags changed.
+    fn set(&self, parent: &mut Value, value: &Value) -> usize {
+        match (self, &mut *parent) {
+            (Self::Child(name), Value::Compound(compound)) => {
+                let old = compound.insert(name.clone(), value.clone());
+                usize::from(old.as_ref() != Some(value))
+            }
+            (Self::MatchChild(name, filter), Value::Compound(compound)) => {
+                match compound.get_mut(name) {
+                    Some(child) if filter.matches(child) && child != value => {
+                        *child = value.clone();
+                        1
+                    }
+                    _ => 0,
+                }
+            }
+            (Self::AllElements, _) => {
+                let Some(length) = collection_len(parent) else {
+                    return 0;
+                };
+                if length == 0 {
+                    return usize::from(insert_element(parent, 0, value));
+                }
+
+                let changed = (0..length)
+                    .filter(|&i| element(parent, i).is_some_and(|element| *element != *value))
+                    .count();
+                if changed == 0 {
+                    return 0;
+                }
+                // Vanilla empties the list first, so the new value can be of a different type
+                clear(parent);
+                let inserted = (0..length)
+                    .take_while(|&i| insert_element(parent, i, value))
+                    .count();
+                if inserted == 0 { 0 } else { changed }
+            }
+            (Self::Index(index), _) => {
+                let length = collection_len(parent).unwrap_or(0);
+                match resolve(*index, length) {
+                    Some(i) if element(parent, i).is_some_and(|element| *element != *value) => {
+                        usize::from(set_element(parent, i, value))
+                    }
+                    _ => 0,
+                }
+            }
+            (Self::MatchElements(filter), Value::List(list)) => {
+                let compatible = list.first().is_none_or(|first| same_type(first, value));
+                let mut changed = 0;
+                for element in list.iter_mut() {
+                    if compatible && filter.matches(element) && element != value {
+                        *element = value.clone();
+                        changed += 1;
+                    }
+                }
+                changed
+            }
+            _ => 0,
+        }
+    }
+
+    /// Removes what this node points to from `parent`, returning how many tags were removed.
+    fn remove(&self, parent: &mut Value) -> usize {
+        match (self, &mut *parent) {
+            (Self::Child(name), Value::Compound(compound)) => {
+                usize::from(compound.remove(name).is_some())
+            }
+            (Self::MatchChild(name, filter), Value::Compound(compound))
+                if compound
+                    .get(name)
+                    .is_some_and(|child| filter.matches(child)) =>
+            {
+                compound.remove(name);
+                1
+            }
+            (Self::AllElements, _) => {
+                let length = collection_len(parent).unwrap_or(0);
+                clear(parent);
+                length
+            }
+            (Self::Index(index), _) => {
+                let length = collection_len(parent).unwrap_or(0);
+                match resolve(*index, length) {
+                    Some(i) => {
+                        remove_element(parent, i);
+                        1
+                    }
+                    None => 0,
+                }
+            }
+            (Self::MatchElements(filter), Value::List(list)) => {
+                let length = list.len();
+                list.retain(|element| !filter.matches(element));
+                length - list.len()
+            }
+            _ => 0,
+        }
+    }
+}
+
+impl std::fmt::Display for NbtPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, node) in self.nodes.iter().enumerate() {
+            let dot = if i == 0 { "" } else { "." };
+            match node {
+                NbtPathNode::MatchRoot(filter) => filter.fmt(f)?,
+                NbtPathNode::Child(name) => {
+                    f.write_str(dot)?;
+                    write_name(name, f)?;
+                }
+                NbtPathNode::MatchChild(name, filter) => {
+                    f.write_str(dot)?;
+                    write_name(name, f)?;
+                    filter.fmt(f)?;
+                }
+                NbtPathNode::AllElements => f.write_str("[]")?,
+                NbtPathNode::Index(index) => write!(f, "[{index}]")?,
+                NbtPathNode::MatchElements(filter) => write!(f, "[{filter}]")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl From<NbtPath> for String {
+    fn from(value: NbtPath) -> Self {
+        value.to_string()
+    }
+}
+
+impl FromStr for NbtPath {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut rest = s;
+        let mut nodes = vec![];
+        while !rest.is_empty() {
+            let (node, after) = read_node(rest, nodes.is_empty())?;
+            nodes.push(node);
+            rest = match after.chars().next() {
+                None | Some('[' | '{') => after,
+                Some('.') if after.len() > 1 => &after[1..],
+                Some(_) => return Err("Expected '.' between NBT path nodes"),
+            };
+        }
+
+        if nodes.is_empty() {
+            return Err("Empty NBT path");
+        }
+        Ok(Self { nodes })
+    }
+}
+
+impl TryFrom<String> for NbtPath {
+    type Error = &'static str;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl NbtFilter {
+    pub fn compound(&self) -> &HashMap<String, Value> {
+        &self.compound
+    }
+
+    /// Whether `value` has everything the filter does. Lists in the filter match lists containing
+    /// a match for each of their elements.
+    pub fn matches(&self, value: &Value) -> bool {
+        let Value::Compound(compound) = value else {
+            return false;
+        };
+        compound_matches(&self.compound, compound)
+    }
+
+    fn to_value(&self) -> Value {
+        Value::Compound(self.compound.clone())
+    }
+
+    /// Parses the filter at the start of `s`, returning it and whatever comes after it.
+    fn read(s: &str) -> Result<(Self, &str), &'static str> {
+        let (compound, length) = snbt::parse_compound_prefix(s).map_err(|e| e.message)?;
+        let filter = Self {
+            snbt: s[..length].to_string(),
+            compound,
+        };
+        Ok((filter, &s[length..]))
+    }
+}
+
+impl std::fmt::Display for NbtFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.snbt)
+    }
+}
+
+impl FromStr for NbtFilter {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match Self::read(s)? {
+            (filter, "") => Ok(filter),
+            _ => Err("Trailing data after NBT filter"),
+        }
+    }
+}
+
+fn compound_matches(filter: &HashMap<String, Value>, compound: &HashMap<String, Value>) -> bool {
+    filter.iter().all(|(key, filter)| {
+        compound
+            .get(key)
+            .is_some_and(|value| value_matches(filter, value))
+    })
+}
+
+fn value_matches(filter: &Value, value: &Value) -> bool {
+    match (filter, value) {
+        (Value::Compound(filter), Value::Compound(compound)) => compound_matches(filter, compound),
+        (Value::List(filter), Value::List(list)) if filter.is_empty() => list.is_empty(),
+        (Value::List(filter), Value::List(list)) => filter
+            .iter()
+            .all(|filter| list.iter().any(|value| value_matches(filter, value))),
+        _ => filter == value,
+    }
+}
+
+/// Names are written bare unless they hold something that would end them.
+const fn is_unquoted(c: char) -> bool {
+    !matches!(c, ' ' | '"' | '\'' | '[' | ']' | '.' | '{' | '}')
+}
+
+fn write_name(name: &str, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    if !name.is_empty() && name.chars().all(is_unquoted) {
+        return f.write_str(name);
+    }
+
+    f.write_str("\"")?;
+    for c in name.chars() {
+        if matches!(c, '"' | '\\') {
+            f.write_str("\\")?;
+        }
+        write!(f, "{c}")?;
+    }
+    f.write_str("\"")
+}
+
+fn read_name(s: &str) -> Result<(String, &str), &'static str> {
+    let mut chars = s.char_indices();
+    match chars.next() {
+        Some((_, quote @ ('"' | '\''))) => {
+            let mut name = String::new();
+            while let Some((i, c)) = chars.next() {
+                match c {
+                    '\\' => match chars.next() {
+                        Some((_, c)) if c == quote || c == '\\' => name.push(c),
+                        _ => return Err("Invalid escape sequence in quoted name"),
+                    },
+                    c if c == quote => return Ok((name, &s[i + 1..])),
+                    c => name.push(c),
+                }
+            }
+            Err("Unclosed quoted name")
+        }
+        _ => {
+            let end = s.find(|c| !is_unquoted(c)).unwrap_or(s.len());
+            if end == 0 {
+                return Err("Invalid NBT path node");
+            }
+            Ok((s[..end].to_string(), &s[end..]))
+        }
+    }
+}
+
+fn read_node(s: &str, first: bool) -> Result<(NbtPathNode, &str), &'static str> {
+    if s.starts_with('{') {
+        if !first {
+            return Err("Filters on their own can only start a path");
+        }
+        let (filter, rest) = NbtFilter::read(s)?;
+        return Ok((NbtPathNode::MatchRoot(filter), rest));
+    }
+
+    if let Some(rest) = s.strip_prefix('[') {
+        if rest.starts_with('{') {
+            let (filter, rest) = NbtFilter::read(rest)?;
+            let rest = rest.strip_prefix(']').ok_or("Expected ']'")?;
+            return Ok((NbtPathNode::MatchElements(filter), rest));
+        }
+        if let Some(rest) = rest.strip_prefix(']') {
+            return Ok((NbtPathNode::AllElements, rest));
+        }
+
+        let (index, rest) = rest.split_once(']').ok_or("Expected ']'")?;
+        let index = index.parse().map_err(|_| "Invalid index")?;
+        return Ok((NbtPathNode::Index(index), rest));
+    }
+
+    let (name, rest) = read_name(s)?;
+    if rest.starts_with('{') {
+        let (filter, rest) = NbtFilter::read(rest)?;
+        Ok((NbtPathNode::MatchChild(name, filter), rest))
+    } else {
+        Ok((NbtPathNode::Child(name), rest))
+    }
+}
+
+/// Turns a possibly negative index into a position in a collection of `length`.
+fn resolve(index: i32, length: usize) -> Option<usize> {
+    let index = if index < 0 {
+        length as i64 + index as i64
+    } else {
+        index as i64
+    };
+    usize::try_from(index).ok().filter(|&index| index < length)
+}
+
+fn same_type(a: &Value, b: &Value) -> bool {
+    std::mem::discriminant(a) == std::mem::discriminant(b)
+}
+
+/// Numbers of any type can go in arrays, converted as vanilla does.
+fn as_long(value: &Value) -> Option<i64> {
+    match *value {
+        Value::Byte(value) => Some(value.into()),
+        Value::Short(value) => Some(value.into()),
+        Value::Int(value) => Some(value.into()),
+        Value::Long(value) => Some(value),
+        Value::Float(value) => Some(value.floor() as i64),
+        Value::Double(value) => Some(value.floor() as i64),
+        _ => None,
+    }
+}
+
+fn collection_len(value: &Value) -> Option<usize> {
+    match value {
+        Value::List(list) => Some(list.len()),
+        Value::ByteArray(array) => Some(array.len()),
+        Value::IntArray(array) => Some(array.len()),
+        Value::LongArray(array) => Some(array.len()),
+        _ => None,
+    }
+}
+
+fn element(collection: &Value, i: usize) -> Option<Cow<'_, Value>> {
+    match collection {
+        Value::List(list) => list.get(i).map(Cow::Borrowed),
+        Value::ByteArray(array) => array.get(i).map(|&value| Cow::Owned(Value::Byte(value))),
+        Value::IntArray(array) => array.get(i).map(|&value| Cow::Owned(Value::Int(value))),
+        Value::LongArray(array) => array.get(i).map(|&value| Cow::Owned(Value::Long(value))),
+        _ => None,
+    }
+}
+
+/// Replaces the element at `i`, returning whether `value` could go in the collection.
+fn set_element(collection: &mut Value, i: usize, value: &Value) -> bool {
+    match collection {
+        Value::List(list) => {
+            if !same_type(&list[i], value) {
+                return false;
+            }
+            list[i] = value.clone();
+        }
+        Value::ByteArray(array) => match as_long(value) {
+            Some(long) => array[i] = long as i8,
+            None => return false,
+        },
+        Value::IntArray(array) => match as_long(value) {
+            Some(long) => array[i] = long as i32,
+            None => return false,
+        },
+        Value::LongArray(array) => match as_long(value) {
+            Some(long) => array[i] = long,
+            None => return false,
+        },
+        _ => return false,
+    }
+    true
+}
+
+/// Inserts `value` before `i`, returning whether it could go in the collection.
+fn insert_element(collection: &mut Value, i: usize, value: &Value) -> bool {
+    match collection {
+        Value::List(list) => {
+            if list.first().is_some_and(|first| !same_type(first, value)) {
+                return false;
+            }
+            list.insert(i, value.clone());
+        }
+        Value::ByteArray(array) => match as_long(value) {
+            Some(long) => *array = ByteArray::new(edited(array, |vec| vec.insert(i, long as i8))),
+            None => return false,
+        },
+        Value::IntArray(array) => match as_long(value) {
+            Some(long) => *array = IntArray::new(edited(array, |vec| vec.insert(i, long as i32))),
+            None => return false,
+        },
+        Value::LongArray(array) => match as_long(value) {
+            Some(long) => *array = LongArray::new(edited(array, |vec| vec.insert(i, long))),
+            None => return false,
+        },
+        _ => return false,
+    }
+    true
+}
+
+/// fastnbt's arrays can't change length in place, so they're copied and rebuilt instead.
+fn edited<T: Copy>(array: &[T], edit: impl FnOnce(&mut Vec<T>)) -> Vec<T> {
+    let mut vec = array.to_vec();
+    edit(&mut vec);
+    vec
+}
+
+fn remove_element(collection: &mut Value, i: usize) {
+    match collection {
+        Value::List(list) => {
+            list.remove(i);
+        }
+        Value::ByteArray(array) => {
+            *array = ByteArray::new(edited(array, |vec| {
+                vec.remove(i);
+            }));
+        }
+        Value::IntArray(array) => {
+            *array = IntArray::new(edited(array, |vec| {
+                vec.remove(i);
+            }));
+        }
+        Value::LongArray(array) => {
+            *array = LongArray::new(edited(array, |vec| {
+                vec.remove(i);
+            }));
+        }
+        _ => {}
+    }
+}
+
+fn clear(collection: &mut Value) {
+    match collection {
+        Value::List(list) => list.clear(),
+        Value::ByteArray(array) => *array = ByteArray::new(vec![]),
+        Value::IntArray(array) => *array = IntArray::new(vec![]),
+        Value::LongArray(array) => *array = LongArray::new(vec![]),
+        _ => {}
+    }
+}
@@ -1,5 +1,449 @@
+use crate::{
+    OneOf,
+    data::{BlockPos, Identifier, Item, block::Block, entity::McUuid},
+    text_component::ARGB,
+    types::{AsVarInt, Codec, Type},
+};
 use serde::{Deserialize, Serialize};
+use serde_with::{TryFromInto, serde_as};
+use std::io::{Read, Write};
+
+/// Every particle type, in network id order so the derived [`Type`] writes the right id ahead of
+/// the options.
+#[derive(Serialize, Deserialize, Clone, Type)]
+#[serde(tag = "type")]
+pub enum Particle {
+    #[serde(rename = "minecraft:angry_villager", alias = "angry_villager")]
+    AngryVillager,
+    #[serde(rename = "minecraft:block", alias = "block")]
+    Block(BlockParticle),
+    #[serde(rename = "minecraft:block_marker", alias = "block_marker")]
+    BlockMarker(BlockParticle),
+    #[serde(rename = "minecraft:bubble", alias = "bubble")]
+    Bubble,
+    #[serde(rename = "minecraft:cloud", alias = "cloud")]
+    Cloud,
+    #[serde(rename = "minecraft:crit", alias = "crit")]
+    Crit,
+    #[serde(rename = "minecraft:damage_indicator", alias = "damage_indicator")]
+    DamageIndicator,
+    #[serde(rename = "minecraft:dragon_breath", alias = "dragon_breath")]
+    DragonBreath,
+    #[serde(rename = "minecraft:dripping_lava", alias = "dripping_lava")]
+    DrippingLava,
+    #[serde(rename = "minecraft:falling_lava", alias = "falling_lava")]
+    FallingLava,
+    #[serde(rename = "minecraft:landing_lava", alias = "landing_lava")]
+    LandingLava,
+    #[serde(rename = "minecraft:dripping_water", alias = "dripping_water")]
+    DrippingWater,
+    #[serde(rename = "minecraft:falling_water", alias = "falling_water")]
+    FallingWater,
+    #[serde(rename = "minecraft:dust", alias = "dust")]
+    Dust(Dust),
+    #[serde(
+        rename = "minecraft:dust_color_transition",
+        alias = "dust_color_transition"
+    )]
+    DustColorTransition(DustColorTransition),
+    #[serde(rename = "minecraft:effect", alias = "effect")]
+    Effect,
+    #[serde(rename = "minecraft:elder_guardian", alias = "elder_guardian")]
+    ElderGuardian,
+    #[serde(rename = "minecraft:enchanted_hit", alias = "enchanted_hit")]
+    EnchantedHit,
+    #[serde(rename = "minecraft:enchant", alias = "enchant")]
+    Enchant,
+    #[serde(rename = "minecraft:end_rod", alias = "end_rod")]
+    EndRod,
+    #[serde(rename = "minecraft:entity_effect", alias = "entity_effect")]
+    EntityEffect(EntityEffect),
+    #[serde(rename = "minecraft:explosion_emitter", alias = "explosion_emitter")]
+    ExplosionEmitter,
+    #[serde(rename = "minecraft:explosion", alias = "explosion")]
+    Explosion,
+    #[serde(rename = "minecraft:gust", alias = "gust")]
+    Gust,
+    #[serde(rename = "minecraft:small_gust", alias = "small_gust")]
+    SmallGust,
+    #[serde(rename = "minecraft:gust_emitter_large", alias = "gust_emitter_large")]
+    GustEmitterLarge,
+    #[serde(rename = "minecraft:gust_emitter_small", alias = "gust_emitter_small")]
+    GustEmitterSmall,
+    #[serde(rename = "minecraft:sonic_boom", alias = "sonic_boom")]
+    SonicBoom,
+    #[serde(rename = "minecraft:falling_dust", alias = "falling_dust")]
+    FallingDust(BlockParticle),
+    #[serde(rename = "minecraft:firework", alias = "firework")]
+    Firework,
+    #[serde(rename = "minecraft:fishing", alias = "fishing")]
+    Fishing,
+    #[serde(rename = "minecraft:flame", alias = "flame")]
+    Flame,
+    #[serde(rename = "minecraft:infested", alias = "infested")]
+    Infested,
+    #[serde(rename = "minecraft:cherry_leaves", alias = "cherry_leaves")]
+    CherryLeaves,
+    #[serde(rename = "minecraft:pale_oak_leaves", alias = "pale_oak_leaves")]
+    PaleOakLeaves,
+    #[serde(rename = "minecraft:sculk_soul", alias = "sculk_soul")]
+    SculkSoul,
+    #[serde(rename = "minecraft:sculk_charge", alias = "sculk_charge")]
+    SculkCharge(SculkCharge),
+    #[serde(rename = "minecraft:sculk_charge_pop", alias = "sculk_charge_pop")]
+    SculkChargePop,
+    #[serde(rename = "minecraft:soul_fire_flame", alias = "soul_fire_flame")]
+    SoulFireFlame,
+    #[serde(rename = "minecraft:soul", alias = "soul")]
+    Soul,
+    #[serde(rename = "minecraft:flash", alias = "flash")]
+    Flash,
+    #[serde(rename = "minecraft:happy_villager", alias = "happy_villager")]
+    HappyVillager,
+    #[serde(rename = "minecraft:composter", alias = "composter")]
+    Composter,
+    #[serde(rename = "minecraft:heart", alias = "heart")]
+    Heart,
+    #[serde(rename = "minecraft:instant_effect", alias = "instant_effect")]
+    InstantEffect,
+    #[serde(rename = "minecraft:item", alias = "item")]
+    // Boxed at recommendation of compiler
+    Item(Box<ItemParticle>),
+    #[serde(rename = "minecraft:vibration", alias = "vibration")]
+    Vibration(Vibration),
+    #[serde(rename = "minecraft:trail", alias = "trail")]
+    Trail(Trail),
+    #[serde(rename = "minecraft:item_slime", alias = "item_slime")]
+    ItemSlime,
+    #[serde(rename = "minecraft:item_cobweb", alias = "item_cobweb")]
+    ItemCobweb,
+    #[serde(rename = "minecraft:item_snowball", alias = "item_snowball")]
+    ItemSnowball,
+    #[serde(rename = "minecraft:large_smoke", alias = "large_smoke")]
+    LargeSmoke,
+    #[serde(rename = "minecraft:lava", alias = "lava")]
+    Lava,
+    #[serde(rename = "minecraft:mycelium", alias = "mycelium")]
+    Mycelium,
+    #[serde(rename = "minecraft:note", alias = "note")]
+    Note,
+    #[serde(rename = "minecraft:poof", alias = "poof")]
+    Poof,
+    #[serde(rename = "minecraft:portal", alias = "portal")]
+    Portal,
+    #[serde(rename = "minecraft:rain", alias = "rain")]
+    Rain,
+    #[serde(rename = "minecraft:smoke", alias = "smoke")]
+    Smoke,
+    #[serde(rename = "minecraft:white_smoke", alias = "white_smoke")]
+    WhiteSmoke,
+    #[serde(rename = "minecraft:sneeze", alias = "sneeze")]
+    Sneeze,
+    #[serde(rename = "minecraft:spit", alias = "spit")]
+    Spit,
+    #[serde(rename = "minecraft:squid_ink", alias = "squid_ink")]
+    SquidInk,
+    #[serde(rename = "minecraft:sweep_attack", alias = "sweep_attack")]
+    SweepAttack,
+    #[serde(rename = "minecraft:totem_of_undying", alias = "totem_of_undying")]
+    TotemOfUndying,
+    #[serde(rename = "minecraft:underwater", alias = "underwater")]
+    Underwater,
+    #[serde(rename = "minecraft:splash", alias = "splash")]
+    Splash,
+    #[serde(rename = "minecraft:witch", alias = "witch")]
+    Witch,
+    #[serde(rename = "minecraft:bubble_pop", alias = "bubble_pop")]
+    BubblePop,
+    #[serde(rename = "minecraft:current_down", alias = "current_down")]
+    CurrentDown,
+    #[serde(rename = "minecraft:bubble_column_up", alias = "bubble_column_up")]
+    BubbleColumnUp,
+    #[serde(rename = "minecraft:nautilus", alias = "nautilus")]
+    Nautilus,
+    #[serde(rename = "minecraft:dolphin", alias = "dolphin")]
+    Dolphin,
+    #[serde(
+        rename = "minecraft:campfire_cosy_smoke",
+        alias = "campfire_cosy_smoke"
+    )]
+    CampfireCosySmoke,
+    #[serde(
+        rename = "minecraft:campfire_signal_smoke",
+        alias = "campfire_signal_smoke"
+    )]
+    CampfireSignalSmoke,
+    #[serde(rename = "minecraft:dripping_honey", alias = "dripping_honey")]
+    DrippingHoney,
+    #[serde(rename = "minecraft:falling_honey", alias = "falling_honey")]
+    FallingHoney,
+    #[serde(rename = "minecraft:landing_honey", alias = "landing_honey")]
+    LandingHoney,
+    #[serde(rename = "minecraft:falling_nectar", alias = "falling_nectar")]
+    FallingNectar,
+    #[serde(
+        rename = "minecraft:falling_spore_blossom",
+        alias = "falling_spore_blossom"
+    )]
+    FallingSporeBlossom,
+    #[serde(rename = "minecraft:ash", alias = "ash")]
+    Ash,
+    #[serde(rename = "minecraft:crimson_spore", alias = "crimson_spore")]
+    CrimsonSpore,
+    #[serde(rename = "minecraft:warped_spore", alias = "warped_spore")]
+    WarpedSpore,
+    #[serde(rename = "minecraft:spore_blossom_air", alias = "spore_blossom_air")]
+    SporeBlossomAir,
+    #[serde(
+        rename = "minecraft:dripping_obsidian_tear",
+        alias = "dripping_obsidian_tear"
+    )]
+    DrippingObsidianTear,
+    #[serde(
+        rename = "minecraft:falling_obsidian_tear",
+        alias = "falling_obsidian_tear"
+    )]
+    FallingObsidianTear,
+    #[serde(
+        rename = "minecraft:landing_obsidian_tear",
+        alias = "landing_obsidian_tear"
+    )]
+    LandingObsidianTear,
+    #[serde(rename = "minecraft:reverse_portal", alias = "reverse_portal")]
+    ReversePortal,
+    #[serde(rename = "minecraft:white_ash", alias = "white_ash")]
+    WhiteAsh,
+    #[serde(rename = "minecraft:small_flame", alias = "small_flame")]
+    SmallFlame,
+    #[serde(rename = "minecraft:snowflake", alias = "snowflake")]
+    Snowflake,
+    #[serde(
+        rename = "minecraft:dripping_dripstone_lava",
+        alias = "dripping_dripstone_lava"
+    )]
+    DrippingDripstoneLava,
+    #[serde(
+        rename = "minecraft:falling_dripstone_lava",
+        alias = "falling_dripstone_lava"
+    )]
+    FallingDripstoneLava,
+    #[serde(
+        rename = "minecraft:dripping_dripstone_water",
+        alias = "dripping_dripstone_water"
+    )]
+    DrippingDripstoneWater,
+    #[serde(
+        rename = "minecraft:falling_dripstone_water",
+        alias = "falling_dripstone_water"
+    )]
+    FallingDripstoneWater,
+    #[serde(rename = "minecraft:glow_squid_ink", alias = "glow_squid_ink")]
+    GlowSquidInk,
+    #[serde(rename = "minecraft:glow", alias = "glow")]
+    Glow,
+    #[serde(rename = "minecraft:wax_on", alias = "wax_on")]
+    WaxOn,
+    #[serde(rename = "minecraft:wax_off", alias = "wax_off")]
+    WaxOff,
+    #[serde(rename = "minecraft:electric_spark", alias = "electric_spark")]
+    ElectricSpark,
+    #[serde(rename = "minecraft:scrape", alias = "scrape")]
+    Scrape,
+    #[serde(rename = "minecraft:shriek", alias = "shriek")]
+    Shriek(Shriek),
+    #[serde(rename = "minecraft:egg_crack", alias = "egg_crack")]
+    EggCrack,
+    #[serde(rename = "minecraft:dust_plume", alias = "dust_plume")]
+    DustPlume,
+    #[serde(
+        rename = "minecraft:trial_spawner_detected_player",
+        alias = "trial_spawner_detected_player"
+    )]
+    TrialSpawnerDetectedPlayer,
+    #[serde(
+        rename = "minecraft:trial_spawner_detected_player_ominous",
+        alias = "trial_spawner_detected_player_ominous"
+    )]
+    TrialSpawnerDetectedPlayerOminous,
+    #[serde(rename = "minecraft:vault_connection", alias = "vault_connection")]
+    VaultConnection,
+    #[serde(rename = "minecraft:dust_pillar", alias = "dust_pillar")]
+    DustPillar(BlockParticle),
+    #[serde(rename = "minecraft:ominous_spawning", alias = "ominous_spawning")]
+    OminousSpawning,
+    #[serde(rename = "minecraft:raid_omen", alias = "raid_omen")]
+    RaidOmen,
+    #[serde(rename = "minecraft:trial_omen", alias = "trial_omen")]
+    TrialOmen,
+    #[serde(rename = "minecraft:block_crumble", alias = "block_crumble")]
+    BlockCrumble(BlockParticle),
+}
+
+#[serde_as]
+#[derive(Serialize, Deserialize, Clone, Type)]
+pub struct BlockParticle {
+    // A bare block id stands for its default state
+    #[serde_as(as = "TryFromInto<OneOf<Block, Identifier>>")]
+    pub block_state: Block,
+}
+
+impl TryFrom<OneOf<Block, Identifier>> for Block {
+    type Error = &'static str;
+
+    fn try_from(value: OneOf<Block, Identifier>) -> Result<Self, Self::Error> {
+        match value {
+            OneOf::Left(block) => Ok(block),
+            OneOf::Right(id) => Self::default_state(&id.to_string()).ok_or("Unknown block"),
+        }
+    }
+}
+
+impl From<Block> for OneOf<Block, Identifier> {
+    fn from(value: Block) -> Self {
+        Self::Left(value)
+    }
+}
+
+/// An RGB color, packed into an int or given as three floats from 0 to 1.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Type)]
+#[serde(from = "OneOf<i32, [f32; 3]>", into = "i32")]
+pub struct Rgb(pub i32);
+
+impl From<OneOf<i32, [f32; 3]>> for Rgb {
+    fn from(value: OneOf<i32, [f32; 3]>) -> Self {
+        match value {
+            OneOf::Left(int) => Self(int & 0xffffff),
+            OneOf::Right(floats) => Self(floats.into_iter().fold(0, |int, channel| {
+                int << 8 | (channel * 255.0).floor() as u8 as i32
+            })),
+        }
+    }
+}
+
+impl From<Rgb> for i32 {
+    fn from(value: Rgb) -> Self {
+        value.0
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Type)]
+pub struct Dust {
+    pub color: Rgb,
+    pub scale: f32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Type)]
+pub struct DustColorTransition {
+    pub from_color: Rgb,
+    pub to_color: Rgb,
+    pub scale: f32,
+}
 
 #[derive(Serialize, Deserialize, Clone)]
+pub struct EntityEffect {
+    pub color: ARGB,
+}
+
+/// The color packed into an int.
+impl Type for EntityEffect {
+    fn write(&self, writer: impl Write) -> std::io::Result<()> {
+        i32::from(self.color.clone()).write(writer)
+    }
+
+    fn read(reader: impl Read) -> std::io::Result<Self> {
+        Ok(Self {
+            color: OneOf::Left(i32::read(reader)? as i64).into(),
+        })
+    }
+}
+
+/// Sent as a slot, with components referring to the vanilla entries of synced registries.
+#[derive(Serialize, Deserialize, Clone, Type)]
+pub struct ItemParticle {
+    pub item: Item,
+}
+
+#[derive(Serialize, Deserialize, Clone, Type)]
+pub struct Vibration {
+    pub destination: PositionSource,
+    #[mc(var_int)]
+    pub arrival_in_ticks: i32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Type)]
 #[serde(tag = "type")]
-pub enum Particle {}
+pub enum PositionSource {
+    #[serde(rename = "minecraft:block", alias = "block")]
+    Block { pos: BlockPos },
+    #[serde(rename = "minecraft:entity", alias = "entity")]
+    Entity {
+        source_entity: EntityReference,
+        #[serde(default)]
+        y_offset: f32,
+    },
+}
+
+/// An entity by UUID when saved, but by its network id when sent to clients.
+#[derive(Clone)]
+pub enum EntityReference {
+    Uuid(McUuid),
+    NetworkId(i32),
+}
+
+impl Serialize for EntityReference {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self {
+            Self::Uuid(uuid) => uuid.serialize(serializer),
+            Self::NetworkId(_) => Err(serde::ser::Error::custom(
+                "Network ids can't be saved, only UUIDs",
+            )),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for EntityReference {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        McUuid::deserialize(deserializer).map(Self::Uuid)
+    }
+}
+
+impl Type for EntityReference {
+    fn write(&self, writer: impl Write) -> std::io::Result<()> {
+        match self {
+            Self::NetworkId(id) => AsVarInt::write(id, writer),
+            Self::Uuid(_) => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "Clients only know entities by network id",
+            )),
+        }
+    }
+
+    fn read(reader: impl Read) -> std::io::Result<Self> {
+        AsVarInt::read(reader).map(Self::NetworkId)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Type)]
+pub struct SculkCharge {
+    pub roll: f32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Type)]
+pub struct Shriek {
+    #[mc(var_int)]
+    pub delay: i32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Type)]
+pub struct Trail {
+    pub target: [f64; 3],
+    pub color: Rgb,
+    #[mc(var_int)]
+    pub duration: i32,
+}
@@ -2,24 +2,62 @@ use super::{Identifier, ItemWithSlot, entity::McUuid};
 use crate::{
     OneOf,
     data::{
-        Item, PositionInDimension,
-        entity::{Entity, Rotation},
+        BlockPos, Item, PositionInDimension,
+        entity::{Entity, EntityBase, mob::Living},
+        unknown_fields,
     },
+    types::{Codec, Prefixed, Type},
 };
+use fastnbt::Value;
 use serde::{Deserialize, Serialize};
-use strum::FromRepr;
+use serde_with::skip_serializing_none;
+use std::{
+    collections::HashMap,
+    io::{Read, Write},
+};
+use strum::{EnumString, FromRepr, IntoStaticStr};
+use uuid::Uuid;
+
+/// A player as saved in `playerdata/<uuid>.dat`, or under `Player` in a singleplayer `level.dat`.
+/// Players save what every living entity does, in the same shape as other entities.
+#[derive(Serialize, Clone)]
+pub struct Player {
+    #[serde(flatten)]
+    base: EntityBase,
+    #[serde(flatten)]
+    living: Living,
+    #[serde(flatten)]
+    player: PlayerFields,
+    /// Fields that aren't modelled, kept as they were saved
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
 
+/// What only players save.
+#[skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(rename_all = "PascalCase")]
-pub struct Player {
+struct PlayerFields {
+    /// Players have no memories, so this is always empty
+    brain: Value,
+
     #[serde(rename = "abilities")]
     abilities: Abilities,
     #[serde(rename = "current_explosion_impact_pos")]
-    current_explosion_impact_pos: [f64; 3],
-    #[serde(rename = "entered_nether_pos")]
-    entered_nether_pos: Option<[f64; 3]>,
+    current_explosion_impact_pos: Option<[f64; 3]>,
+    #[serde(rename = "current_impulse_context_reset_grace_time")]
+    #[serde(default)]
+    current_impulse_context_reset_grace_time: i32,
+    #[serde(rename = "enteredNetherPosition")]
+    entered_nether_position: Option<Vec3>,
     #[serde(rename = "ignore_fall_damage_from_current_explosion")]
+    #[serde(default)]
     ignore_fall_damage_from_current_explosion: bool,
+    #[serde(rename = "raid_omen_position")]
+    raid_omen_position: Option<BlockPos>,
+    #[serde(rename = "spawn_extra_particles_on_fall")]
+    #[serde(default)]
+    spawn_extra_particles_on_fall: bool,
     #[serde(rename = "warden_spawn_tracker")]
     warden_spawn_tracker: WardenSpawnTracker,
 
@@ -27,25 +65,24 @@ pub struct Player {
     dimension: Identifier,
     #[serde(default)]
     ender_items: Vec<ItemWithSlot>,
+    /// The selected item isn't saved, it's whichever of these is in the selected hotbar slot
     #[serde(default)]
-    inventory: Vec<Item>,
+    inventory: Vec<ItemWithSlot>,
     last_death_location: Option<PositionInDimension>,
-    root_vehicle: RootVehicle,
+    // Boxed at recommendation of compiler
+    root_vehicle: Option<Box<RootVehicle>>,
     score: i32,
-    // FIXME: not saved in player.dat
-    selected_item: Item,
     selected_item_slot: i32,
-    shoulder_entity_left: Entity,
-    shoulder_entity_right: Entity,
+    shoulder_entity_left: Option<Entity>,
+    shoulder_entity_right: Option<Entity>,
     sleep_timer: i16,
-    spawn_x: i32,
-    spawn_y: i32,
-    spawn_z: i32,
-    spawn_angle: Rotation,
-    #[serde(default = "default_spawn")]
-    spawn_dimension: Identifier,
-    #[serde(default)]
-    spawn_forced: bool,
+    // The respawn point, only saved once a bed or respawn anchor is used
+    spawn_x: Option<i32>,
+    spawn_y: Option<i32>,
+    spawn_z: Option<i32>,
+    spawn_angle: Option<f32>,
+    spawn_dimension: Option<Identifier>,
+    spawn_forced: Option<bool>,
     xp_level: i32,
     xp_p: f32,
     xp_seed: i32,
@@ -62,15 +99,153 @@ pub struct Player {
     #[serde(rename = "playerGameType")]
     player_game_type: GameType,
     #[serde(rename = "previousPlayerGameType")]
-    previous_player_game_type: GameType,
+    previous_player_game_type: Option<GameType>,
     #[serde(rename = "recipeBook")]
     recipe_book: RecipeBook,
     #[serde(rename = "seenCredits")]
     seen_credits: bool,
 }
 
+impl Player {
+    pub fn uuid(&self) -> &McUuid {
+        &self.base.uuid
+    }
+
+    /// Fields that aren't modelled, like those of newer versions and mods, kept as they were saved
+    pub fn extra(&self) -> &HashMap<String, Value> {
+        &self.extra
+    }
+
+    fn from_fields(fields: HashMap<String, Value>) -> fastnbt::error::Result<Self> {
+        // Each part is read from the encoded player, as entities are, since flattening doesn't get
+        // along with reading from a `Value`
+        let nbt = fastnbt::to_bytes(&fields)?;
+        let mut player = Self {
+            base: fastnbt::from_bytes(&nbt)?,
+            living: fastnbt::from_bytes(&nbt)?,
+            player: fastnbt::from_bytes(&nbt)?,
+            extra: HashMap::new(),
+        };
+        player.extra = unknown_fields(&player, fields)?;
+        Ok(player)
+    }
+
+    pub const fn pos(&self) -> [f64; 3] {
+        self.base.pos
+    }
+
+    pub fn set_pos(&mut self, pos: [f64; 3]) {
+        self.base.pos = pos;
+    }
+
+    pub fn dimension(&self) -> &Identifier {
+        &self.player.dimension
+    }
+
+    pub const fn data_version(&self) -> i32 {
+        self.player.data_version
+    }
+
+    pub const fn health(&self) -> f32 {
+        self.living.health
+    }
+
+    pub const fn food_level(&self) -> i32 {
+        self.player.food_level
+    }
+
+    pub const fn xp_level(&self) -> i32 {
+        self.player.xp_level
+    }
+
+    pub const fn game_type(&self) -> GameType {
+        self.player.player_game_type
+    }
+
+    pub fn set_game_type(&mut self, game_type: GameType) {
+        if game_type != self.player.player_game_type {
+            self.player.previous_player_game_type = Some(self.player.player_game_type);
+            self.player.player_game_type = game_type;
+        }
+    }
+
+    pub fn inventory(&self) -> &[ItemWithSlot] {
+        &self.player.inventory
+    }
+
+    pub fn ender_items(&self) -> &[ItemWithSlot] {
+        &self.player.ender_items
+    }
+
+    pub fn inventory_mut(&mut self) -> &mut Vec<ItemWithSlot> {
+        &mut self.player.inventory
+    }
+
+    pub fn ender_items_mut(&mut self) -> &mut Vec<ItemWithSlot> {
+        &mut self.player.ender_items
+    }
+
+    /// The hotbar slot, from 0 to 8
+    pub const fn selected_item_slot(&self) -> i32 {
+        self.player.selected_item_slot
+    }
+
+    /// The item in the selected hotbar slot, if there is one
+    pub fn selected_item(&self) -> Option<&Item> {
+        self.player
+            .inventory
+            .iter()
+            .find(|item| i32::from(item.slot) == self.player.selected_item_slot)
+            .map(|item| &item.item)
+    }
+
+    pub fn last_death_location(&self) -> Option<&PositionInDimension> {
+        self.player.last_death_location.as_ref()
+    }
+
+    /// The bed or respawn anchor the player respawns at, with the angle they face
+    pub fn respawn_point(&self) -> Option<(PositionInDimension, f32)> {
+        let pos = BlockPos::new(
+            self.player.spawn_x?,
+            self.player.spawn_y?,
+            self.player.spawn_z?,
+        );
+        let dimension = self
+            .player
+            .spawn_dimension
+            .clone()
+            .unwrap_or_else(default_spawn);
+        Some((
+            PositionInDimension::new(dimension, pos),
+            self.player.spawn_angle.unwrap_or_default(),
+        ))
+    }
+}
+
+impl<'de> Deserialize<'de> for Player {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let Value::Compound(fields) = Value::deserialize(deserializer)? else {
+            return Err(serde::de::Error::custom("Expected a player compound"));
+        };
+
+        Self::from_fields(fields)
+            .map_err(|e| serde::de::Error::custom(format!("Invalid player: {e}")))
+    }
+}
+
 fn default_spawn() -> Identifier {
-    "overworld".parse().unwrap()
+    Identifier::minecraft("overworld")
+}
+
+/// A position saved as a compound rather than a list.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct Vec3 {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -104,8 +279,11 @@ pub struct RecipeBook {
     is_smoker_gui_open: bool,
 }
 
-#[derive(Serialize, Deserialize, Clone, FromRepr)]
+#[derive(
+    Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, FromRepr, EnumString, IntoStaticStr,
+)]
 #[serde(try_from = "i32", into = "i32")]
+#[strum(serialize_all = "lowercase")]
 pub enum GameType {
     Survival,
     Creative,
@@ -135,15 +313,17 @@ pub struct Abilities {
     instabuild: bool,
     invulnerable: bool,
     may_build: bool,
+    #[serde(rename = "mayfly")]
     may_fly: bool,
     walk_speed: f32,
 }
 
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 #[serde(from = "OneOf<ProfileInner, String>")]
 pub struct Profile(ProfileInner);
 
-#[derive(Serialize, Deserialize, Clone)]
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct ProfileInner {
     name: Option<String>,
     id: Option<McUuid>,
@@ -154,7 +334,54 @@ pub struct ProfileInner {
     properties: Vec<PlayerProperty>,
 }
 
-#[derive(Serialize, Deserialize, Clone, Default)]
+impl Profile {
+    /// A complete profile, as the session server returns and Login Success sends.
+    pub fn new(id: Uuid, name: String, properties: Vec<PlayerProperty>) -> Self {
+        Self(ProfileInner {
+            name: Some(name),
+            id: Some(id.into()),
+            texture: None,
+            cape: None,
+            model: None,
+            properties,
+        })
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.0.name.as_deref()
+    }
+
+    pub fn id(&self) -> Option<Uuid> {
+        self.0.id.as_deref().copied()
+    }
+
+    pub fn properties(&self) -> &[PlayerProperty] {
+        &self.0.properties
+    }
+}
+
+/// The name, UUID and properties. The skin fields of newer versions aren't sent.
+impl Type for Profile {
+    fn write(&self, mut writer: impl Write) -> std::io::Result<()> {
+        let profile = &self.0;
+        profile.name.write(&mut writer)?;
+        profile.id.as_ref().map(|id| **id).write(&mut writer)?;
+        Prefixed::write(&profile.properties, writer)
+    }
+
+    fn read(mut reader: impl Read) -> std::io::Result<Self> {
+        Ok(Self(ProfileInner {
+            name: Type::read(&mut reader)?,
+            id: Option::<Uuid>::read(&mut reader)?.map(McUuid::from),
+            texture: None,
+            cape: None,
+            model: None,
+            properties: Prefixed::read(reader)?,
+        }))
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Default, Debug, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum Model {
     Wide,
@@ -162,18 +389,77 @@ pub enum Model {
     Slim,
 }
 
-#[derive(Serialize, Deserialize, Clone)]
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
 pub struct PlayerProperty {
     name: PlayerPropertyKind,
     value: String,
     signature: Option<String>,
 }
 
-#[derive(Serialize, Deserialize, Clone)]
+impl PlayerProperty {
+    pub fn new(name: PlayerPropertyKind, value: String, signature: Option<String>) -> Self {
+        Self {
+            name,
+            value,
+            signature,
+        }
+    }
+
+    pub fn name(&self) -> &PlayerPropertyKind {
+        &self.name
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    pub fn signature(&self) -> Option<&str> {
+        self.signature.as_deref()
+    }
+}
+
+impl Type for PlayerProperty {
+    fn write(&self, mut writer: impl Write) -> std::io::Result<()> {
+        self.name.as_str().to_string().write(&mut writer)?;
+        self.value.write(&mut writer)?;
+        self.signature.write(writer)
+    }
+
+    fn read(mut reader: impl Read) -> std::io::Result<Self> {
+        Ok(Self {
+            name: String::read(&mut reader)?.into(),
+            value: String::read(&mut reader)?,
+            signature: Type::read(reader)?,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
-#[non_exhaustive]
 pub enum PlayerPropertyKind {
     Textures,
+    /// A property vanilla doesn't know, kept so that it's passed on as it was received
+    #[serde(untagged)]
+    Other(String),
+}
+
+impl PlayerPropertyKind {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Textures => "textures",
+            Self::Other(name) => name,
+        }
+    }
+}
+
+impl From<String> for PlayerPropertyKind {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "textures" => Self::Textures,
+            _ => Self::Other(value),
+        }
+    }
 }
 
 impl From<OneOf<ProfileInner, String>> for Profile {
@@ -0,0 +1,146 @@
+//! The block stream lz4-java writes for `LZ4BlockOutputStream`, which is what vanilla uses for LZ4
+//! compressed chunks. Each block has its own header, and an empty block ends the stream.
+
+use std::io::{Error, ErrorKind};
+
+const MAGIC: &[u8; 8] = b"LZ4Block";
+const HEADER_LENGTH: usize = MAGIC.len() + 1 + 4 + 4 + 4;
+const BLOCK_SIZE: usize = 1 << 16;
+const METHOD_RAW: u8 = 0x10;
+const METHOD_LZ4: u8 = 0x20;
+// Stored in the low bits of the token, though readers don't need it
+const COMPRESSION_LEVEL: u8 = (BLOCK_SIZE.trailing_zeros() - 10) as u8;
+const SEED: u32 = 0x9747b28c;
+
+pub fn compress(data: &[u8]) -> Vec<u8> {
+    let mut out = vec![];
+    for block in data.chunks(BLOCK_SIZE) {
+        let compressed = lz4_flex::block::compress(block);
+        let (method, body) = if compressed.len() < block.len() {
+            (METHOD_LZ4, compressed.as_slice())
+        } else {
+            (METHOD_RAW, block)
+        };
+        write_header(&mut out, method, body.len(), block.len(), checksum(block));
+        out.extend_from_slice(body);
+    }
+    write_header(&mut out, METHOD_RAW, 0, 0, 0);
+
+    out
+}
+
+pub fn decompress(mut data: &[u8]) -> std::io::Result<Vec<u8>> {
+    let mut out = vec![];
+    loop {
+        let Some((header, rest)) = data.split_first_chunk::<HEADER_LENGTH>() else {
+            return Err(corrupted("Truncated LZ4 block header"));
+        };
+        if !header.starts_with(MAGIC) {
+            return Err(corrupted("Missing LZ4 block magic"));
+        }
+        let method = header[8] & 0xf0;
+        let compressed_length = read_length(&header[9..13])?;
+        let original_length = read_length(&header[13..17])?;
+        let check = u32::from_le_bytes(header[17..21].try_into().unwrap());
+
+        if original_length == 0 {
+            return Ok(out);
+        }
+        if compressed_length > rest.len() {
+            return Err(corrupted("Truncated LZ4 block"));
+        }
+        let (body, rest) = rest.split_at(compressed_length);
+        let block = match method {
+            METHOD_RAW if compressed_length == original_length => body.to_vec(),
+            METHOD_LZ4 => lz4_flex::block::decompress(body, original_length)
+                .map_err(|e| Error::new(ErrorKind::InvalidData, e))?,
+            _ => return Err(corrupted("Invalid LZ4 block method")),
+        };
+        if block.len() != original_length || checksum(&block) != check {
+            return Err(corrupted("LZ4 block checksum mismatch"));
+        }
+        out.extend_from_slice(&block);
+        data = rest;
+    }
+}
+
+fn write_header(out: &mut Vec<u8>, method: u8, compressed: usize, original: usize, check: u32) {
+    out.extend_from_slice(MAGIC);
+    out.push(method | COMPRESSION_LEVEL);
+    out.extend_from_slice(&(compressed as u32).to_le_bytes());
+    out.extend_from_slice(&(original as u32).to_le_bytes());
+    out.extend_from_slice(&check.to_le_bytes());
+}
+
+fn read_length(bytes: &[u8]) -> std::io::Result<usize> {
+    let length = i32::from_le_bytes(bytes.try_into().unwrap());
+    usize::try_from(length).map_err(|_| corrupted("Negative LZ4 block length"))
+}
+
+fn corrupted(message: &'static str) -> Error {
+    Error::new(ErrorKind::InvalidData, message)
+}
+
+/// lz4-java only keeps the low 28 bits of the xxHash32 of each block.
+fn checksum(data: &[u8]) -> u32 {
+    xxh32(data, SEED) & 0xfffffff
+}
+
+const PRIME_1: u32 = 2654435761;
+const PRIME_2: u32 = 2246822519;
+const PRIME_3: u32 = 3266489917;
+const PRIME_4: u32 = 668265263;
+const PRIME_5: u32 = 374761393;
+
+fn xxh32(data: &[u8], seed: u32) -> u32 {
+    let round = |acc: u32, lane: &[u8]| {
+        acc.wrapping_add(u32::from_le_bytes(lane.try_into().unwrap()).wrapping_mul(PRIME_2))
+            .rotate_left(13)
+            .wrapping_mul(PRIME_1)
+    };
+
+    let stripes = data.chunks_exact(16);
+    let rest = stripes.remainder();
+    let mut hash = if data.len() >= 16 {
+        let mut accs = [
+            seed.wrapping_add(PRIME_1).wrapping_add(PRIME_2),
+            seed.wrapping_add(PRIME_2),
+            seed,
+            seed.wrapping_sub(PRIME_1),
+        ];
+        for stripe in stripes {
+            for (acc, lane) in accs.iter_mut().zip(stripe.chunks_exact(4)) {
+                *acc = round(*acc, lane);
+            }
+        }
+        accs[0]
+            .rotate_left(1)
+            .wrapping_add(accs[1].rotate_left(7))
+            .wrapping_add(accs[2].rotate_left(12))
+            .wrapping_add(accs[3].rotate_left(18))
+    } else {
+        seed.wrapping_add(PRIME_5)
+    }
+    .wrapping_add(data.len() as u32);
+
+    let words = rest.chunks_exact(4);
+    let bytes = words.remainder();
+    for word in words {
+        hash = hash
+            .wrapping_add(u32::from_le_bytes(word.try_into().unwrap()).wrapping_mul(PRIME_3))
+            .rotate_left(17)
+            .wrapping_mul(PRIME_4);
+    }
+    for &byte in bytes {
+        hash = hash
+            .wrapping_add((byte as u32).wrapping_mul(PRIME_5))
+            .rotate_left(11)
+            .wrapping_mul(PRIME_1);
+    }
+
+    hash ^= hash >> 15;
+    hash = hash.wrapping_mul(PRIME_2);
+    hash ^= hash >> 13;
+    hash = hash.wrapping_mul(PRIME_3);
+    hash ^ (hash >> 16)
+}
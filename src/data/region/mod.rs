@@ -0,0 +1,425 @@
+mod lz4;
+
+use serde::{Serialize, de::DeserializeOwned};
+use std::{
+    fs::{File, OpenOptions},
+    io::{Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+const SECTOR_SIZE: usize = 4096;
+const CHUNKS: usize = 32 * 32;
+/// The location and timestamp tables
+const HEADER_SECTORS: usize = 2;
+/// The sector count is a single byte, so bigger chunks go to their own `.mcc` file
+const MAX_SECTORS: usize = 255;
+const EXTERNAL: u8 = 0x80;
+
+/// An Anvil region file, `r.<x>.<z>.mca`, holding the 32 by 32 chunks of one region.
+///
+/// Chunks are addressed by their absolute chunk coordinates, which wrap around to the region
+/// like they do in vanilla.
+pub struct Region {
+    file: File,
+    directory: PathBuf,
+    x: i32,
+    z: i32,
+    locations: [u32; CHUNKS],
+    timestamps: [u32; CHUNKS],
+    /// Which sectors of the file are taken, including the header
+    used: Vec<bool>,
+}
+
+impl Region {
+    /// Opens the region file at `path`, creating it if it doesn't exist yet.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, RegionError> {
+        let path = path.as_ref();
+        let (x, z) = Self::parse_file_name(path).ok_or(RegionError::InvalidFileName)?;
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)?;
+
+        let length = file.metadata()?.len() as usize;
+        let mut header = vec![0; HEADER_SECTORS * SECTOR_SIZE];
+        if length < header.len() {
+            file.write_all(&header)?;
+        } else {
+            file.read_exact(&mut header)?;
+        }
+        // A trailing partial sector still counts, in case the last chunk was never padded
+        let sectors = length.div_ceil(SECTOR_SIZE).max(HEADER_SECTORS);
+
+        let mut region = Self {
+            file,
+            directory: path.parent().unwrap_or(Path::new("")).to_path_buf(),
+            x,
+            z,
+            locations: [0; CHUNKS],
+            timestamps: [0; CHUNKS],
+            used: vec![false; sectors],
+        };
+        region.used[..HEADER_SECTORS].fill(true);
+
+        let (locations, timestamps) = header.split_at(SECTOR_SIZE);
+        for (i, (location, timestamp)) in locations
+            .chunks_exact(4)
+            .zip(timestamps.chunks_exact(4))
+            .enumerate()
+        {
+            let location = u32::from_be_bytes(location.try_into().unwrap());
+            let (offset, count) = unpack(location);
+            // Drop entries pointing into the header or past the end rather than fail the region
+            if count == 0 || offset < HEADER_SECTORS || offset + count > sectors {
+                continue;
+            }
+            region.locations[i] = location;
+            region.timestamps[i] = u32::from_be_bytes(timestamp.try_into().unwrap());
+            region.used[offset..offset + count].fill(true);
+        }
+
+        Ok(region)
+    }
+
+    /// The region coordinates from the file name
+    pub const fn position(&self) -> (i32, i32) {
+        (self.x, self.z)
+    }
+
+    /// The absolute coordinates of every chunk saved in this region.
+    pub fn chunks(&self) -> impl Iterator<Item = (i32, i32)> + '_ {
+        self.locations
+            .iter()
+            .enumerate()
+            .filter(|(_, location)| **location != 0)
+            .map(|(i, _)| (self.x * 32 + (i % 32) as i32, self.z * 32 + (i / 32) as i32))
+    }
+
+    pub fn contains(&self, x: i32, z: i32) -> bool {
+        self.locations[index(x, z)] != 0
+    }
+
+    /// When the chunk was last written, in seconds since the Unix epoch.
+    pub fn timestamp(&self, x: i32, z: i32) -> Option<u32> {
+        self.contains(x, z).then(|| self.timestamps[index(x, z)])
+    }
+
+    /// Reads the chunk's decompressed NBT, or `None` if it was never saved.
+    pub fn read(&mut self, x: i32, z: i32) -> Result<Option<Vec<u8>>, RegionError> {
+        let (offset, count) = unpack(self.locations[index(x, z)]);
+        if count == 0 {
+            return Ok(None);
+        }
+
+        self.file
+            .seek(SeekFrom::Start((offset * SECTOR_SIZE) as u64))?;
+        let mut length = [0; 4];
+        self.file.read_exact(&mut length)?;
+        let length = u32::from_be_bytes(length) as usize;
+        if length == 0 || length > count * SECTOR_SIZE - 4 {
+            return Err(RegionError::CorruptedChunk { x, z });
+        }
+        let mut data = vec![0; length];
+        self.file.read_exact(&mut data)?;
+
+        let compression = data[0];
+        let data = if compression & EXTERNAL != 0 {
+            std::fs::read(self.external_path(x, z))?
+        } else {
+            data.split_off(1)
+        };
+        let compression = Compression::from_id(compression & !EXTERNAL)
+            .ok_or(RegionError::UnknownCompression(compression & !EXTERNAL))?;
+
+        Ok(Some(compression.decompress(&data)?))
+    }
+
+    /// Reads and deserializes the chunk, or `None` if it was never saved.
+    pub fn read_chunk<T: DeserializeOwned>(
+        &mut self,
+        x: i32,
+        z: i32,
+    ) -> Result<Option<T>, RegionError> {
+        match self.read(x, z)? {
+            Some(data) => Ok(Some(fastnbt::from_bytes(&data)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Compresses and writes the chunk's NBT, moving it wherever it now fits.
+    pub fn write(
+        &mut self,
+        x: i32,
+        z: i32,
+        data: &[u8],
+        compression: Compression,
+    ) -> Result<(), RegionError> {
+        let data = compression.compress(data)?;
+        let external_path = self.external_path(x, z);
+
+        let mut payload = Vec::with_capacity(data.len() + 5);
+        if (data.len() + 5).div_ceil(SECTOR_SIZE) > MAX_SECTORS {
+            std::fs::write(&external_path, &data)?;
+            payload.extend_from_slice(&1_u32.to_be_bytes());
+            payload.push(compression as u8 | EXTERNAL);
+        } else {
+            payload.extend_from_slice(&(data.len() as u32 + 1).to_be_bytes());
+            payload.push(compression as u8);
+            payload.extend_from_slice(&data);
+            if external_path.exists() {
+                std::fs::remove_file(&external_path)?;
+            }
+        }
+
+        // The old sectors stay taken until the new copy is in place
+        let count = payload.len().div_ceil(SECTOR_SIZE);
+        let offset = self.allocate(count);
+        payload.resize(count * SECTOR_SIZE, 0);
+        self.file
+            .seek(SeekFrom::Start((offset * SECTOR_SIZE) as u64))?;
+        self.file.write_all(&payload)?;
+
+        let i = index(x, z);
+        let old = self.locations[i];
+        self.set_location(i, pack(offset, count), now())?;
+        self.release(old);
+
+        Ok(())
+    }
+
+    /// Serializes and writes the chunk.
+    pub fn write_chunk<T: Serialize>(
+        &mut self,
+        x: i32,
+        z: i32,
+        chunk: &T,
+        compression: Compression,
+    ) -> Result<(), RegionError> {
+        self.write(x, z, &fastnbt::to_bytes(chunk)?, compression)
+    }
+
+    pub fn remove(&mut self, x: i32, z: i32) -> Result<(), RegionError> {
+        let i = index(x, z);
+        let old = self.locations[i];
+        if old == 0 {
+            return Ok(());
+        }
+
+        self.set_location(i, 0, 0)?;
+        self.release(old);
+        let external_path = self.external_path(x, z);
+        if external_path.exists() {
+            std::fs::remove_file(external_path)?;
+        }
+
+        Ok(())
+    }
+
+    /// Moves every chunk towards the start of the file, closing the gaps left by chunks that
+    /// moved or shrank, and truncates the file after the last one.
+    pub fn defragment(&mut self) -> Result<(), RegionError> {
+        let mut chunks = (0..CHUNKS)
+            .filter(|&i| self.locations[i] != 0)
+            .collect::<Vec<_>>();
+        chunks.sort_by_key(|&i| self.locations[i]);
+
+        // Chunks only ever move backwards, so none is overwritten before it's been moved
+        let mut next = HEADER_SECTORS;
+        for i in chunks {
+            let (offset, count) = unpack(self.locations[i]);
+            if offset != next {
+                let mut sectors = vec![0; count * SECTOR_SIZE];
+                self.file
+                    .seek(SeekFrom::Start((offset * SECTOR_SIZE) as u64))?;
+                self.file.read_exact(&mut sectors)?;
+                self.file
+                    .seek(SeekFrom::Start((next * SECTOR_SIZE) as u64))?;
+                self.file.write_all(&sectors)?;
+                self.set_location(i, pack(next, count), self.timestamps[i])?;
+            }
+            next += count;
+        }
+
+        self.file.set_len((next * SECTOR_SIZE) as u64)?;
+        self.used = vec![true; next];
+
+        Ok(())
+    }
+
+    fn set_location(&mut self, i: usize, location: u32, timestamp: u32) -> std::io::Result<()> {
+        self.locations[i] = location;
+        self.timestamps[i] = timestamp;
+
+        self.file.seek(SeekFrom::Start((i * 4) as u64))?;
+        self.file.write_all(&location.to_be_bytes())?;
+        self.file
+            .seek(SeekFrom::Start((SECTOR_SIZE + i * 4) as u64))?;
+        self.file.write_all(&timestamp.to_be_bytes())
+    }
+
+    /// Takes the first run of `count` free sectors, growing the file if there is none.
+    fn allocate(&mut self, count: usize) -> usize {
+        let mut start = 0;
+        for (i, &used) in self.used.iter().enumerate() {
+            if used {
+                start = i + 1;
+            } else if i + 1 - start == count {
+                break;
+            }
+        }
+
+        let end = start + count;
+        if end > self.used.len() {
+            self.used.resize(end, false);
+        }
+        self.used[start..end].fill(true);
+
+        start
+    }
+
+    fn release(&mut self, location: u32) {
+        let (offset, count) = unpack(location);
+        self.used[offset..offset + count].fill(false);
+    }
+
+    fn external_path(&self, x: i32, z: i32) -> PathBuf {
+        let x = self.x * 32 + (x & 31);
+        let z = self.z * 32 + (z & 31);
+        self.directory.join(format!("c.{x}.{z}.mcc"))
+    }
+
+    pub(crate) fn parse_file_name(path: &Path) -> Option<(i32, i32)> {
+        let name = path.file_name()?.to_str()?;
+        let (x, z) = name
+            .strip_prefix("r.")?
+            .strip_suffix(".mca")?
+            .split_once('.')?;
+
+        Some((x.parse().ok()?, z.parse().ok()?))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Compression {
+    Gzip = 1,
+    #[default]
+    Zlib = 2,
+    None = 3,
+    Lz4 = 4,
+}
+
+impl Compression {
+    pub const fn from_id(id: u8) -> Option<Self> {
+        match id {
+            1 => Some(Self::Gzip),
+            2 => Some(Self::Zlib),
+            3 => Some(Self::None),
+            4 => Some(Self::Lz4),
+            _ => None,
+        }
+    }
+
+    fn compress(self, data: &[u8]) -> std::io::Result<Vec<u8>> {
+        let level = flate2::Compression::default();
+        match self {
+            Self::Gzip => {
+                let mut encoder = flate2::write::GzEncoder::new(vec![], level);
+                encoder.write_all(data)?;
+                encoder.finish()
+            }
+            Self::Zlib => {
+                let mut encoder = flate2::write::ZlibEncoder::new(vec![], level);
+                encoder.write_all(data)?;
+                encoder.finish()
+            }
+            Self::None => Ok(data.to_vec()),
+            Self::Lz4 => Ok(lz4::compress(data)),
+        }
+    }
+
+    fn decompress(self, data: &[u8]) -> std::io::Result<Vec<u8>> {
+        let mut out = vec![];
+        match self {
+            Self::Gzip => {
+                flate2::read::GzDecoder::new(data).read_to_end(&mut out)?;
+            }
+            Self::Zlib => {
+                flate2::read::ZlibDecoder::new(data).read_to_end(&mut out)?;
+            }
+            Self::None => out.extend_from_slice(data),
+            Self::Lz4 => out = lz4::decompress(data)?,
+        }
+
+        Ok(out)
+    }
+}
+
+#[derive(Debug)]
+pub enum RegionError {
+    Io(std::io::Error),
+    Nbt(fastnbt::error::Error),
+    /// Region files are named `r.<x>.<z>.mca` after the region they hold
+    InvalidFileName,
+    /// The chunk's length doesn't fit the sectors it was given
+    CorruptedChunk {
+        x: i32,
+        z: i32,
+    },
+    /// The chunk was compressed with a scheme other than gzip, zlib, LZ4 or none at all
+    UnknownCompression(u8),
+}
+
+impl std::fmt::Display for RegionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(e) => e.fmt(f),
+            Self::Nbt(e) => e.fmt(f),
+            Self::InvalidFileName => f.write_str("Invalid region file name"),
+            Self::CorruptedChunk { x, z } => write!(f, "Corrupted chunk at {x}, {z}"),
+            Self::UnknownCompression(id) => write!(f, "Unknown chunk compression {id}"),
+        }
+    }
+}
+
+impl std::error::Error for RegionError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            Self::Nbt(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for RegionError {
+    fn from(value: std::io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+impl From<fastnbt::error::Error> for RegionError {
+    fn from(value: fastnbt::error::Error) -> Self {
+        Self::Nbt(value)
+    }
+}
+
+const fn index(x: i32, z: i32) -> usize {
+    ((x & 31) + (z & 31) * 32) as usize
+}
+
+const fn pack(offset: usize, count: usize) -> u32 {
+    ((offset as u32) << 8) | count as u32
+}
+
+const fn unpack(location: u32) -> (usize, usize) {
+    ((location >> 8) as usize, (location & 0xff) as usize)
+}
+
+fn now() -> u32 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs() as u32)
+}
@@ -0,0 +1,1171 @@
+//! The data-driven registries the server has to send the client during configuration, and the
+//! built-in ones the client already has.
+
+use crate::{
+    OneOf,
+    data::{Identifier, entity::mob::wolf},
+    protocol::configuration::{KnownPack, RegistryData, RegistryEntry},
+    text_component::TextComponent,
+    types::{AsVarInt, Codec},
+};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use std::{
+    collections::HashMap,
+    io::{Read, Write},
+};
+use strum::IntoEnumIterator;
+
+#[derive(Debug)]
+pub enum RegistryError {
+    Nbt(fastnbt::error::Error),
+    /// The entry has no data of its own, and the client doesn't have the pack it comes from
+    MissingData {
+        registry: Identifier,
+        entry: Identifier,
+    },
+}
+
+impl std::fmt::Display for RegistryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Nbt(e) => e.fmt(f),
+            Self::MissingData { registry, entry } => write!(
+                f,
+                "Entry {entry} in {registry} has no data and the client doesn't know its pack"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for RegistryError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Nbt(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<fastnbt::error::Error> for RegistryError {
+    fn from(value: fastnbt::error::Error) -> Self {
+        Self::Nbt(value)
+    }
+}
+
+#[derive(Clone)]
+pub struct Entry<T> {
+    pub id: Identifier,
+    /// Can be left out for entries from a known pack, but then they can only be sent to clients
+    /// that have the pack too
+    pub data: Option<T>,
+    /// The pack this entry comes from, which the client may already have
+    pub pack: Option<KnownPack>,
+}
+
+impl<T> Entry<T> {
+    /// An entry that isn't part of any pack, so is always sent in full.
+    pub fn new(id: Identifier, data: T) -> Self {
+        Self {
+            id,
+            data: Some(data),
+            pack: None,
+        }
+    }
+
+    /// An entry from the vanilla `minecraft:core` pack.
+    pub fn core(path: &str, data: Option<T>) -> Self {
+        Self {
+            id: Identifier::minecraft(path),
+            data,
+            pack: Some(KnownPack::core()),
+        }
+    }
+}
+
+/// The entries of one registry, in the order that determines their network ids.
+#[derive(Clone)]
+pub struct SyncedRegistry<T> {
+    id: Identifier,
+    entries: Vec<Entry<T>>,
+}
+
+impl<T: Serialize> SyncedRegistry<T> {
+    pub fn new(id: Identifier) -> Self {
+        Self {
+            id,
+            entries: vec![],
+        }
+    }
+
+    pub fn id(&self) -> &Identifier {
+        &self.id
+    }
+
+    pub fn entries(&self) -> &[Entry<T>] {
+        &self.entries
+    }
+
+    pub fn get(&self, id: &Identifier) -> Option<&Entry<T>> {
+        self.entries.iter().find(|entry| entry.id == *id)
+    }
+
+    /// The id the entry is referred to by in other packets
+    pub fn network_id(&self, id: &Identifier) -> Option<i32> {
+        self.entries
+            .iter()
+            .position(|entry| entry.id == *id)
+            .map(|i| i as i32)
+    }
+
+    /// The entry referred to by `network_id` in other packets
+    pub fn by_network_id(&self, network_id: i32) -> Option<&Entry<T>> {
+        let index = usize::try_from(network_id).ok()?;
+        self.entries.get(index)
+    }
+
+    /// Adds an entry, replacing any existing entry with the same id in place.
+    pub fn insert(&mut self, entry: Entry<T>) {
+        match self.entries.iter_mut().find(|e| e.id == entry.id) {
+            Some(existing) => *existing = entry,
+            None => self.entries.push(entry),
+        }
+    }
+
+    pub fn remove(&mut self, id: &Identifier) -> Option<Entry<T>> {
+        let index = self.entries.iter().position(|entry| entry.id == *id)?;
+        Some(self.entries.remove(index))
+    }
+
+    /// Builds the Registry Data packet for a client that has `known_packs`, leaving out the data
+    /// of entries it already has.
+    pub fn to_packet(&self, known_packs: &[KnownPack]) -> Result<RegistryData, RegistryError> {
+        let entries = self
+            .entries
+            .iter()
+            .map(|entry| {
+                let known = entry
+                    .pack
+                    .as_ref()
+                    .is_some_and(|pack| known_packs.contains(pack));
+                let data = match (&entry.data, known) {
+                    (_, true) => None,
+                    (Some(data), false) => Some(fastnbt::to_value(data)?),
+                    (None, false) => {
+                        return Err(RegistryError::MissingData {
+                            registry: self.id.clone(),
+                            entry: entry.id.clone(),
+                        });
+                    }
+                };
+
+                Ok(RegistryEntry {
+                    entry_id: entry.id.clone(),
+                    data,
+                })
+            })
+            .collect::<Result<_, RegistryError>>()?;
+
+        Ok(RegistryData {
+            registry_id: self.id.clone(),
+            entries,
+        })
+    }
+}
+
+impl<T: Serialize> Extend<Entry<T>> for SyncedRegistry<T> {
+    fn extend<I: IntoIterator<Item = Entry<T>>>(&mut self, iter: I) {
+        for entry in iter {
+            self.insert(entry);
+        }
+    }
+}
+
+/// Like [`crate::types::Type`], for what refers to entries of synced registries by their network
+/// ids, so is encoded differently depending on the registries the client was sent.
+pub trait SyncedType: Sized {
+    fn write_with(&self, registries: &Registries, writer: impl Write) -> std::io::Result<()>;
+
+    fn read_with(registries: &Registries, reader: impl Read) -> std::io::Result<Self>;
+}
+
+impl<T: SyncedType> SyncedType for Box<T> {
+    fn write_with(&self, registries: &Registries, writer: impl Write) -> std::io::Result<()> {
+        T::write_with(self, registries, writer)
+    }
+
+    fn read_with(registries: &Registries, reader: impl Read) -> std::io::Result<Self> {
+        T::read_with(registries, reader).map(Box::new)
+    }
+}
+
+/// Like [`Codec`], for encodings that depend on the registries the client was sent.
+pub trait SyncedCodec<T> {
+    fn write_with(value: &T, registries: &Registries, writer: impl Write) -> std::io::Result<()>;
+
+    fn read_with(registries: &Registries, reader: impl Read) -> std::io::Result<T>;
+}
+
+/// The registries built into the game, generated by `build.rs` from `reports/registries.json`
+/// and the block report.
+pub mod builtin {
+    use super::BuiltinRegistry;
+
+    include!(concat!(env!("OUT_DIR"), "/registries.rs"));
+}
+
+/// A registry that isn't synced, so the client knows every entry by the same network id as the
+/// server.
+#[derive(Clone, Copy, Debug)]
+pub struct BuiltinRegistry {
+    id: &'static str,
+    entries: &'static [&'static str],
+}
+
+impl BuiltinRegistry {
+    pub(crate) const fn new(id: &'static str, entries: &'static [&'static str]) -> Self {
+        Self { id, entries }
+    }
+
+    pub fn id(&self) -> Identifier {
+        self.id.parse().unwrap()
+    }
+
+    pub const fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The id the entry is sent as, or `None` if there's no such entry
+    pub fn network_id(&self, id: &Identifier) -> Option<i32> {
+        self.entries
+            .iter()
+            .position(|entry| {
+                entry.split_once(':') == Some((id.namespace.as_str(), id.path.as_str()))
+            })
+            .map(|i| i as i32)
+    }
+
+    /// The entry sent as `network_id`
+    pub fn get(&self, network_id: i32) -> Option<Identifier> {
+        let index = usize::try_from(network_id).ok()?;
+        self.entries.get(index).map(|entry| entry.parse().unwrap())
+    }
+
+    /// Writes the network id of `id` as a `VarInt`.
+    pub(crate) fn write_entry(&self, id: &Identifier, writer: impl Write) -> std::io::Result<()> {
+        let network_id = self.network_id(id).ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("{id} isn't in {}", self.id),
+            )
+        })?;
+        AsVarInt::write(&network_id, writer)
+    }
+
+    /// Reads a network id as a `VarInt` and looks up its entry.
+    pub(crate) fn read_entry(&self, reader: impl Read) -> std::io::Result<Identifier> {
+        let network_id = AsVarInt::read(reader)?;
+        self.get(network_id).ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("Invalid {} id {network_id}", self.id),
+            )
+        })
+    }
+}
+
+/// Every registry sent to the client during configuration. These are the registries a 1.21.4
+/// client requires, which are all synced ones it has.
+#[derive(Clone)]
+pub struct Registries {
+    pub dimension_type: SyncedRegistry<DimensionType>,
+    pub biome: SyncedRegistry<Biome>,
+    pub chat_type: SyncedRegistry<ChatType>,
+    pub damage_type: SyncedRegistry<DamageType>,
+    pub wolf_variant: SyncedRegistry<WolfVariant>,
+    pub painting_variant: SyncedRegistry<PaintingVariant>,
+    pub trim_material: SyncedRegistry<TrimMaterial>,
+    pub trim_pattern: SyncedRegistry<TrimPattern>,
+    pub banner_pattern: SyncedRegistry<BannerPattern>,
+    /// Enchantments are left as tags, as their effects aren't modelled
+    pub enchantment: SyncedRegistry<fastnbt::Value>,
+    pub jukebox_song: SyncedRegistry<JukeboxSong>,
+    pub instrument: SyncedRegistry<Instrument>,
+    /// Registries added by mods, which vanilla clients don't know of
+    pub other: Vec<SyncedRegistry<fastnbt::Value>>,
+}
+
+impl Default for Registries {
+    fn default() -> Self {
+        fn registry<T: Serialize>(path: &str) -> SyncedRegistry<T> {
+            SyncedRegistry::new(Identifier::minecraft(path))
+        }
+
+        Self {
+            dimension_type: registry("dimension_type"),
+            biome: registry("worldgen/biome"),
+            chat_type: registry("chat_type"),
+            damage_type: registry("damage_type"),
+            wolf_variant: registry("wolf_variant"),
+            painting_variant: registry("painting_variant"),
+            trim_material: registry("trim_material"),
+            trim_pattern: registry("trim_pattern"),
+            banner_pattern: registry("banner_pattern"),
+            enchantment: registry("enchantment"),
+            jukebox_song: registry("jukebox_song"),
+            instrument: registry("instrument"),
+            other: vec![],
+        }
+    }
+}
+
+impl Registries {
+    /// Empty registries
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The vanilla entries of every registry, all from the `minecraft:core` pack, in the
+    /// alphabetical order vanilla loads them in.
+    ///
+    /// Only dimension types, chat types, wolf variants and the plains biome carry their data, so
+    /// these registries can only be sent to clients that know the core pack, which every vanilla
+    /// client of the same version does.
+    pub fn vanilla() -> Self {
+        fn ids<T: Serialize>(paths: &[&str]) -> impl Iterator<Item = Entry<T>> {
+            paths.iter().map(|path| Entry::core(path, None))
+        }
+
+        let mut out = Self::new();
+
+        out.dimension_type.extend(
+            DimensionType::vanilla()
+                .into_iter()
+                .map(|(path, data)| Entry::core(path, Some(data))),
+        );
+        out.biome.extend(
+            VANILLA_BIOMES
+                .iter()
+                .map(|path| Entry::core(path, (*path == "plains").then(Biome::plains))),
+        );
+        out.chat_type.extend(
+            ChatType::vanilla()
+                .into_iter()
+                .map(|(path, data)| Entry::core(path, Some(data))),
+        );
+        out.damage_type.extend(ids(VANILLA_DAMAGE_TYPES));
+        let mut wolves: Vec<_> = wolf::Variant::iter().collect();
+        wolves.sort_unstable_by_key(|variant| <&'static str>::from(*variant));
+        out.wolf_variant.extend(
+            wolves
+                .into_iter()
+                .map(|variant| Entry::core(variant.into(), Some(WolfVariant::vanilla(variant)))),
+        );
+        out.painting_variant.extend(ids(VANILLA_PAINTINGS));
+        out.trim_material.extend(ids(VANILLA_TRIM_MATERIALS));
+        out.trim_pattern.extend(ids(VANILLA_TRIM_PATTERNS));
+        out.banner_pattern.extend(ids(VANILLA_BANNER_PATTERNS));
+        out.enchantment.extend(ids(VANILLA_ENCHANTMENTS));
+        out.jukebox_song.extend(ids(VANILLA_JUKEBOX_SONGS));
+        out.instrument.extend(ids(VANILLA_INSTRUMENTS));
+
+        out
+    }
+
+    /// Builds a Registry Data packet for every registry, leaving out the data of entries from
+    /// `known_packs`.
+    pub fn to_packets(
+        &self,
+        known_packs: &[KnownPack],
+    ) -> Result<Vec<RegistryData>, RegistryError> {
+        let mut out = vec![
+            self.dimension_type.to_packet(known_packs)?,
+            self.biome.to_packet(known_packs)?,
+            self.chat_type.to_packet(known_packs)?,
+            self.damage_type.to_packet(known_packs)?,
+            self.wolf_variant.to_packet(known_packs)?,
+            self.painting_variant.to_packet(known_packs)?,
+            self.trim_material.to_packet(known_packs)?,
+            self.trim_pattern.to_packet(known_packs)?,
+            self.banner_pattern.to_packet(known_packs)?,
+            self.enchantment.to_packet(known_packs)?,
+            self.jukebox_song.to_packet(known_packs)?,
+            self.instrument.to_packet(known_packs)?,
+        ];
+        for registry in &self.other {
+            out.push(registry.to_packet(known_packs)?);
+        }
+
+        Ok(out)
+    }
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct DimensionType {
+    pub fixed_time: Option<i64>,
+    pub has_skylight: bool,
+    pub has_ceiling: bool,
+    pub ultrawarm: bool,
+    pub natural: bool,
+    pub coordinate_scale: f64,
+    pub bed_works: bool,
+    pub respawn_anchor_works: bool,
+    pub min_y: i32,
+    pub height: i32,
+    pub logical_height: i32,
+    /// A block tag, starting with `#`
+    pub infiniburn: String,
+    pub effects: Identifier,
+    pub ambient_light: f32,
+    pub piglin_safe: bool,
+    pub has_raids: bool,
+    pub monster_spawn_light_level: OneOf<i32, UniformInt>,
+    pub monster_spawn_block_light_limit: i32,
+}
+
+impl DimensionType {
+    fn vanilla() -> [(&'static str, Self); 4] {
+        let overworld = Self {
+            fixed_time: None,
+            has_skylight: true,
+            has_ceiling: false,
+            ultrawarm: false,
+            natural: true,
+            coordinate_scale: 1.0,
+            bed_works: true,
+            respawn_anchor_works: false,
+            min_y: -64,
+            height: 384,
+            logical_height: 384,
+            infiniburn: "#minecraft:infiniburn_overworld".to_string(),
+            effects: Identifier::minecraft("overworld"),
+            ambient_light: 0.0,
+            piglin_safe: false,
+            has_raids: true,
+            monster_spawn_light_level: OneOf::Right(UniformInt::new(0, 7)),
+            monster_spawn_block_light_limit: 0,
+        };
+
+        [
+            ("overworld", overworld.clone()),
+            (
+                "overworld_caves",
+                Self {
+                    has_ceiling: true,
+                    ..overworld.clone()
+                },
+            ),
+            (
+                "the_end",
+                Self {
+                    fixed_time: Some(6000),
+                    has_skylight: false,
+                    natural: false,
+                    bed_works: false,
+                    min_y: 0,
+                    height: 256,
+                    logical_height: 256,
+                    infiniburn: "#minecraft:infiniburn_end".to_string(),
+                    effects: Identifier::minecraft("the_end"),
+                    ..overworld.clone()
+                },
+            ),
+            (
+                "the_nether",
+                Self {
+                    fixed_time: Some(18000),
+                    has_skylight: false,
+                    has_ceiling: true,
+                    ultrawarm: true,
+                    natural: false,
+                    coordinate_scale: 8.0,
+                    bed_works: false,
+                    respawn_anchor_works: true,
+                    min_y: 0,
+                    height: 256,
+                    logical_height: 128,
+                    infiniburn: "#minecraft:infiniburn_nether".to_string(),
+                    effects: Identifier::minecraft("the_nether"),
+                    ambient_light: 0.1,
+                    piglin_safe: true,
+                    has_raids: false,
+                    monster_spawn_light_level: OneOf::Left(7),
+                    monster_spawn_block_light_limit: 15,
+                },
+            ),
+        ]
+    }
+}
+
+/// An int provider picking uniformly from an inclusive range
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct UniformInt {
+    #[serde(rename = "type")]
+    kind: Identifier,
+    pub min_inclusive: i32,
+    pub max_inclusive: i32,
+}
+
+impl UniformInt {
+    pub fn new(min_inclusive: i32, max_inclusive: i32) -> Self {
+        Self {
+            kind: Identifier::minecraft("uniform"),
+            min_inclusive,
+            max_inclusive,
+        }
+    }
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Biome {
+    pub has_precipitation: bool,
+    pub temperature: f32,
+    pub temperature_modifier: Option<TemperatureModifier>,
+    pub downfall: f32,
+    pub effects: BiomeEffects,
+}
+
+impl Biome {
+    fn plains() -> Self {
+        Self {
+            has_precipitation: true,
+            temperature: 0.8,
+            temperature_modifier: None,
+            downfall: 0.4,
+            effects: BiomeEffects {
+                fog_color: 12638463,
+                water_color: 4159204,
+                water_fog_color: 329011,
+                sky_color: 7907327,
+                foliage_color: None,
+                grass_color: None,
+                grass_color_modifier: None,
+                particle: None,
+                ambient_sound: None,
+                mood_sound: Some(MoodSound {
+                    sound: Identifier::minecraft("ambient.cave"),
+                    tick_delay: 6000,
+                    block_search_extent: 8,
+                    offset: 2.0,
+                }),
+                additions_sound: None,
+                music: None,
+            },
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TemperatureModifier {
+    None,
+    Frozen,
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct BiomeEffects {
+    pub fog_color: i32,
+    pub water_color: i32,
+    pub water_fog_color: i32,
+    pub sky_color: i32,
+    pub foliage_color: Option<i32>,
+    pub grass_color: Option<i32>,
+    pub grass_color_modifier: Option<GrassColorModifier>,
+    pub particle: Option<fastnbt::Value>,
+    /// A sound event id or an inline sound event
+    pub ambient_sound: Option<fastnbt::Value>,
+    pub mood_sound: Option<MoodSound>,
+    pub additions_sound: Option<fastnbt::Value>,
+    pub music: Option<fastnbt::Value>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum GrassColorModifier {
+    None,
+    DarkForest,
+    Swamp,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct MoodSound {
+    pub sound: Identifier,
+    pub tick_delay: i32,
+    pub block_search_extent: i32,
+    pub offset: f64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ChatType {
+    pub chat: ChatTypeDecoration,
+    pub narration: ChatTypeDecoration,
+}
+
+impl ChatType {
+    fn vanilla() -> [(&'static str, Self); 7] {
+        fn decoration(
+            translation_key: &str,
+            parameters: &[&str],
+            style: Option<fastnbt::Value>,
+        ) -> ChatTypeDecoration {
+            ChatTypeDecoration {
+                translation_key: translation_key.to_string(),
+                parameters: parameters.iter().map(|p| p.to_string()).collect(),
+                style,
+            }
+        }
+
+        let narrate = decoration("chat.type.text.narrate", &["sender", "content"], None);
+        let whisper = || Some(fastnbt::nbt!({ "color": "gray", "italic": true }));
+
+        [
+            (
+                "chat",
+                Self {
+                    chat: decoration("chat.type.text", &["sender", "content"], None),
+                    narration: narrate.clone(),
+                },
+            ),
+            (
+                "emote_command",
+                Self {
+                    chat: decoration("chat.type.emote", &["sender", "content"], None),
+                    narration: decoration("chat.type.emote", &["sender", "content"], None),
+                },
+            ),
+            (
+                "msg_command_incoming",
+                Self {
+                    chat: decoration(
+                        "commands.message.display.incoming",
+                        &["sender", "content"],
+                        whisper(),
+                    ),
+                    narration: narrate.clone(),
+                },
+            ),
+            (
+                "msg_command_outgoing",
+                Self {
+                    chat: decoration(
+                        "commands.message.display.outgoing",
+                        &["target", "content"],
+                        whisper(),
+                    ),
+                    narration: narrate.clone(),
+                },
+            ),
+            (
+                "say_command",
+                Self {
+                    chat: decoration("chat.type.announcement", &["sender", "content"], None),
+                    narration: narrate.clone(),
+                },
+            ),
+            (
+                "team_msg_command_incoming",
+                Self {
+                    chat: decoration(
+                        "chat.type.team.text",
+                        &["target", "sender", "content"],
+                        None,
+                    ),
+                    narration: narrate.clone(),
+                },
+            ),
+            (
+                "team_msg_command_outgoing",
+                Self {
+                    chat: decoration(
+                        "chat.type.team.sent",
+                        &["target", "sender", "content"],
+                        None,
+                    ),
+                    narration: narrate,
+                },
+            ),
+        ]
+    }
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ChatTypeDecoration {
+    pub translation_key: String,
+    /// Which of `sender`, `target` and `content` fill in the translation, in order
+    pub parameters: Vec<String>,
+    pub style: Option<fastnbt::Value>,
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct DamageType {
+    pub message_id: String,
+    pub scaling: DamageScaling,
+    pub exhaustion: f32,
+    pub effects: Option<DamageEffects>,
+    pub death_message_type: Option<DeathMessageType>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DamageScaling {
+    Never,
+    WhenCausedByLivingNonPlayer,
+    Always,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DamageEffects {
+    Hurt,
+    Thorns,
+    Drowning,
+    Burning,
+    Poking,
+    Freezing,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DeathMessageType {
+    Default,
+    FallVariants,
+    IntentionalGameDesign,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct WolfVariant {
+    pub wild_texture: Identifier,
+    pub tame_texture: Identifier,
+    pub angry_texture: Identifier,
+    /// A biome id, a list of them or a biome tag starting with `#`
+    pub biomes: OneOf<String, Vec<String>>,
+}
+
+impl WolfVariant {
+    fn vanilla(variant: wolf::Variant) -> Self {
+        let texture = match variant {
+            wolf::Variant::Pale => "entity/wolf/wolf".to_string(),
+            _ => format!("entity/wolf/wolf_{}", <&'static str>::from(variant)),
+        };
+        let biomes = match variant {
+            wolf::Variant::Pale => "minecraft:taiga",
+            wolf::Variant::Ashen => "minecraft:snowy_taiga",
+            wolf::Variant::Black => "minecraft:old_growth_pine_taiga",
+            wolf::Variant::Chestnut => "minecraft:old_growth_spruce_taiga",
+            wolf::Variant::Rusty => "#minecraft:is_jungle",
+            wolf::Variant::Snowy => "minecraft:grove",
+            wolf::Variant::Spotted => "#minecraft:is_savanna",
+            wolf::Variant::Striped => "#minecraft:is_badlands",
+            wolf::Variant::Woods => "minecraft:forest",
+        };
+
+        Self {
+            wild_texture: Identifier::minecraft(texture.clone()),
+            tame_texture: Identifier::minecraft(format!("{texture}_tame")),
+            angry_texture: Identifier::minecraft(format!("{texture}_angry")),
+            biomes: OneOf::Left(biomes.to_string()),
+        }
+    }
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone)]
+pub struct PaintingVariant {
+    pub asset_id: Identifier,
+    pub width: i32,
+    pub height: i32,
+    pub title: Option<TextComponent>,
+    pub author: Option<TextComponent>,
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TrimMaterial {
+    /// The suffix of the trim's textures
+    pub asset_name: String,
+    pub ingredient: Identifier,
+    /// A different `asset_name` for armor made of the equipment asset of each id, so that a trim
+    /// doesn't vanish against armor of the same material
+    pub override_armor_assets: Option<HashMap<Identifier, String>>,
+    pub description: TextComponent,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TrimPattern {
+    pub asset_id: Identifier,
+    pub template_item: Identifier,
+    pub description: TextComponent,
+    /// Whether the trim is drawn over the armor rather than in place of it
+    pub decal: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct BannerPattern {
+    pub asset_id: Identifier,
+    pub translation_key: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct JukeboxSong {
+    /// A sound event id or an inline sound event
+    pub sound_event: fastnbt::Value,
+    pub description: TextComponent,
+    pub length_in_seconds: f32,
+    /// The signal a comparator reads from a jukebox playing the song
+    pub comparator_output: i32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Instrument {
+    /// A sound event id or an inline sound event
+    pub sound_event: fastnbt::Value,
+    /// In seconds
+    pub use_duration: f32,
+    /// How far away the sound can be heard, in blocks
+    pub range: f32,
+    pub description: TextComponent,
+}
+
+const VANILLA_BIOMES: &[&str] = &[
+    "badlands",
+    "bamboo_jungle",
+    "basalt_deltas",
+    "beach",
+    "birch_forest",
+    "cherry_grove",
+    "cold_ocean",
+    "crimson_forest",
+    "dark_forest",
+    "deep_cold_ocean",
+    "deep_dark",
+    "deep_frozen_ocean",
+    "deep_lukewarm_ocean",
+    "deep_ocean",
+    "desert",
+    "dripstone_caves",
+    "end_barrens",
+    "end_highlands",
+    "end_midlands",
+    "eroded_badlands",
+    "flower_forest",
+    "forest",
+    "frozen_ocean",
+    "frozen_peaks",
+    "frozen_river",
+    "grove",
+    "ice_spikes",
+    "jagged_peaks",
+    "jungle",
+    "lukewarm_ocean",
+    "lush_caves",
+    "mangrove_swamp",
+    "meadow",
+    "mushroom_fields",
+    "nether_wastes",
+    "ocean",
+    "old_growth_birch_forest",
+    "old_growth_pine_taiga",
+    "old_growth_spruce_taiga",
+    "pale_garden",
+    "plains",
+    "river",
+    "savanna",
+    "savanna_plateau",
+    "small_end_islands",
+    "snowy_beach",
+    "snowy_plains",
+    "snowy_slopes",
+    "snowy_taiga",
+    "soul_sand_valley",
+    "sparse_jungle",
+    "stony_peaks",
+    "stony_shore",
+    "sunflower_plains",
+    "swamp",
+    "taiga",
+    "the_end",
+    "the_void",
+    "warm_ocean",
+    "warped_forest",
+    "windswept_forest",
+    "windswept_gravelly_hills",
+    "windswept_hills",
+    "windswept_savanna",
+    "wooded_badlands",
+];
+
+const VANILLA_DAMAGE_TYPES: &[&str] = &[
+    "arrow",
+    "bad_respawn_point",
+    "cactus",
+    "campfire",
+    "cramming",
+    "dragon_breath",
+    "drown",
+    "dry_out",
+    "ender_pearl",
+    "explosion",
+    "fall",
+    "falling_anvil",
+    "falling_block",
+    "falling_stalactite",
+    "fireball",
+    "fireworks",
+    "fly_into_wall",
+    "freeze",
+    "generic",
+    "generic_kill",
+    "hot_floor",
+    "in_fire",
+    "in_wall",
+    "indirect_magic",
+    "lava",
+    "lightning_bolt",
+    "mace_smash",
+    "magic",
+    "mob_attack",
+    "mob_attack_no_aggro",
+    "mob_projectile",
+    "on_fire",
+    "out_of_world",
+    "outside_border",
+    "player_attack",
+    "player_explosion",
+    "sonic_boom",
+    "spit",
+    "stalagmite",
+    "starve",
+    "sting",
+    "sweet_berry_bush",
+    "thorns",
+    "thrown",
+    "trident",
+    "unattributed_fireball",
+    "wind_charge",
+    "wither",
+    "wither_skull",
+];
+
+const VANILLA_PAINTINGS: &[&str] = &[
+    "alban",
+    "aztec",
+    "aztec2",
+    "backyard",
+    "baroque",
+    "bomb",
+    "bouquet",
+    "burning_skull",
+    "bust",
+    "cavebird",
+    "changing",
+    "cotan",
+    "courbet",
+    "creebet",
+    "donkey_kong",
+    "earth",
+    "endboss",
+    "fern",
+    "fighters",
+    "finding",
+    "fire",
+    "graham",
+    "humble",
+    "kebab",
+    "lowmist",
+    "match",
+    "meditative",
+    "orb",
+    "owlemons",
+    "passage",
+    "pigscene",
+    "plant",
+    "pointer",
+    "pond",
+    "pool",
+    "prairie_ride",
+    "sea",
+    "skeleton",
+    "skull_and_roses",
+    "stage",
+    "sunflowers",
+    "sunset",
+    "tides",
+    "unpacked",
+    "void",
+    "wanderer",
+    "wasteland",
+    "water",
+    "wind",
+    "wither",
+];
+
+// The synced registries that items refer to by network id. Vanilla lists their entries in
+// alphabetical order, so these ids hold for clients sent the core pack's entries.
+
+const VANILLA_BANNER_PATTERNS: &[&str] = &[
+    "base",
+    "border",
+    "bricks",
+    "circle",
+    "creeper",
+    "cross",
+    "curly_border",
+    "diagonal_left",
+    "diagonal_right",
+    "diagonal_up_left",
+    "diagonal_up_right",
+    "flow",
+    "flower",
+    "globe",
+    "gradient",
+    "gradient_up",
+    "guster",
+    "half_horizontal",
+    "half_horizontal_bottom",
+    "half_vertical",
+    "half_vertical_right",
+    "mojang",
+    "piglin",
+    "rhombus",
+    "skull",
+    "small_stripes",
+    "square_bottom_left",
+    "square_bottom_right",
+    "square_top_left",
+    "square_top_right",
+    "straight_cross",
+    "stripe_bottom",
+    "stripe_center",
+    "stripe_downleft",
+    "stripe_downright",
+    "stripe_left",
+    "stripe_middle",
+    "stripe_right",
+    "stripe_top",
+    "triangle_bottom",
+    "triangle_top",
+    "triangles_bottom",
+    "triangles_top",
+];
+
+const VANILLA_ENCHANTMENTS: &[&str] = &[
+    "aqua_affinity",
+    "bane_of_arthropods",
+    "binding_curse",
+    "blast_protection",
+    "breach",
+    "channeling",
+    "density",
+    "depth_strider",
+    "efficiency",
+    "feather_falling",
+    "fire_aspect",
+    "fire_protection",
+    "flame",
+    "fortune",
+    "frost_walker",
+    "impaling",
+    "infinity",
+    "knockback",
+    "looting",
+    "loyalty",
+    "luck_of_the_sea",
+    "lure",
+    "mending",
+    "multishot",
+    "piercing",
+    "power",
+    "projectile_protection",
+    "protection",
+    "punch",
+    "quick_charge",
+    "respiration",
+    "riptide",
+    "sharpness",
+    "silk_touch",
+    "smite",
+    "soul_speed",
+    "sweeping_edge",
+    "swift_sneak",
+    "thorns",
+    "unbreaking",
+    "vanishing_curse",
+    "wind_burst",
+];
+
+const VANILLA_INSTRUMENTS: &[&str] = &[
+    "admire_goat_horn",
+    "call_goat_horn",
+    "dream_goat_horn",
+    "feel_goat_horn",
+    "ponder_goat_horn",
+    "seek_goat_horn",
+    "sing_goat_horn",
+    "yearn_goat_horn",
+];
+
+const VANILLA_JUKEBOX_SONGS: &[&str] = &[
+    "11",
+    "13",
+    "5",
+    "blocks",
+    "cat",
+    "chirp",
+    "creator",
+    "creator_music_box",
+    "far",
+    "mall",
+    "mellohi",
+    "otherside",
+    "pigstep",
+    "precipice",
+    "relic",
+    "stal",
+    "strad",
+    "wait",
+    "ward",
+];
+
+const VANILLA_TRIM_MATERIALS: &[&str] = &[
+    "amethyst",
+    "copper",
+    "diamond",
+    "emerald",
+    "gold",
+    "iron",
+    "lapis",
+    "netherite",
+    "quartz",
+    "redstone",
+    "resin",
+];
+
+const VANILLA_TRIM_PATTERNS: &[&str] = &[
+    "bolt",
+    "coast",
+    "dune",
+    "eye",
+    "flow",
+    "host",
+    "raiser",
+    "rib",
+    "sentry",
+    "shaper",
+    "silence",
+    "snout",
+    "spire",
+    "tide",
+    "vex",
+    "ward",
+    "wayfinder",
+    "wild",
+];
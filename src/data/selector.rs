@@ -0,0 +1,730 @@
+use crate::{
+    data::{Identifier, player::GameType},
+    nbt::snbt,
+};
+use fastnbt::Value;
+use serde::{Deserialize, Serialize};
+use std::{mem::discriminant, str::FromStr};
+use strum::{EnumString, IntoStaticStr};
+use uuid::Uuid;
+
+/// How commands and text components name entities: a target selector like `@e[type=zombie]`, a
+/// player name or a UUID.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(try_from = "String", into = "String")]
+pub enum Selector {
+    Player(String),
+    Uuid(Uuid),
+    Target {
+        kind: SelectorKind,
+        arguments: Vec<SelectorArgument>,
+    },
+}
+
+impl From<SelectorKind> for Selector {
+    fn from(kind: SelectorKind) -> Self {
+        Self::Target {
+            kind,
+            arguments: vec![],
+        }
+    }
+}
+
+impl std::fmt::Display for Selector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Player(name) => f.write_str(name),
+            Self::Uuid(uuid) => uuid.hyphenated().fmt(f),
+            Self::Target { kind, arguments } => {
+                write!(f, "@{}", kind.char())?;
+                if let Some((first, rest)) = arguments.split_first() {
+                    write!(f, "[{first}")?;
+                    for argument in rest {
+                        write!(f, ",{argument}")?;
+                    }
+                    f.write_str("]")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl From<Selector> for String {
+    fn from(value: Selector) -> Self {
+        value.to_string()
+    }
+}
+
+impl FromStr for Selector {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if !s.starts_with('@') {
+            // Vanilla only takes the hyphenated form, and names can't be long enough to clash
+            if let Ok(uuid) = Uuid::try_parse(s)
+                && s.contains('-')
+            {
+                return Ok(Self::Uuid(uuid));
+            }
+            if s.is_empty() || s.len() > 16 || s.contains(char::is_whitespace) {
+                return Err("Invalid player name or UUID");
+            }
+            return Ok(Self::Player(s.to_string()));
+        }
+
+        let mut reader = Reader::new(&s[1..]);
+        let kind = reader
+            .next()
+            .and_then(SelectorKind::from_char)
+            .ok_or("Unknown selector type")?;
+
+        let mut arguments = vec![];
+        if reader.eat('[') {
+            reader.skip_whitespace();
+            if !reader.eat(']') {
+                loop {
+                    reader.skip_whitespace();
+                    let argument = SelectorArgument::read(&mut reader)?;
+                    argument.check_applicable(kind, &arguments)?;
+                    arguments.push(argument);
+                    reader.skip_whitespace();
+                    if reader.eat(']') {
+                        break;
+                    }
+                    reader.expect(',', "Expected ',' or ']' between selector arguments")?;
+                }
+            }
+        }
+
+        if !reader.is_empty() {
+            return Err("Trailing characters after selector");
+        }
+        Ok(Self::Target { kind, arguments })
+    }
+}
+
+impl TryFrom<String> for Selector {
+    type Error = &'static str;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SelectorKind {
+    NearestPlayer,
+    RandomPlayer,
+    AllPlayers,
+    AllEntities,
+    Executor,
+    NearestEntity,
+}
+
+impl SelectorKind {
+    pub const fn char(self) -> char {
+        match self {
+            Self::NearestPlayer => 'p',
+            Self::RandomPlayer => 'r',
+            Self::AllPlayers => 'a',
+            Self::AllEntities => 'e',
+            Self::Executor => 's',
+            Self::NearestEntity => 'n',
+        }
+    }
+
+    /// Whether the selector only ever picks players, so can't be narrowed down by `type`.
+    pub const fn is_players_only(self) -> bool {
+        matches!(
+            self,
+            Self::NearestPlayer | Self::RandomPlayer | Self::AllPlayers
+        )
+    }
+
+    pub const fn from_char(c: char) -> Option<Self> {
+        Some(match c {
+            'p' => Self::NearestPlayer,
+            'r' => Self::RandomPlayer,
+            'a' => Self::AllPlayers,
+            'e' => Self::AllEntities,
+            's' => Self::Executor,
+            'n' => Self::NearestEntity,
+            _ => return None,
+        })
+    }
+}
+
+/// A single `key=value` between the brackets of a target selector.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SelectorArgument {
+    X(f64),
+    Y(f64),
+    Z(f64),
+    Dx(f64),
+    Dy(f64),
+    Dz(f64),
+    Distance(Bounds<f64>),
+    XRotation(Bounds<f32>),
+    YRotation(Bounds<f32>),
+    Level(Bounds<i32>),
+    Limit(i32),
+    Sort(Sort),
+    Name(Negatable<String>),
+    Team(Negatable<String>),
+    Tag(Negatable<String>),
+    Type(Negatable<EntityTypeFilter>),
+    Gamemode(Negatable<GameType>),
+    /// A compound the entity's NBT has to contain
+    Nbt(Negatable<Value>),
+    Predicate(Negatable<WrittenIdentifier>),
+    Scores(Vec<(String, Bounds<i32>)>),
+    Advancements(Vec<(WrittenIdentifier, AdvancementPredicate)>),
+}
+
+impl SelectorArgument {
+    fn read(reader: &mut Reader) -> Result<Self, &'static str> {
+        let key = reader.read_unquoted();
+        reader.skip_whitespace();
+        reader.expect('=', "Expected '=' after selector option")?;
+        reader.skip_whitespace();
+
+        Ok(match key {
+            "x" => Self::X(reader.read_number()?),
+            "y" => Self::Y(reader.read_number()?),
+            "z" => Self::Z(reader.read_number()?),
+            "dx" => Self::Dx(reader.read_number()?),
+            "dy" => Self::Dy(reader.read_number()?),
+            "dz" => Self::Dz(reader.read_number()?),
+            "distance" => {
+                let distance = reader.read_number::<Bounds<f64>>()?;
+                if distance.min.is_some_and(|min| min < 0.0)
+                    || distance.max.is_some_and(|max| max < 0.0)
+                {
+                    return Err("Distance cannot be negative");
+                }
+                Self::Distance(distance)
+            }
+            "x_rotation" => Self::XRotation(reader.read_number()?),
+            "y_rotation" => Self::YRotation(reader.read_number()?),
+            "level" => {
+                let level = reader.read_number::<Bounds<i32>>()?;
+                if level.min.is_some_and(|min| min < 0) || level.max.is_some_and(|max| max < 0) {
+                    return Err("Level cannot be negative");
+                }
+                Self::Level(level)
+            }
+            "limit" => {
+                let limit = reader.read_number()?;
+                if limit < 1 {
+                    return Err("Limit must be at least 1");
+                }
+                Self::Limit(limit)
+            }
+            "sort" => Self::Sort(reader.read_unquoted().parse().map_err(|_| "Invalid sort")?),
+            "name" => Self::Name(Negatable::read(reader, Reader::read_string)?),
+            "team" => Self::Team(Negatable::read(reader, |reader| {
+                Ok(reader.read_unquoted().to_string())
+            })?),
+            "tag" => Self::Tag(Negatable::read(reader, |reader| {
+                Ok(reader.read_unquoted().to_string())
+            })?),
+            "type" => Self::Type(Negatable::read(reader, |reader| {
+                if reader.eat('#') {
+                    Ok(EntityTypeFilter::Tag(reader.read_identifier()?))
+                } else {
+                    Ok(EntityTypeFilter::Kind(reader.read_identifier()?))
+                }
+            })?),
+            "gamemode" => Self::Gamemode(Negatable::read(reader, |reader| {
+                reader
+                    .read_unquoted()
+                    .parse()
+                    .map_err(|_| "Invalid game mode")
+            })?),
+            "nbt" => Self::Nbt(Negatable::read(reader, Reader::read_compound)?),
+            "predicate" => Self::Predicate(Negatable::read(reader, Reader::read_identifier)?),
+            "scores" => Self::Scores(reader.read_map(|reader| {
+                let objective = reader.read_unquoted().to_string();
+                if objective.is_empty() {
+                    return Err("Expected objective name");
+                }
+                Ok((objective, reader.read_value(Reader::read_number)?))
+            })?),
+            "advancements" => Self::Advancements(reader.read_map(|reader| {
+                let advancement = reader.read_identifier()?;
+                let predicate = reader.read_value(|reader| {
+                    if reader.peek() == Some('{') {
+                        Ok(AdvancementPredicate::Criteria(reader.read_map(
+                            |reader| {
+                                let criterion = reader.read_unquoted().to_string();
+                                Ok((criterion, reader.read_value(Reader::read_bool)?))
+                            },
+                        )?))
+                    } else {
+                        Ok(AdvancementPredicate::Done(reader.read_bool()?))
+                    }
+                })?;
+                Ok((advancement, predicate))
+            })?),
+            _ => return Err("Unknown selector option"),
+        })
+    }
+
+    /// Whether a `negated` option was written with `!`, for the options that can be.
+    const fn negated(&self) -> Option<bool> {
+        match self {
+            Self::Name(Negatable { negated, .. })
+            | Self::Team(Negatable { negated, .. })
+            | Self::Tag(Negatable { negated, .. })
+            | Self::Type(Negatable { negated, .. })
+            | Self::Gamemode(Negatable { negated, .. })
+            | Self::Nbt(Negatable { negated, .. })
+            | Self::Predicate(Negatable { negated, .. }) => Some(*negated),
+            _ => None,
+        }
+    }
+
+    /// Checks that the option can follow `before` in a selector of `kind`. As in vanilla, most
+    /// options can only be used once, and those that can be negated once without `!` or any number
+    /// of times with it, but not without it after a negated one.
+    fn check_applicable(
+        &self,
+        kind: SelectorKind,
+        before: &[SelectorArgument],
+    ) -> Result<(), &'static str> {
+        const INAPPLICABLE: &str = "Selector option isn't applicable here";
+
+        let mut same = before
+            .iter()
+            .filter(|argument| discriminant(*argument) == discriminant(self));
+        let applicable = match self {
+            Self::Limit(_) | Self::Sort(_) if kind == SelectorKind::Executor => false,
+            Self::Type(_) if kind.is_players_only() => false,
+            // Any number of tags, compounds and predicates can be required
+            Self::Tag(_) | Self::Nbt(_) | Self::Predicate(_) => true,
+            // Tags don't narrow the selector down to a single type, so more can follow them
+            Self::Type(filter) => {
+                !before.iter().any(|argument| {
+                    matches!(
+                        argument,
+                        Self::Type(Negatable {
+                            negated: false,
+                            value: EntityTypeFilter::Kind(_),
+                        })
+                    )
+                }) && (filter.negated || !same.any(|argument| argument.negated() == Some(true)))
+            }
+            _ => match self.negated() {
+                Some(true) => !same.any(|argument| argument.negated() == Some(false)),
+                _ => same.next().is_none(),
+            },
+        };
+
+        if applicable {
+            Ok(())
+        } else {
+            Err(INAPPLICABLE)
+        }
+    }
+}
+
+impl std::fmt::Display for SelectorArgument {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::X(x) => write!(f, "x={x}"),
+            Self::Y(y) => write!(f, "y={y}"),
+            Self::Z(z) => write!(f, "z={z}"),
+            Self::Dx(dx) => write!(f, "dx={dx}"),
+            Self::Dy(dy) => write!(f, "dy={dy}"),
+            Self::Dz(dz) => write!(f, "dz={dz}"),
+            Self::Distance(distance) => write!(f, "distance={distance}"),
+            Self::XRotation(rotation) => write!(f, "x_rotation={rotation}"),
+            Self::YRotation(rotation) => write!(f, "y_rotation={rotation}"),
+            Self::Level(level) => write!(f, "level={level}"),
+            Self::Limit(limit) => write!(f, "limit={limit}"),
+            Self::Sort(sort) => write!(f, "sort={}", <&'static str>::from(sort)),
+            Self::Name(name) => {
+                write!(f, "name={}", if name.negated { "!" } else { "" })?;
+                write_string(&name.value, f)
+            }
+            Self::Team(team) => write!(f, "team={team}"),
+            Self::Tag(tag) => write!(f, "tag={tag}"),
+            Self::Type(kind) => write!(f, "type={kind}"),
+            Self::Gamemode(game_mode) => write!(
+                f,
+                "gamemode={}{}",
+                if game_mode.negated { "!" } else { "" },
+                <&'static str>::from(&game_mode.value)
+            ),
+            Self::Nbt(nbt) => write!(
+                f,
+                "nbt={}{}",
+                if nbt.negated { "!" } else { "" },
+                snbt::print(&nbt.value)
+            ),
+            Self::Predicate(predicate) => write!(f, "predicate={predicate}"),
+            Self::Scores(scores) => {
+                f.write_str("scores={")?;
+                for (i, (objective, bounds)) in scores.iter().enumerate() {
+                    let separator = if i == 0 { "" } else { "," };
+                    write!(f, "{separator}{objective}={bounds}")?;
+                }
+                f.write_str("}")
+            }
+            Self::Advancements(advancements) => {
+                f.write_str("advancements={")?;
+                for (i, (advancement, predicate)) in advancements.iter().enumerate() {
+                    let separator = if i == 0 { "" } else { "," };
+                    write!(f, "{separator}{advancement}={predicate}")?;
+                }
+                f.write_str("}")
+            }
+        }
+    }
+}
+
+/// A value that can be inverted with a leading `!`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Negatable<T> {
+    pub negated: bool,
+    pub value: T,
+}
+
+impl<T> Negatable<T> {
+    pub const fn new(value: T) -> Self {
+        Self {
+            negated: false,
+            value,
+        }
+    }
+
+    pub const fn not(value: T) -> Self {
+        Self {
+            negated: true,
+            value,
+        }
+    }
+
+    fn read<'a>(
+        reader: &mut Reader<'a>,
+        value: impl FnOnce(&mut Reader<'a>) -> Result<T, &'static str>,
+    ) -> Result<Self, &'static str> {
+        let negated = reader.eat('!');
+        reader.skip_whitespace();
+        Ok(Self {
+            negated,
+            value: value(reader)?,
+        })
+    }
+}
+
+impl<T: std::fmt::Display> std::fmt::Display for Negatable<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.negated {
+            f.write_str("!")?;
+        }
+        self.value.fmt(f)
+    }
+}
+
+/// A range like `1..5`, `..5`, `1..` or just `3`, with both ends inclusive.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Bounds<T> {
+    pub min: Option<T>,
+    pub max: Option<T>,
+}
+
+impl<T: Copy> Bounds<T> {
+    pub const fn exactly(value: T) -> Self {
+        Self {
+            min: Some(value),
+            max: Some(value),
+        }
+    }
+}
+
+impl<T: std::fmt::Display + PartialEq> std::fmt::Display for Bounds<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (&self.min, &self.max) {
+            (Some(min), Some(max)) if min == max => min.fmt(f),
+            (min, max) => {
+                if let Some(min) = min {
+                    min.fmt(f)?;
+                }
+                f.write_str("..")?;
+                if let Some(max) = max {
+                    max.fmt(f)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl<T: FromStr + Copy> FromStr for Bounds<T> {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse = |s: &str| {
+            if s.is_empty() {
+                Ok(None)
+            } else {
+                s.parse().map(Some).map_err(|_| "Invalid number in range")
+            }
+        };
+
+        let (min, max) = match s.split_once("..") {
+            Some((min, max)) => (parse(min)?, parse(max)?),
+            None => {
+                let value = parse(s)?;
+                (value, value)
+            }
+        };
+        if min.is_none() && max.is_none() {
+            return Err("Expected a value or range");
+        }
+
+        Ok(Self { min, max })
+    }
+}
+
+#[derive(EnumString, IntoStaticStr, Clone, Copy, Debug, PartialEq, Eq)]
+#[strum(serialize_all = "snake_case")]
+pub enum Sort {
+    Nearest,
+    Furthest,
+    Random,
+    Arbitrary,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EntityTypeFilter {
+    Kind(WrittenIdentifier),
+    /// An entity type tag, written with a leading `#`
+    Tag(WrittenIdentifier),
+}
+
+impl std::fmt::Display for EntityTypeFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Kind(id) => id.fmt(f),
+            Self::Tag(id) => write!(f, "#{id}"),
+        }
+    }
+}
+
+/// An identifier as it was written in a selector, which may have left out the `minecraft`
+/// namespace.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WrittenIdentifier {
+    pub id: Identifier,
+    /// Whether the namespace was written out
+    pub namespaced: bool,
+}
+
+impl From<Identifier> for WrittenIdentifier {
+    fn from(id: Identifier) -> Self {
+        Self {
+            id,
+            namespaced: true,
+        }
+    }
+}
+
+impl std::fmt::Display for WrittenIdentifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.namespaced || self.id.namespace != "minecraft" {
+            self.id.fmt(f)
+        } else {
+            f.write_str(&self.id.path)
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AdvancementPredicate {
+    /// Whether the whole advancement is done
+    Done(bool),
+    /// Whether each named criterion is done
+    Criteria(Vec<(String, bool)>),
+}
+
+impl std::fmt::Display for AdvancementPredicate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Done(done) => done.fmt(f),
+            Self::Criteria(criteria) => {
+                f.write_str("{")?;
+                for (i, (criterion, done)) in criteria.iter().enumerate() {
+                    let separator = if i == 0 { "" } else { "," };
+                    write!(f, "{separator}{criterion}={done}")?;
+                }
+                f.write_str("}")
+            }
+        }
+    }
+}
+
+const fn is_unquoted(c: char) -> bool {
+    matches!(c, '0'..='9' | 'A'..='Z' | 'a'..='z' | '_' | '-' | '.' | '+')
+}
+
+/// Writes `s` bare if vanilla would read it back that way, and quoted otherwise.
+fn write_string(s: &str, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    if !s.is_empty() && s.chars().all(is_unquoted) {
+        return f.write_str(s);
+    }
+
+    f.write_str("\"")?;
+    for c in s.chars() {
+        if matches!(c, '"' | '\\') {
+            f.write_str("\\")?;
+        }
+        write!(f, "{c}")?;
+    }
+    f.write_str("\"")
+}
+
+/// A cursor over selector arguments, after the vanilla `StringReader`.
+struct Reader<'a> {
+    s: &'a str,
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    const fn new(s: &'a str) -> Self {
+        Self { s, position: 0 }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.s[self.position..]
+    }
+
+    fn is_empty(&self) -> bool {
+        self.rest().is_empty()
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.position += c.len_utf8();
+        Some(c)
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        let matches = self.peek() == Some(c);
+        if matches {
+            self.position += c.len_utf8();
+        }
+        matches
+    }
+
+    fn expect(&mut self, c: char, error: &'static str) -> Result<(), &'static str> {
+        if self.eat(c) { Ok(()) } else { Err(error) }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.next();
+        }
+    }
+
+    fn read_while(&mut self, predicate: impl Fn(char) -> bool) -> &'a str {
+        let start = self.position;
+        while self.peek().is_some_and(&predicate) {
+            self.next();
+        }
+        &self.s[start..self.position]
+    }
+
+    fn read_unquoted(&mut self) -> &'a str {
+        self.read_while(is_unquoted)
+    }
+
+    fn read_string(&mut self) -> Result<String, &'static str> {
+        let Some(quote @ ('"' | '\'')) = self.peek() else {
+            return Ok(self.read_unquoted().to_string());
+        };
+        self.next();
+
+        let mut out = String::new();
+        loop {
+            match self.next().ok_or("Unclosed quoted string")? {
+                '\\' => match self.next() {
+                    Some(c) if c == quote || c == '\\' => out.push(c),
+                    _ => return Err("Invalid escape sequence in quoted string"),
+                },
+                c if c == quote => return Ok(out),
+                c => out.push(c),
+            }
+        }
+    }
+
+    /// Reads a number or a range of numbers.
+    fn read_number<T: FromStr>(&mut self) -> Result<T, &'static str> {
+        self.read_while(|c| c.is_ascii_digit() || matches!(c, '.' | '-'))
+            .parse()
+            .map_err(|_| "Invalid number")
+    }
+
+    fn read_bool(&mut self) -> Result<bool, &'static str> {
+        match self.read_unquoted() {
+            "true" => Ok(true),
+            "false" => Ok(false),
+            _ => Err("Expected true or false"),
+        }
+    }
+
+    fn read_identifier(&mut self) -> Result<WrittenIdentifier, &'static str> {
+        let s =
+            self.read_while(|c| matches!(c, '0'..='9' | 'a'..='z' | '_' | '-' | '.' | ':' | '/'));
+        Ok(WrittenIdentifier {
+            id: s.parse()?,
+            namespaced: s.contains(':'),
+        })
+    }
+
+    fn read_compound(&mut self) -> Result<Value, &'static str> {
+        let (compound, length) = snbt::parse_compound_prefix(self.rest()).map_err(|e| e.message)?;
+        self.position += length;
+        Ok(Value::Compound(compound))
+    }
+
+    /// Reads `= value`, with optional whitespace either side of the `=`.
+    fn read_value<T>(
+        &mut self,
+        value: impl FnOnce(&mut Self) -> Result<T, &'static str>,
+    ) -> Result<T, &'static str> {
+        self.skip_whitespace();
+        self.expect('=', "Expected '='")?;
+        self.skip_whitespace();
+        value(self)
+    }
+
+    /// Reads `{entry, entry, ...}`.
+    fn read_map<T>(
+        &mut self,
+        entry: impl Fn(&mut Self) -> Result<T, &'static str>,
+    ) -> Result<Vec<T>, &'static str> {
+        self.expect('{', "Expected '{'")?;
+        self.skip_whitespace();
+
+        let mut out = vec![];
+        if self.eat('}') {
+            return Ok(out);
+        }
+        loop {
+            self.skip_whitespace();
+            out.push(entry(self)?);
+            self.skip_whitespace();
+            if self.eat('}') {
+                return Ok(out);
+            }
+            self.expect(',', "Expected ',' or '}'")?;
+        }
+    }
+}
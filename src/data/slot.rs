@@ -0,0 +1,94 @@
+//! Items as they're sent over the network, which is as slots: a count, and if it isn't zero, the
+//! item's network id and the components it adds and removes. Components can refer to entries of
+//! synced registries, so slots are encoded for the registries the client was sent.
+
+use crate::{
+    data::{
+        Components, Item,
+        registry::{Registries, SyncedCodec, SyncedType, builtin::ITEM},
+    },
+    types::{AsVarInt, Codec, Type, VarInt},
+};
+use std::{
+    io::{Read, Write},
+    sync::LazyLock,
+};
+
+static VANILLA: LazyLock<Registries> = LazyLock::new(Registries::vanilla);
+
+/// A slot with an item in it. Empty slots go through [`Slot`].
+impl SyncedType for Item {
+    fn write_with(&self, registries: &Registries, mut writer: impl Write) -> std::io::Result<()> {
+        if self.count() < 1 {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("Can't send {} of an item", self.count()),
+            ));
+        }
+
+        AsVarInt::write(&self.count(), &mut writer)?;
+        ITEM.write_entry(&self.id, &mut writer)?;
+        match &self.components {
+            Some(components) => components.write_with(registries, writer),
+            None => Components::default().write_with(registries, writer),
+        }
+    }
+
+    fn read_with(registries: &Registries, mut reader: impl Read) -> std::io::Result<Self> {
+        match AsVarInt::read(&mut reader)? {
+            count @ ..=0 => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("Empty slot with a count of {count}"),
+            )),
+            count => read_item(count, registries, reader),
+        }
+    }
+}
+
+/// Encoded for the vanilla registries, which every vanilla client of this version has been sent
+/// unless data packs add to them. Items sent to a particular client go through [`SyncedType`]
+/// with the registries it was sent instead.
+impl Type for Item {
+    fn write(&self, writer: impl Write) -> std::io::Result<()> {
+        self.write_with(&VANILLA, writer)
+    }
+
+    fn read(reader: impl Read) -> std::io::Result<Self> {
+        Self::read_with(&VANILLA, reader)
+    }
+}
+
+fn read_item(count: i32, registries: &Registries, mut reader: impl Read) -> std::io::Result<Item> {
+    let id = ITEM.read_entry(&mut reader)?;
+    let components = Components::read_with(registries, reader)?;
+    let is_empty = components.sends_nothing(registries);
+
+    Ok(Item {
+        id,
+        count: Some(count),
+        components: (!is_empty).then_some(components),
+    })
+}
+
+/// Encodes a slot that may be empty, which is sent as a count of zero and nothing else.
+pub struct Slot;
+
+impl SyncedCodec<Option<Item>> for Slot {
+    fn write_with(
+        value: &Option<Item>,
+        registries: &Registries,
+        writer: impl Write,
+    ) -> std::io::Result<()> {
+        match value {
+            Some(item) => item.write_with(registries, writer),
+            None => VarInt::new(0).write(writer),
+        }
+    }
+
+    fn read_with(registries: &Registries, mut reader: impl Read) -> std::io::Result<Option<Item>> {
+        match AsVarInt::read(&mut reader)? {
+            ..=0 => Ok(None),
+            count => read_item(count, registries, reader).map(Some),
+        }
+    }
+}
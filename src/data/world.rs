@@ -0,0 +1,398 @@
+use crate::data::{
+    Identifier,
+    chunk::{Chunk, EntityChunk},
+    entity::{Entity, McUuid},
+    level::LevelData,
+    player::Player,
+    region::{Compression, Region, RegionError},
+};
+use fastnbt::Value;
+use flate2::{read::GzDecoder, write::GzEncoder};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use std::{
+    collections::HashMap,
+    ffi::OsString,
+    fs::File,
+    io::{BufReader, BufWriter, Read, Write},
+    path::{Path, PathBuf},
+};
+use uuid::Uuid;
+
+/// A world save: `level.dat` and the directories of region, entity, POI and player files around
+/// it.
+pub struct World {
+    root: PathBuf,
+    level: LevelDat,
+}
+
+impl World {
+    /// Opens the world in `root`, falling back to `level.dat_old` if `level.dat` can't be read.
+    pub fn open(root: impl Into<PathBuf>) -> Result<Self, WorldError> {
+        let root = root.into();
+        let path = root.join("level.dat");
+        let level = match read_level(&path) {
+            Ok(level) => level,
+            Err(e) => match read_level(&backup_path(&path)) {
+                Ok(level) => level,
+                Err(_) => return Err(e),
+            },
+        };
+
+        Ok(Self { root, level })
+    }
+
+    /// Creates a new world in `root` with `level`, making the directory if it doesn't exist. Fails
+    /// if there's already a world there.
+    pub fn create(root: impl Into<PathBuf>, level: LevelData) -> Result<Self, WorldError> {
+        let root = root.into();
+        let path = root.join("level.dat");
+        if path.exists() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::AlreadyExists,
+                format!("{} already exists", path.display()),
+            )
+            .into());
+        }
+
+        std::fs::create_dir_all(&root)?;
+        let world = Self {
+            root,
+            level: LevelDat {
+                data: level,
+                extra: HashMap::new(),
+            },
+        };
+        world.save_level()?;
+
+        Ok(world)
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn level(&self) -> &LevelData {
+        &self.level.data
+    }
+
+    pub fn level_mut(&mut self) -> &mut LevelData {
+        &mut self.level.data
+    }
+
+    /// Writes `level.dat`, keeping the previous one as `level.dat_old`.
+    pub fn save_level(&self) -> Result<(), WorldError> {
+        write_compressed(&self.root.join("level.dat"), &self.level)
+    }
+
+    /// Where a dimension keeps its files. The vanilla dimensions keep their old locations, with
+    /// the overworld in the root of the world.
+    pub fn dimension_path(&self, dimension: &Identifier) -> PathBuf {
+        match (dimension.namespace.as_str(), dimension.path.as_str()) {
+            ("minecraft", "overworld") => self.root.clone(),
+            ("minecraft", "the_nether") => self.root.join("DIM-1"),
+            ("minecraft", "the_end") => self.root.join("DIM1"),
+            (namespace, path) => self.root.join("dimensions").join(namespace).join(path),
+        }
+    }
+
+    /// Opens a region file of a dimension by its region coordinates, creating it if needed.
+    pub fn region(
+        &self,
+        dimension: &Identifier,
+        kind: RegionKind,
+        x: i32,
+        z: i32,
+    ) -> Result<Region, WorldError> {
+        std::fs::create_dir_all(self.region_directory(dimension, kind))?;
+        Ok(Region::open(self.region_path(dimension, kind, x, z))?)
+    }
+
+    /// The coordinates of every region file of a dimension, in no particular order
+    pub fn regions(
+        &self,
+        dimension: &Identifier,
+        kind: RegionKind,
+    ) -> Result<Vec<(i32, i32)>, WorldError> {
+        let directory = self.region_directory(dimension, kind);
+        if !directory.is_dir() {
+            return Ok(vec![]);
+        }
+
+        let mut regions = vec![];
+        for entry in std::fs::read_dir(directory)? {
+            if let Some(position) = Region::parse_file_name(&entry?.path()) {
+                regions.push(position);
+            }
+        }
+
+        Ok(regions)
+    }
+
+    /// Reads a chunk by its chunk coordinates, or `None` if it was never saved.
+    pub fn read_chunk(
+        &self,
+        dimension: &Identifier,
+        x: i32,
+        z: i32,
+    ) -> Result<Option<Chunk>, WorldError> {
+        let path = self
+            .region_directory(dimension, RegionKind::Chunks)
+            .join(format!("r.{}.{}.mca", x >> 5, z >> 5));
+        // Not opened through `Self::region`, which would leave an empty file behind
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        Ok(Region::open(path)?.read_chunk(x, z)?)
+    }
+
+    pub fn write_chunk(&self, dimension: &Identifier, chunk: &Chunk) -> Result<(), WorldError> {
+        let (x, z) = chunk.position();
+        self.region(dimension, RegionKind::Chunks, x >> 5, z >> 5)?
+            .write_chunk(x, z, chunk, Compression::default())?;
+        Ok(())
+    }
+
+    /// The entities saved in a chunk, which is empty if none ever were.
+    pub fn read_entities(
+        &self,
+        dimension: &Identifier,
+        x: i32,
+        z: i32,
+    ) -> Result<Vec<Entity>, WorldError> {
+        let path = self.region_path(dimension, RegionKind::Entities, x >> 5, z >> 5);
+        if !path.exists() {
+            return Ok(vec![]);
+        }
+
+        let chunk: Option<EntityChunk> = Region::open(path)?.read_chunk(x, z)?;
+        Ok(chunk.map(EntityChunk::into_entities).unwrap_or_default())
+    }
+
+    /// Replaces the entities saved in a chunk. Like vanilla, a chunk left without entities is
+    /// removed rather than saved empty.
+    pub fn write_entities(
+        &self,
+        dimension: &Identifier,
+        x: i32,
+        z: i32,
+        entities: Vec<Entity>,
+    ) -> Result<(), WorldError> {
+        if entities.is_empty() {
+            // Nothing to remove, so don't make a region file only to leave it empty
+            if !self
+                .region_path(dimension, RegionKind::Entities, x >> 5, z >> 5)
+                .exists()
+            {
+                return Ok(());
+            }
+            self.region(dimension, RegionKind::Entities, x >> 5, z >> 5)?
+                .remove(x, z)?;
+        } else {
+            let chunk = EntityChunk::new(x, z, entities);
+            self.region(dimension, RegionKind::Entities, x >> 5, z >> 5)?
+                .write_chunk(x, z, &chunk, Compression::default())?;
+        }
+
+        Ok(())
+    }
+
+    /// The coordinates of every saved chunk of a dimension, in no particular order
+    pub fn chunks(
+        &self,
+        dimension: &Identifier,
+        kind: RegionKind,
+    ) -> Result<Vec<(i32, i32)>, WorldError> {
+        let mut chunks = vec![];
+        for (x, z) in self.regions(dimension, kind)? {
+            chunks.extend(self.region(dimension, kind, x, z)?.chunks());
+        }
+
+        Ok(chunks)
+    }
+
+    /// The `playerdata` directory, with a `<uuid>.dat` file for each player
+    pub fn player_data_directory(&self) -> PathBuf {
+        self.root.join("playerdata")
+    }
+
+    pub fn player_data_path(&self, uuid: &McUuid) -> PathBuf {
+        self.player_data_directory()
+            .join(format!("{}.dat", uuid.hyphenated()))
+    }
+
+    /// Reads a player's data, falling back to the backup like vanilla does, or `None` if they
+    /// have never joined.
+    pub fn read_player(&self, uuid: &McUuid) -> Result<Option<Player>, WorldError> {
+        let path = self.player_data_path(uuid);
+        let backup = backup_path(&path);
+        if !path.exists() && !backup.exists() {
+            return Ok(None);
+        }
+
+        match read_compressed(&path) {
+            Ok(player) => Ok(Some(player)),
+            Err(e) => match read_compressed(&backup) {
+                Ok(player) => Ok(Some(player)),
+                Err(_) => Err(e),
+            },
+        }
+    }
+
+    /// Writes a player's data, keeping the previous save as `<uuid>.dat_old`.
+    pub fn write_player(&self, player: &Player) -> Result<(), WorldError> {
+        std::fs::create_dir_all(self.player_data_directory())?;
+        write_compressed(&self.player_data_path(player.uuid()), player)
+    }
+
+    /// The UUIDs of every player with saved data, in no particular order
+    pub fn players(&self) -> Result<Vec<McUuid>, WorldError> {
+        let directory = self.player_data_directory();
+        if !directory.is_dir() {
+            return Ok(vec![]);
+        }
+
+        let mut players = vec![];
+        for entry in std::fs::read_dir(directory)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|extension| extension == "dat")
+                && let Some(uuid) = path
+                    .file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .and_then(|stem| Uuid::parse_str(stem).ok())
+            {
+                players.push(McUuid::from(uuid));
+            }
+        }
+
+        Ok(players)
+    }
+
+    fn region_directory(&self, dimension: &Identifier, kind: RegionKind) -> PathBuf {
+        self.dimension_path(dimension).join(kind.directory())
+    }
+
+    fn region_path(&self, dimension: &Identifier, kind: RegionKind, x: i32, z: i32) -> PathBuf {
+        self.region_directory(dimension, kind)
+            .join(format!("r.{x}.{z}.mca"))
+    }
+}
+
+/// The kinds of region files, each in their own directory of a dimension.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RegionKind {
+    /// Terrain, block entities and scheduled ticks, in `region/`
+    Chunks,
+    /// In `entities/`
+    Entities,
+    /// Points of interest like beds and workstations, in `poi/`
+    Poi,
+}
+
+impl RegionKind {
+    pub const fn directory(self) -> &'static str {
+        match self {
+            Self::Chunks => "region",
+            Self::Entities => "entities",
+            Self::Poi => "poi",
+        }
+    }
+}
+
+/// `level.dat` puts everything under a `Data` compound, though mods may save beside it.
+#[derive(Serialize, Deserialize)]
+struct LevelDat {
+    #[serde(rename = "Data")]
+    data: LevelData,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+fn read_level(path: &Path) -> Result<LevelDat, WorldError> {
+    read_compressed(path)
+}
+
+/// Reads a gzip compressed NBT file, like `level.dat` and player data.
+pub(crate) fn read_compressed<T: DeserializeOwned>(path: &Path) -> Result<T, WorldError> {
+    let mut data = vec![];
+    GzDecoder::new(BufReader::new(File::open(path)?)).read_to_end(&mut data)?;
+    Ok(fastnbt::from_bytes(&data)?)
+}
+
+/// Writes a gzip compressed NBT file the way vanilla does: to a temporary file first, which then
+/// replaces the old file after it has been moved to `<name>_old`.
+pub(crate) fn write_compressed<T: Serialize>(path: &Path, value: &T) -> Result<(), WorldError> {
+    let data = fastnbt::to_bytes(value)?;
+    let mut temporary = OsString::from(path.as_os_str());
+    temporary.push(".tmp");
+    let temporary = PathBuf::from(temporary);
+
+    let mut encoder = GzEncoder::new(
+        BufWriter::new(File::create(&temporary)?),
+        flate2::Compression::default(),
+    );
+    encoder.write_all(&data)?;
+    encoder
+        .finish()?
+        .into_inner()
+        .map_err(|e| e.into_error())?
+        .sync_all()?;
+
+    if path.exists() {
+        std::fs::rename(path, backup_path(path))?;
+    }
+    std::fs::rename(temporary, path)?;
+
+    Ok(())
+}
+
+pub(crate) fn backup_path(path: &Path) -> PathBuf {
+    let mut backup = OsString::from(path.as_os_str());
+    backup.push("_old");
+    backup.into()
+}
+
+#[derive(Debug)]
+pub enum WorldError {
+    Io(std::io::Error),
+    Nbt(fastnbt::error::Error),
+    Region(RegionError),
+}
+
+impl std::fmt::Display for WorldError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(e) => e.fmt(f),
+            Self::Nbt(e) => e.fmt(f),
+            Self::Region(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for WorldError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            Self::Nbt(e) => Some(e),
+            Self::Region(e) => Some(e),
+        }
+    }
+}
+
+impl From<std::io::Error> for WorldError {
+    fn from(value: std::io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+impl From<fastnbt::error::Error> for WorldError {
+    fn from(value: fastnbt::error::Error) -> Self {
+        Self::Nbt(value)
+    }
+}
+
+impl From<RegionError> for WorldError {
+    fn from(value: RegionError) -> Self {
+        Self::Region(value)
+    }
+}
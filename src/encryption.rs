@@ -0,0 +1,434 @@
+use crate::types::Type;
+use aes::{
+    Aes128,
+    cipher::{BlockDecryptMut, BlockEncryptMut, KeyIvInit, generic_array::GenericArray},
+};
+use rsa::{Pkcs1v15Encrypt, RsaPrivateKey, RsaPublicKey, pkcs8::DecodePublicKey};
+use sha1::{Digest, Sha1};
+use std::{
+    io::{Read, Write},
+    pin::Pin,
+    task::{Context, Poll, ready},
+};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+
+type Encryptor = cfb8::Encryptor<Aes128>;
+type Decryptor = cfb8::Decryptor<Aes128>;
+
+/// The size of the key the vanilla server generates
+pub const KEY_BITS: usize = 1024;
+
+#[derive(Debug)]
+pub enum EncryptionError {
+    Rsa(rsa::Error),
+    Der(rsa::pkcs8::spki::Error),
+    VerifyTokenMismatch,
+    InvalidSecretLength(usize),
+}
+
+impl std::fmt::Display for EncryptionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Rsa(e) => e.fmt(f),
+            Self::Der(e) => e.fmt(f),
+            Self::VerifyTokenMismatch => write!(f, "Verify token does not match"),
+            Self::InvalidSecretLength(length) => {
+                write!(f, "Shared secret must be 16 bytes, not {length}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for EncryptionError {}
+
+impl From<rsa::Error> for EncryptionError {
+    fn from(value: rsa::Error) -> Self {
+        Self::Rsa(value)
+    }
+}
+
+impl From<rsa::pkcs8::spki::Error> for EncryptionError {
+    fn from(value: rsa::pkcs8::spki::Error) -> Self {
+        Self::Der(value)
+    }
+}
+
+#[derive(Type, Clone, Debug, PartialEq, Eq)]
+pub struct EncryptionRequest {
+    /// Always empty on vanilla servers
+    pub server_id: String,
+    #[mc(prefixed)]
+    pub public_key: Vec<u8>,
+    #[mc(prefixed)]
+    pub verify_token: Vec<u8>,
+    pub should_authenticate: bool,
+}
+
+#[derive(Type, Clone, Debug, PartialEq, Eq)]
+pub struct EncryptionResponse {
+    #[mc(prefixed)]
+    pub shared_secret: Vec<u8>,
+    #[mc(prefixed)]
+    pub verify_token: Vec<u8>,
+}
+
+impl EncryptionResponse {
+    /// Builds the response the client sends, encrypting `shared_secret` and `verify_token` with
+    /// the server's public key.
+    pub fn new(
+        request: &EncryptionRequest,
+        shared_secret: &[u8; 16],
+    ) -> Result<Self, EncryptionError> {
+        let key = RsaPublicKey::from_public_key_der(&request.public_key)?;
+        let mut rng = rand::thread_rng();
+
+        Ok(Self {
+            shared_secret: key.encrypt(&mut rng, Pkcs1v15Encrypt, shared_secret)?,
+            verify_token: key.encrypt(&mut rng, Pkcs1v15Encrypt, &request.verify_token)?,
+        })
+    }
+}
+
+/// The server's RSA keypair, used only to exchange the shared secret during login.
+#[derive(Clone)]
+pub struct ServerKey {
+    private: RsaPrivateKey,
+    public_der: Vec<u8>,
+}
+
+impl ServerKey {
+    pub fn generate() -> Result<Self, EncryptionError> {
+        Self::from_private_key(RsaPrivateKey::new(&mut rand::thread_rng(), KEY_BITS)?)
+    }
+
+    pub fn from_private_key(private: RsaPrivateKey) -> Result<Self, EncryptionError> {
+        use rsa::pkcs8::EncodePublicKey;
+
+        let public_der = private
+            .to_public_key()
+            .to_public_key_der()?
+            .as_bytes()
+            .to_vec();
+
+        Ok(Self {
+            private,
+            public_der,
+        })
+    }
+
+    /// The public key as an ASN.1 DER SubjectPublicKeyInfo, as sent in the Encryption Request.
+    pub fn public_key_der(&self) -> &[u8] {
+        &self.public_der
+    }
+
+    pub fn encryption_request(
+        &self,
+        verify_token: Vec<u8>,
+        should_authenticate: bool,
+    ) -> EncryptionRequest {
+        EncryptionRequest {
+            server_id: String::new(),
+            public_key: self.public_der.clone(),
+            verify_token,
+            should_authenticate,
+        }
+    }
+
+    /// Decrypts the client's response, checking it against the token sent in the request, and
+    /// returns the shared secret.
+    pub fn decrypt_response(
+        &self,
+        response: &EncryptionResponse,
+        verify_token: &[u8],
+    ) -> Result<[u8; 16], EncryptionError> {
+        let token = self
+            .private
+            .decrypt(Pkcs1v15Encrypt, &response.verify_token)?;
+        if token != verify_token {
+            return Err(EncryptionError::VerifyTokenMismatch);
+        }
+
+        let secret = self
+            .private
+            .decrypt(Pkcs1v15Encrypt, &response.shared_secret)?;
+        secret
+            .as_slice()
+            .try_into()
+            .map_err(|_| EncryptionError::InvalidSecretLength(secret.len()))
+    }
+}
+
+/// The hash sent to the session server to authenticate a player, as Minecraft's signed hex
+/// digest of the SHA-1 of the server id, shared secret and public key.
+pub fn server_hash(server_id: &str, shared_secret: &[u8], public_key: &[u8]) -> String {
+    let mut hasher = Sha1::new();
+    hasher.update(server_id.as_bytes());
+    hasher.update(shared_secret);
+    hasher.update(public_key);
+    let mut digest: [u8; 20] = hasher.finalize().into();
+
+    // The digest is interpreted as a two's complement integer, so negative digests are negated
+    // and printed with a minus sign
+    let negative = digest[0] & 0x80 != 0;
+    if negative {
+        let mut carry = true;
+        for byte in digest.iter_mut().rev() {
+            *byte = !*byte;
+            if carry {
+                (*byte, carry) = byte.overflowing_add(1);
+            }
+        }
+    }
+
+    let hex = digest
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect::<String>();
+    let hex = hex.trim_start_matches('0');
+
+    match (negative, hex.is_empty()) {
+        (_, true) => "0".to_string(),
+        (true, false) => format!("-{hex}"),
+        (false, false) => hex.to_string(),
+    }
+}
+
+/// A stream that is encrypted in both directions.
+pub type EncryptedStream<S> = EncryptedWriter<EncryptedReader<S>>;
+
+/// Encrypts both directions of `stream`, as both sides do once the login key exchange is done.
+pub fn encrypt_stream<S>(stream: S, shared_secret: &[u8; 16]) -> EncryptedStream<S> {
+    EncryptedWriter::new(EncryptedReader::new(stream, shared_secret), shared_secret)
+}
+
+fn encryptor(shared_secret: &[u8; 16]) -> Encryptor {
+    Encryptor::new(shared_secret.into(), shared_secret.into())
+}
+
+fn decryptor(shared_secret: &[u8; 16]) -> Decryptor {
+    Decryptor::new(shared_secret.into(), shared_secret.into())
+}
+
+/// Decrypts everything read from `R` with AES-128-CFB8, using the shared secret as both key and
+/// IV.
+pub struct EncryptedReader<R> {
+    inner: R,
+    cipher: Decryptor,
+}
+
+impl<R> EncryptedReader<R> {
+    pub fn new(inner: R, shared_secret: &[u8; 16]) -> Self {
+        Self {
+            inner,
+            cipher: decryptor(shared_secret),
+        }
+    }
+
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    fn decrypt(&mut self, buffer: &mut [u8]) {
+        for byte in buffer {
+            self.cipher
+                .decrypt_block_mut(GenericArray::from_mut_slice(std::slice::from_mut(byte)));
+        }
+    }
+}
+
+impl<R: Read> Read for EncryptedReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.decrypt(&mut buf[..read]);
+
+        Ok(read)
+    }
+}
+
+// Writes pass straight through, so that a reader and writer can be stacked on one stream
+impl<R: Write> Write for EncryptedReader<R> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.inner.write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<R: AsyncRead + Unpin> AsyncRead for EncryptedReader<R> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        let this = self.get_mut();
+        let filled = buf.filled().len();
+        ready!(Pin::new(&mut this.inner).poll_read(cx, buf))?;
+        this.decrypt(&mut buf.filled_mut()[filled..]);
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<R: AsyncWrite + Unpin> AsyncWrite for EncryptedReader<R> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        Pin::new(&mut self.get_mut().inner).poll_write(cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_shutdown(cx)
+    }
+}
+
+/// Encrypts everything written to `W` with AES-128-CFB8, using the shared secret as both key and
+/// IV.
+pub struct EncryptedWriter<W> {
+    inner: W,
+    cipher: Encryptor,
+    // Bytes that have gone through the cipher but haven't been accepted by `inner` yet. CFB8 is
+    // stateful, so these can't be re-encrypted later.
+    pending: Vec<u8>,
+}
+
+impl<W> EncryptedWriter<W> {
+    pub fn new(inner: W, shared_secret: &[u8; 16]) -> Self {
+        Self {
+            inner,
+            cipher: encryptor(shared_secret),
+            pending: Vec::new(),
+        }
+    }
+
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    fn encrypt(&mut self, buffer: &[u8]) {
+        let start = self.pending.len();
+        self.pending.extend_from_slice(buffer);
+        for byte in &mut self.pending[start..] {
+            self.cipher
+                .encrypt_block_mut(GenericArray::from_mut_slice(std::slice::from_mut(byte)));
+        }
+    }
+}
+
+impl<W: Write> EncryptedWriter<W> {
+    fn write_pending(&mut self) -> std::io::Result<()> {
+        while !self.pending.is_empty() {
+            match self.inner.write(&self.pending) {
+                Ok(0) => return Err(std::io::ErrorKind::WriteZero.into()),
+                Ok(written) => {
+                    self.pending.drain(..written);
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<W: Write> Write for EncryptedWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.write_pending()?;
+        self.encrypt(buf);
+
+        // The bytes are ours now, so any error writing them out is reported on the next call
+        // rather than having the caller write them again
+        let _ = self.write_pending();
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.write_pending()?;
+        self.inner.flush()
+    }
+}
+
+// Reads pass straight through, so that a reader and writer can be stacked on one stream
+impl<W: Read> Read for EncryptedWriter<W> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.inner.read(buf)
+    }
+}
+
+impl<W: AsyncRead + Unpin> AsyncRead for EncryptedWriter<W> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_read(cx, buf)
+    }
+}
+
+impl<W: AsyncWrite + Unpin> EncryptedWriter<W> {
+    fn poll_pending(&mut self, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        while !self.pending.is_empty() {
+            let written = ready!(Pin::new(&mut self.inner).poll_write(cx, &self.pending))?;
+            if written == 0 {
+                return Poll::Ready(Err(std::io::ErrorKind::WriteZero.into()));
+            }
+            self.pending.drain(..written);
+        }
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<W: AsyncWrite + Unpin> AsyncWrite for EncryptedWriter<W> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        let this = self.get_mut();
+        ready!(this.poll_pending(cx))?;
+        this.encrypt(buf);
+
+        // The bytes are ours now, so any error writing them out is reported on the next call
+        let _ = this.poll_pending(cx);
+
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        let this = self.get_mut();
+        ready!(this.poll_pending(cx))?;
+        Pin::new(&mut this.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        let this = self.get_mut();
+        ready!(this.poll_pending(cx))?;
+        Pin::new(&mut this.inner).poll_shutdown(cx)
+    }
+}
@@ -0,0 +1,301 @@
+use crate::types::{Type, VarInt};
+use flate2::{Compression, read::ZlibDecoder, write::ZlibEncoder};
+use std::io::{Read, Write};
+
+/// The largest packet length the vanilla client and server will accept (3 byte `VarInt`)
+pub const MAX_FRAME_SIZE: usize = (1 << 21) - 1;
+/// The largest uncompressed packet the vanilla server will accept
+pub const MAX_UNCOMPRESSED_SIZE: usize = 1 << 23;
+
+/// A single packet as it appears on the wire: its id and its still-encoded payload.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Frame {
+    pub id: i32,
+    pub data: Vec<u8>,
+}
+
+impl Frame {
+    pub fn new(id: i32, data: Vec<u8>) -> Self {
+        Self { id, data }
+    }
+
+    pub fn encode(id: i32, packet: &impl Type) -> std::io::Result<Self> {
+        let mut data = Vec::new();
+        packet.write(&mut data)?;
+
+        Ok(Self { id, data })
+    }
+
+    /// Decodes the payload as a `T`, failing if any bytes are left over.
+    pub fn decode<T: Type>(&self) -> std::io::Result<T> {
+        let mut data = self.data.as_slice();
+        let out = T::read(&mut data)?;
+        if !data.is_empty() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!(
+                    "{} bytes left over after packet {:#04x}",
+                    data.len(),
+                    self.id
+                ),
+            ));
+        }
+
+        Ok(out)
+    }
+}
+
+#[derive(Debug)]
+pub enum FrameError {
+    Io(std::io::Error),
+    NegativeLength(i32),
+    TooLarge {
+        length: usize,
+        max: usize,
+    },
+    /// A compressed packet claimed to be smaller than the compression threshold
+    BelowThreshold {
+        length: usize,
+        threshold: usize,
+    },
+    /// A compressed packet did not decompress to the length it claimed
+    LengthMismatch {
+        expected: usize,
+        actual: usize,
+    },
+    /// A compressed frame ended before its uncompressed length could be read
+    MissingDataLength,
+    /// The frame ended before a packet id could be read
+    MissingId,
+}
+
+impl std::fmt::Display for FrameError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(e) => e.fmt(f),
+            Self::NegativeLength(length) => write!(f, "Negative frame length {length}"),
+            Self::TooLarge { length, max } => {
+                write!(f, "Frame length {length} exceeds maximum of {max}")
+            }
+            Self::BelowThreshold { length, threshold } => write!(
+                f,
+                "Compressed packet of length {length} is below the threshold of {threshold}"
+            ),
+            Self::LengthMismatch { expected, actual } => write!(
+                f,
+                "Packet decompressed to {actual} bytes, but {expected} were expected"
+            ),
+            Self::MissingDataLength => write!(f, "Compressed frame is missing its data length"),
+            Self::MissingId => write!(f, "Frame is missing a packet id"),
+        }
+    }
+}
+
+impl std::error::Error for FrameError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for FrameError {
+    fn from(value: std::io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+impl From<FrameError> for std::io::Error {
+    fn from(value: FrameError) -> Self {
+        match value {
+            FrameError::Io(e) => e,
+            other => Self::new(std::io::ErrorKind::InvalidData, other),
+        }
+    }
+}
+
+/// Reads and writes [`Frame`]s, switching to the compressed layout once a threshold is set.
+#[derive(Clone, Debug)]
+pub struct FrameCodec {
+    compression_threshold: Option<usize>,
+    max_frame_size: usize,
+    compression_level: Compression,
+}
+
+impl Default for FrameCodec {
+    fn default() -> Self {
+        Self {
+            compression_threshold: None,
+            max_frame_size: MAX_FRAME_SIZE,
+            compression_level: Compression::default(),
+        }
+    }
+}
+
+impl FrameCodec {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_max_frame_size(mut self, max_frame_size: usize) -> Self {
+        self.max_frame_size = max_frame_size;
+        self
+    }
+
+    pub fn with_compression_level(mut self, level: u32) -> Self {
+        self.compression_level = Compression::new(level);
+        self
+    }
+
+    pub fn compression_threshold(&self) -> Option<usize> {
+        self.compression_threshold
+    }
+
+    /// Applies the threshold from a Set Compression packet. A negative threshold disables
+    /// compression, as in vanilla.
+    pub fn set_compression_threshold(&mut self, threshold: i32) {
+        self.compression_threshold = usize::try_from(threshold).ok();
+    }
+
+    pub fn max_frame_size(&self) -> usize {
+        self.max_frame_size
+    }
+
+    pub fn write_frame(&self, frame: &Frame, mut writer: impl Write) -> Result<(), FrameError> {
+        let mut body = Vec::with_capacity(frame.data.len() + 5);
+        VarInt::new(frame.id).write(&mut body)?;
+        body.extend_from_slice(&frame.data);
+
+        let body = match self.compression_threshold {
+            None => body,
+            Some(threshold) if body.len() < threshold => {
+                let mut out = Vec::with_capacity(body.len() + 1);
+                VarInt::new(0).write(&mut out)?;
+                out.extend_from_slice(&body);
+                out
+            }
+            Some(_) => {
+                if body.len() > MAX_UNCOMPRESSED_SIZE {
+                    return Err(FrameError::TooLarge {
+                        length: body.len(),
+                        max: MAX_UNCOMPRESSED_SIZE,
+                    });
+                }
+
+                let mut out = Vec::new();
+                VarInt::new(body.len() as i32).write(&mut out)?;
+                let mut encoder = ZlibEncoder::new(out, self.compression_level);
+                encoder.write_all(&body)?;
+                encoder.finish()?
+            }
+        };
+
+        if body.len() > self.max_frame_size {
+            return Err(FrameError::TooLarge {
+                length: body.len(),
+                max: self.max_frame_size,
+            });
+        }
+
+        VarInt::new(body.len() as i32).write(&mut writer)?;
+        writer.write_all(&body)?;
+
+        Ok(())
+    }
+
+    pub fn read_frame(&self, mut reader: impl Read) -> Result<Frame, FrameError> {
+        let length = self.check_length(*VarInt::read(&mut reader)?)?;
+
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body)?;
+
+        self.decode_body(&body)
+    }
+
+    /// Decodes a frame from the front of `buffer` without blocking, for use with nonblocking and
+    /// async sockets.
+    ///
+    /// Returns `None` if `buffer` doesn't contain a whole frame yet, or the frame and the number of
+    /// bytes it took up.
+    pub fn decode_frame(&self, buffer: &[u8]) -> Result<Option<(Frame, usize)>, FrameError> {
+        let mut cursor = buffer;
+        let length = match VarInt::read(&mut cursor) {
+            Ok(length) => self.check_length(*length)?,
+            Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+
+        let header = buffer.len() - cursor.len();
+        if cursor.len() < length {
+            return Ok(None);
+        }
+
+        Ok(Some((
+            self.decode_body(&cursor[..length])?,
+            header + length,
+        )))
+    }
+
+    fn check_length(&self, length: i32) -> Result<usize, FrameError> {
+        let length = usize::try_from(length).map_err(|_| FrameError::NegativeLength(length))?;
+        if length > self.max_frame_size {
+            return Err(FrameError::TooLarge {
+                length,
+                max: self.max_frame_size,
+            });
+        }
+
+        Ok(length)
+    }
+
+    fn decode_body(&self, body: &[u8]) -> Result<Frame, FrameError> {
+        let decompressed;
+        let mut body = body;
+
+        if let Some(threshold) = self.compression_threshold {
+            let data_length =
+                *VarInt::read(&mut body).map_err(|_| FrameError::MissingDataLength)?;
+            let data_length = usize::try_from(data_length)
+                .map_err(|_| FrameError::NegativeLength(data_length))?;
+
+            if data_length != 0 {
+                if data_length < threshold {
+                    return Err(FrameError::BelowThreshold {
+                        length: data_length,
+                        threshold,
+                    });
+                }
+                if data_length > MAX_UNCOMPRESSED_SIZE {
+                    return Err(FrameError::TooLarge {
+                        length: data_length,
+                        max: MAX_UNCOMPRESSED_SIZE,
+                    });
+                }
+
+                // Read one past the claimed length so that overlong data is caught without
+                // decompressing all of it
+                let mut out = Vec::with_capacity(data_length);
+                ZlibDecoder::new(body)
+                    .take(data_length as u64 + 1)
+                    .read_to_end(&mut out)?;
+                if out.len() != data_length {
+                    return Err(FrameError::LengthMismatch {
+                        expected: data_length,
+                        actual: out.len(),
+                    });
+                }
+
+                decompressed = out;
+                body = &decompressed;
+            }
+        }
+
+        let id = *VarInt::read(&mut body).map_err(|_| FrameError::MissingId)?;
+
+        Ok(Frame {
+            id,
+            data: body.to_vec(),
+        })
+    }
+}
@@ -1,10 +1,18 @@
 use serde::{Deserialize, Serialize};
 
+// Lets `#[derive(Type)]` refer to `::mc_server_rs` from inside this crate
+extern crate self as mc_server_rs;
+
 pub mod data;
+pub mod encryption;
+pub mod frame;
+pub mod nbt;
+pub mod protocol;
+pub mod server;
 pub mod text_component;
 pub mod types;
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
 #[serde(untagged)]
 pub enum OneOf<L, R> {
     Left(L),
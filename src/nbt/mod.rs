@@ -0,0 +1,226 @@
+//! Network NBT with any tag at the root, which [`fastnbt`] only supports for compounds.
+//!
+//! Unlike NBT on disk, the root tag has no name: it's the tag id followed directly by the payload.
+
+pub mod snbt;
+
+use fastnbt::{ByteArray, IntArray, LongArray, Value};
+use std::{
+    collections::HashMap,
+    io::{Read, Write},
+};
+
+/// How deeply compounds and lists can be nested, as in vanilla
+pub const MAX_DEPTH: usize = 512;
+
+const END: u8 = 0x00;
+const BYTE: u8 = 0x01;
+const SHORT: u8 = 0x02;
+const INT: u8 = 0x03;
+const LONG: u8 = 0x04;
+const FLOAT: u8 = 0x05;
+const DOUBLE: u8 = 0x06;
+const BYTE_ARRAY: u8 = 0x07;
+const STRING: u8 = 0x08;
+const LIST: u8 = 0x09;
+const COMPOUND: u8 = 0x0a;
+const INT_ARRAY: u8 = 0x0b;
+const LONG_ARRAY: u8 = 0x0c;
+
+fn invalid(message: impl Into<String>) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, message.into())
+}
+
+/// Reads a nameless root tag.
+pub fn read(mut reader: impl Read) -> std::io::Result<Value> {
+    let id = read_array::<1>(&mut reader)?[0];
+    read_payload(&mut reader, id, 0)
+}
+
+/// Writes `value` as a nameless root tag.
+pub fn write(value: &Value, mut writer: impl Write) -> std::io::Result<()> {
+    writer.write_all(&[id(value)])?;
+    write_payload(value, &mut writer)
+}
+
+const fn id(value: &Value) -> u8 {
+    match value {
+        Value::Byte(_) => BYTE,
+        Value::Short(_) => SHORT,
+        Value::Int(_) => INT,
+        Value::Long(_) => LONG,
+        Value::Float(_) => FLOAT,
+        Value::Double(_) => DOUBLE,
+        Value::String(_) => STRING,
+        Value::ByteArray(_) => BYTE_ARRAY,
+        Value::IntArray(_) => INT_ARRAY,
+        Value::LongArray(_) => LONG_ARRAY,
+        Value::List(_) => LIST,
+        Value::Compound(_) => COMPOUND,
+    }
+}
+
+fn read_array<const N: usize>(mut reader: impl Read) -> std::io::Result<[u8; N]> {
+    let mut buffer = [0; N];
+    reader.read_exact(&mut buffer)?;
+    Ok(buffer)
+}
+
+fn read_length(mut reader: impl Read) -> std::io::Result<usize> {
+    let length = i32::from_be_bytes(read_array(&mut reader)?);
+    usize::try_from(length).map_err(|_| invalid(format!("Negative length {length}")))
+}
+
+fn read_string(mut reader: impl Read) -> std::io::Result<String> {
+    let length = u16::from_be_bytes(read_array(&mut reader)?);
+    let mut buffer = vec![0; length as usize];
+    reader.read_exact(&mut buffer)?;
+
+    cesu8::from_java_cesu8(&buffer)
+        .map(|string| string.into_owned())
+        .map_err(|e| invalid(e.to_string()))
+}
+
+/// Reads `length` big endian numbers of `N` bytes each, without trusting `length` for the
+/// allocation up front.
+fn read_numbers<const N: usize, T>(
+    mut reader: impl Read,
+    length: usize,
+    from_bytes: impl Fn([u8; N]) -> T,
+) -> std::io::Result<Vec<T>> {
+    let mut out = Vec::with_capacity(length.min(1024));
+    for _ in 0..length {
+        out.push(from_bytes(read_array(&mut reader)?));
+    }
+    Ok(out)
+}
+
+fn read_payload<R: Read>(reader: &mut R, id: u8, depth: usize) -> std::io::Result<Value> {
+    if depth > MAX_DEPTH {
+        return Err(invalid(format!("NBT nested deeper than {MAX_DEPTH}")));
+    }
+
+    Ok(match id {
+        BYTE => Value::Byte(i8::from_be_bytes(read_array(&mut *reader)?)),
+        SHORT => Value::Short(i16::from_be_bytes(read_array(&mut *reader)?)),
+        INT => Value::Int(i32::from_be_bytes(read_array(&mut *reader)?)),
+        LONG => Value::Long(i64::from_be_bytes(read_array(&mut *reader)?)),
+        FLOAT => Value::Float(f32::from_be_bytes(read_array(&mut *reader)?)),
+        DOUBLE => Value::Double(f64::from_be_bytes(read_array(&mut *reader)?)),
+        BYTE_ARRAY => {
+            let length = read_length(&mut *reader)?;
+            Value::ByteArray(ByteArray::new(read_numbers(
+                &mut *reader,
+                length,
+                i8::from_be_bytes,
+            )?))
+        }
+        STRING => Value::String(read_string(&mut *reader)?),
+        LIST => {
+            let element = read_array::<1>(&mut *reader)?[0];
+            let length = read_length(&mut *reader)?;
+            if element == END && length != 0 {
+                return Err(invalid("Non-empty list of end tags"));
+            }
+
+            let mut list = Vec::with_capacity(length.min(1024));
+            for _ in 0..length {
+                list.push(read_payload(reader, element, depth + 1)?);
+            }
+            Value::List(list)
+        }
+        COMPOUND => {
+            let mut compound = HashMap::new();
+            loop {
+                let id = read_array::<1>(&mut *reader)?[0];
+                if id == END {
+                    break;
+                }
+                let name = read_string(&mut *reader)?;
+                compound.insert(name, read_payload(reader, id, depth + 1)?);
+            }
+            Value::Compound(compound)
+        }
+        INT_ARRAY => {
+            let length = read_length(&mut *reader)?;
+            Value::IntArray(IntArray::new(read_numbers(
+                &mut *reader,
+                length,
+                i32::from_be_bytes,
+            )?))
+        }
+        LONG_ARRAY => {
+            let length = read_length(&mut *reader)?;
+            Value::LongArray(LongArray::new(read_numbers(
+                &mut *reader,
+                length,
+                i64::from_be_bytes,
+            )?))
+        }
+        _ => return Err(invalid(format!("Invalid tag id {id:#04x}"))),
+    })
+}
+
+fn write_length(length: usize, mut writer: impl Write) -> std::io::Result<()> {
+    let length = i32::try_from(length)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::FileTooLarge, e))?;
+    writer.write_all(&length.to_be_bytes())
+}
+
+fn write_string(string: &str, mut writer: impl Write) -> std::io::Result<()> {
+    let bytes = cesu8::to_java_cesu8(string);
+    let length = u16::try_from(bytes.len())
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::FileTooLarge, e))?;
+    writer.write_all(&length.to_be_bytes())?;
+    writer.write_all(&bytes)
+}
+
+fn write_payload<W: Write>(value: &Value, writer: &mut W) -> std::io::Result<()> {
+    match value {
+        Value::Byte(value) => writer.write_all(&value.to_be_bytes()),
+        Value::Short(value) => writer.write_all(&value.to_be_bytes()),
+        Value::Int(value) => writer.write_all(&value.to_be_bytes()),
+        Value::Long(value) => writer.write_all(&value.to_be_bytes()),
+        Value::Float(value) => writer.write_all(&value.to_be_bytes()),
+        Value::Double(value) => writer.write_all(&value.to_be_bytes()),
+        Value::String(value) => write_string(value, &mut *writer),
+        Value::ByteArray(values) => {
+            write_length(values.len(), &mut *writer)?;
+            values
+                .iter()
+                .try_for_each(|value| writer.write_all(&value.to_be_bytes()))
+        }
+        Value::IntArray(values) => {
+            write_length(values.len(), &mut *writer)?;
+            values
+                .iter()
+                .try_for_each(|value| writer.write_all(&value.to_be_bytes()))
+        }
+        Value::LongArray(values) => {
+            write_length(values.len(), &mut *writer)?;
+            values
+                .iter()
+                .try_for_each(|value| writer.write_all(&value.to_be_bytes()))
+        }
+        Value::List(values) => {
+            let element = values.first().map_or(END, id);
+            if values.iter().any(|value| id(value) != element) {
+                return Err(invalid("NBT lists can only hold one type of tag"));
+            }
+
+            writer.write_all(&[element])?;
+            write_length(values.len(), &mut *writer)?;
+            values
+                .iter()
+                .try_for_each(|value| write_payload(value, writer))
+        }
+        Value::Compound(values) => {
+            for (name, value) in values {
+                writer.write_all(&[id(value)])?;
+                write_string(name, &mut *writer)?;
+                write_payload(value, writer)?;
+            }
+            writer.write_all(&[END])
+        }
+    }
+}
@@ -0,0 +1,319 @@
+//! Reading values from tags through serde, so they can be read from SNBT.
+
+use super::{Error, parse};
+use fastnbt::Value;
+use serde::{
+    de::{
+        self, DeserializeOwned, DeserializeSeed, EnumAccess, MapAccess, SeqAccess, VariantAccess,
+        Visitor,
+        value::{BorrowedStrDeserializer, BytesDeserializer, SeqDeserializer},
+    },
+    forward_to_deserialize_any,
+};
+use std::collections::hash_map;
+
+/// Reads a value from SNBT.
+pub fn from_str<T: DeserializeOwned>(s: &str) -> Result<T, Error> {
+    let value = parse(s)?;
+    T::deserialize(Deserializer::new(&value))
+}
+
+/// Reads values from tags the way fastnbt reads them from NBT: booleans are numbers, unsigned
+/// numbers wrap around from negative ones, and arrays can be read as sequences of their elements
+/// as well as as fastnbt's array types.
+#[derive(Clone, Copy)]
+pub struct Deserializer<'de> {
+    value: &'de Value,
+}
+
+impl<'de> Deserializer<'de> {
+    pub const fn new(value: &'de Value) -> Self {
+        Self { value }
+    }
+}
+
+impl<'de> de::Deserializer<'de> for Deserializer<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.value {
+            Value::Byte(n) => visitor.visit_i8(*n),
+            Value::Short(n) => visitor.visit_i16(*n),
+            Value::Int(n) => visitor.visit_i32(*n),
+            Value::Long(n) => visitor.visit_i64(*n),
+            Value::Float(n) => visitor.visit_f32(*n),
+            Value::Double(n) => visitor.visit_f64(*n),
+            Value::String(s) => visitor.visit_borrowed_str(s),
+            Value::ByteArray(array) => visitor.visit_map(Array::new(
+                "__fastnbt_byte_array",
+                array.iter().map(|n| *n as u8).collect(),
+            )),
+            Value::IntArray(array) => visitor.visit_map(Array::new(
+                "__fastnbt_int_array",
+                array.iter().flat_map(|n| n.to_be_bytes()).collect(),
+            )),
+            Value::LongArray(array) => visitor.visit_map(Array::new(
+                "__fastnbt_long_array",
+                array.iter().flat_map(|n| n.to_be_bytes()).collect(),
+            )),
+            Value::List(values) => visitor.visit_seq(Elements(values.iter())),
+            Value::Compound(compound) => visitor.visit_map(Entries {
+                entries: compound.iter(),
+                value: None,
+            }),
+        }
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.value {
+            Value::Byte(n) => visitor.visit_bool(*n != 0),
+            Value::Short(n) => visitor.visit_bool(*n != 0),
+            Value::Int(n) => visitor.visit_bool(*n != 0),
+            Value::Long(n) => visitor.visit_bool(*n != 0),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_u8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.value {
+            Value::Byte(n) => visitor.visit_u8(*n as u8),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_u16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.value {
+            Value::Short(n) => visitor.visit_u16(*n as u16),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_u32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.value {
+            Value::Int(n) => visitor.visit_u32(*n as u32),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_u64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.value {
+            Value::Long(n) => visitor.visit_u64(*n as u64),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.value {
+            Value::ByteArray(array) => {
+                visitor.visit_seq(SeqDeserializer::new(array.iter().copied()))
+            }
+            Value::IntArray(array) => {
+                visitor.visit_seq(SeqDeserializer::new(array.iter().copied()))
+            }
+            Value::LongArray(array) => {
+                visitor.visit_seq(SeqDeserializer::new(array.iter().copied()))
+            }
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        match self.value {
+            Value::String(variant) => visitor.visit_enum(BorrowedStrDeserializer::new(variant)),
+            Value::Compound(compound) if compound.len() == 1 => {
+                let Some((variant, value)) = compound.iter().next() else {
+                    unreachable!()
+                };
+                visitor.visit_enum(Variant { variant, value })
+            }
+            _ => Err(Error::Data(
+                "Expected a string or a compound with a single entry".to_string(),
+            )),
+        }
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        i8 i16 i32 i64 i128 u128 f32 f64 char str string bytes byte_buf map struct identifier
+    }
+}
+
+struct Elements<'de>(std::slice::Iter<'de, Value>);
+
+impl<'de> SeqAccess<'de> for Elements<'de> {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Error> {
+        self.0
+            .next()
+            .map(|value| seed.deserialize(Deserializer::new(value)))
+            .transpose()
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.0.len())
+    }
+}
+
+struct Entries<'de> {
+    entries: hash_map::Iter<'de, String, Value>,
+    /// The value of the entry whose key was just read
+    value: Option<&'de Value>,
+}
+
+impl<'de> MapAccess<'de> for Entries<'de> {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Error> {
+        let Some((key, value)) = self.entries.next() else {
+            return Ok(None);
+        };
+        self.value = Some(value);
+        seed.deserialize(BorrowedStrDeserializer::new(key))
+            .map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+        let value = self
+            .value
+            .take()
+            .ok_or_else(|| Error::Data("Map value without a key".to_string()))?;
+        seed.deserialize(Deserializer::new(value))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.entries.len())
+    }
+}
+
+/// An array as fastnbt reads it, which is a map with a single entry named for its kind holding
+/// the elements as big-endian bytes.
+struct Array {
+    kind: Option<&'static str>,
+    data: Vec<u8>,
+}
+
+impl Array {
+    const fn new(kind: &'static str, data: Vec<u8>) -> Self {
+        Self {
+            kind: Some(kind),
+            data,
+        }
+    }
+}
+
+impl<'de> MapAccess<'de> for Array {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Error> {
+        self.kind
+            .take()
+            .map(|kind| seed.deserialize(BorrowedStrDeserializer::new(kind)))
+            .transpose()
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+        seed.deserialize(BytesDeserializer::new(&self.data))
+    }
+}
+
+/// A variant holding data, which is saved as a compound of its name to the data.
+struct Variant<'de> {
+    variant: &'de str,
+    value: &'de Value,
+}
+
+impl<'de> EnumAccess<'de> for Variant<'de> {
+    type Error = Error;
+    type Variant = Deserializer<'de>;
+
+    fn variant_seed<T: DeserializeSeed<'de>>(
+        self,
+        seed: T,
+    ) -> Result<(T::Value, Deserializer<'de>), Error> {
+        let variant = seed.deserialize(BorrowedStrDeserializer::<Error>::new(self.variant))?;
+        Ok((variant, Deserializer::new(self.value)))
+    }
+}
+
+impl<'de> VariantAccess<'de> for Deserializer<'de> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, Error> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value, Error> {
+        de::Deserializer::deserialize_seq(self, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        de::Deserializer::deserialize_map(self, visitor)
+    }
+}
@@ -0,0 +1,572 @@
+//! Stringified NBT, as written in commands: `{Name: "Steve", Health: 20.0f, Tags: [a, b]}`.
+//!
+//! Tags are read as 1.21.5 reads them, and written the way vanilla writes them, either all on one
+//! line or indented. Anything that can be saved as NBT can also be written and read as SNBT
+//! through serde.
+
+mod de;
+mod names;
+mod print;
+mod ser;
+
+pub use de::{Deserializer, from_str};
+pub use print::{print, print_pretty};
+pub use ser::{Serializer, to_string, to_string_pretty};
+
+use super::MAX_DEPTH;
+use fastnbt::{ByteArray, IntArray, LongArray, Value};
+use std::{collections::HashMap, fmt::Display};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnbtError {
+    pub message: &'static str,
+    /// Byte offset into the input
+    pub position: usize,
+}
+
+impl std::fmt::Display for SnbtError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} at position {}", self.message, self.position)
+    }
+}
+
+impl std::error::Error for SnbtError {}
+
+/// Why a value couldn't be written or read as SNBT through serde.
+#[derive(Debug)]
+pub enum Error {
+    /// The SNBT itself is invalid
+    Syntax(SnbtError),
+    /// The value has no NBT shape, or the tag doesn't have the value's
+    Data(String),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Syntax(error) => error.fmt(f),
+            Self::Data(message) => f.write_str(message),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<SnbtError> for Error {
+    fn from(error: SnbtError) -> Self {
+        Self::Syntax(error)
+    }
+}
+
+impl serde::ser::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Self::Data(msg.to_string())
+    }
+}
+
+impl serde::de::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Self::Data(msg.to_string())
+    }
+}
+
+/// Parses a whole string as a single tag.
+pub fn parse(s: &str) -> Result<Value, SnbtError> {
+    let mut parser = Parser::new(s);
+    let value = parser.read_value(0)?;
+    parser.skip_whitespace();
+    if parser.position != s.len() {
+        return Err(parser.error("Trailing data after tag"));
+    }
+
+    Ok(value)
+}
+
+/// Parses the compound at the start of `s`, returning it along with how many bytes it took up.
+pub(crate) fn parse_compound_prefix(s: &str) -> Result<(HashMap<String, Value>, usize), SnbtError> {
+    let mut parser = Parser::new(s);
+    let compound = parser.read_compound(0)?;
+    Ok((compound, parser.position))
+}
+
+const fn is_unquoted(c: char) -> bool {
+    matches!(c, '0'..='9' | 'A'..='Z' | 'a'..='z' | '_' | '-' | '.' | '+')
+}
+
+struct Parser<'a> {
+    s: &'a str,
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    const fn new(s: &'a str) -> Self {
+        Self { s, position: 0 }
+    }
+
+    const fn error(&self, message: &'static str) -> SnbtError {
+        SnbtError {
+            message,
+            position: self.position,
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.s[self.position..].chars().next()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.position += c.len_utf8();
+        Some(c)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.next();
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), SnbtError> {
+        self.skip_whitespace();
+        if self.peek() == Some(c) {
+            self.next();
+            Ok(())
+        } else {
+            Err(self.error(match c {
+                ':' => "Expected ':'",
+                ']' => "Expected ']'",
+                '}' => "Expected '}'",
+                ')' => "Expected ')'",
+                _ => "Unexpected character",
+            }))
+        }
+    }
+
+    /// Skips a `,` between elements, returning whether there was one.
+    fn separator(&mut self) -> bool {
+        self.skip_whitespace();
+        if self.peek() == Some(',') {
+            self.next();
+            self.skip_whitespace();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn read_unquoted(&mut self) -> &'a str {
+        let start = self.position;
+        while self.peek().is_some_and(is_unquoted) {
+            self.next();
+        }
+        &self.s[start..self.position]
+    }
+
+    fn read_quoted(&mut self) -> Result<String, SnbtError> {
+        let quote = self.next().ok_or_else(|| self.error("Expected string"))?;
+        let mut out = String::new();
+        loop {
+            match self.next() {
+                Some('\\') => out.push(self.read_escape()?),
+                Some(c) if c == quote => return Ok(out),
+                Some(c) => out.push(c),
+                None => return Err(self.error("Unclosed quoted string")),
+            }
+        }
+    }
+
+    /// Reads what follows a backslash in a quoted string.
+    fn read_escape(&mut self) -> Result<char, SnbtError> {
+        let start = self.position - 1;
+        let invalid = SnbtError {
+            message: "Invalid escape sequence",
+            position: start,
+        };
+        let hex = |parser: &mut Self, digits: usize| {
+            let end = parser.position + digits;
+            let code = parser
+                .s
+                .get(parser.position..end)
+                .filter(|code| code.chars().all(|c| c.is_ascii_hexdigit()))
+                .and_then(|code| u32::from_str_radix(code, 16).ok())
+                .and_then(char::from_u32)
+                .ok_or(invalid.clone())?;
+            parser.position = end;
+            Ok(code)
+        };
+
+        match self.next() {
+            Some(c @ ('\\' | '\'' | '"')) => Ok(c),
+            Some('b') => Ok('\u{8}'),
+            Some('s') => Ok(' '),
+            Some('t') => Ok('\t'),
+            Some('n') => Ok('\n'),
+            Some('f') => Ok('\u{c}'),
+            Some('r') => Ok('\r'),
+            Some('x') => hex(self, 2),
+            Some('u') => hex(self, 4),
+            Some('U') => hex(self, 8),
+            Some('N') if self.peek() == Some('{') => {
+                let name = &self.s[self.position + 1..];
+                let name = &name[..name.find('}').ok_or(invalid.clone())?];
+                self.position += name.len() + 2;
+                names::char_named(name).ok_or(SnbtError {
+                    message: "Unknown character name",
+                    position: start,
+                })
+            }
+            _ => Err(invalid),
+        }
+    }
+
+    fn read_key(&mut self) -> Result<String, SnbtError> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('"' | '\'') => self.read_quoted(),
+            _ => match self.read_unquoted() {
+                "" => Err(self.error("Expected key")),
+                key => Ok(key.to_string()),
+            },
+        }
+    }
+
+    fn read_value(&mut self, depth: usize) -> Result<Value, SnbtError> {
+        if depth > MAX_DEPTH {
+            return Err(self.error("Tag nested too deeply"));
+        }
+
+        self.skip_whitespace();
+        match self.peek() {
+            Some('{') => Ok(Value::Compound(self.read_compound(depth)?)),
+            Some('[') => self.read_list_or_array(depth),
+            Some('"' | '\'') => Ok(Value::String(self.read_quoted()?)),
+            _ => {
+                let start = self.position;
+                match self.read_unquoted() {
+                    "" => Err(SnbtError {
+                        message: "Expected value",
+                        position: start,
+                    }),
+                    operation @ ("bool" | "uuid") if self.peek() == Some('(') => {
+                        self.read_operation(operation, start, depth)
+                    }
+                    s => typed(s).map_err(|message| SnbtError {
+                        message,
+                        position: start,
+                    }),
+                }
+            }
+        }
+    }
+
+    /// Reads the argument of `bool(..)` or `uuid(..)`, which stand for the tag they work out.
+    fn read_operation(
+        &mut self,
+        operation: &str,
+        start: usize,
+        depth: usize,
+    ) -> Result<Value, SnbtError> {
+        self.expect('(')?;
+        let argument = self.read_value(depth + 1)?;
+        self.expect(')')?;
+
+        let value = match (operation, argument) {
+            ("bool", Value::Byte(n)) => Some(n != 0),
+            ("bool", Value::Short(n)) => Some(n != 0),
+            ("bool", Value::Int(n)) => Some(n != 0),
+            ("bool", Value::Long(n)) => Some(n != 0),
+            ("bool", Value::Float(n)) => Some(n != 0.0),
+            ("bool", Value::Double(n)) => Some(n != 0.0),
+            ("uuid", Value::String(uuid)) => {
+                return Uuid::parse_str(&uuid)
+                    .map(|uuid| {
+                        let bits = uuid.as_u128();
+                        Value::IntArray(IntArray::new(
+                            (0..4).map(|i| (bits >> (96 - 32 * i)) as i32).collect(),
+                        ))
+                    })
+                    .map_err(|_| SnbtError {
+                        message: "Invalid UUID",
+                        position: start,
+                    });
+            }
+            _ => None,
+        };
+
+        value
+            .map(|value| Value::Byte(value as i8))
+            .ok_or(SnbtError {
+                message: "Invalid argument",
+                position: start,
+            })
+    }
+
+    fn read_compound(&mut self, depth: usize) -> Result<HashMap<String, Value>, SnbtError> {
+        self.expect('{')?;
+        self.skip_whitespace();
+
+        let mut compound = HashMap::new();
+        while self.peek() != Some('}') {
+            let key = self.read_key()?;
+            self.expect(':')?;
+            compound.insert(key, self.read_value(depth + 1)?);
+            if !self.separator() {
+                break;
+            }
+        }
+        self.expect('}')?;
+
+        Ok(compound)
+    }
+
+    fn read_list_or_array(&mut self, depth: usize) -> Result<Value, SnbtError> {
+        self.expect('[')?;
+        let mut rest = self.s[self.position..].chars();
+        let kind = rest.next().filter(|_| rest.next() == Some(';'));
+        let array = match kind {
+            Some(kind @ ('B' | 'I' | 'L')) => {
+                self.position += 2;
+                Some(kind)
+            }
+            Some(_) => return Err(self.error("Invalid array type")),
+            None => None,
+        };
+        self.skip_whitespace();
+
+        let mut values = vec![];
+        while self.peek() != Some(']') {
+            let start = self.position;
+            let value = match array {
+                Some(kind) => self.read_array_element(kind, depth)?,
+                None => Some(self.read_value(depth + 1)?),
+            };
+            let compatible = match (array, &value) {
+                (Some(_), value) => value.is_some(),
+                (None, Some(value)) => values.first().is_none_or(|first| same_type(first, value)),
+                (None, None) => false,
+            };
+            let Some(value) = value.filter(|_| compatible) else {
+                return Err(SnbtError {
+                    message: "Mismatched element type",
+                    position: start,
+                });
+            };
+            values.push(value);
+            if !self.separator() {
+                break;
+            }
+        }
+        self.expect(']')?;
+
+        let values = values.into_iter();
+        Ok(match array {
+            Some('B') => Value::ByteArray(ByteArray::new(
+                values
+                    .filter_map(|value| match value {
+                        Value::Byte(value) => Some(value),
+                        _ => None,
+                    })
+                    .collect(),
+            )),
+            Some('I') => Value::IntArray(IntArray::new(
+                values
+                    .filter_map(|value| match value {
+                        Value::Int(value) => Some(value),
+                        _ => None,
+                    })
+                    .collect(),
+            )),
+            Some(_) => Value::LongArray(LongArray::new(
+                values
+                    .filter_map(|value| match value {
+                        Value::Long(value) => Some(value),
+                        _ => None,
+                    })
+                    .collect(),
+            )),
+            None => Value::List(values.collect()),
+        })
+    }
+
+    /// Reads an element of an array of `kind`, which is an integer of that type. Integers without
+    /// a type are taken to be of the array's type, and booleans go in byte arrays.
+    fn read_array_element(&mut self, kind: char, depth: usize) -> Result<Option<Value>, SnbtError> {
+        let kind = kind.to_ascii_lowercase();
+        let start = self.position;
+        let s = self.read_unquoted();
+        if s.is_empty() || self.peek() == Some('(') {
+            self.position = start;
+            let value = self.read_value(depth + 1)?;
+            return Ok(matches!((kind, &value), ('b', Value::Byte(_))).then_some(value));
+        }
+
+        Ok(match Integer::parse(s) {
+            Some(integer) if integer.kind.is_none_or(|suffix| suffix == kind) => {
+                integer.value(kind)
+            }
+            Some(_) => None,
+            None => match (kind, s) {
+                ('b', "true") => Some(Value::Byte(1)),
+                ('b', "false") => Some(Value::Byte(0)),
+                _ => None,
+            },
+        })
+    }
+}
+
+fn same_type(a: &Value, b: &Value) -> bool {
+    std::mem::discriminant(a) == std::mem::discriminant(b)
+}
+
+/// Whether `s` is digits of `radix` with underscores between them, as numbers can be written
+/// since 1.21.5.
+fn is_digits(s: &str, radix: u32) -> bool {
+    !s.is_empty()
+        && !s.starts_with('_')
+        && !s.ends_with('_')
+        && s.chars().all(|c| c == '_' || c.is_digit(radix))
+}
+
+/// An integer as it's written: optionally signed, in decimal, hex (`0x`) or binary (`0b`), then
+/// optionally whether it's signed (`s`) or unsigned (`u`) and its type (`b`, `s`, `i` or `l`).
+struct Integer {
+    negative: bool,
+    magnitude: u64,
+    /// Decimal integers are signed unless they say otherwise, and others unsigned
+    signed: bool,
+    /// The type suffix, in lowercase
+    kind: Option<char>,
+}
+
+impl Integer {
+    fn parse(s: &str) -> Option<Self> {
+        let negative = s.starts_with('-');
+        let s = s.strip_prefix(['-', '+']).unwrap_or(s);
+        let (radix, s) = match s.get(..2).map(str::to_ascii_lowercase).as_deref() {
+            Some("0x") => (16, &s[2..]),
+            Some("0b") if s[2..].starts_with(['0', '1']) => (2, &s[2..]),
+            _ => (10, s),
+        };
+
+        let end = s
+            .find(|c: char| c != '_' && !c.is_digit(radix))
+            .unwrap_or(s.len());
+        let (digits, suffix) = s.split_at(end);
+        if !is_digits(digits, radix) {
+            return None;
+        }
+        // Decimal integers don't have leading zeros
+        if radix == 10 && digits.starts_with('0') && digits != "0" {
+            return None;
+        }
+
+        let suffix = suffix.to_ascii_lowercase();
+        let (signed, kind) = match suffix.as_bytes() {
+            [] => (None, None),
+            [kind @ (b'b' | b's' | b'i' | b'l')] => (None, Some(*kind as char)),
+            [sign @ (b's' | b'u'), kind @ (b'b' | b's' | b'i' | b'l')] => {
+                (Some(*sign == b's'), Some(*kind as char))
+            }
+            _ => return None,
+        };
+
+        Some(Self {
+            negative,
+            magnitude: u64::from_str_radix(&digits.replace('_', ""), radix).ok()?,
+            signed: signed.unwrap_or(radix == 10),
+            kind,
+        })
+    }
+
+    /// The integer as a tag of type `kind`, if it's in range. Unsigned integers take up the whole
+    /// range of the type, and wrap around to negative past its signed maximum.
+    fn value(&self, kind: char) -> Option<Value> {
+        let bits = match kind {
+            'b' => 8,
+            's' => 16,
+            'i' => 32,
+            _ => 64,
+        };
+        let value = if self.signed {
+            let value = if self.negative {
+                -i128::from(self.magnitude)
+            } else {
+                i128::from(self.magnitude)
+            };
+            let max = (1_i128 << (bits - 1)) - 1;
+            (-max - 1..=max).contains(&value).then_some(value as i64)?
+        } else {
+            let max = u64::MAX >> (64 - bits);
+            (!self.negative && self.magnitude <= max).then_some(self.magnitude as i64)?
+        };
+
+        Some(match kind {
+            'b' => Value::Byte(value as i8),
+            's' => Value::Short(value as i16),
+            'i' => Value::Int(value as i32),
+            _ => Value::Long(value),
+        })
+    }
+}
+
+/// An optionally signed decimal with an optional exponent. The dot is only optional for numbers
+/// with a type suffix.
+fn is_decimal(s: &str, needs_dot: bool) -> bool {
+    let s = s.strip_prefix(['-', '+']).unwrap_or(s);
+    let (mantissa, exponent) = match s.split_once(['e', 'E']) {
+        Some((mantissa, exponent)) => (mantissa, Some(exponent)),
+        None => (s, None),
+    };
+    let digits = |s: &str| s.is_empty() || is_digits(s, 10);
+
+    let mantissa = match mantissa.split_once('.') {
+        Some((whole, fraction)) => {
+            digits(whole) && digits(fraction) && !(whole.is_empty() && fraction.is_empty())
+        }
+        None => !needs_dot && !mantissa.is_empty() && digits(mantissa),
+    };
+    let exponent = exponent.is_none_or(|exponent| {
+        let exponent = exponent.strip_prefix(['-', '+']).unwrap_or(exponent);
+        !exponent.is_empty() && digits(exponent)
+    });
+
+    mantissa && exponent
+}
+
+/// Works out the type of an unquoted value the way vanilla does. Anything that starts like a number
+/// has to be a number in range, and anything else that isn't a boolean is a string.
+fn typed(s: &str) -> Result<Value, &'static str> {
+    let split = s.len() - s.chars().last().map_or(0, char::len_utf8);
+    let (body, suffix) = s.split_at(split);
+    let suffix = suffix.to_ascii_lowercase();
+    let decimal = |s: &str| s.replace('_', "");
+
+    let number = match Integer::parse(s) {
+        Some(integer) => Some(
+            integer
+                .value(integer.kind.unwrap_or('i'))
+                .ok_or("Number out of range")?,
+        ),
+        None => match suffix.as_str() {
+            "f" if is_decimal(body, false) => decimal(body).parse().ok().map(Value::Float),
+            "d" if is_decimal(body, false) => decimal(body).parse().ok().map(Value::Double),
+            _ if is_decimal(s, true) => decimal(s).parse().ok().map(Value::Double),
+            _ => None,
+        },
+    };
+
+    match (number, s) {
+        (Some(Value::Float(n)), _) if !n.is_finite() => Err("Number out of range"),
+        (Some(Value::Double(n)), _) if !n.is_finite() => Err("Number out of range"),
+        (Some(number), _) => Ok(number),
+        (None, "true") => Ok(Value::Byte(1)),
+        (None, "false") => Ok(Value::Byte(0)),
+        (None, s)
+            if s.starts_with(|c: char| c.is_ascii_digit() || matches!(c, '+' | '-' | '.')) =>
+        {
+            Err("Invalid number")
+        }
+        (None, s) => Ok(Value::String(s.to_string())),
+    }
+}
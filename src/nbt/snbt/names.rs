@@ -0,0 +1,6 @@
+//! The names `\N{..}` escapes can use, which are those of the Unicode Character Database.
+
+/// The character with the given name, ignoring case and surrounding whitespace as vanilla does.
+pub(super) fn char_named(name: &str) -> Option<char> {
+    unicode_names2::character(&name.trim().to_ascii_uppercase())
+}
@@ -0,0 +1,171 @@
+//! Writing tags as SNBT, in the forms vanilla writes them.
+
+use super::is_unquoted;
+use fastnbt::Value;
+use std::{collections::HashMap, fmt::Write};
+
+const INDENT: usize = 4;
+
+/// Writes `value` on one line with nothing between elements: `{Health:20.0f,Tags:["a","b"]}`.
+/// Keys are sorted, so equal tags are always written the same way.
+pub fn print(value: &Value) -> String {
+    let mut out = String::new();
+    write_value(&mut out, value, None);
+    out
+}
+
+/// Writes `value` with compounds and lists of anything but numbers and strings spread over
+/// several lines, indented by four spaces.
+pub fn print_pretty(value: &Value) -> String {
+    let mut out = String::new();
+    write_value(&mut out, value, Some(0));
+    out
+}
+
+fn write_value(out: &mut String, value: &Value, indent: Option<usize>) {
+    // Writing to a string can't fail
+    let _ = match value {
+        Value::Byte(n) => write!(out, "{n}b"),
+        Value::Short(n) => write!(out, "{n}s"),
+        Value::Int(n) => write!(out, "{n}"),
+        Value::Long(n) => write!(out, "{n}L"),
+        Value::Float(n) => write!(out, "{n:?}f"),
+        Value::Double(n) => write!(out, "{n:?}d"),
+        Value::String(s) => {
+            write_quoted(out, s);
+            Ok(())
+        }
+        Value::ByteArray(array) => {
+            write_array(out, 'B', array.iter().map(|n| format!("{n}b")), indent)
+        }
+        Value::IntArray(array) => write_array(out, 'I', array.iter().map(i32::to_string), indent),
+        Value::LongArray(array) => {
+            write_array(out, 'L', array.iter().map(|n| format!("{n}L")), indent)
+        }
+        Value::List(values) => {
+            write_list(out, values, indent);
+            Ok(())
+        }
+        Value::Compound(compound) => {
+            write_compound(out, compound, indent);
+            Ok(())
+        }
+    };
+}
+
+fn write_array(
+    out: &mut String,
+    kind: char,
+    elements: impl Iterator<Item = String>,
+    indent: Option<usize>,
+) -> std::fmt::Result {
+    let separator = if indent.is_some() { ", " } else { "," };
+    let elements: Vec<_> = elements.collect();
+    match (elements.is_empty(), indent) {
+        (true, _) => write!(out, "[{kind};]"),
+        (false, Some(_)) => write!(out, "[{kind}; {}]", elements.join(separator)),
+        (false, None) => write!(out, "[{kind};{}]", elements.join(separator)),
+    }
+}
+
+fn write_list(out: &mut String, values: &[Value], indent: Option<usize>) {
+    let nested = values
+        .iter()
+        .any(|value| matches!(value, Value::List(_) | Value::Compound(_)));
+    match indent {
+        Some(indent) if nested => {
+            out.push('[');
+            for (i, value) in values.iter().enumerate() {
+                out.push_str(if i == 0 { "\n" } else { ",\n" });
+                push_indent(out, indent + INDENT);
+                write_value(out, value, Some(indent + INDENT));
+            }
+            out.push('\n');
+            push_indent(out, indent);
+            out.push(']');
+        }
+        _ => {
+            out.push('[');
+            for (i, value) in values.iter().enumerate() {
+                if i > 0 {
+                    out.push_str(if indent.is_some() { ", " } else { "," });
+                }
+                write_value(out, value, indent);
+            }
+            out.push(']');
+        }
+    }
+}
+
+fn write_compound(out: &mut String, compound: &HashMap<String, Value>, indent: Option<usize>) {
+    let mut entries: Vec<_> = compound.iter().collect();
+    entries.sort_unstable_by_key(|(key, _)| *key);
+
+    out.push('{');
+    for (i, (key, value)) in entries.iter().enumerate() {
+        match indent {
+            Some(indent) => {
+                out.push_str(if i == 0 { "\n" } else { ",\n" });
+                push_indent(out, indent + INDENT);
+                write_key(out, key);
+                out.push_str(": ");
+                write_value(out, value, Some(indent + INDENT));
+            }
+            None => {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_key(out, key);
+                out.push(':');
+                write_value(out, value, None);
+            }
+        }
+    }
+    if let Some(indent) = indent.filter(|_| !entries.is_empty()) {
+        out.push('\n');
+        push_indent(out, indent);
+    }
+    out.push('}');
+}
+
+fn push_indent(out: &mut String, indent: usize) {
+    out.extend(std::iter::repeat_n(' ', indent));
+}
+
+fn write_key(out: &mut String, key: &str) {
+    if !key.is_empty() && key.chars().all(is_unquoted) {
+        out.push_str(key);
+    } else {
+        write_quoted(out, key);
+    }
+}
+
+/// Quotes `s` with double quotes, or with single quotes if that saves escaping any.
+fn write_quoted(out: &mut String, s: &str) {
+    let quote = if s.contains('"') && !s.contains('\'') {
+        '\''
+    } else {
+        '"'
+    };
+
+    out.push(quote);
+    for c in s.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '\u{8}' => out.push_str("\\b"),
+            '\t' => out.push_str("\\t"),
+            '\n' => out.push_str("\\n"),
+            '\u{c}' => out.push_str("\\f"),
+            '\r' => out.push_str("\\r"),
+            c if c == quote => {
+                out.push('\\');
+                out.push(c);
+            }
+            c if c.is_ascii_control() => {
+                let _ = write!(out, "\\x{:02x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push(quote);
+}
@@ -0,0 +1,393 @@
+//! Saving values as tags through serde, so they can be written as SNBT.
+
+use super::{Error, print, print_pretty};
+use fastnbt::{ByteArray, IntArray, LongArray, Value};
+use serde::{
+    Serialize,
+    ser::{
+        self, SerializeMap, SerializeSeq, SerializeStruct, SerializeStructVariant, SerializeTuple,
+        SerializeTupleStruct, SerializeTupleVariant,
+    },
+};
+use std::collections::HashMap;
+
+/// Writes `value` as SNBT on one line, as [`print`] does.
+pub fn to_string<T: ?Sized + Serialize>(value: &T) -> Result<String, Error> {
+    Ok(print(&to_value(value)?))
+}
+
+/// Writes `value` as SNBT over several lines, as [`print_pretty`] does.
+pub fn to_string_pretty<T: ?Sized + Serialize>(value: &T) -> Result<String, Error> {
+    Ok(print_pretty(&to_value(value)?))
+}
+
+fn to_value<T: ?Sized + Serialize>(value: &T) -> Result<Value, Error> {
+    value
+        .serialize(Serializer)?
+        .ok_or_else(|| Error::Data("Can't write `None` on its own".to_string()))
+}
+
+/// Saves values as the tags fastnbt saves them as. Unlike [`fastnbt::to_value`], fields that are
+/// `None` are left out rather than failing, which is what [`Self::Ok`] being `None` means.
+pub struct Serializer;
+
+/// The tag of a compound with a single entry, which is how variants with data are saved.
+fn variant(variant: &str, value: Value) -> Value {
+    Value::Compound(HashMap::from([(variant.to_string(), value)]))
+}
+
+/// An array saved by fastnbt, which is a struct with a single field named for its kind holding
+/// the elements as big-endian bytes.
+fn array(kind: &str, data: &[i8]) -> Option<Value> {
+    let bytes: Vec<u8> = data.iter().map(|byte| *byte as u8).collect();
+    Some(match kind {
+        "__fastnbt_byte_array" => Value::ByteArray(ByteArray::new(data.to_vec())),
+        "__fastnbt_int_array" => Value::IntArray(IntArray::new(
+            bytes
+                .chunks_exact(4)
+                .map(|int| i32::from_be_bytes(int.try_into().unwrap_or_default()))
+                .collect(),
+        )),
+        "__fastnbt_long_array" => Value::LongArray(LongArray::new(
+            bytes
+                .chunks_exact(8)
+                .map(|long| i64::from_be_bytes(long.try_into().unwrap_or_default()))
+                .collect(),
+        )),
+        _ => return None,
+    })
+}
+
+impl ser::Serializer for Serializer {
+    type Ok = Option<Value>;
+    type Error = Error;
+    type SerializeSeq = Collect;
+    type SerializeTuple = Collect;
+    type SerializeTupleStruct = Collect;
+    type SerializeTupleVariant = Variant;
+    type SerializeMap = Collect;
+    type SerializeStruct = Collect;
+    type SerializeStructVariant = Variant;
+
+    fn serialize_bool(self, v: bool) -> Result<Option<Value>, Error> {
+        Ok(Some(Value::Byte(v as i8)))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Option<Value>, Error> {
+        Ok(Some(Value::Byte(v)))
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Option<Value>, Error> {
+        Ok(Some(Value::Short(v)))
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Option<Value>, Error> {
+        Ok(Some(Value::Int(v)))
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Option<Value>, Error> {
+        Ok(Some(Value::Long(v)))
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Option<Value>, Error> {
+        self.serialize_i8(v as i8)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Option<Value>, Error> {
+        self.serialize_i16(v as i16)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Option<Value>, Error> {
+        self.serialize_i32(v as i32)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Option<Value>, Error> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Option<Value>, Error> {
+        Ok(Some(Value::Float(v)))
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Option<Value>, Error> {
+        Ok(Some(Value::Double(v)))
+    }
+
+    fn serialize_char(self, v: char) -> Result<Option<Value>, Error> {
+        self.serialize_str(v.encode_utf8(&mut [0; 4]))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Option<Value>, Error> {
+        Ok(Some(Value::String(v.to_string())))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Option<Value>, Error> {
+        Ok(Some(Value::ByteArray(ByteArray::new(
+            v.iter().map(|byte| *byte as i8).collect(),
+        ))))
+    }
+
+    fn serialize_none(self) -> Result<Option<Value>, Error> {
+        Ok(None)
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<Option<Value>, Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Option<Value>, Error> {
+        Ok(Some(Value::Compound(HashMap::new())))
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Option<Value>, Error> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Option<Value>, Error> {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Option<Value>, Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        name: &'static str,
+        value: &T,
+    ) -> Result<Option<Value>, Error> {
+        Ok(value.serialize(self)?.map(|value| variant(name, value)))
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Collect, Error> {
+        Ok(Collect::new())
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Collect, Error> {
+        Ok(Collect::new())
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, _len: usize) -> Result<Collect, Error> {
+        Ok(Collect::new())
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Variant, Error> {
+        Ok(Variant {
+            variant,
+            inner: Collect::new(),
+        })
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Collect, Error> {
+        Ok(Collect::new())
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Collect, Error> {
+        Ok(Collect::new())
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Variant, Error> {
+        Ok(Variant {
+            variant,
+            inner: Collect::new(),
+        })
+    }
+}
+
+/// The tags of a list's elements, or of a compound's entries.
+pub struct Collect {
+    list: Vec<Value>,
+    compound: HashMap<String, Value>,
+    key: Option<String>,
+}
+
+impl Collect {
+    fn new() -> Self {
+        Self {
+            list: vec![],
+            compound: HashMap::new(),
+            key: None,
+        }
+    }
+
+    fn push<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        let value = value
+            .serialize(Serializer)?
+            .ok_or_else(|| Error::Data("Lists can't hold `None`".to_string()))?;
+        if self
+            .list
+            .first()
+            .is_some_and(|first| std::mem::discriminant(first) != std::mem::discriminant(&value))
+        {
+            return Err(Error::Data(
+                "Lists can only hold one type of tag".to_string(),
+            ));
+        }
+
+        self.list.push(value);
+        Ok(())
+    }
+
+    fn insert<T: ?Sized + Serialize>(&mut self, key: String, value: &T) -> Result<(), Error> {
+        if let Some(value) = value.serialize(Serializer)? {
+            self.compound.insert(key, value);
+        }
+        Ok(())
+    }
+
+    fn list(self) -> Value {
+        Value::List(self.list)
+    }
+
+    fn compound(self) -> Value {
+        Value::Compound(self.compound)
+    }
+}
+
+impl SerializeSeq for Collect {
+    type Ok = Option<Value>;
+    type Error = Error;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Option<Value>, Error> {
+        Ok(Some(self.list()))
+    }
+}
+
+impl SerializeTuple for Collect {
+    type Ok = Option<Value>;
+    type Error = Error;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Option<Value>, Error> {
+        Ok(Some(self.list()))
+    }
+}
+
+impl SerializeTupleStruct for Collect {
+    type Ok = Option<Value>;
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Option<Value>, Error> {
+        Ok(Some(self.list()))
+    }
+}
+
+impl SerializeMap for Collect {
+    type Ok = Option<Value>;
+    type Error = Error;
+
+    fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> Result<(), Error> {
+        self.key = Some(match key.serialize(Serializer)? {
+            Some(Value::String(key)) => key,
+            Some(key) if key.as_i64().is_some() => key.as_i64().unwrap_or_default().to_string(),
+            _ => return Err(Error::Data("Compound keys must be strings".to_string())),
+        });
+        Ok(())
+    }
+
+    fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        let key = self
+            .key
+            .take()
+            .ok_or_else(|| Error::Data("Map value without a key".to_string()))?;
+        self.insert(key, value)
+    }
+
+    fn end(self) -> Result<Option<Value>, Error> {
+        Ok(Some(self.compound()))
+    }
+}
+
+impl SerializeStruct for Collect {
+    type Ok = Option<Value>;
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.insert(key.to_string(), value)
+    }
+
+    fn end(self) -> Result<Option<Value>, Error> {
+        if self.compound.len() == 1
+            && let Some((kind, Value::ByteArray(data))) = self.compound.iter().next()
+            && let Some(array) = array(kind, data)
+        {
+            return Ok(Some(array));
+        }
+
+        Ok(Some(self.compound()))
+    }
+}
+
+/// A variant holding fields, saved as a compound of its name to them.
+pub struct Variant {
+    variant: &'static str,
+    inner: Collect,
+}
+
+impl SerializeTupleVariant for Variant {
+    type Ok = Option<Value>;
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        self.inner.push(value)
+    }
+
+    fn end(self) -> Result<Option<Value>, Error> {
+        Ok(Some(variant(self.variant, self.inner.list())))
+    }
+}
+
+impl SerializeStructVariant for Variant {
+    type Ok = Option<Value>;
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.inner.insert(key.to_string(), value)
+    }
+
+    fn end(self) -> Result<Option<Value>, Error> {
+        Ok(Some(variant(self.variant, self.inner.compound())))
+    }
+}
@@ -0,0 +1,180 @@
+use crate::{
+    data::Identifier,
+    protocol::{VERSION_NAME, packets},
+    text_component::TextComponent,
+    types::Type,
+};
+use uuid::Uuid;
+
+#[derive(Type, Clone, Debug, PartialEq, Eq)]
+pub struct ClientboundPluginMessage {
+    pub channel: Identifier,
+    #[mc(rest)]
+    pub data: Vec<u8>,
+}
+
+#[derive(Type, Clone, Debug, PartialEq)]
+pub struct Disconnect {
+    // Boxed at recommendation of compiler
+    pub reason: Box<TextComponent>,
+}
+
+#[derive(Type, Clone, Debug, PartialEq, Eq)]
+pub struct FinishConfiguration;
+
+#[derive(Type, Clone, Debug, PartialEq, Eq)]
+pub struct ClientboundKeepAlive {
+    pub id: i64,
+}
+
+#[derive(Type, Clone, Debug, PartialEq, Eq)]
+pub struct Ping {
+    pub id: i32,
+}
+
+#[derive(Type, Clone, Debug, PartialEq, Eq)]
+pub struct ResetChat;
+
+#[derive(Type, Clone, Debug, PartialEq)]
+pub struct RegistryData {
+    pub registry_id: Identifier,
+    #[mc(prefixed)]
+    pub entries: Vec<RegistryEntry>,
+}
+
+#[derive(Type, Clone, Debug, PartialEq)]
+pub struct RegistryEntry {
+    pub entry_id: Identifier,
+    /// Left out when the client already has the entry from a known pack
+    pub data: Option<fastnbt::Value>,
+}
+
+#[derive(Type, Clone, Debug, PartialEq, Eq)]
+pub struct FeatureFlags {
+    #[mc(prefixed)]
+    pub feature_flags: Vec<Identifier>,
+}
+
+#[derive(Type, Clone, Debug, PartialEq, Eq)]
+pub struct ClientboundKnownPacks {
+    #[mc(prefixed)]
+    pub known_packs: Vec<KnownPack>,
+}
+
+/// A data pack that both sides may have, identified by namespace, id and version.
+#[derive(Type, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct KnownPack {
+    pub namespace: String,
+    pub id: String,
+    pub version: String,
+}
+
+impl KnownPack {
+    /// The built-in vanilla pack for this crate's game version
+    pub fn core() -> Self {
+        Self {
+            namespace: "minecraft".to_string(),
+            id: "core".to_string(),
+            version: VERSION_NAME.to_string(),
+        }
+    }
+}
+
+#[derive(Type, Clone, Debug, PartialEq, Eq)]
+pub struct ClientInformation {
+    pub locale: String,
+    pub view_distance: i8,
+    pub chat_mode: ChatMode,
+    pub chat_colors: bool,
+    pub displayed_skin_parts: u8,
+    pub main_hand: MainHand,
+    pub enable_text_filtering: bool,
+    pub allow_server_listings: bool,
+    pub particle_status: ParticleStatus,
+}
+
+#[derive(Type, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChatMode {
+    Enabled,
+    CommandsOnly,
+    Hidden,
+}
+
+#[derive(Type, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MainHand {
+    Left,
+    Right,
+}
+
+#[derive(Type, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParticleStatus {
+    All,
+    Decreased,
+    Minimal,
+}
+
+#[derive(Type, Clone, Debug, PartialEq, Eq)]
+pub struct ServerboundPluginMessage {
+    pub channel: Identifier,
+    #[mc(rest)]
+    pub data: Vec<u8>,
+}
+
+#[derive(Type, Clone, Debug, PartialEq, Eq)]
+pub struct AcknowledgeFinishConfiguration;
+
+#[derive(Type, Clone, Debug, PartialEq, Eq)]
+pub struct ServerboundKeepAlive {
+    pub id: i64,
+}
+
+#[derive(Type, Clone, Debug, PartialEq, Eq)]
+pub struct Pong {
+    pub id: i32,
+}
+
+#[derive(Type, Clone, Debug, PartialEq, Eq)]
+pub struct ResourcePackResponse {
+    pub uuid: Uuid,
+    pub result: ResourcePackResult,
+}
+
+#[derive(Type, Clone, Debug, PartialEq, Eq)]
+pub struct ServerboundKnownPacks {
+    #[mc(prefixed)]
+    pub known_packs: Vec<KnownPack>,
+}
+
+#[derive(Type, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ResourcePackResult {
+    SuccessfullyDownloaded,
+    Declined,
+    FailedToDownload,
+    Accepted,
+    Downloaded,
+    InvalidUrl,
+    FailedToReload,
+    Discarded,
+}
+
+packets!(Configuration, Clientbound, Clientbound {
+    0x01 => ClientboundPluginMessage,
+    0x02 => Disconnect,
+    0x03 => FinishConfiguration,
+    0x04 => ClientboundKeepAlive,
+    0x05 => Ping,
+    0x06 => ResetChat,
+    0x07 => RegistryData,
+    0x0c => FeatureFlags,
+    0x0e => ClientboundKnownPacks,
+});
+
+packets!(Configuration, Serverbound, Serverbound {
+    0x00 => ClientInformation,
+    0x02 => ServerboundPluginMessage,
+    0x03 => AcknowledgeFinishConfiguration,
+    0x04 => ServerboundKeepAlive,
+    0x05 => Pong,
+    0x06 => ResourcePackResponse,
+    0x07 => ServerboundKnownPacks,
+});
@@ -0,0 +1,21 @@
+use crate::{protocol::packets, types::Type};
+
+#[derive(Type, Clone, Debug, PartialEq, Eq)]
+pub struct Handshake {
+    #[mc(var_int)]
+    pub protocol_version: i32,
+    pub server_address: String,
+    pub server_port: u16,
+    pub intent: Intent,
+}
+
+#[derive(Type, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Intent {
+    Status = 1,
+    Login,
+    Transfer,
+}
+
+packets!(Handshaking, Serverbound, Serverbound {
+    0x00 => Handshake,
+});
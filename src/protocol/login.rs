@@ -0,0 +1,97 @@
+use crate::{
+    data::{Identifier, player::Profile},
+    protocol::packets,
+    text_component::TextComponent,
+    types::{Codec, Json, Prefixed, Type},
+};
+use std::io::{Read, Write};
+use uuid::Uuid;
+
+pub use crate::encryption::{EncryptionRequest, EncryptionResponse};
+
+#[derive(Type, Clone)]
+pub struct Disconnect {
+    #[mc(with = Json)]
+    // Boxed at recommendation of compiler
+    pub reason: Box<TextComponent>,
+}
+
+#[derive(Type, Clone, Debug, PartialEq)]
+pub struct LoginSuccess {
+    #[mc(with = LoginProfile)]
+    pub profile: Profile,
+}
+
+/// A profile as Login Success sends it, where the UUID comes first and both it and the name are
+/// required.
+struct LoginProfile;
+
+impl Codec<Profile> for LoginProfile {
+    fn write(value: &Profile, mut writer: impl Write) -> std::io::Result<()> {
+        let (Some(id), Some(name)) = (value.id(), value.name()) else {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "Login Success needs a profile with a UUID and a name",
+            ));
+        };
+        id.write(&mut writer)?;
+        name.to_string().write(&mut writer)?;
+        Prefixed::write(&value.properties().to_vec(), writer)
+    }
+
+    fn read(mut reader: impl Read) -> std::io::Result<Profile> {
+        Ok(Profile::new(
+            Uuid::read(&mut reader)?,
+            String::read(&mut reader)?,
+            Prefixed::read(reader)?,
+        ))
+    }
+}
+
+#[derive(Type, Clone, Debug, PartialEq, Eq)]
+pub struct SetCompression {
+    #[mc(var_int)]
+    pub threshold: i32,
+}
+
+#[derive(Type, Clone, Debug, PartialEq, Eq)]
+pub struct LoginPluginRequest {
+    #[mc(var_int)]
+    pub message_id: i32,
+    pub channel: Identifier,
+    #[mc(rest)]
+    pub data: Vec<u8>,
+}
+
+#[derive(Type, Clone, Debug, PartialEq, Eq)]
+pub struct LoginStart {
+    pub name: String,
+    pub uuid: Uuid,
+}
+
+#[derive(Type, Clone, Debug, PartialEq, Eq)]
+pub struct LoginPluginResponse {
+    #[mc(var_int)]
+    pub message_id: i32,
+    pub successful: bool,
+    #[mc(rest)]
+    pub data: Vec<u8>,
+}
+
+#[derive(Type, Clone, Debug, PartialEq, Eq)]
+pub struct LoginAcknowledged;
+
+packets!(Login, Clientbound, Clientbound {
+    0x00 => Disconnect,
+    0x01 => EncryptionRequest,
+    0x02 => LoginSuccess,
+    0x03 => SetCompression,
+    0x04 => LoginPluginRequest,
+});
+
+packets!(Login, Serverbound, Serverbound {
+    0x00 => LoginStart,
+    0x01 => EncryptionResponse,
+    0x02 => LoginPluginResponse,
+    0x03 => LoginAcknowledged,
+});
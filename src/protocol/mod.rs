@@ -0,0 +1,423 @@
+pub mod configuration;
+pub mod handshaking;
+pub mod login;
+pub mod play;
+pub mod status;
+
+use crate::{
+    frame::{Frame, FrameError},
+    types::Type,
+};
+use std::collections::HashMap;
+
+/// The protocol version of the game version this crate targets
+pub const PROTOCOL_VERSION: i32 = 769;
+/// The game version this crate targets
+pub const VERSION_NAME: &str = "1.21.4";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ConnectionState {
+    Handshaking,
+    Status,
+    Login,
+    Configuration,
+    Play,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    /// Client to server
+    Serverbound,
+    /// Server to client
+    Clientbound,
+}
+
+#[derive(Debug)]
+pub enum PacketError {
+    Io(std::io::Error),
+    /// No packet with this id exists in this state and direction. The frame has still been fully
+    /// read, so the connection can carry on.
+    UnknownId {
+        state: ConnectionState,
+        direction: Direction,
+        id: i32,
+    },
+    /// The packet is not valid in the connection's current state
+    UnexpectedPacket {
+        state: ConnectionState,
+        direction: Direction,
+        id: i32,
+    },
+}
+
+impl std::fmt::Display for PacketError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(e) => e.fmt(f),
+            Self::UnknownId {
+                state,
+                direction,
+                id,
+            } => write!(f, "Unknown {direction:?} packet {id:#04x} in {state:?}"),
+            Self::UnexpectedPacket {
+                state,
+                direction,
+                id,
+            } => write!(f, "Unexpected {direction:?} packet {id:#04x} in {state:?}"),
+        }
+    }
+}
+
+impl std::error::Error for PacketError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for PacketError {
+    fn from(value: std::io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+impl From<FrameError> for PacketError {
+    fn from(value: FrameError) -> Self {
+        Self::Io(value.into())
+    }
+}
+
+impl PacketError {
+    /// Whether the connection can keep going after this error, skipping the offending packet.
+    pub fn is_recoverable(&self) -> bool {
+        matches!(self, Self::UnknownId { .. })
+    }
+}
+
+/// A single packet in a particular state and direction.
+pub trait Packet: Type {
+    const ID: i32;
+    const STATE: ConnectionState;
+    const DIRECTION: Direction;
+
+    fn to_frame(&self) -> std::io::Result<Frame> {
+        Frame::encode(Self::ID, self)
+    }
+}
+
+/// Every packet in a particular state and direction.
+pub trait Packets: Sized {
+    const STATE: ConnectionState;
+    const DIRECTION: Direction;
+    const IDS: &'static [i32];
+
+    fn id(&self) -> i32;
+
+    fn decode(frame: &Frame) -> Result<Self, PacketError>;
+
+    fn encode(&self) -> std::io::Result<Frame>;
+}
+
+macro_rules! packets {
+    ($state:ident, $direction:ident, $name:ident { $($id:literal => $packet:ident),* $(,)? }) => {
+        #[derive(Clone)]
+        pub enum $name {
+            $($packet($packet)),*
+        }
+
+        $(
+            impl $crate::protocol::Packet for $packet {
+                const ID: i32 = $id;
+                const STATE: $crate::protocol::ConnectionState =
+                    $crate::protocol::ConnectionState::$state;
+                const DIRECTION: $crate::protocol::Direction =
+                    $crate::protocol::Direction::$direction;
+            }
+
+            impl From<$packet> for $name {
+                fn from(value: $packet) -> Self {
+                    Self::$packet(value)
+                }
+            }
+
+            impl TryFrom<$name> for $packet {
+                type Error = $name;
+
+                fn try_from(value: $name) -> Result<Self, Self::Error> {
+                    #[allow(unreachable_patterns)]
+                    match value {
+                        $name::$packet(packet) => Ok(packet),
+                        other => Err(other),
+                    }
+                }
+            }
+        )*
+
+        impl $crate::protocol::Packets for $name {
+            const STATE: $crate::protocol::ConnectionState =
+                $crate::protocol::ConnectionState::$state;
+            const DIRECTION: $crate::protocol::Direction =
+                $crate::protocol::Direction::$direction;
+            const IDS: &'static [i32] = &[$($id),*];
+
+            fn id(&self) -> i32 {
+                match *self {
+                    $(Self::$packet(_) => $id,)*
+                }
+            }
+
+            fn decode(
+                frame: &$crate::frame::Frame,
+            ) -> Result<Self, $crate::protocol::PacketError> {
+                match frame.id {
+                    $($id => Ok(Self::$packet(frame.decode()?)),)*
+                    id => Err($crate::protocol::PacketError::UnknownId {
+                        state: Self::STATE,
+                        direction: Self::DIRECTION,
+                        id,
+                    }),
+                }
+            }
+
+            fn encode(&self) -> std::io::Result<$crate::frame::Frame> {
+                match self {
+                    $(Self::$packet(packet) => $crate::frame::Frame::encode($id, packet),)*
+                }
+            }
+        }
+    };
+}
+
+pub(crate) use packets;
+
+/// A packet from any state and direction.
+#[derive(Clone)]
+pub enum AnyPacket {
+    Handshaking(handshaking::Serverbound),
+    StatusServerbound(status::Serverbound),
+    StatusClientbound(status::Clientbound),
+    LoginServerbound(login::Serverbound),
+    LoginClientbound(login::Clientbound),
+    ConfigurationServerbound(configuration::Serverbound),
+    ConfigurationClientbound(configuration::Clientbound),
+    PlayServerbound(play::Serverbound),
+    PlayClientbound(play::Clientbound),
+}
+
+macro_rules! any_packet {
+    ($($variant:ident($packets:ty)),* $(,)?) => {
+        $(impl From<$packets> for AnyPacket {
+            fn from(value: $packets) -> Self {
+                Self::$variant(value)
+            }
+        })*
+
+        impl AnyPacket {
+            pub fn state(&self) -> ConnectionState {
+                match self {
+                    $(Self::$variant(_) => <$packets as Packets>::STATE,)*
+                }
+            }
+
+            pub fn direction(&self) -> Direction {
+                match self {
+                    $(Self::$variant(_) => <$packets as Packets>::DIRECTION,)*
+                }
+            }
+
+            pub fn id(&self) -> i32 {
+                match self {
+                    $(Self::$variant(packets) => packets.id(),)*
+                }
+            }
+
+            pub fn encode(&self) -> std::io::Result<Frame> {
+                match self {
+                    $(Self::$variant(packets) => packets.encode(),)*
+                }
+            }
+        }
+
+        impl Registry {
+            /// A registry containing every packet this crate models.
+            pub fn vanilla() -> Self {
+                let mut out = Self::new();
+                $(out.register::<$packets>();)*
+                out
+            }
+        }
+    };
+}
+
+any_packet! {
+    Handshaking(handshaking::Serverbound),
+    StatusServerbound(status::Serverbound),
+    StatusClientbound(status::Clientbound),
+    LoginServerbound(login::Serverbound),
+    LoginClientbound(login::Clientbound),
+    ConfigurationServerbound(configuration::Serverbound),
+    ConfigurationClientbound(configuration::Clientbound),
+    PlayServerbound(play::Serverbound),
+    PlayClientbound(play::Clientbound),
+}
+
+type Decoder = fn(&Frame) -> Result<AnyPacket, PacketError>;
+
+/// Maps a state, direction and packet id to the decoder for that packet.
+#[derive(Clone, Default)]
+pub struct Registry {
+    decoders: HashMap<(ConnectionState, Direction, i32), Decoder>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register<P: Packets + Into<AnyPacket>>(&mut self) {
+        fn decode<P: Packets + Into<AnyPacket>>(frame: &Frame) -> Result<AnyPacket, PacketError> {
+            P::decode(frame).map(Into::into)
+        }
+
+        for id in P::IDS {
+            self.decoders
+                .insert((P::STATE, P::DIRECTION, *id), decode::<P>);
+        }
+    }
+
+    pub fn decode(
+        &self,
+        state: ConnectionState,
+        direction: Direction,
+        frame: &Frame,
+    ) -> Result<AnyPacket, PacketError> {
+        let decoder =
+            self.decoders
+                .get(&(state, direction, frame.id))
+                .ok_or(PacketError::UnknownId {
+                    state,
+                    direction,
+                    id: frame.id,
+                })?;
+
+        decoder(frame)
+    }
+}
+
+/// Tracks the state of a connection from the server's side, rejecting packets that don't belong in
+/// the current state and switching states as the vanilla server does.
+#[derive(Clone, Debug)]
+pub struct StateMachine {
+    state: ConnectionState,
+    // The packet sent to the client that it has to acknowledge before the state changes
+    awaiting_acknowledgement: bool,
+}
+
+impl Default for StateMachine {
+    fn default() -> Self {
+        Self {
+            state: ConnectionState::Handshaking,
+            awaiting_acknowledgement: false,
+        }
+    }
+}
+
+impl StateMachine {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn state(&self) -> ConnectionState {
+        self.state
+    }
+
+    /// Whether the client has been told to change state but hasn't acknowledged it yet. Vanilla
+    /// clients can still send packets for the old state in the meantime.
+    pub fn is_awaiting_acknowledgement(&self) -> bool {
+        self.awaiting_acknowledgement
+    }
+
+    /// Records a packet received from the client.
+    pub fn received(&mut self, packet: &AnyPacket) -> Result<(), PacketError> {
+        self.check(packet, Direction::Serverbound)?;
+
+        match packet {
+            AnyPacket::Handshaking(handshaking::Serverbound::Handshake(handshake)) => {
+                self.state = match handshake.intent {
+                    handshaking::Intent::Status => ConnectionState::Status,
+                    handshaking::Intent::Login | handshaking::Intent::Transfer => {
+                        ConnectionState::Login
+                    }
+                };
+            }
+            AnyPacket::LoginServerbound(login::Serverbound::LoginAcknowledged(_)) => {
+                self.acknowledge(packet, ConnectionState::Configuration)?;
+            }
+            AnyPacket::ConfigurationServerbound(
+                configuration::Serverbound::AcknowledgeFinishConfiguration(_),
+            ) => {
+                self.acknowledge(packet, ConnectionState::Play)?;
+            }
+            AnyPacket::PlayServerbound(play::Serverbound::ConfigurationAcknowledged(_)) => {
+                self.acknowledge(packet, ConnectionState::Configuration)?;
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    /// Records a packet sent to the client.
+    pub fn sent(&mut self, packet: &AnyPacket) -> Result<(), PacketError> {
+        self.check(packet, Direction::Clientbound)?;
+
+        if matches!(
+            packet,
+            AnyPacket::LoginClientbound(login::Clientbound::LoginSuccess(_))
+                | AnyPacket::ConfigurationClientbound(
+                    configuration::Clientbound::FinishConfiguration(_)
+                )
+                | AnyPacket::PlayClientbound(play::Clientbound::StartConfiguration(_))
+        ) {
+            if self.awaiting_acknowledgement {
+                return Err(self.unexpected(packet));
+            }
+            self.awaiting_acknowledgement = true;
+        }
+
+        Ok(())
+    }
+
+    fn check(&self, packet: &AnyPacket, direction: Direction) -> Result<(), PacketError> {
+        if packet.state() == self.state && packet.direction() == direction {
+            Ok(())
+        } else {
+            Err(self.unexpected(packet))
+        }
+    }
+
+    fn acknowledge(
+        &mut self,
+        packet: &AnyPacket,
+        next: ConnectionState,
+    ) -> Result<(), PacketError> {
+        if !self.awaiting_acknowledgement {
+            return Err(self.unexpected(packet));
+        }
+
+        self.awaiting_acknowledgement = false;
+        self.state = next;
+
+        Ok(())
+    }
+
+    fn unexpected(&self, packet: &AnyPacket) -> PacketError {
+        PacketError::UnexpectedPacket {
+            state: self.state,
+            direction: packet.direction(),
+            id: packet.id(),
+        }
+    }
+}
@@ -0,0 +1,127 @@
+use crate::{
+    data::{
+        chunk::{Heightmaps, LightArray},
+        particle::Particle,
+    },
+    protocol::packets,
+    text_component::TextComponent,
+    types::{Nbt, Type},
+};
+
+#[derive(Type, Clone, Debug, PartialEq)]
+pub struct Disconnect {
+    // Boxed at recommendation of compiler
+    pub reason: Box<TextComponent>,
+}
+
+#[derive(Type, Clone, Debug, PartialEq, Eq)]
+pub struct ClientboundKeepAlive {
+    pub id: i64,
+}
+
+#[derive(Type, Clone, Debug, PartialEq)]
+pub struct ChunkDataAndUpdateLight {
+    pub x: i32,
+    pub z: i32,
+    // Boxed at recommendation of compiler
+    #[mc(with = Nbt)]
+    pub heightmaps: Box<Heightmaps>,
+    /// Every section from the bottom up: its count of non-air blocks, then its block states and
+    /// biomes
+    #[mc(prefixed)]
+    pub data: Vec<u8>,
+    #[mc(prefixed)]
+    pub block_entities: Vec<ChunkBlockEntity>,
+    // Boxed at recommendation of compiler
+    pub light: Box<LightData>,
+}
+
+#[derive(Type, Clone, Debug, PartialEq)]
+pub struct ChunkBlockEntity {
+    /// The x in the high four bits and z in the low
+    pub packed_xz: u8,
+    pub y: i16,
+    #[mc(var_int)]
+    pub kind: i32,
+    pub data: fastnbt::Value,
+}
+
+/// A section's light is sent when its bit in the mask is set, and cleared when its bit in the
+/// empty mask is. The first bit is the section below the world.
+#[derive(Type, Clone, Debug, PartialEq, Default)]
+pub struct LightData {
+    #[mc(prefixed)]
+    pub sky_light_mask: Vec<i64>,
+    #[mc(prefixed)]
+    pub block_light_mask: Vec<i64>,
+    #[mc(prefixed)]
+    pub empty_sky_light_mask: Vec<i64>,
+    #[mc(prefixed)]
+    pub empty_block_light_mask: Vec<i64>,
+    #[mc(prefixed)]
+    pub sky_light: Vec<LightArray>,
+    #[mc(prefixed)]
+    pub block_light: Vec<LightArray>,
+}
+
+#[derive(Type, Clone)]
+pub struct LevelParticles {
+    pub long_distance: bool,
+    pub always_visible: bool,
+    pub position: [f64; 3],
+    pub offset: [f32; 3],
+    pub max_speed: f32,
+    pub count: i32,
+    pub particle: Particle,
+}
+
+#[derive(Type, Clone, Debug, PartialEq, Eq)]
+pub struct Ping {
+    pub id: i32,
+}
+
+#[derive(Type, Clone, Debug, PartialEq, Eq)]
+pub struct StartConfiguration;
+
+#[derive(Type, Clone, Debug, PartialEq, Eq)]
+pub struct ConfirmTeleportation {
+    #[mc(var_int)]
+    pub teleport_id: i32,
+}
+
+#[derive(Type, Clone, Debug, PartialEq, Eq)]
+pub struct ClientTickEnd;
+
+#[derive(Type, Clone, Debug, PartialEq, Eq)]
+pub struct ConfigurationAcknowledged;
+
+#[derive(Type, Clone, Debug, PartialEq, Eq)]
+pub struct ServerboundKeepAlive {
+    pub id: i64,
+}
+
+#[derive(Type, Clone, Debug, PartialEq, Eq)]
+pub struct PlayerLoaded;
+
+#[derive(Type, Clone, Debug, PartialEq, Eq)]
+pub struct Pong {
+    pub id: i32,
+}
+
+packets!(Play, Clientbound, Clientbound {
+    0x1d => Disconnect,
+    0x27 => ClientboundKeepAlive,
+    0x28 => ChunkDataAndUpdateLight,
+    0x2a => LevelParticles,
+    0x37 => Ping,
+    0x70 => StartConfiguration,
+});
+
+packets!(Play, Serverbound, Serverbound {
+    0x00 => ConfirmTeleportation,
+    0x0b => ClientTickEnd,
+    0x0e => ConfigurationAcknowledged,
+    0x1a => ServerboundKeepAlive,
+    0x2a => PlayerLoaded,
+    0x2b => Pong,
+});
@@ -0,0 +1,160 @@
+use crate::{
+    protocol::{PROTOCOL_VERSION, VERSION_NAME, packets},
+    text_component::TextComponent,
+    types::{Json, Type},
+};
+use base64::{Engine, engine::general_purpose::STANDARD};
+use serde::{Deserialize, Serialize};
+use serde_with::{DisplayFromStr, serde_as};
+use uuid::Uuid;
+
+#[derive(Type, Clone, Debug, PartialEq, Eq)]
+pub struct StatusRequest;
+
+#[derive(Type, Clone)]
+pub struct StatusResponse {
+    // Boxed at recommendation of compiler
+    #[mc(with = Json)]
+    pub status: Box<Status>,
+}
+
+#[derive(Type, Clone, Debug, PartialEq, Eq)]
+pub struct PingRequest {
+    pub timestamp: i64,
+}
+
+#[derive(Type, Clone, Debug, PartialEq, Eq)]
+pub struct PongResponse {
+    pub timestamp: i64,
+}
+
+packets!(Status, Serverbound, Serverbound {
+    0x00 => StatusRequest,
+    0x01 => PingRequest,
+});
+
+packets!(Status, Clientbound, Clientbound {
+    0x00 => StatusResponse,
+    0x01 => PongResponse,
+});
+
+/// The JSON body of a [`StatusResponse`], shown in the client's server list.
+#[serde_as]
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Status {
+    pub version: Version,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub players: Option<Players>,
+    #[serde(default = "empty_description")]
+    pub description: TextComponent,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub favicon: Option<Favicon>,
+    #[serde(default)]
+    pub enforces_secure_chat: bool,
+}
+
+fn empty_description() -> TextComponent {
+    "".into()
+}
+
+impl Status {
+    /// A status for this crate's game version with no players online.
+    pub fn new(description: impl Into<TextComponent>, max_players: i32) -> Self {
+        Self {
+            version: Version::default(),
+            players: Some(Players {
+                max: max_players,
+                online: 0,
+                sample: vec![],
+            }),
+            description: description.into(),
+            favicon: None,
+            enforces_secure_chat: false,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Version {
+    pub name: String,
+    pub protocol: i32,
+}
+
+impl Default for Version {
+    fn default() -> Self {
+        Self {
+            name: VERSION_NAME.to_string(),
+            protocol: PROTOCOL_VERSION,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Players {
+    pub max: i32,
+    pub online: i32,
+    /// Shown when hovering over the player count
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sample: Vec<PlayerSample>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PlayerSample {
+    pub name: String,
+    pub id: Uuid,
+}
+
+/// A 64x64 PNG server icon, sent as a base64 data URI.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Favicon(Vec<u8>);
+
+const FAVICON_PREFIX: &str = "data:image/png;base64,";
+const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+
+impl Favicon {
+    /// Checks that `png` is a 64x64 PNG, the only size the client will display.
+    pub fn from_png(png: Vec<u8>) -> Result<Self, &'static str> {
+        // The signature is followed by the IHDR chunk's length and type, then its width and height
+        if png.len() < 24 || png[..8] != PNG_SIGNATURE || &png[12..16] != b"IHDR" {
+            return Err("Favicon is not a PNG");
+        }
+
+        let width = u32::from_be_bytes(png[16..20].try_into().unwrap());
+        let height = u32::from_be_bytes(png[20..24].try_into().unwrap());
+        if width != 64 || height != 64 {
+            return Err("Favicon must be 64x64");
+        }
+
+        Ok(Self(png))
+    }
+
+    pub fn as_png(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for Favicon {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{FAVICON_PREFIX}{}", STANDARD.encode(&self.0))
+    }
+}
+
+impl std::str::FromStr for Favicon {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let data = s
+            .strip_prefix(FAVICON_PREFIX)
+            .ok_or("Favicon must be a PNG data URI")?;
+        // Vanilla servers wrap the base64 at 76 characters
+        let data = data.replace(['\n', '\r'], "");
+
+        Self::from_png(
+            STANDARD
+                .decode(data)
+                .map_err(|_| "Invalid base64 in favicon")?,
+        )
+    }
+}
@@ -0,0 +1,139 @@
+use crate::{
+    data::{
+        Identifier,
+        registry::{Registries, RegistryError},
+    },
+    protocol::{
+        AnyPacket, ConnectionState, PacketError,
+        configuration::{
+            ClientInformation, Clientbound, ClientboundKnownPacks, FeatureFlags,
+            FinishConfiguration, KnownPack, Serverbound,
+        },
+    },
+    server::Connection,
+};
+use std::io::{Read, Write};
+
+#[derive(Debug)]
+pub enum ConfigurationError {
+    Packet(PacketError),
+    Registry(RegistryError),
+}
+
+impl std::fmt::Display for ConfigurationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Packet(e) => e.fmt(f),
+            Self::Registry(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for ConfigurationError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Packet(e) => Some(e),
+            Self::Registry(e) => Some(e),
+        }
+    }
+}
+
+impl From<PacketError> for ConfigurationError {
+    fn from(value: PacketError) -> Self {
+        Self::Packet(value)
+    }
+}
+
+impl From<RegistryError> for ConfigurationError {
+    fn from(value: RegistryError) -> Self {
+        Self::Registry(value)
+    }
+}
+
+/// Runs the configuration phase: feature flags, known pack negotiation and registry data.
+#[derive(Clone)]
+pub struct Configuration {
+    registries: Registries,
+    feature_flags: Vec<Identifier>,
+    known_packs: Vec<KnownPack>,
+}
+
+impl Default for Configuration {
+    fn default() -> Self {
+        Self::new(Registries::vanilla())
+    }
+}
+
+impl Configuration {
+    /// Sends `registries`, offering the client the vanilla core pack and feature flag.
+    pub fn new(registries: Registries) -> Self {
+        Self {
+            registries,
+            feature_flags: vec![Identifier::minecraft("vanilla")],
+            known_packs: vec![KnownPack::core()],
+        }
+    }
+
+    pub fn with_feature_flags(mut self, feature_flags: Vec<Identifier>) -> Self {
+        self.feature_flags = feature_flags;
+        self
+    }
+
+    /// The packs the client is asked about. Only entries from packs that both sides have are sent
+    /// without their data.
+    pub fn with_known_packs(mut self, known_packs: Vec<KnownPack>) -> Self {
+        self.known_packs = known_packs;
+        self
+    }
+
+    pub fn registries(&self) -> &Registries {
+        &self.registries
+    }
+
+    /// Configures a client that has just acknowledged Login Success, returning the connection in
+    /// the play state along with the client's settings if it sent them.
+    pub fn run<T: Read + Write>(
+        &self,
+        mut connection: Connection<T>,
+    ) -> Result<(Connection<T>, Option<ClientInformation>), ConfigurationError> {
+        connection.send(Clientbound::from(FeatureFlags {
+            feature_flags: self.feature_flags.clone(),
+        }))?;
+        connection.send(Clientbound::from(ClientboundKnownPacks {
+            known_packs: self.known_packs.clone(),
+        }))?;
+
+        let mut information = None;
+        while connection.state() == ConnectionState::Configuration {
+            let packet = match connection.receive() {
+                Ok(packet) => packet,
+                Err(e) if e.is_recoverable() => continue,
+                Err(e) => return Err(e.into()),
+            };
+
+            match packet {
+                AnyPacket::ConfigurationServerbound(Serverbound::ClientInformation(packet)) => {
+                    information = Some(packet);
+                }
+                AnyPacket::ConfigurationServerbound(Serverbound::ServerboundKnownPacks(packet)) => {
+                    // The client may claim packs it wasn't asked about, which can't be trusted to
+                    // match ours
+                    let known = packet
+                        .known_packs
+                        .into_iter()
+                        .filter(|pack| self.known_packs.contains(pack))
+                        .collect::<Vec<_>>();
+
+                    for registry in self.registries.to_packets(&known)? {
+                        connection.send(Clientbound::from(registry))?;
+                    }
+                    connection.send(Clientbound::from(FinishConfiguration))?;
+                }
+                // Nothing else needs an answer, including the acknowledgement that ends the loop
+                _ => {}
+            }
+        }
+
+        Ok((connection, information))
+    }
+}
@@ -0,0 +1,320 @@
+use crate::{
+    data::player::{PlayerProperty, Profile},
+    encryption::{EncryptionError, ServerKey, server_hash},
+    protocol::{
+        AnyPacket, ConnectionState, PacketError,
+        login::{Clientbound, Disconnect, LoginStart, LoginSuccess, Serverbound, SetCompression},
+    },
+    server::Connection,
+    text_component::TextComponent,
+};
+use md5::{Digest, Md5};
+use rand::RngCore;
+use serde::Deserialize;
+use std::{
+    io::{Read, Write},
+    time::Duration,
+};
+use uuid::Uuid;
+
+/// The longest username the vanilla server accepts
+pub const MAX_USERNAME_LENGTH: usize = 16;
+/// The endpoint the vanilla server authenticates players against
+pub const MOJANG_SESSION_URL: &str = "https://sessionserver.mojang.com/session/minecraft/hasJoined";
+/// How long to wait for the session server to accept a connection, as in vanilla
+pub const SESSION_CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+/// How long to wait for the session server to answer once connected, as in vanilla
+pub const SESSION_READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug)]
+pub enum LoginError {
+    Packet(PacketError),
+    Encryption(EncryptionError),
+    /// The username is too long or contains characters vanilla doesn't allow
+    InvalidUsername(String),
+    /// The session server doesn't know of the player joining this server
+    UnverifiedUsername(String),
+    /// The session server couldn't be reached
+    SessionServer(std::io::Error),
+}
+
+impl std::fmt::Display for LoginError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Packet(e) => e.fmt(f),
+            Self::Encryption(e) => e.fmt(f),
+            Self::InvalidUsername(name) => write!(f, "Invalid username {name:?}"),
+            Self::UnverifiedUsername(name) => write!(f, "Failed to verify username {name:?}"),
+            Self::SessionServer(e) => write!(f, "Session server unavailable: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for LoginError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Packet(e) => Some(e),
+            Self::Encryption(e) => Some(e),
+            Self::SessionServer(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<PacketError> for LoginError {
+    fn from(value: PacketError) -> Self {
+        Self::Packet(value)
+    }
+}
+
+impl From<EncryptionError> for LoginError {
+    fn from(value: EncryptionError) -> Self {
+        Self::Encryption(value)
+    }
+}
+
+impl LoginError {
+    /// The reason shown to the player when they're disconnected over this error, if they can
+    /// still be told.
+    pub fn reason(&self) -> Option<TextComponent> {
+        match self {
+            Self::Packet(_) => None,
+            Self::Encryption(_) => Some("Failed to log in: invalid encryption response".into()),
+            Self::InvalidUsername(_) => Some("Invalid characters in username".into()),
+            Self::UnverifiedUsername(_) => Some("Failed to verify username!".into()),
+            Self::SessionServer(_) => {
+                Some("Authentication servers are down. Please try again later, sorry!".into())
+            }
+        }
+    }
+}
+
+/// Checks that players logging in in online mode have authenticated with the session server.
+pub trait SessionService {
+    /// Returns the profile of `username` if they have told the session server they're joining the
+    /// server identified by `server_hash`.
+    fn has_joined(&self, username: &str, server_hash: &str) -> std::io::Result<Option<Profile>>;
+}
+
+/// Mojang's session server, or anything serving the same API.
+#[derive(Clone, Debug)]
+pub struct MojangSessionService {
+    url: String,
+    agent: ureq::Agent,
+}
+
+impl Default for MojangSessionService {
+    fn default() -> Self {
+        Self::new(MOJANG_SESSION_URL)
+    }
+}
+
+impl MojangSessionService {
+    pub fn new(url: impl Into<String>) -> Self {
+        let agent = ureq::Agent::config_builder()
+            .timeout_connect(Some(SESSION_CONNECT_TIMEOUT))
+            .timeout_recv_response(Some(SESSION_READ_TIMEOUT))
+            .timeout_recv_body(Some(SESSION_READ_TIMEOUT))
+            .build()
+            .into();
+
+        Self {
+            url: url.into(),
+            agent,
+        }
+    }
+}
+
+impl SessionService for MojangSessionService {
+    fn has_joined(&self, username: &str, server_hash: &str) -> std::io::Result<Option<Profile>> {
+        let mut response = self
+            .agent
+            .get(&self.url)
+            .query("username", username)
+            .query("serverId", server_hash)
+            .call()
+            .map_err(std::io::Error::other)?;
+
+        // No content means the player hasn't joined
+        if response.status() == 204 {
+            return Ok(None);
+        }
+
+        let body = response
+            .body_mut()
+            .read_to_string()
+            .map_err(std::io::Error::other)?;
+        let profile: SessionProfile = serde_json::from_str(&body)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+
+        Ok(Some(Profile::new(
+            profile.id,
+            profile.name,
+            profile.properties,
+        )))
+    }
+}
+
+/// A profile as the session server returns it, with its UUID as a string rather than the int
+/// array [`Profile`] saves it as.
+#[derive(Deserialize)]
+struct SessionProfile {
+    id: Uuid,
+    name: String,
+    #[serde(default)]
+    properties: Vec<PlayerProperty>,
+}
+
+/// The UUID the vanilla server gives players in offline mode: an MD5 (version 3) UUID of
+/// `OfflinePlayer:<name>`, with no namespace.
+pub fn offline_uuid(username: &str) -> Uuid {
+    let digest: [u8; 16] = Md5::digest(format!("OfflinePlayer:{username}")).into();
+    uuid::Builder::from_md5_bytes(digest).into_uuid()
+}
+
+/// Whether vanilla would accept `username`: at most 16 characters, none of them spaces, control
+/// characters or outside of ASCII.
+pub fn is_valid_username(username: &str) -> bool {
+    username.len() <= MAX_USERNAME_LENGTH && username.chars().all(|c| c.is_ascii_graphic())
+}
+
+/// Runs the login sequence on a connection that has just been handshaken into the login state.
+pub struct Login<S = MojangSessionService> {
+    online: Option<(ServerKey, S)>,
+    compression_threshold: Option<i32>,
+}
+
+impl Login {
+    /// Trusts whatever name the player gives, deriving their UUID with [`offline_uuid`].
+    pub fn offline() -> Self {
+        Self {
+            online: None,
+            compression_threshold: None,
+        }
+    }
+}
+
+impl<S: SessionService> Login<S> {
+    /// Encrypts the connection and authenticates the player with `session`.
+    pub fn online(key: ServerKey, session: S) -> Self {
+        Self {
+            online: Some((key, session)),
+            compression_threshold: None,
+        }
+    }
+
+    /// Enables compression before sending Login Success.
+    pub fn with_compression_threshold(mut self, threshold: i32) -> Self {
+        self.compression_threshold = Some(threshold);
+        self
+    }
+
+    pub fn is_online(&self) -> bool {
+        self.online.is_some()
+    }
+
+    /// Logs the player in, returning the connection in the configuration state along with their
+    /// profile. If the player is rejected, they are sent a Disconnect with
+    /// [`LoginError::reason`].
+    pub fn run<T: Read + Write>(
+        &self,
+        mut connection: Connection<T>,
+    ) -> Result<(Connection<T>, Profile), LoginError> {
+        let start: LoginStart = expect(&mut connection)?;
+
+        let (mut connection, profile) = self.authenticate(connection, start);
+        let profile = match profile {
+            Ok(profile) => profile,
+            Err(e) => {
+                if let Some(reason) = e.reason() {
+                    // The player is gone either way, so failing to tell them doesn't matter
+                    let _ = connection.send(Clientbound::from(Disconnect {
+                        reason: Box::new(reason),
+                    }));
+                }
+                return Err(e);
+            }
+        };
+
+        if let Some(threshold) = self.compression_threshold {
+            connection.send(Clientbound::from(SetCompression { threshold }))?;
+            connection.set_compression_threshold(threshold);
+        }
+
+        connection.send(Clientbound::from(LoginSuccess {
+            profile: profile.clone(),
+        }))?;
+
+        // Login plugin responses may still arrive before the acknowledgement
+        while connection.state() == ConnectionState::Login {
+            connection.receive()?;
+        }
+
+        Ok((connection, profile))
+    }
+
+    // Hands the connection back either way, since encryption replaces it
+    fn authenticate<T: Read + Write>(
+        &self,
+        mut connection: Connection<T>,
+        start: LoginStart,
+    ) -> (Connection<T>, Result<Profile, LoginError>) {
+        if !is_valid_username(&start.name) {
+            return (connection, Err(LoginError::InvalidUsername(start.name)));
+        }
+
+        let Some((key, session)) = &self.online else {
+            let profile = Profile::new(offline_uuid(&start.name), start.name, vec![]);
+            return (connection, Ok(profile));
+        };
+
+        let mut verify_token = vec![0; 4];
+        rand::thread_rng().fill_bytes(&mut verify_token);
+        let request = key.encryption_request(verify_token.clone(), true);
+
+        let shared_secret = connection
+            .send(Clientbound::from(request.clone()))
+            .map_err(LoginError::from)
+            .and_then(|()| expect(&mut connection))
+            .and_then(|response| Ok(key.decrypt_response(&response, &verify_token)?));
+        let shared_secret = match shared_secret {
+            Ok(secret) => secret,
+            Err(e) => return (connection, Err(e)),
+        };
+
+        // Everything after the Encryption Response is encrypted, including any Disconnect
+        let connection = connection.with_encryption(&shared_secret);
+        let hash = server_hash(&request.server_id, &shared_secret, key.public_key_der());
+
+        let profile = match session.has_joined(&start.name, &hash) {
+            Ok(Some(profile)) => Ok(profile),
+            Ok(None) => Err(LoginError::UnverifiedUsername(start.name)),
+            Err(e) => Err(LoginError::SessionServer(e)),
+        };
+
+        (connection, profile)
+    }
+}
+
+/// Reads the next login packet, failing if it isn't a `P`.
+fn expect<P, T>(connection: &mut Connection<T>) -> Result<P, LoginError>
+where
+    P: TryFrom<Serverbound>,
+    T: Read + Write,
+{
+    let packet = match connection.receive()? {
+        AnyPacket::LoginServerbound(packet) => packet,
+        other => return Err(unexpected(&other).into()),
+    };
+    let unexpected = unexpected(&packet.clone().into());
+
+    P::try_from(packet).map_err(|_| unexpected.into())
+}
+
+fn unexpected(packet: &AnyPacket) -> PacketError {
+    PacketError::UnexpectedPacket {
+        state: packet.state(),
+        direction: packet.direction(),
+        id: packet.id(),
+    }
+}
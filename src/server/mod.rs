@@ -0,0 +1,122 @@
+pub mod configuration;
+pub mod login;
+pub mod status;
+
+use crate::{
+    encryption::{EncryptedStream, encrypt_stream},
+    frame::FrameCodec,
+    protocol::{AnyPacket, ConnectionState, Direction, PacketError, Registry, StateMachine},
+};
+use std::io::{Read, Write};
+
+/// A client's connection from the server's side, handling framing, compression, encryption and
+/// which packets are allowed in which state.
+pub struct Connection<S> {
+    stream: Stream<S>,
+    codec: FrameCodec,
+    machine: StateMachine,
+    registry: Registry,
+}
+
+enum Stream<S> {
+    Plain(S),
+    // Boxed at recommendation of compiler
+    Encrypted(Box<EncryptedStream<S>>),
+}
+
+impl<S: Read + Write> Read for Stream<S> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        match self {
+            Self::Plain(stream) => stream.read(buf),
+            Self::Encrypted(stream) => stream.read(buf),
+        }
+    }
+}
+
+impl<S: Read + Write> Write for Stream<S> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self {
+            Self::Plain(stream) => stream.write(buf),
+            Self::Encrypted(stream) => stream.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self {
+            Self::Plain(stream) => stream.flush(),
+            Self::Encrypted(stream) => stream.flush(),
+        }
+    }
+}
+
+impl<S: Read + Write> Connection<S> {
+    /// Wraps a freshly accepted stream, which starts out in the handshaking state.
+    pub fn new(stream: S) -> Self {
+        Self {
+            stream: Stream::Plain(stream),
+            codec: FrameCodec::new(),
+            machine: StateMachine::new(),
+            registry: Registry::vanilla(),
+        }
+    }
+
+    pub fn with_registry(mut self, registry: Registry) -> Self {
+        self.registry = registry;
+        self
+    }
+
+    pub fn state(&self) -> ConnectionState {
+        self.machine.state()
+    }
+
+    pub fn is_encrypted(&self) -> bool {
+        matches!(self.stream, Stream::Encrypted(_))
+    }
+
+    /// Encrypts everything from here on with the shared secret from the login key exchange.
+    ///
+    /// # Panics
+    ///
+    /// If the connection is already encrypted.
+    pub fn with_encryption(self, shared_secret: &[u8; 16]) -> Self {
+        let stream = match self.stream {
+            Stream::Plain(stream) => {
+                Stream::Encrypted(Box::new(encrypt_stream(stream, shared_secret)))
+            }
+            Stream::Encrypted(_) => panic!("Connection is already encrypted"),
+        };
+
+        Self { stream, ..self }
+    }
+
+    pub fn compression_threshold(&self) -> Option<usize> {
+        self.codec.compression_threshold()
+    }
+
+    /// Compresses frames from here on, as announced by a Set Compression packet.
+    pub fn set_compression_threshold(&mut self, threshold: i32) {
+        self.codec.set_compression_threshold(threshold);
+    }
+
+    /// Reads the next packet from the client. Unknown packets are recoverable errors, after which
+    /// the connection can carry on.
+    pub fn receive(&mut self) -> Result<AnyPacket, PacketError> {
+        let frame = self.codec.read_frame(&mut self.stream)?;
+        let packet = self
+            .registry
+            .decode(self.machine.state(), Direction::Serverbound, &frame)?;
+        self.machine.received(&packet)?;
+
+        Ok(packet)
+    }
+
+    pub fn send(&mut self, packet: impl Into<AnyPacket>) -> Result<(), PacketError> {
+        let packet = packet.into();
+        self.machine.sent(&packet)?;
+        self.codec
+            .write_frame(&packet.encode()?, &mut self.stream)?;
+        self.stream.flush()?;
+
+        Ok(())
+    }
+}
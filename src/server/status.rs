@@ -0,0 +1,211 @@
+use crate::{
+    frame::FrameCodec,
+    protocol::{
+        AnyPacket, PacketError, Packets, StateMachine, handshaking,
+        status::{Clientbound, PongResponse, Serverbound, Status, StatusResponse},
+    },
+};
+use std::{
+    io::{Read, Write},
+    net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+    sync::Arc,
+    time::Duration,
+};
+
+/// How long a connection may sit idle before it's dropped
+pub const TIMEOUT: Duration = Duration::from_secs(5);
+/// How long to wait for the payload that 1.4 to 1.6 clients send after a legacy ping
+pub const LEGACY_TIMEOUT: Duration = Duration::from_millis(100);
+/// The protocol version reported to pre-1.7 clients. Anything they don't know makes them show the
+/// server as outdated rather than letting them try to join.
+pub const LEGACY_PROTOCOL_VERSION: i32 = 127;
+
+const LEGACY_PING: u8 = 0xfe;
+const LEGACY_PAYLOAD: u8 = 0x01;
+const LEGACY_PLUGIN_MESSAGE: u8 = 0xfa;
+const LEGACY_KICK: u8 = 0xff;
+
+/// A minimal listener that only answers server list pings.
+pub struct StatusServer<F> {
+    listener: TcpListener,
+    status: Arc<F>,
+}
+
+impl<F: Fn() -> Status + Send + Sync + 'static> StatusServer<F> {
+    /// Binds to `addr`. `status` is called for every ping, so it can report live player counts.
+    pub fn bind(addr: impl ToSocketAddrs, status: F) -> std::io::Result<Self> {
+        Ok(Self {
+            listener: TcpListener::bind(addr)?,
+            status: Arc::new(status),
+        })
+    }
+
+    pub fn local_addr(&self) -> std::io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Accepts connections forever, answering each on its own thread.
+    pub fn run(self) -> std::io::Result<()> {
+        for stream in self.listener.incoming() {
+            let stream = stream?;
+            let status = self.status.clone();
+            std::thread::spawn(move || {
+                // Errors only ever concern the one client, which is disconnected by dropping the
+                // stream either way
+                let _ = handle(stream, &*status);
+            });
+        }
+
+        Ok(())
+    }
+}
+
+fn handle(stream: TcpStream, status: impl Fn() -> Status) -> Result<(), PacketError> {
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    stream.set_write_timeout(Some(TIMEOUT))?;
+    stream.set_nodelay(true)?;
+
+    // Beta clients send nothing after the legacy ping, so don't keep them waiting to find that out
+    let mut first = 0;
+    if stream.peek(std::slice::from_mut(&mut first))? == 1 && first == LEGACY_PING {
+        stream.set_read_timeout(Some(LEGACY_TIMEOUT))?;
+    }
+    let start = read_start(&stream);
+    // Whatever the start turned out to be, the rest of the connection gets the full timeout
+    stream.set_read_timeout(Some(TIMEOUT))?;
+
+    respond_after(stream, status, start?)
+}
+
+/// Whether `bytes`, read from the start of a connection, are a legacy ping rather than the start
+/// of a frame that happens to be 254 bytes long. As in vanilla, that's 0xfe on its own, followed
+/// only by 0x01, or followed by 0x01 and the 0xfa plugin message that 1.6 clients send.
+fn is_legacy(bytes: &[u8]) -> bool {
+    matches!(
+        bytes,
+        [LEGACY_PING]
+            | [LEGACY_PING, LEGACY_PAYLOAD]
+            | [LEGACY_PING, LEGACY_PAYLOAD, LEGACY_PLUGIN_MESSAGE, ..]
+    )
+}
+
+/// Answers a single server list ping on `stream`, either the modern one or the legacy one sent by
+/// clients before 1.7. Connections that want to log in are dropped.
+pub fn respond(
+    mut stream: impl Read + Write,
+    status: impl Fn() -> Status,
+) -> Result<(), PacketError> {
+    let start = read_start(&mut stream)?;
+    respond_after(stream, status, start)
+}
+
+/// Reads the first byte of a connection, and if it's a legacy ping, as many of the bytes that may
+/// follow it as arrive.
+fn read_start(mut stream: impl Read) -> std::io::Result<Vec<u8>> {
+    let mut first = 0;
+    stream.read_exact(std::slice::from_mut(&mut first))?;
+
+    let mut start = vec![first];
+    if first == LEGACY_PING {
+        // Running out of bytes here is how a legacy ping is told apart from a Handshake frame 254
+        // bytes long, as beta clients send nothing after the ping and 1.4 and 1.5 clients only
+        // 0x01
+        while start.len() < 3 && is_legacy(&start) {
+            let mut byte = 0;
+            match stream.read(std::slice::from_mut(&mut byte)) {
+                Ok(1) => start.push(byte),
+                _ => break,
+            }
+        }
+    }
+
+    Ok(start)
+}
+
+/// Answers the ping whose first bytes, read by [`read_start`], are `start`.
+fn respond_after(
+    mut stream: impl Read + Write,
+    status: impl Fn() -> Status,
+    start: Vec<u8>,
+) -> Result<(), PacketError> {
+    if is_legacy(&start) {
+        return respond_legacy(stream, &status(), start.len() > 1);
+    }
+
+    let codec = FrameCodec::new();
+    let mut machine = StateMachine::new();
+
+    // Whatever was read above is the start of the Handshake's frame
+    let frame = codec.read_frame(start.as_slice().chain(&mut stream))?;
+    let packet = handshaking::Serverbound::decode(&frame)?;
+    machine.received(&packet.clone().into())?;
+    let handshaking::Serverbound::Handshake(handshake) = packet;
+    if handshake.intent != handshaking::Intent::Status {
+        return Ok(());
+    }
+
+    loop {
+        let frame = codec.read_frame(&mut stream)?;
+        let packet = Serverbound::decode(&frame)?;
+        machine.received(&packet.clone().into())?;
+
+        let response = match packet {
+            Serverbound::StatusRequest(_) => Clientbound::from(StatusResponse {
+                status: Box::new(status()),
+            }),
+            Serverbound::PingRequest(ping) => Clientbound::from(PongResponse {
+                timestamp: ping.timestamp,
+            }),
+        };
+        let response = AnyPacket::from(response);
+        machine.sent(&response)?;
+        codec.write_frame(&response.encode()?, &mut stream)?;
+        stream.flush()?;
+
+        // The pong is the last thing the client expects
+        if let AnyPacket::StatusClientbound(Clientbound::PongResponse(_)) = response {
+            return Ok(());
+        }
+    }
+}
+
+/// Answers a legacy ping in the format 1.4 to 1.6 expect if `payload` was sent, or the one beta
+/// 1.8 to 1.3 expect if not.
+fn respond_legacy(
+    mut stream: impl Read + Write,
+    status: &Status,
+    payload: bool,
+) -> Result<(), PacketError> {
+    let (online, max) = status
+        .players
+        .as_ref()
+        .map_or((0, 0), |players| (players.online, players.max));
+    let motd = status.description.to_plain_text();
+
+    let response = if payload {
+        format!(
+            "§1\0{LEGACY_PROTOCOL_VERSION}\0{}\0{motd}\0{online}\0{max}",
+            status.version.name
+        )
+    } else {
+        format!("{}§{online}§{max}", motd.replace('§', ""))
+    };
+
+    let response = response.encode_utf16().collect::<Vec<_>>();
+    let mut out = Vec::with_capacity(3 + response.len() * 2);
+    out.push(LEGACY_KICK);
+    let length = u16::try_from(response.len()).map_err(|_| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "legacy status response is too long",
+        )
+    })?;
+    out.extend_from_slice(&length.to_be_bytes());
+    for unit in response {
+        out.extend_from_slice(&unit.to_be_bytes());
+    }
+    stream.write_all(&out)?;
+    stream.flush()?;
+
+    Ok(())
+}
@@ -1,14 +1,19 @@
 use crate::{
     OneOf,
     data::{Coordinates, Identifier, Item, NbtPath, Selector},
+    types::Type,
 };
 use derive_more::{AsMut, AsRef, Deref, DerefMut};
 use serde::{Deserialize, Serialize};
-use serde_with::{DisplayFromStr, FromInto, serde_as};
-use std::{path::PathBuf, str::FromStr};
+use serde_with::{DisplayFromStr, FromInto, serde_as, skip_serializing_none};
+use std::{
+    io::{Read, Write},
+    path::PathBuf,
+    str::FromStr,
+};
 use strum::{EnumString, IntoStaticStr};
 
-#[derive(Serialize, Deserialize, Deref, DerefMut, AsRef, AsMut, Clone)]
+#[derive(Serialize, Deserialize, Deref, DerefMut, AsRef, AsMut, Clone, Debug, PartialEq)]
 #[serde(try_from = "OneOf<TextComponentInner, OneOf<Vec<TextComponent>, String>>")]
 #[repr(transparent)]
 pub struct TextComponent(TextComponentInner);
@@ -27,6 +32,91 @@ impl TryFrom<OneOf<TextComponentInner, OneOf<Vec<TextComponent>, String>>> for T
     }
 }
 
+impl TextComponent {
+    /// The literal text of this component and its children, without any formatting. Content that
+    /// can only be resolved by the client, like translations and keybinds, is left out.
+    pub fn to_plain_text(&self) -> String {
+        let mut out = String::new();
+        self.push_plain_text(&mut out);
+        out
+    }
+
+    fn push_plain_text(&self, out: &mut String) {
+        if let TextComponentContent::Text { text } = &self.content {
+            out.push_str(text);
+        }
+        for child in &self.extra {
+            child.push_plain_text(out);
+        }
+    }
+
+    /// The text of a component with no style or children, which vanilla encodes as just a string.
+    pub(crate) fn as_unstyled_text(&self) -> Option<&str> {
+        let TextComponentInner {
+            content: TextComponentContent::Text { text },
+            extra,
+            color: None,
+            font: None,
+            bold: None,
+            italic: None,
+            underlined: None,
+            strikethrough: None,
+            obfuscated: None,
+            shadow_color: None,
+            insertion: None,
+            click_event: None,
+            hover_event: None,
+        } = &self.0
+        else {
+            return None;
+        };
+
+        extra.is_empty().then_some(text)
+    }
+}
+
+/// Network NBT. Unstyled text is sent as a bare string tag, and a list tag is read as its first
+/// component with the rest appended to its children.
+impl Type for TextComponent {
+    fn write(&self, writer: impl Write) -> std::io::Result<()> {
+        let value = match self.as_unstyled_text() {
+            Some(text) => fastnbt::Value::String(text.to_string()),
+            None => fastnbt::to_value(self)
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?,
+        };
+
+        value.write(writer)
+    }
+
+    fn read(reader: impl Read) -> std::io::Result<Self> {
+        let mut value = fastnbt::Value::read(reader)?;
+        unwrap_list_elements(&mut value);
+
+        fastnbt::from_value(&value)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+    }
+}
+
+/// NBT lists can only hold one type of tag, so vanilla wraps each element of a mixed list in a
+/// compound with a single, empty key, e.g. `[{"": "text"}, {"": {"translate": "..."}}]`.
+fn unwrap_list_elements(value: &mut fastnbt::Value) {
+    match value {
+        fastnbt::Value::List(list) => {
+            for element in list {
+                if let fastnbt::Value::Compound(compound) = element
+                    && compound.len() == 1
+                    && let Some(inner) = compound.remove("")
+                {
+                    *element = inner;
+                }
+                unwrap_list_elements(element);
+            }
+        }
+        fastnbt::Value::Compound(compound) => compound.values_mut().for_each(unwrap_list_elements),
+        _ => {}
+    }
+}
+
 impl<T: Into<TextComponentInner>> From<T> for TextComponent {
     fn from(value: T) -> Self {
         Self(value.into())
@@ -34,23 +124,30 @@ impl<T: Into<TextComponentInner>> From<T> for TextComponent {
 }
 
 #[serde_as]
-#[derive(Serialize, Deserialize, Clone)]
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct TextComponentInner {
-    // TODO: Test this!! sketchy af
     #[serde(flatten)]
     #[serde_as(as = "FromInto<OneOf<TextComponentContent, TextComponentContentUntagged>>")]
     content: TextComponentContent,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     extra: Vec<TextComponent>,
     #[serde_as(as = "Option<DisplayFromStr>")]
     color: Option<TextComponentColor>,
     font: Option<PathBuf>,
+    // Booleans are bytes in NBT
+    #[serde_as(as = "Option<FromInto<OneOf<bool, i8>>>")]
     bold: Option<bool>,
+    #[serde_as(as = "Option<FromInto<OneOf<bool, i8>>>")]
     italic: Option<bool>,
+    #[serde_as(as = "Option<FromInto<OneOf<bool, i8>>>")]
     underlined: Option<bool>,
+    #[serde_as(as = "Option<FromInto<OneOf<bool, i8>>>")]
     strikethrough: Option<bool>,
+    #[serde_as(as = "Option<FromInto<OneOf<bool, i8>>>")]
     obfuscated: Option<bool>,
+    #[serde(rename = "shadow_color")]
     shadow_color: Option<ARGB>,
     insertion: Option<String>,
     #[serde(rename = "clickEvent")]
@@ -67,8 +164,48 @@ pub enum HoverEvent {
     ShowItem(Box<Item>),
 }
 
-#[derive(Serialize, Deserialize, Clone)]
-#[serde(from = "OneOf<u32, [f32; 4]>", into = "[f32; 4]")]
+impl std::fmt::Debug for HoverEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ShowText(text) => f.debug_tuple("ShowText").field(text).finish(),
+            Self::ShowItem(_) => f.debug_tuple("ShowItem").finish_non_exhaustive(),
+        }
+    }
+}
+
+/// Items are compared by their NBT, as they don't implement `PartialEq` themselves.
+impl PartialEq for HoverEvent {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::ShowText(a), Self::ShowText(b)) => a == b,
+            (Self::ShowItem(a), Self::ShowItem(b)) => {
+                matches!(
+                    (fastnbt::to_value(a), fastnbt::to_value(b)),
+                    (Ok(a), Ok(b)) if a == b
+                )
+            }
+            _ => false,
+        }
+    }
+}
+
+impl From<OneOf<bool, i8>> for bool {
+    fn from(value: OneOf<bool, i8>) -> Self {
+        match value {
+            OneOf::Left(value) => value,
+            OneOf::Right(value) => value != 0,
+        }
+    }
+}
+
+impl From<bool> for OneOf<bool, i8> {
+    fn from(value: bool) -> Self {
+        Self::Left(value)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(from = "OneOf<i64, [f32; 4]>", into = "[f32; 4]")]
 pub struct ARGB {
     pub a: f32,
     pub r: f32,
@@ -76,9 +213,10 @@ pub struct ARGB {
     pub b: f32,
 }
 
-impl From<OneOf<u32, [f32; 4]>> for ARGB {
-    fn from(value: OneOf<u32, [f32; 4]>) -> Self {
+impl From<OneOf<i64, [f32; 4]>> for ARGB {
+    fn from(value: OneOf<i64, [f32; 4]>) -> Self {
         match value {
+            // Packed colors with the alpha bit set are negative in NBT and JSON alike
             OneOf::Left(int) => Self {
                 a: ((int >> 24) as u8) as f32 / 255.0,
                 r: ((int >> 16) as u8) as f32 / 255.0,
@@ -101,6 +239,16 @@ impl From<ARGB> for [f32; 4] {
     }
 }
 
+impl From<ARGB> for i32 {
+    fn from(value: ARGB) -> Self {
+        [value.a, value.r, value.g, value.b]
+            .into_iter()
+            .fold(0, |int, channel| {
+                int << 8 | (channel * 255.0).round() as u8 as i32
+            })
+    }
+}
+
 impl From<String> for TextComponentInner {
     fn from(value: String) -> Self {
         Self {
@@ -108,11 +256,11 @@ impl From<String> for TextComponentInner {
             extra: vec![],
             color: None,
             font: None,
-            bold: Some(false),
-            italic: Some(false),
-            underlined: Some(false),
-            strikethrough: Some(false),
-            obfuscated: Some(false),
+            bold: None,
+            italic: None,
+            underlined: None,
+            strikethrough: None,
+            obfuscated: None,
             shadow_color: None,
             insertion: None,
             click_event: None,
@@ -130,24 +278,26 @@ impl From<&str> for TextComponentInner {
 impl TryFrom<Vec<TextComponent>> for TextComponentInner {
     type Error = &'static str;
 
-    fn try_from(mut value: Vec<TextComponent>) -> Result<Self, Self::Error> {
+    /// The first component is the parent of the rest, as in vanilla.
+    fn try_from(value: Vec<TextComponent>) -> Result<Self, Self::Error> {
+        let mut value = value.into_iter();
         let mut out = value
-            .pop()
+            .next()
             .ok_or("At least one component must be provided")?
             .0;
-        out.extra = value;
+        out.extra.extend(value);
 
         Ok(out)
     }
 }
 
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct ClickEvent {
     action: ClickEventAction,
     value: String,
 }
 
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum ClickEventAction {
     OpenUrl,
@@ -189,7 +339,7 @@ impl TryFrom<Identifier> for DialogKind {
     }
 }
 
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 #[serde(rename_all = "snake_case", tag = "type")]
 pub enum TextComponentContent {
     Text { text: String },
@@ -242,70 +392,75 @@ impl From<TextComponentContent> for OneOf<TextComponentContent, TextComponentCon
     }
 }
 
-#[derive(Serialize, Deserialize, Clone)]
+#[serde_as]
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct NbtComponent {
-    #[serde(flatten)]
-    nbt: Option<NbtData>,
+    nbt: NbtPath,
+    #[serde_as(as = "Option<FromInto<OneOf<bool, i8>>>")]
+    interpret: Option<bool>,
     // Boxed for indirection
-    // TODO: should this be gray?
-    #[serde(default = "default_separator")]
-    separator: Box<TextComponent>,
-}
-
-#[derive(Serialize, Deserialize, Clone)]
-pub struct NbtData {
-    source: NbtSource,
+    separator: Option<Box<TextComponent>>,
     #[serde(flatten)]
-    locator: Option<NbtLocator>,
+    source: NbtSource,
 }
 
-#[derive(Serialize, Deserialize, Clone)]
-pub struct NbtLocator {
-    nbt: NbtPath,
-    interpret: Option<bool>,
-    block: Coordinates,
-    entity: Selector,
-    storage: Identifier,
+impl NbtComponent {
+    /// Vanilla separates multiple matching values with an unstyled `", "` by default.
+    pub fn separator(&self) -> TextComponent {
+        self.separator
+            .as_deref()
+            .cloned()
+            .unwrap_or_else(|| ", ".into())
+    }
 }
 
-#[derive(Serialize, Deserialize, Clone)]
-#[serde(rename_all = "snake_case")]
+/// Where an [`NbtComponent`] reads its NBT from. Vanilla also accepts a redundant `source` field
+/// naming the variant, which is ignored.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(untagged)]
 pub enum NbtSource {
-    Block,
-    Entity,
-    Storage,
+    Block { block: Coordinates },
+    Entity { entity: Selector },
+    Storage { storage: Identifier },
 }
 
-#[derive(Serialize, Deserialize, Clone)]
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct SelectorComponent {
     selector: Selector,
-    #[serde(default = "default_separator")]
     // Boxed for indirection
-    separator: Box<TextComponent>,
+    separator: Option<Box<TextComponent>>,
 }
 
-fn default_separator() -> Box<TextComponent> {
-    Box::new(TextComponent(TextComponentInner {
-        color: Some("gray".parse().unwrap()),
-        ..", ".into()
-    }))
+impl SelectorComponent {
+    /// Vanilla separates multiple matching entities with a gray `", "` by default.
+    pub fn separator(&self) -> TextComponent {
+        self.separator.as_deref().cloned().unwrap_or_else(|| {
+            TextComponent(TextComponentInner {
+                color: Some("gray".parse().unwrap()),
+                ..", ".into()
+            })
+        })
+    }
 }
 
-#[derive(Serialize, Deserialize, Clone)]
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct TranslatableText {
-    translate: Identifier,
+    translate: String,
     fallback: Option<String>,
-    #[serde(default)]
-    with: Vec<Option<TextComponent>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    with: Vec<TextComponent>,
 }
 
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct Score {
     name: String,
     objective: String,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct TextComponentColor(String);
 
 impl std::fmt::Display for TextComponentColor {
@@ -318,9 +473,8 @@ impl FromStr for TextComponentColor {
     type Err = &'static str;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if s.strip_prefix("#<")
-            .and_then(|s| s.strip_suffix('>'))
-            .is_some_and(|s| u32::from_str_radix(s, 16).is_ok())
+        if s.strip_prefix('#')
+            .is_some_and(|s| s.len() == 6 && s.chars().all(|c| c.is_ascii_hexdigit()))
             || matches!(
                 s,
                 "black"
@@ -1,5 +1,9 @@
 use derive_more::{AsMut, AsRef, Deref, DerefMut};
+use serde::{Serialize, de::DeserializeOwned};
 use std::io::{Read, Write};
+use uuid::Uuid;
+
+pub use mc_server_rs_derive::Type;
 
 pub trait Type: Sized {
     fn write(&self, writer: impl Write) -> std::io::Result<()>;
@@ -161,25 +165,237 @@ impl_var_int! {
     VarLong, u64, i64
 }
 
+/// Vanilla strings are at most 32767 UTF-16 code units
+const MAX_STRING_UNITS: usize = 32767;
+// Each of which is at most 3 bytes of UTF-8, which bounds the length read before decoding
+const MAX_STRING_BYTES: i32 = MAX_STRING_UNITS as i32 * 3;
+
+fn check_string_units(s: &str, kind: std::io::ErrorKind) -> std::io::Result<()> {
+    let units = s.encode_utf16().count();
+    if units > MAX_STRING_UNITS {
+        return Err(std::io::Error::new(
+            kind,
+            format!("String of {units} UTF-16 code units is too long"),
+        ));
+    }
+    Ok(())
+}
+
 impl Type for String {
     fn write(&self, mut writer: impl Write) -> std::io::Result<()> {
-        let bytes = self.as_bytes();
+        check_string_units(self, std::io::ErrorKind::InvalidInput)?;
+        VarInt(self.len() as i32).write(&mut writer)?;
+        writer.write_all(self.as_bytes())
+    }
+
+    fn read(mut reader: impl Read) -> std::io::Result<Self> {
+        let length = VarInt::read(&mut reader)?;
+        if !(0..=MAX_STRING_BYTES).contains(&length.0) {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("Invalid string length {length}"),
+            ));
+        }
+
+        let mut buffer = vec![0; length.0 as usize];
+        reader.read_exact(&mut buffer)?;
+
+        let string = String::from_utf8(buffer)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        check_string_units(&string, std::io::ErrorKind::InvalidData)?;
+        Ok(string)
+    }
+}
+
+impl Type for Uuid {
+    fn write(&self, mut writer: impl Write) -> std::io::Result<()> {
+        writer.write_all(self.as_bytes())
+    }
+
+    fn read(mut reader: impl Read) -> std::io::Result<Self> {
+        let mut buffer = [0; 16];
+        reader.read_exact(&mut buffer)?;
+
+        Ok(Self::from_bytes(buffer))
+    }
+}
+
+/// Network NBT with any tag at the root.
+impl Type for fastnbt::Value {
+    fn write(&self, writer: impl Write) -> std::io::Result<()> {
+        crate::nbt::write(self, writer)
+    }
+
+    fn read(reader: impl Read) -> std::io::Result<Self> {
+        crate::nbt::read(reader)
+    }
+}
+
+impl<T: Type> Type for Box<T> {
+    fn write(&self, writer: impl Write) -> std::io::Result<()> {
+        T::write(self, writer)
+    }
+
+    fn read(reader: impl Read) -> std::io::Result<Self> {
+        T::read(reader).map(Box::new)
+    }
+}
+
+/// Each element in turn, with no length prefix.
+impl<T: Type, const N: usize> Type for [T; N] {
+    fn write(&self, mut writer: impl Write) -> std::io::Result<()> {
+        for value in self {
+            value.write(&mut writer)?;
+        }
+
+        Ok(())
+    }
+
+    fn read(mut reader: impl Read) -> std::io::Result<Self> {
+        let values = (0..N)
+            .map(|_| T::read(&mut reader))
+            .collect::<std::io::Result<Vec<_>>>()?;
+
+        Ok(values.try_into().unwrap_or_else(|_| unreachable!()))
+    }
+}
+
+/// A `bool` saying whether the value is present, followed by the value if it is.
+impl<T: Type> Type for Option<T> {
+    fn write(&self, mut writer: impl Write) -> std::io::Result<()> {
+        self.is_some().write(&mut writer)?;
+        if let Some(value) = self {
+            value.write(&mut writer)?;
+        }
+
+        Ok(())
+    }
+
+    fn read(mut reader: impl Read) -> std::io::Result<Self> {
+        if bool::read(&mut reader)? {
+            Ok(Some(T::read(&mut reader)?))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+/// Alternative wire encoding for a `T`, selected on a field with `#[mc(with = ...)]` when deriving
+/// [`Type`].
+pub trait Codec<T> {
+    fn write(value: &T, writer: impl Write) -> std::io::Result<()>;
+
+    fn read(reader: impl Read) -> std::io::Result<T>;
+}
+
+/// Encodes an `i32` as a [`VarInt`].
+pub struct AsVarInt;
+
+impl Codec<i32> for AsVarInt {
+    fn write(value: &i32, writer: impl Write) -> std::io::Result<()> {
+        VarInt(*value).write(writer)
+    }
+
+    fn read(reader: impl Read) -> std::io::Result<i32> {
+        Ok(*VarInt::read(reader)?)
+    }
+}
+
+/// Encodes an `i64` as a [`VarLong`].
+pub struct AsVarLong;
+
+impl Codec<i64> for AsVarLong {
+    fn write(value: &i64, writer: impl Write) -> std::io::Result<()> {
+        VarLong(*value).write(writer)
+    }
+
+    fn read(reader: impl Read) -> std::io::Result<i64> {
+        Ok(*VarLong::read(reader)?)
+    }
+}
+
+/// Encodes a `Vec<T>` as its length as a [`VarInt`], followed by each element.
+pub struct Prefixed;
+
+impl<T: Type> Codec<Vec<T>> for Prefixed {
+    fn write(value: &Vec<T>, mut writer: impl Write) -> std::io::Result<()> {
         VarInt(
-            bytes
+            value
                 .len()
                 .try_into()
                 .map_err(|e| std::io::Error::new(std::io::ErrorKind::FileTooLarge, e))?,
         )
         .write(&mut writer)?;
-        writer.write_all(bytes)
+
+        for element in value {
+            element.write(&mut writer)?;
+        }
+
+        Ok(())
     }
 
-    fn read(mut reader: impl Read) -> std::io::Result<Self> {
+    fn read(mut reader: impl Read) -> std::io::Result<Vec<T>> {
         let length = VarInt::read(&mut reader)?;
-        let mut buffer = vec![0; length.0 as usize];
-        reader.read_exact(&mut buffer)?;
+        if *length < 0 {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "Negative array length",
+            ));
+        }
+
+        // Elements are pushed one at a time so that a bogus length can't force a huge allocation
+        let mut out = Vec::new();
+        for _ in 0..*length {
+            out.push(T::read(&mut reader)?);
+        }
+
+        Ok(out)
+    }
+}
+
+/// Encodes a `Vec<u8>` as raw bytes that take up the rest of the input.
+pub struct Rest;
+
+impl Codec<Vec<u8>> for Rest {
+    fn write(value: &Vec<u8>, mut writer: impl Write) -> std::io::Result<()> {
+        writer.write_all(value)
+    }
+
+    fn read(mut reader: impl Read) -> std::io::Result<Vec<u8>> {
+        let mut out = Vec::new();
+        reader.read_to_end(&mut out)?;
+
+        Ok(out)
+    }
+}
+
+/// Encodes any serde type as a JSON [`String`].
+pub struct Json;
+
+impl<T: Serialize + DeserializeOwned> Codec<T> for Json {
+    fn write(value: &T, writer: impl Write) -> std::io::Result<()> {
+        serde_json::to_string(value)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?
+            .write(writer)
+    }
+
+    fn read(reader: impl Read) -> std::io::Result<T> {
+        serde_json::from_str(&String::read(reader)?)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+    }
+}
+
+/// Encodes any serde type as network NBT: a compound with no root name.
+pub struct Nbt;
+
+impl<T: Serialize + DeserializeOwned> Codec<T> for Nbt {
+    fn write(value: &T, writer: impl Write) -> std::io::Result<()> {
+        fastnbt::to_writer_with_opts(writer, value, fastnbt::SerOpts::network_nbt())
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+    }
 
-        String::from_utf8(buffer)
+    fn read(reader: impl Read) -> std::io::Result<T> {
+        fastnbt::from_reader_with_opts(reader, fastnbt::DeOpts::network_nbt())
             .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
     }
 }
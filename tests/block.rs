@@ -0,0 +1,113 @@
+use fastnbt::{Value, nbt};
+use mc_server_rs::{
+    data::block::{
+        Block, STATE_COUNT,
+        property::{Axis, ChestType, DispenserFacing, Face, Facing, NetherPortalAxis},
+    },
+    types::{AsVarInt, Codec, Type},
+};
+use rstest::rstest;
+
+#[test]
+fn state_ids() {
+    for id in 0..STATE_COUNT {
+        let block = Block::from_state_id(id).unwrap();
+        assert_eq!(id, block.state_id(), "{block}");
+    }
+    assert_eq!(None, Block::from_state_id(STATE_COUNT));
+    assert_eq!(27866, STATE_COUNT);
+
+    assert_eq!(0, Block::Air.state_id());
+    assert_eq!(
+        Block::GrassBlock { snowy: false },
+        Block::default_state("minecraft:grass_block").unwrap()
+    );
+    assert_eq!(
+        Block::OakLog { axis: Axis::Y },
+        Block::default_state("minecraft:oak_log").unwrap()
+    );
+}
+
+#[rstest]
+#[case::stone(Block::Stone, 1)]
+#[case::grass_block(Block::GrassBlock { snowy: false }, 9)]
+#[case::oak_log(Block::OakLog { axis: Axis::Y }, 137)]
+#[case::nether_portal(Block::NetherPortal { axis: NetherPortalAxis::X }, 6033)]
+#[case::last(Block::PottedClosedEyeblossom, STATE_COUNT - 1)]
+fn vanilla_ids(#[case] block: Block, #[case] id: u32) {
+    assert_eq!(id, block.state_id());
+}
+
+#[rstest]
+#[case(Block::Stone, nbt!({ "Name": "minecraft:stone" }))]
+#[case(Block::Water { level: 3 }, nbt!({ "Name": "minecraft:water", "Properties": { "level": "3" } }))]
+#[case(
+    Block::Chest { facing: Facing::East, r#type: ChestType::Left, waterlogged: true },
+    nbt!({
+        "Name": "minecraft:chest",
+        "Properties": { "facing": "east", "type": "left", "waterlogged": "true" },
+    })
+)]
+fn nbt(#[case] block: Block, #[case] expected: Value) {
+    assert_eq!(expected, fastnbt::to_value(block).unwrap());
+    assert_eq!(block, fastnbt::from_value(&expected).unwrap());
+}
+
+#[test]
+fn missing_properties_are_defaulted() {
+    assert_eq!(
+        Block::Dispenser {
+            facing: DispenserFacing::Up,
+            triggered: false,
+        },
+        fastnbt::from_value(&nbt!({
+            "Name": "minecraft:dispenser",
+            "Properties": { "facing": "up" },
+        }))
+        .unwrap()
+    );
+    assert_eq!(
+        Block::Tnt { unstable: false },
+        fastnbt::from_value(&nbt!({ "Name": "minecraft:tnt" })).unwrap()
+    );
+}
+
+#[rstest]
+#[case(nbt!({ "Name": "minecraft:not_a_block" }))]
+#[case(nbt!({ "Name": "minecraft:stone", "Properties": { "snowy": "true" } }))]
+#[case(nbt!({ "Name": "minecraft:water", "Properties": { "level": "16" } }))]
+#[case(nbt!({ "Name": "minecraft:chest", "Properties": { "facing": "up" } }))]
+#[case(nbt!({ "Name": "minecraft:tnt", "Properties": { "unstable": "1" } }))]
+fn invalid(#[case] value: Value) {
+    assert!(fastnbt::from_value::<Block>(&value).is_err());
+}
+
+#[test]
+fn display() {
+    assert_eq!("minecraft:stone", Block::Stone.to_string());
+    assert_eq!(
+        "minecraft:oak_leaves[distance=2,persistent=true,waterlogged=false]",
+        Block::OakLeaves {
+            distance: 2,
+            persistent: true,
+            waterlogged: false,
+        }
+        .to_string()
+    );
+}
+
+#[test]
+fn network() {
+    let block = Block::Lever {
+        face: Face::Ceiling,
+        facing: Facing::West,
+        powered: true,
+    };
+    let mut buffer = vec![];
+    block.write(&mut buffer).unwrap();
+    assert_eq!(block, Block::read(buffer.as_slice()).unwrap());
+
+    let mut buffer = vec![];
+    AsVarInt::write(&(STATE_COUNT as i32), &mut buffer).unwrap();
+    assert!(Block::read(buffer.as_slice()).is_err());
+}
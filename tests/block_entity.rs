@@ -0,0 +1,243 @@
+use fastnbt::{Value, nbt};
+use mc_server_rs::data::{
+    NbtPath,
+    block::{BlockEntity, entity::BlockEntityKind},
+};
+use rstest::rstest;
+
+#[rstest]
+#[case(nbt!({
+    "id": "minecraft:chest",
+    "x": 1, "y": 64, "z": -2,
+    "CustomName": { "type": "text", "text": "Loot", "color": "gold" },
+    "Items": [
+        { "Slot": 0_i8, "id": "minecraft:diamond", "count": 3 },
+        { "Slot": 26_i8, "id": "minecraft:stone", "count": 64 },
+    ],
+}))]
+#[case(nbt!({
+    "id": "minecraft:barrel",
+    "LootTable": "minecraft:chests/village/village_fisher",
+    "LootTableSeed": 5_i64,
+}))]
+#[case(nbt!({
+    "id": "minecraft:hopper",
+    "Items": [{ "Slot": 4_i8, "id": "minecraft:hopper", "count": 1 }],
+    "TransferCooldown": 8,
+}))]
+#[case(nbt!({
+    "id": "minecraft:sign",
+    "is_waxed": 1_i8,
+    "front_text": {
+        "has_glowing_text": 1_i8,
+        "color": "red",
+        "messages": [
+            { "type": "text", "text": "Hello" },
+            { "type": "text", "text": "world", "bold": 1_i8 },
+            { "type": "text", "text": "" },
+            { "type": "text", "text": "" },
+        ],
+    },
+    "back_text": {
+        "has_glowing_text": 0_i8,
+        "color": "black",
+        "messages": [
+            { "type": "text", "text": "" },
+            { "type": "text", "text": "" },
+            { "type": "text", "text": "" },
+            { "type": "text", "text": "" },
+        ],
+    },
+}))]
+#[case(nbt!({
+    "id": "minecraft:banner",
+    "patterns": [{ "color": "white", "pattern": "minecraft:creeper" }],
+}))]
+#[case(nbt!({
+    "id": "minecraft:furnace",
+    "Items": [{ "Slot": 0_i8, "id": "minecraft:iron_ore", "count": 1 }],
+    "lit_time_remaining": 40_i16,
+    "lit_total_time": 1600_i16,
+    "cooking_time_spent": 20_i16,
+    "cooking_total_time": 200_i16,
+    "RecipesUsed": { "minecraft:iron_ingot_from_smelting_iron_ore": 2 },
+}))]
+#[case(nbt!({
+    "id": "minecraft:campfire",
+    "Items": [{ "Slot": 1_i8, "id": "minecraft:beef", "count": 1 }],
+    "CookingTimes": [I; 0, 40, 0, 0],
+    "CookingTotalTimes": [I; 0, 600, 0, 0],
+}))]
+#[case(nbt!({
+    "id": "minecraft:brewing_stand",
+    "Items": [],
+    "BrewTime": 0_i16,
+    "Fuel": 20_i8,
+}))]
+#[case(nbt!({
+    "id": "minecraft:command_block",
+    "Command": "say hi",
+    "SuccessCount": 1,
+    "TrackOutput": 1_i8,
+    "auto": 0_i8,
+    "powered": 1_i8,
+    "conditionMet": 1_i8,
+    "UpdateLastExecution": 1_i8,
+    "LastExecution": 1200_i64,
+}))]
+#[case(nbt!({
+    "id": "minecraft:skull",
+    "profile": { "name": "Notch", "properties": [] },
+    "note_block_sound": "minecraft:entity.zombie.ambient",
+}))]
+#[case(nbt!({
+    "id": "minecraft:lectern",
+    "Book": { "id": "minecraft:written_book", "count": 1 },
+    "Page": 2,
+}))]
+#[case(nbt!({
+    "id": "minecraft:jukebox",
+    "RecordItem": { "id": "minecraft:music_disc_cat", "count": 1 },
+    "ticks_since_song_started": 100_i64,
+}))]
+#[case(nbt!({
+    "id": "minecraft:decorated_pot",
+    "sherds": ["minecraft:brick", "minecraft:arms_up_pottery_sherd", "minecraft:brick", "minecraft:brick"],
+}))]
+#[case(nbt!({
+    "id": "minecraft:vault",
+    "config": { "loot_table": "minecraft:chests/trial_chambers/reward", "key_item": { "id": "minecraft:trial_key", "count": 1 } },
+    "server_data": { "rewarded_players": [[I; 1, 2, 3, 4]], "state_updating_resumes_at": 0_i64, "items_to_eject": [], "total_ejections_needed": 0 },
+}))]
+#[case(nbt!({
+    "id": "minecraft:trial_spawner",
+    "normal_config": "minecraft:trial_chamber/zombie/normal",
+    "registered_players": [],
+    "current_mobs": [],
+    "cooldown_ends_at": 0_i64,
+    "next_mob_spawns_at": 0_i64,
+    "total_mobs_spawned": 0,
+}))]
+#[case(nbt!({ "id": "minecraft:bell" }))]
+#[case(nbt!({ "id": "minecraft:daylight_detector" }))]
+#[case(nbt!({ "id": "minecraft:comparator", "OutputSignal": 7 }))]
+#[case(nbt!({
+    "id": "minecraft:piston",
+    "blockState": { "Name": "minecraft:stone" },
+    "facing": 1,
+    "progress": 0.5_f32,
+    "extending": 1_i8,
+    "source": 0_i8,
+}))]
+#[case(nbt!({
+    "id": "minecraft:structure_block",
+    "name": "minecraft:village/houses/plains_small_house_1",
+    "author": "Notch",
+    "metadata": "",
+    "posX": 0, "posY": 1, "posZ": 0,
+    "sizeX": 7, "sizeY": 6, "sizeZ": 9,
+    "rotation": "NONE",
+    "mirror": "NONE",
+    "mode": "SAVE",
+    "ignoreEntities": 1_i8,
+    "strict": 0_i8,
+    "powered": 0_i8,
+    "showair": 0_i8,
+    "showboundingbox": 1_i8,
+    "integrity": 1.0_f32,
+    "seed": 0_i64,
+}))]
+#[case(nbt!({
+    "id": "minecraft:jigsaw",
+    "name": "minecraft:bottom",
+    "target": "minecraft:top",
+    "pool": "minecraft:empty",
+    "final_state": "minecraft:structure_void",
+    "joint": "rollable",
+    "placement_priority": 0,
+    "selection_priority": 0,
+}))]
+#[case(nbt!({
+    "id": "minecraft:crafter",
+    "Items": [{ "Slot": 4_i8, "id": "minecraft:stick", "count": 1 }],
+    "crafting_ticks_remaining": 0,
+    "triggered": 1,
+    "disabled_slots": [I; 0, 8],
+}))]
+#[case(nbt!({
+    "id": "minecraft:chiseled_bookshelf",
+    "Items": [{ "Slot": 5_i8, "id": "minecraft:book", "count": 1 }],
+    "last_interacted_slot": 5,
+}))]
+#[case(nbt!({
+    "id": "minecraft:sculk_sensor",
+    "last_vibration_frequency": 3,
+    "listener": { "event_delay": 0, "selector": { "tick": -1_i64 } },
+}))]
+#[case(nbt!({
+    "id": "minecraft:brushable_block",
+    "LootTable": "minecraft:archaeology/desert_pyramid",
+    "LootTableSeed": 12_i64,
+}))]
+#[case(nbt!({ "id": "minecraft:creaking_heart", "creaking": [I; 1, 2, 3, 4] }))]
+// Fields saved by a mod are kept
+#[case(nbt!({
+    "id": "minecraft:chest",
+    "x": 1, "y": 64, "z": -2,
+    "Items": [],
+    "example:lock": { "Owner": [I; 1, 2, 3, 4], "Keys": [L; 5_i64] },
+    "Tier": 2_i8,
+}))]
+fn round_trip(#[case] value: Value) {
+    let block_entity: BlockEntity =
+        fastnbt::from_bytes(&fastnbt::to_bytes(&value).unwrap()).unwrap();
+    let bytes = fastnbt::to_bytes(&block_entity).unwrap();
+    assert_eq!(value, fastnbt::from_bytes::<Value>(&bytes).unwrap());
+}
+
+#[rstest]
+#[case(nbt!({ "x": 0, "y": 0, "z": 0 }))]
+#[case(nbt!({ "id": "minecraft:sign", "front_text": { "messages": [] } }))]
+fn invalid(#[case] value: Value) {
+    assert!(fastnbt::from_bytes::<BlockEntity>(&fastnbt::to_bytes(&value).unwrap()).is_err());
+}
+
+#[rstest]
+#[case(nbt!({ "id": "minecraft:not_a_block_entity" }))]
+#[case(nbt!({
+    "id": "example:generator",
+    "x": 1, "y": 64, "z": -2,
+    "Energy": 5000_i64,
+    "Sides": [I; 0, 1, 1, 0, 0, 0],
+}))]
+fn unknown(#[case] value: Value) {
+    let block_entity: BlockEntity =
+        fastnbt::from_bytes(&fastnbt::to_bytes(&value).unwrap()).unwrap();
+    assert!(matches!(block_entity.kind(), BlockEntityKind::Unknown(_)));
+    assert_eq!(None, block_entity.kind().network_id());
+
+    let bytes = fastnbt::to_bytes(&block_entity).unwrap();
+    assert_eq!(value, fastnbt::from_bytes::<Value>(&bytes).unwrap());
+}
+
+#[test]
+fn plain_sign_text() {
+    let value = nbt!({
+        "id": "minecraft:sign",
+        "front_text": { "messages": ["Hello", "", "", ""] },
+        "back_text": { "messages": ["", "", "", ""] },
+    });
+    let sign: BlockEntity = fastnbt::from_bytes(&fastnbt::to_bytes(&value).unwrap()).unwrap();
+    let bytes = fastnbt::to_bytes(&sign).unwrap();
+    let value: Value = fastnbt::from_bytes(&bytes).unwrap();
+    let get = |path: &str| {
+        path.parse::<NbtPath>().unwrap().get(&value).unwrap()[0]
+            .clone()
+            .into_owned()
+    };
+    assert_eq!(
+        nbt!({ "type": "text", "text": "Hello" }),
+        get("front_text.messages[0]")
+    );
+    assert_eq!(Value::String("black".to_string()), get("front_text.color"));
+}
@@ -0,0 +1,318 @@
+use fastnbt::{ByteArray, LongArray, Value, nbt};
+use mc_server_rs::{
+    data::{
+        Identifier,
+        block::{Block, STATE_COUNT, property::Axis},
+        chunk::{Chunk, ChunkSection, ChunkStatus, LightArray, PalettedContainer},
+        registry::{DimensionType, Registries},
+    },
+    types::Type,
+};
+use rstest::rstest;
+
+fn overworld(registries: &Registries) -> DimensionType {
+    registries
+        .dimension_type
+        .get(&Identifier::minecraft("overworld"))
+        .unwrap()
+        .data
+        .clone()
+        .unwrap()
+}
+
+fn round_trip<T: serde::Serialize + serde::de::DeserializeOwned>(value: &Value) -> Value {
+    let decoded: T = fastnbt::from_bytes(&fastnbt::to_bytes(value).unwrap()).unwrap();
+    fastnbt::from_bytes(&fastnbt::to_bytes(&decoded).unwrap()).unwrap()
+}
+
+#[test]
+fn palette_grows_and_shrinks() {
+    let mut blocks = PalettedContainer::new(Block::Air);
+    assert_eq!(Block::Air, blocks.set(0, Block::Stone));
+    assert_eq!(Block::Air, blocks.set(4095, Block::Dirt));
+    assert_eq!(3, blocks.palette().len());
+    assert_eq!(2, blocks.count(|block| !block.is_air()));
+
+    // Setting the same block again changes nothing
+    assert_eq!(Block::Stone, blocks.set(0, Block::Stone));
+    assert_eq!(Block::Stone, blocks.set(0, Block::Air));
+    assert_eq!(2, blocks.palette().len());
+    assert_eq!(Block::Dirt, *blocks.get(4095));
+    assert_eq!(Block::Air, *blocks.get(1));
+
+    assert_eq!(Block::Dirt, blocks.set(4095, Block::Air));
+    assert_eq!(&[Block::Air], blocks.palette());
+    assert_eq!(PalettedContainer::new(Block::Air), blocks);
+
+    // Filling every entry but one leaves a single other value
+    for i in 0..4096 {
+        blocks.set(i, Block::Sand);
+    }
+    assert_eq!(&[Block::Sand], blocks.palette());
+}
+
+#[rstest]
+#[case(nbt!({ "palette": [{ "Name": "minecraft:stone" }] }))]
+#[case(nbt!({
+    "palette": [{ "Name": "minecraft:air" }, { "Name": "minecraft:stone" }],
+    "data": LongArray::new([vec![0x10], vec![0; 255]].concat()),
+}))]
+fn block_states_nbt(#[case] value: Value) {
+    assert_eq!(value, round_trip::<PalettedContainer<Block>>(&value));
+}
+
+#[test]
+fn packed_data() {
+    // 17 entries need 5 bits, 12 to a long with 4 bits left over
+    let mut palette = (0..16)
+        .map(|level| nbt!({ "Name": "minecraft:water", "Properties": { "level": level.to_string() } }))
+        .collect::<Vec<_>>();
+    palette.push(nbt!({ "Name": "minecraft:stone" }));
+    let mut data = vec![0_i64; 4096_usize.div_ceil(12)];
+    data[0] = 16 << 55 | 3 << 5;
+    data[1] = 1;
+    *data.last_mut().unwrap() = 2 << 15;
+    let blocks: PalettedContainer<Block> = fastnbt::from_bytes(
+        &fastnbt::to_bytes(&nbt!({ "palette": palette, "data": LongArray::new(data) })).unwrap(),
+    )
+    .unwrap();
+
+    // Palette values nothing uses are dropped
+    assert_eq!(5, blocks.palette().len());
+    assert_eq!(Block::Water { level: 0 }, *blocks.get(0));
+    assert_eq!(Block::Water { level: 3 }, *blocks.get(1));
+    assert_eq!(Block::Stone, *blocks.get(11));
+    assert_eq!(Block::Water { level: 1 }, *blocks.get(12));
+    assert_eq!(Block::Water { level: 2 }, *blocks.get(4095));
+}
+
+#[rstest]
+#[case(nbt!({ "palette": [] }))]
+#[case(nbt!({ "palette": [{ "Name": "minecraft:air" }, { "Name": "minecraft:stone" }] }))]
+#[case(nbt!({
+    "palette": [{ "Name": "minecraft:air" }, { "Name": "minecraft:stone" }],
+    "data": LongArray::new(vec![0; 255]),
+}))]
+#[case(nbt!({
+    "palette": [{ "Name": "minecraft:air" }, { "Name": "minecraft:stone" }],
+    "data": LongArray::new(vec![2; 256]),
+}))]
+fn invalid_block_states(#[case] value: Value) {
+    assert!(
+        fastnbt::from_bytes::<PalettedContainer<Block>>(&fastnbt::to_bytes(&value).unwrap())
+            .is_err()
+    );
+}
+
+#[test]
+fn section_nbt() {
+    let value = nbt!({
+        "Y": 3_i8,
+        "block_states": {
+            "palette": [{ "Name": "minecraft:stone" }, { "Name": "minecraft:oak_log", "Properties": { "axis": "z" } }],
+            "data": LongArray::new([vec![0x1000], vec![0; 255]].concat()),
+        },
+        "biomes": {
+            "palette": ["minecraft:plains", "minecraft:desert"],
+            "data": LongArray::new(vec![1 << 63]),
+        },
+        "BlockLight": ByteArray::new([vec![0x7f], vec![0; 2047]].concat()),
+        "SkyLight": ByteArray::new(vec![-1; 2048]),
+    });
+    let section: ChunkSection = fastnbt::from_bytes(&fastnbt::to_bytes(&value).unwrap()).unwrap();
+
+    assert_eq!(Block::OakLog { axis: Axis::Z }, section.block(3, 48, 0));
+    assert_eq!(Block::Stone, section.block(2, 48, 0));
+    assert_eq!(
+        Some(&Identifier::minecraft("desert")),
+        section.biome(15, 15, 15)
+    );
+    assert_eq!(
+        Some(&Identifier::minecraft("plains")),
+        section.biome(0, 15, 15)
+    );
+    let block_light = section.block_light().unwrap();
+    assert_eq!(15, block_light.get(0, 0, 0));
+    assert_eq!(7, block_light.get(1, 0, 0));
+    assert_eq!(0, block_light.get(2, 0, 0));
+    assert_eq!(15, section.sky_light().unwrap().get(9, 9, 9));
+
+    assert_eq!(value, round_trip::<ChunkSection>(&value));
+}
+
+#[test]
+fn light() {
+    let mut light = LightArray::new(0);
+    assert!(light.is_empty());
+    light.set(1, 2, 3, 9);
+    light.set(0, 2, 3, 4);
+    assert_eq!(9, light.get(1, 2, 3));
+    assert_eq!(4, light.get(0, 2, 3));
+    assert!(!light.is_empty());
+    assert_eq!(15, LightArray::new(15).get(5, 5, 5));
+
+    assert!(
+        fastnbt::from_bytes::<ChunkSection>(
+            &fastnbt::to_bytes(&nbt!({ "Y": 0_i8, "SkyLight": ByteArray::new(vec![0; 2047]) }))
+                .unwrap()
+        )
+        .is_err()
+    );
+}
+
+#[test]
+fn blocks() {
+    let registries = Registries::vanilla();
+    let mut chunk = Chunk::new(2, -3, &overworld(&registries));
+    assert_eq!(ChunkStatus::Full, chunk.status());
+    assert_eq!(24, chunk.sections().len());
+    assert_eq!(Block::Air, chunk.block(0, -64, 0));
+
+    assert_eq!(Block::Air, chunk.set_block(17, -64, -1, Block::Bedrock));
+    assert_eq!(Block::Bedrock, chunk.block(1, -64, 15));
+    assert_eq!(Block::Air, chunk.block(1, -63, 15));
+
+    // Outside the dimension, the section is added
+    assert_eq!(Block::Air, chunk.set_block(0, 400, 0, Block::Stone));
+    assert_eq!(Block::Stone, chunk.block(0, 400, 0));
+    assert_eq!(25, chunk.sections().len());
+
+    assert_eq!(
+        Some(Identifier::minecraft("plains")),
+        chunk.set_biome(0, 0, 0, Identifier::minecraft("desert"))
+    );
+    assert_eq!(Some(&Identifier::minecraft("desert")), chunk.biome(3, 3, 3));
+    assert_eq!(Some(&Identifier::minecraft("plains")), chunk.biome(4, 3, 3));
+}
+
+#[test]
+fn chunk_nbt() {
+    let value = nbt!({
+        "DataVersion": 4189,
+        "xPos": 2,
+        "yPos": -4,
+        "zPos": -3,
+        "Status": "minecraft:features",
+        "LastUpdate": 100_i64,
+        "InhabitedTime": 0_i64,
+        "isLightOn": 0_i8,
+        "sections": [
+            { "Y": -5_i8, "SkyLight": ByteArray::new(vec![0; 2048]) },
+            {
+                "Y": -4_i8,
+                "block_states": { "palette": [{ "Name": "minecraft:bedrock" }] },
+                "biomes": { "palette": ["minecraft:plains"] },
+            },
+        ],
+        "block_entities": [],
+        "Heightmaps": {
+            "OCEAN_FLOOR_WG": LongArray::new(vec![0; 37]),
+            "WORLD_SURFACE_WG": LongArray::new(vec![0; 37]),
+        },
+        "block_ticks": [{ "i": "minecraft:sand", "x": 32, "y": -60, "z": -48, "t": 2, "p": 0 }],
+        "fluid_ticks": [{ "i": "minecraft:water", "x": 33, "y": -60, "z": -48, "t": 5, "p": -1 }],
+        "PostProcessing": [],
+        // Saved by a mod
+        "example:pollution": { "Levels": LongArray::new(vec![7; 4]), "Source": [I; 1, 2, 3] },
+    });
+    assert_eq!(value, round_trip::<Chunk>(&value));
+
+    let chunk: Chunk = fastnbt::from_bytes(&fastnbt::to_bytes(&value).unwrap()).unwrap();
+    assert_eq!(ChunkStatus::Features, chunk.status());
+    assert_eq!(Block::Bedrock, chunk.block(0, -64, 0));
+    assert_eq!(Block::Air, chunk.block(0, -65, 0));
+    assert_eq!(2, chunk.block_ticks()[0].delay);
+    assert_eq!(Identifier::minecraft("water"), chunk.fluid_ticks()[0].id);
+}
+
+#[test]
+fn packet() {
+    let registries = Registries::vanilla();
+    let overworld = overworld(&registries);
+    let mut chunk = Chunk::new(2, -3, &overworld);
+    chunk.set_block(0, -64, 0, Block::Bedrock);
+    chunk.set_block(1, -64, 0, Block::Air);
+    chunk.set_block(2, -64, 0, Block::Stone);
+    chunk.set_biome(0, 0, 0, Identifier::minecraft("desert"));
+    *chunk.section_mut(-4).unwrap().sky_light_mut() = Some(LightArray::new(15));
+    *chunk.section_mut(19).unwrap().sky_light_mut() = Some(LightArray::new(0));
+    let mut block_light = LightArray::new(0);
+    block_light.set(0, 0, 0, 14);
+    *chunk.section_mut(0).unwrap().block_light_mut() = Some(block_light.clone());
+
+    let packet = chunk.to_packet(&overworld, &registries.biome).unwrap();
+    assert_eq!((2, -3), (packet.x, packet.z));
+
+    let block_bits = (STATE_COUNT as f64).log2().ceil() as u32;
+    let biome_bits = (registries.biome.entries().len() as f64).log2().ceil() as u32;
+    let mut data = packet.data.as_slice();
+    for y in -4..20 {
+        let non_air = i16::read(&mut data).unwrap();
+        let blocks = PalettedContainer::read_network(&mut data, block_bits, |id| {
+            Ok(Block::from_state_id(id as u32).unwrap())
+        })
+        .unwrap();
+        let biomes = PalettedContainer::read_network(&mut data, biome_bits, |id| {
+            Ok(registries.biome.entries()[id as usize].id.clone())
+        })
+        .unwrap();
+
+        assert_eq!(blocks, *chunk.section(y).unwrap().block_states().unwrap());
+        assert_eq!(biomes, *chunk.section(y).unwrap().biomes().unwrap());
+        assert_eq!(if y == -4 { 2 } else { 0 }, non_air);
+    }
+    assert!(data.is_empty());
+
+    // Light masks start from the section below the world, at -5
+    assert_eq!(vec![1 << 1], packet.light.sky_light_mask);
+    assert_eq!(vec![1 << 24], packet.light.empty_sky_light_mask);
+    assert_eq!(vec![1 << 5], packet.light.block_light_mask);
+    assert!(packet.light.empty_block_light_mask.is_empty());
+    assert_eq!(vec![LightArray::new(15)], packet.light.sky_light);
+    assert_eq!(vec![block_light], packet.light.block_light);
+
+    let mut buffer = vec![];
+    packet.write(&mut buffer).unwrap();
+    assert_eq!(packet, Type::read(buffer.as_slice()).unwrap());
+}
+
+#[test]
+fn unknown_biome() {
+    let registries = Registries::vanilla();
+    let overworld = overworld(&registries);
+    let mut chunk = Chunk::new(0, 0, &overworld);
+    chunk.set_biome(0, 0, 0, Identifier::new("example", "custom"));
+    assert!(chunk.to_packet(&overworld, &registries.biome).is_err());
+}
+
+#[test]
+fn packet_block_entities() {
+    let registries = Registries::vanilla();
+    let overworld = overworld(&registries);
+    let value = nbt!({
+        "DataVersion": 4189,
+        "xPos": 1,
+        "yPos": -4,
+        "zPos": 1,
+        "Status": "minecraft:full",
+        "LastUpdate": 0_i64,
+        "InhabitedTime": 0_i64,
+        "block_entities": [
+            { "id": "minecraft:chest", "x": 17, "y": -10, "z": 30, "CustomName": { "type": "text", "text": "Loot" } },
+            { "id": "minecraft:bell", "x": 31, "y": 100, "z": 16 },
+        ],
+    });
+    let chunk: Chunk = fastnbt::from_bytes(&fastnbt::to_bytes(&value).unwrap()).unwrap();
+    let packet = chunk.to_packet(&overworld, &registries.biome).unwrap();
+
+    let [chest, bell] = packet.block_entities.as_slice() else {
+        panic!("Expected two block entities");
+    };
+    assert_eq!((0x1e, -10, 1), (chest.packed_xz, chest.y, chest.kind));
+    assert_eq!(
+        nbt!({ "CustomName": { "type": "text", "text": "Loot" } }),
+        chest.data
+    );
+    assert_eq!((0xf0, 100, 31), (bell.packed_xz, bell.y, bell.kind));
+    assert_eq!(nbt!({}), bell.data);
+}
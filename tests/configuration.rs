@@ -0,0 +1,257 @@
+use fastnbt::Value;
+use mc_server_rs::{
+    data::{
+        Identifier,
+        registry::{BannerPattern, Entry, Registries, RegistryError},
+    },
+    frame::FrameCodec,
+    protocol::{
+        ConnectionState, Packet, Packets,
+        configuration::{self, KnownPack, RegistryData},
+        handshaking, login,
+    },
+    server::{
+        Connection,
+        configuration::{Configuration, ConfigurationError},
+        login::Login,
+    },
+};
+use rstest::{fixture, rstest};
+use std::net::{TcpListener, TcpStream};
+use uuid::Uuid;
+
+#[fixture]
+fn vanilla() -> Registries {
+    Registries::vanilla()
+}
+
+fn registry<'a>(packets: &'a [RegistryData], id: &str) -> &'a RegistryData {
+    packets
+        .iter()
+        .find(|packet| packet.registry_id.to_string() == id)
+        .unwrap()
+}
+
+#[rstest]
+fn known_core(vanilla: Registries) {
+    let packets = vanilla.to_packets(&[KnownPack::core()]).unwrap();
+
+    assert!(
+        packets
+            .iter()
+            .flat_map(|packet| &packet.entries)
+            .all(|entry| entry.data.is_none())
+    );
+    assert_eq!(
+        4,
+        registry(&packets, "minecraft:dimension_type").entries.len()
+    );
+    assert_eq!(
+        9,
+        registry(&packets, "minecraft:wolf_variant").entries.len()
+    );
+    assert_eq!(
+        "minecraft:the_end",
+        registry(&packets, "minecraft:dimension_type").entries[2]
+            .entry_id
+            .to_string()
+    );
+}
+
+#[rstest]
+fn required_registries(vanilla: Registries) {
+    let packets = vanilla.to_packets(&[KnownPack::core()]).unwrap();
+    let mut ids: Vec<_> = packets
+        .iter()
+        .map(|packet| packet.registry_id.path.as_str())
+        .collect();
+    ids.sort_unstable();
+
+    // Exactly the synced registries a 1.21.4 client needs, none of which may be empty
+    assert_eq!(
+        vec![
+            "banner_pattern",
+            "chat_type",
+            "damage_type",
+            "dimension_type",
+            "enchantment",
+            "instrument",
+            "jukebox_song",
+            "painting_variant",
+            "trim_material",
+            "trim_pattern",
+            "wolf_variant",
+            "worldgen/biome",
+        ],
+        ids
+    );
+    assert!(packets.iter().all(|packet| !packet.entries.is_empty()));
+}
+
+#[rstest]
+fn unknown_core(vanilla: Registries) {
+    assert!(matches!(
+        vanilla.to_packets(&[]),
+        Err(RegistryError::MissingData { registry, .. }) if registry.path == "worldgen/biome"
+    ));
+
+    let packet = vanilla.dimension_type.to_packet(&[]).unwrap();
+    let overworld = packet.entries[0].data.as_ref().unwrap();
+    let Value::Compound(overworld) = overworld else {
+        panic!("Dimension type is not a compound");
+    };
+    assert_eq!(Some(&Value::Int(-64)), overworld.get("min_y"));
+    assert_eq!(Some(&Value::Byte(1)), overworld.get("has_skylight"));
+    assert!(!overworld.contains_key("fixed_time"));
+    assert!(matches!(
+        overworld.get("monster_spawn_light_level"),
+        Some(Value::Compound(_))
+    ));
+}
+
+#[rstest]
+fn custom_entries(mut vanilla: Registries) {
+    let id = Identifier::new("example", "spotted");
+    vanilla.banner_pattern.insert(Entry::new(
+        id.clone(),
+        BannerPattern {
+            asset_id: Identifier::new("example", "spotted"),
+            translation_key: "block.example.banner.spotted".to_string(),
+        },
+    ));
+    assert_eq!(Some(43), vanilla.banner_pattern.network_id(&id));
+
+    let packets = vanilla.to_packets(&[KnownPack::core()]).unwrap();
+    let patterns = registry(&packets, "minecraft:banner_pattern");
+    assert!(
+        patterns.entries[..43]
+            .iter()
+            .all(|entry| entry.data.is_none())
+    );
+    assert_eq!(
+        Some(&fastnbt::nbt!({
+            "asset_id": "example:spotted",
+            "translation_key": "block.example.banner.spotted",
+        })),
+        patterns.entries[43].data.as_ref()
+    );
+
+    let frame = patterns.to_frame().unwrap();
+    assert_eq!(*patterns, frame.decode::<RegistryData>().unwrap());
+}
+
+/// Logs a client in offline, then runs `configuration` on the server side.
+fn serve(
+    configuration: Configuration,
+) -> (
+    TcpStream,
+    std::thread::JoinHandle<Result<ConnectionState, ConfigurationError>>,
+) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let mut client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+
+    let server = std::thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut connection = Connection::new(stream);
+        connection.receive()?;
+        let (connection, _) = Login::offline().run(connection).unwrap();
+        let (connection, information) = configuration.run(connection)?;
+        assert_eq!("en_us", information.unwrap().locale);
+        Ok(connection.state())
+    });
+
+    let codec = FrameCodec::new();
+    let frames = [
+        handshaking::Handshake {
+            protocol_version: 769,
+            server_address: "localhost".to_string(),
+            server_port: 25565,
+            intent: handshaking::Intent::Login,
+        }
+        .to_frame()
+        .unwrap(),
+        login::LoginStart {
+            name: "Notch".to_string(),
+            uuid: Uuid::nil(),
+        }
+        .to_frame()
+        .unwrap(),
+    ];
+    for frame in frames {
+        codec.write_frame(&frame, &mut client).unwrap();
+    }
+    codec.read_frame(&mut client).unwrap();
+    send(&mut client, login::LoginAcknowledged.to_frame().unwrap());
+
+    (client, server)
+}
+
+fn send(client: &mut TcpStream, frame: mc_server_rs::frame::Frame) {
+    FrameCodec::new().write_frame(&frame, client).unwrap();
+}
+
+fn receive(client: &mut TcpStream) -> configuration::Clientbound {
+    configuration::Clientbound::decode(&FrameCodec::new().read_frame(client).unwrap()).unwrap()
+}
+
+fn client_information() -> configuration::ClientInformation {
+    configuration::ClientInformation {
+        locale: "en_us".to_string(),
+        view_distance: 10,
+        chat_mode: configuration::ChatMode::Enabled,
+        chat_colors: true,
+        displayed_skin_parts: 0x7f,
+        main_hand: configuration::MainHand::Right,
+        enable_text_filtering: false,
+        allow_server_listings: true,
+        particle_status: configuration::ParticleStatus::All,
+    }
+}
+
+#[test]
+fn negotiation() {
+    let (mut client, server) = serve(Configuration::default());
+
+    let configuration::Clientbound::FeatureFlags(flags) = receive(&mut client) else {
+        panic!("Expected Feature Flags");
+    };
+    assert_eq!(vec![Identifier::minecraft("vanilla")], flags.feature_flags);
+    let configuration::Clientbound::ClientboundKnownPacks(packs) = receive(&mut client) else {
+        panic!("Expected Known Packs");
+    };
+    assert_eq!(vec![KnownPack::core()], packs.known_packs);
+
+    send(&mut client, client_information().to_frame().unwrap());
+    send(
+        &mut client,
+        configuration::ServerboundKnownPacks {
+            known_packs: packs.known_packs,
+        }
+        .to_frame()
+        .unwrap(),
+    );
+
+    let mut registries = vec![];
+    loop {
+        match receive(&mut client) {
+            configuration::Clientbound::RegistryData(registry) => registries.push(registry),
+            configuration::Clientbound::FinishConfiguration(_) => break,
+            _ => panic!("Unexpected packet"),
+        }
+    }
+    assert_eq!(12, registries.len());
+    assert!(
+        registries
+            .iter()
+            .flat_map(|registry| &registry.entries)
+            .all(|entry| entry.data.is_none())
+    );
+
+    send(
+        &mut client,
+        configuration::AcknowledgeFinishConfiguration
+            .to_frame()
+            .unwrap(),
+    );
+    assert_eq!(ConnectionState::Play, server.join().unwrap().unwrap());
+}
@@ -0,0 +1,40 @@
+use mc_server_rs::data::Coordinates;
+use rstest::rstest;
+
+#[rstest]
+#[case::absolute("1 64 -3")]
+#[case::relative("~1 ~-2 ~0.5")]
+#[case::local("^0.5 ^1 ^2")]
+#[case::mixed("1 ~2 3")]
+#[case::bare("~ ~1 ~")]
+fn round_trip(#[case] s: &str) {
+    assert_eq!(s, s.parse::<Coordinates>().unwrap().to_string());
+}
+
+#[test]
+fn axis_order() {
+    assert_ne!(
+        "1 2 3".parse::<Coordinates>().unwrap(),
+        "3 2 1".parse::<Coordinates>().unwrap()
+    );
+}
+
+#[test]
+fn bare_prefix_is_no_offset() {
+    assert_eq!(
+        "~0 ^0 ~".parse::<Coordinates>().unwrap(),
+        "~ ^ ~0".parse::<Coordinates>().unwrap()
+    );
+    assert_eq!(
+        "~ ^ ~",
+        "~0 ^0 ~0".parse::<Coordinates>().unwrap().to_string()
+    );
+}
+
+#[rstest]
+#[case::too_few("1 2")]
+#[case::too_many("1 2 3 4")]
+#[case::not_a_number("1 two 3")]
+fn invalid(#[case] s: &str) {
+    assert!(s.parse::<Coordinates>().is_err());
+}
@@ -0,0 +1,126 @@
+use mc_server_rs::types::{Type, VarInt};
+use rstest::rstest;
+use std::fmt::Debug;
+
+#[derive(Type, Debug, PartialEq)]
+struct Handshake {
+    #[mc(var_int)]
+    protocol_version: i32,
+    server_address: String,
+    server_port: u16,
+    intent: Intent,
+}
+
+#[derive(Type, Debug, PartialEq)]
+enum Intent {
+    Status = 1,
+    Login,
+    Transfer,
+}
+
+#[derive(Type, Debug, PartialEq)]
+struct Tuple(#[mc(var_long)] i64, bool);
+
+#[derive(Type, Debug, PartialEq)]
+struct Unit;
+
+#[derive(Type, Debug, PartialEq)]
+struct Prefixed {
+    #[mc(prefixed)]
+    strings: Vec<String>,
+    #[mc(rest)]
+    data: Vec<u8>,
+}
+
+#[derive(Type, Debug, PartialEq)]
+enum WithFields {
+    #[mc(id = 7)]
+    Named {
+        value: i32,
+    },
+    Unnamed(VarInt, String),
+    Unit,
+}
+
+#[derive(Type, Debug, PartialEq)]
+struct Generic<T> {
+    value: T,
+}
+
+fn round_trip<T: Type + Debug + PartialEq>(value: T, bytes: &[u8]) {
+    let mut buffer = Vec::new();
+    value.write(&mut buffer).unwrap();
+    assert_eq!(bytes, buffer);
+
+    let read = T::read(buffer.as_slice()).unwrap();
+    assert_eq!(value, read);
+}
+
+#[test]
+fn handshake() {
+    round_trip(
+        Handshake {
+            protocol_version: 769,
+            server_address: "localhost".to_string(),
+            server_port: 25565,
+            intent: Intent::Login,
+        },
+        &[
+            0x81, 0x06, 0x09, b'l', b'o', b'c', b'a', b'l', b'h', b'o', b's', b't', 0x63, 0xdd,
+            0x02,
+        ],
+    );
+}
+
+#[rstest]
+#[case(Intent::Status, &[0x01])]
+#[case(Intent::Login, &[0x02])]
+#[case(Intent::Transfer, &[0x03])]
+fn unit_enum(#[case] value: Intent, #[case] bytes: &[u8]) {
+    round_trip(value, bytes);
+}
+
+#[rstest]
+#[case(WithFields::Named { value: 1 }, &[0x07, 0x00, 0x00, 0x00, 0x01])]
+#[case(WithFields::Unnamed(VarInt::new(300), "a".to_string()), &[0x08, 0xac, 0x02, 0x01, b'a'])]
+#[case(WithFields::Unit, &[0x09])]
+fn enum_with_fields(#[case] value: WithFields, #[case] bytes: &[u8]) {
+    round_trip(value, bytes);
+}
+
+#[test]
+fn tuple() {
+    round_trip(
+        Tuple(-1, true),
+        &[
+            0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01, 0x01,
+        ],
+    );
+}
+
+#[test]
+fn unit() {
+    round_trip(Unit, &[]);
+}
+
+#[test]
+fn prefixed() {
+    round_trip(
+        Prefixed {
+            strings: vec!["a".to_string(), "bc".to_string()],
+            data: vec![1, 2, 3],
+        },
+        &[0x02, 0x01, b'a', 0x02, b'b', b'c', 0x01, 0x02, 0x03],
+    );
+}
+
+#[test]
+fn generic() {
+    round_trip(Generic { value: 0x1234u16 }, &[0x12, 0x34]);
+}
+
+#[test]
+fn invalid_discriminant() {
+    let error = Intent::read([0x04].as_slice()).unwrap_err();
+    assert_eq!(std::io::ErrorKind::InvalidData, error.kind());
+}
@@ -0,0 +1,182 @@
+use mc_server_rs::{
+    encryption::{
+        EncryptedReader, EncryptedWriter, EncryptionResponse, ServerKey, encrypt_stream,
+        server_hash,
+    },
+    frame::{Frame, FrameCodec},
+    types::Type,
+};
+use rstest::rstest;
+use std::io::{Read, Write};
+
+const SECRET: [u8; 16] = [
+    0x2b, 0x7e, 0x15, 0x16, 0x28, 0xae, 0xd2, 0xa6, 0xab, 0xf7, 0x15, 0x88, 0x09, 0xcf, 0x4f, 0x3c,
+];
+
+const PLAINTEXT: [u8; 18] = [
+    0x6b, 0xc1, 0xbe, 0xe2, 0x2e, 0x40, 0x9f, 0x96, 0xe9, 0x3d, 0x7e, 0x11, 0x73, 0x93, 0x17, 0x2a,
+    0xae, 0x2d,
+];
+
+// AES-128-CFB8 of `PLAINTEXT` with `SECRET` as both key and IV
+const CIPHERTEXT: [u8; 18] = [
+    0x14, 0x11, 0x8e, 0x07, 0x10, 0xeb, 0x1b, 0x4c, 0xa3, 0x0a, 0xe2, 0xde, 0x24, 0x74, 0x7e, 0xfb,
+    0x61, 0x73,
+];
+
+// Sample hashes from Minecraft Wiki (https://minecraft.wiki/w/Minecraft_Wiki:Projects/wiki.vg_merge/Protocol_Encryption#Sample_Code)
+#[rstest]
+#[case("Notch", "4ed1f46bbe04bc756bcb17c0c7ce3e4632f06a48")]
+#[case("jeb_", "-7c9d5b0044c130109a5d7b5fb5c317c02b4e28c1")]
+#[case("simon", "88e16a1019277b15d58faf0541e11910eb756f6")]
+fn hash(#[case] input: &str, #[case] expected: &str) {
+    assert_eq!(expected, server_hash(input, &[], &[]));
+}
+
+#[test]
+fn encrypt() {
+    let mut writer = EncryptedWriter::new(Vec::new(), &SECRET);
+    // Split across writes to make sure the cipher state carries over
+    writer.write_all(&PLAINTEXT[..5]).unwrap();
+    writer.write_all(&PLAINTEXT[5..]).unwrap();
+    assert_eq!(CIPHERTEXT.as_slice(), writer.into_inner());
+}
+
+#[test]
+fn decrypt() {
+    let mut reader = EncryptedReader::new(CIPHERTEXT.as_slice(), &SECRET);
+    let mut buffer = Vec::new();
+    reader.read_to_end(&mut buffer).unwrap();
+    assert_eq!(PLAINTEXT.as_slice(), buffer);
+}
+
+/// Accepts at most 4 bytes at a time, and fails the calls listed in `failing`.
+struct Flaky {
+    written: Vec<u8>,
+    calls: usize,
+    failing: &'static [usize],
+}
+
+impl Write for Flaky {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.calls += 1;
+        if self.failing.contains(&self.calls) {
+            return Err(std::io::ErrorKind::ConnectionReset.into());
+        }
+
+        let length = buf.len().min(4);
+        self.written.extend_from_slice(&buf[..length]);
+        Ok(length)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn retry_after_error() {
+    let flaky = Flaky {
+        written: Vec::new(),
+        calls: 0,
+        failing: &[2, 3],
+    };
+    let mut writer = EncryptedWriter::new(flaky, &SECRET);
+    // The first write is accepted even though writing it out fails part way through
+    assert_eq!(5, writer.write(&PLAINTEXT[..5]).unwrap());
+    // The second is refused before it goes through the cipher, so retrying it is safe
+    assert!(writer.write(&PLAINTEXT[5..]).is_err());
+    writer.write_all(&PLAINTEXT[5..]).unwrap();
+    writer.flush().unwrap();
+    assert_eq!(CIPHERTEXT.as_slice(), writer.into_inner().written);
+}
+
+#[tokio::test]
+async fn async_round_trip() {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let mut writer = EncryptedWriter::new(Vec::new(), &SECRET);
+    AsyncWriteExt::write_all(&mut writer, &PLAINTEXT)
+        .await
+        .unwrap();
+    AsyncWriteExt::flush(&mut writer).await.unwrap();
+    let encrypted = writer.into_inner();
+    assert_eq!(CIPHERTEXT.as_slice(), encrypted);
+
+    let mut reader = EncryptedReader::new(encrypted.as_slice(), &SECRET);
+    let mut buffer = Vec::new();
+    AsyncReadExt::read_to_end(&mut reader, &mut buffer)
+        .await
+        .unwrap();
+    assert_eq!(PLAINTEXT.as_slice(), buffer);
+}
+
+#[test]
+fn frames() {
+    let frame = Frame::encode(0x05, &"encrypted".to_string()).unwrap();
+    let codec = FrameCodec::new();
+
+    let mut writer = EncryptedWriter::new(Vec::new(), &SECRET);
+    codec.write_frame(&frame, &mut writer).unwrap();
+    let encrypted = writer.into_inner();
+
+    let reader = EncryptedReader::new(encrypted.as_slice(), &SECRET);
+    assert_eq!(frame, codec.read_frame(reader).unwrap());
+}
+
+#[tokio::test]
+async fn duplex_frames() {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    async fn send(stream: &mut (impl AsyncWriteExt + Unpin), codec: &FrameCodec, frame: &Frame) {
+        let mut buffer = Vec::new();
+        codec.write_frame(frame, &mut buffer).unwrap();
+        stream.write_all(&buffer).await.unwrap();
+        stream.flush().await.unwrap();
+    }
+
+    async fn receive(stream: &mut (impl AsyncReadExt + Unpin), codec: &FrameCodec) -> Frame {
+        let mut buffer = Vec::new();
+        loop {
+            if let Some((frame, read)) = codec.decode_frame(&buffer).unwrap() {
+                assert_eq!(buffer.len(), read);
+                return frame;
+            }
+            let mut byte = [0];
+            stream.read_exact(&mut byte).await.unwrap();
+            buffer.push(byte[0]);
+        }
+    }
+
+    let (client, server) = tokio::io::duplex(64);
+    let mut client = encrypt_stream(client, &SECRET);
+    let mut server = encrypt_stream(server, &SECRET);
+    let codec = FrameCodec::new();
+
+    for i in 0..3 {
+        let request = Frame::encode(0x01, &format!("request {i}")).unwrap();
+        send(&mut client, &codec, &request).await;
+        assert_eq!(request, receive(&mut server, &codec).await);
+
+        let response = Frame::encode(0x02, &format!("response {i}")).unwrap();
+        send(&mut server, &codec, &response).await;
+        assert_eq!(response, receive(&mut client, &codec).await);
+    }
+}
+
+#[test]
+fn key_exchange() {
+    let key = ServerKey::generate().unwrap();
+    let request = key.encryption_request(vec![1, 2, 3, 4], true);
+
+    let mut buffer = Vec::new();
+    request.write(&mut buffer).unwrap();
+    let request = Type::read(buffer.as_slice()).unwrap();
+
+    let response = EncryptionResponse::new(&request, &SECRET).unwrap();
+    assert_eq!(
+        SECRET,
+        key.decrypt_response(&response, &[1, 2, 3, 4]).unwrap()
+    );
+    assert!(key.decrypt_response(&response, &[4, 3, 2, 1]).is_err());
+}
@@ -0,0 +1,420 @@
+use fastnbt::{Value, nbt};
+use mc_server_rs::data::{
+    Identifier,
+    chunk::EntityChunk,
+    entity::{
+        Entity, EntityKind, EntitySubtype,
+        mob::{UniqueMob, tropical_fish::Pattern},
+        spawn::EntityError,
+    },
+    level::LevelData,
+    world::{RegionKind, World},
+};
+use strum::IntoEnumIterator;
+
+fn entity(id: &str, uuid: i32, pos: [f64; 3], extra: Value) -> Value {
+    let Value::Compound(mut entity) = nbt!({
+        "id": id,
+        "Air": 300_i16,
+        "FallDistance": 0.0_f32,
+        "Fire": -1_i16,
+        "Invulnerable": 0_i8,
+        "Motion": [0.0_f64, 0.0_f64, 0.0_f64],
+        "OnGround": 1_i8,
+        "PortalCooldown": 0_i32,
+        "Pos": [pos[0], pos[1], pos[2]],
+        "Rotation": [45.0_f32, 0.0_f32],
+        "UUID": [I; 0, 0, 0, uuid],
+    }) else {
+        unreachable!()
+    };
+    let Value::Compound(extra) = extra else {
+        unreachable!()
+    };
+    entity.extend(extra);
+    Value::Compound(entity)
+}
+
+fn experience_orb(uuid: i32, pos: [f64; 3]) -> Value {
+    entity(
+        "minecraft:experience_orb",
+        uuid,
+        pos,
+        nbt!({ "Age": 10_i16, "Count": 1_i32, "Health": 5_i16, "Value": 7_i16 }),
+    )
+}
+
+fn mob(id: &str, uuid: i32, extra: Value) -> Value {
+    let Value::Compound(mut mob) = nbt!({
+        "AbsorptionAmount": 0.0_f32,
+        "ArmorDropChances": [0.085_f32, 0.085_f32, 0.085_f32, 0.085_f32],
+        "ArmorItems": [{}, {}, {}, {}],
+        "attributes": [{ "id": "minecraft:movement_speed", "base": 0.23_f64 }],
+        "Brain": { "memories": {} },
+        "CanPickUpLoot": 0_i8,
+        "DeathTime": 0_i16,
+        "FallFlying": 0_i8,
+        "HandDropChances": [0.085_f32, 0.085_f32],
+        "HandItems": [{}, {}],
+        "Health": 20.0_f32,
+        "HurtByTimestamp": 0_i32,
+        "HurtTime": 0_i16,
+        "LeftHanded": 0_i8,
+        "PersistenceRequired": 0_i8,
+    }) else {
+        unreachable!()
+    };
+    let Value::Compound(extra) = extra else {
+        unreachable!()
+    };
+    mob.extend(extra);
+    entity(id, uuid, [0.5, 64.0, 0.5], Value::Compound(mob))
+}
+
+fn zombie(id: &str, uuid: i32) -> Value {
+    mob(
+        id,
+        uuid,
+        nbt!({
+            "CanBreakDoors": 0_i8,
+            "DrownedConversionTime": -1_i32,
+            "InWaterTime": -1_i32,
+            "IsBaby": 0_i8,
+        }),
+    )
+}
+
+fn unique(entity: &Entity) -> &UniqueMob {
+    let EntitySubtype::Mob(mob) = entity.subtype() else {
+        panic!("Expected a mob");
+    };
+    mob.unique().expect("Expected a unique mob")
+}
+
+fn decode<T: serde::de::DeserializeOwned>(value: &Value) -> T {
+    fastnbt::from_bytes(&fastnbt::to_bytes(value).unwrap()).unwrap()
+}
+
+fn encode<T: serde::Serialize>(value: &T) -> Value {
+    fastnbt::from_bytes(&fastnbt::to_bytes(value).unwrap()).unwrap()
+}
+
+#[test]
+fn entity_chunk() {
+    let value = nbt!({
+        "DataVersion": 4189_i32,
+        "Position": [I; -3, 7],
+        "Entities": [experience_orb(1, [-40.5, 64.0, 120.5]), experience_orb(2, [-35.0, 70.0, 113.0])],
+    });
+    let chunk: EntityChunk = decode(&value);
+    assert_eq!((-3, 7), chunk.position());
+    assert_eq!(4189, chunk.data_version());
+    assert_eq!(2, chunk.entities().len());
+    assert_eq!(
+        Some(&Identifier::minecraft("experience_orb")),
+        chunk.entities()[0].id()
+    );
+    assert!(matches!(
+        chunk.entities()[0].subtype(),
+        EntitySubtype::ExperienceOrb(_)
+    ));
+    assert_eq!(value, encode(&chunk));
+}
+
+#[test]
+fn read_and_write() {
+    let directory = tempfile::tempdir().unwrap();
+    let world = World::create(directory.path(), LevelData::new("Entities", 0)).unwrap();
+    let overworld = Identifier::minecraft("overworld");
+
+    assert!(world.read_entities(&overworld, -3, 7).unwrap().is_empty());
+
+    let entities: Vec<Entity> = vec![
+        decode(&experience_orb(1, [-40.5, 64.0, 120.5])),
+        decode(&experience_orb(2, [-35.0, 70.0, 113.0])),
+    ];
+    world.write_entities(&overworld, -3, 7, entities).unwrap();
+    world
+        .write_entities(
+            &overworld,
+            40,
+            7,
+            vec![decode(&experience_orb(3, [640.5, 64.0, 120.5]))],
+        )
+        .unwrap();
+    assert!(directory.path().join("entities/r.-1.0.mca").exists());
+    // Terrain is left alone
+    assert!(!directory.path().join("region").exists());
+
+    let mut chunks = world.chunks(&overworld, RegionKind::Entities).unwrap();
+    chunks.sort();
+    assert_eq!(vec![(-3, 7), (40, 7)], chunks);
+
+    // Drop one entity and move the other
+    let mut entities = world.read_entities(&overworld, -3, 7).unwrap();
+    assert_eq!(2, entities.len());
+    entities.retain(|entity| entity.pos()[0] > -40.0);
+    entities[0].set_pos([-36.0, 71.0, 114.0]);
+    world.write_entities(&overworld, -3, 7, entities).unwrap();
+
+    let entities = world.read_entities(&overworld, -3, 7).unwrap();
+    assert_eq!(1, entities.len());
+    assert_eq!([-36.0, 71.0, 114.0], entities[0].pos());
+    assert_eq!(
+        experience_orb(2, [-36.0, 71.0, 114.0]),
+        encode(&entities[0])
+    );
+
+    // A chunk left empty is removed
+    world.write_entities(&overworld, 40, 7, vec![]).unwrap();
+    assert_eq!(
+        vec![(-3, 7)],
+        world.chunks(&overworld, RegionKind::Entities).unwrap()
+    );
+
+    // Clearing a chunk that was never saved doesn't make a region file for it
+    let nether = Identifier::minecraft("the_nether");
+    world.write_entities(&nether, 0, 0, vec![]).unwrap();
+    assert!(!directory.path().join("DIM-1/entities").exists());
+}
+
+#[test]
+fn zombies_by_id() {
+    let zombie_value = zombie("minecraft:zombie", 1);
+    let husk_value = zombie("minecraft:husk", 2);
+    let drowned_value = zombie("minecraft:drowned", 3);
+    let zombie: Entity = decode(&zombie_value);
+    let husk: Entity = decode(&husk_value);
+    let drowned: Entity = decode(&drowned_value);
+
+    assert_eq!(Some(EntityKind::Zombie), zombie.kind());
+    assert!(matches!(unique(&zombie), UniqueMob::Zombie(_)));
+    assert_eq!(Some(EntityKind::Husk), husk.kind());
+    assert!(matches!(unique(&husk), UniqueMob::Husk(_)));
+    assert_eq!(Some(EntityKind::Drowned), drowned.kind());
+    assert!(matches!(unique(&drowned), UniqueMob::Drowned(_)));
+
+    assert_eq!(zombie_value, encode(&zombie));
+    assert_eq!(husk_value, encode(&husk));
+    assert_eq!(drowned_value, encode(&drowned));
+}
+
+#[test]
+fn wolf() {
+    let value = mob(
+        "minecraft:wolf",
+        1,
+        nbt!({
+            "Age": 0_i32,
+            "AngerTime": 0_i32,
+            "CollarColor": 14_i8,
+            "ForcedAge": 0_i32,
+            "InLove": 0_i32,
+            "Owner": [I; 1, 2, 3, 4],
+            "Sitting": 1_i8,
+            "variant": "minecraft:snowy",
+        }),
+    );
+    let wolf: Entity = decode(&value);
+    assert!(matches!(unique(&wolf), UniqueMob::Wolf(_)));
+    assert_eq!(value, encode(&wolf));
+}
+
+#[test]
+fn end_crystal() {
+    let value = entity(
+        "minecraft:end_crystal",
+        1,
+        [0.5, 80.0, 0.5],
+        nbt!({ "ShowBottom": 1_i8 }),
+    );
+    let crystal: Entity = decode(&value);
+    assert_eq!(Some(EntityKind::EndCrystal), crystal.kind());
+    assert!(matches!(crystal.subtype(), EntitySubtype::EndCrystal(_)));
+    assert_eq!(value, encode(&crystal));
+}
+
+#[test]
+fn flags_left_out_when_unset() {
+    let crystal = |extra: Value| {
+        let Value::Compound(mut fields) = nbt!({ "ShowBottom": 1_i8 }) else {
+            unreachable!()
+        };
+        let Value::Compound(extra) = extra else {
+            unreachable!()
+        };
+        fields.extend(extra);
+        entity(
+            "minecraft:end_crystal",
+            1,
+            [0.5, 80.0, 0.5],
+            Value::Compound(fields),
+        )
+    };
+    let value = crystal(nbt!({ "HasVisualFire": 1_i8, "NoGravity": 1_i8, "TicksFrozen": 140 }));
+    let mut entity: Entity = decode(&value);
+    assert!(entity.has_visual_fire());
+    assert!(entity.no_gravity());
+    assert_eq!(140, entity.ticks_frozen());
+    assert_eq!(value, encode(&entity));
+
+    entity.set_has_visual_fire(false);
+    entity.set_no_gravity(false);
+    entity.set_ticks_frozen(0);
+    assert_eq!(crystal(nbt!({})), encode(&entity));
+}
+
+#[test]
+fn unknown() {
+    let boat = entity(
+        "minecraft:oak_boat",
+        1,
+        [0.5, 62.5, 0.5],
+        nbt!({ "Type": "oak" }),
+    );
+    let modded = entity(
+        "example:robot",
+        2,
+        [0.5, 64.0, 0.5],
+        nbt!({ "Battery": 0.5_f32, "Program": [I; 1, 2, 3] }),
+    );
+    for value in [boat, modded] {
+        let entity: Entity = decode(&value);
+        assert!(matches!(entity.subtype(), EntitySubtype::Unknown(_)));
+        assert_eq!(value, encode(&entity));
+    }
+}
+
+#[test]
+fn errors() {
+    let Value::Compound(mut husk) = zombie("minecraft:husk", 1) else {
+        unreachable!()
+    };
+    husk.remove("CanBreakDoors");
+    let bytes = fastnbt::to_bytes(&Value::Compound(husk)).unwrap();
+    let error = fastnbt::from_bytes::<Entity>(&bytes).err().unwrap();
+    assert!(
+        error
+            .to_string()
+            .contains("Invalid minecraft:husk entity: missing field `CanBreakDoors`"),
+        "{error}"
+    );
+}
+
+#[test]
+fn unknown_fields() {
+    let Value::Compound(mut rider) = zombie("minecraft:zombie", 2) else {
+        unreachable!()
+    };
+    rider.insert("FutureField".to_string(), nbt!([L; 3_i64]));
+    let Value::Compound(mut value) = zombie("minecraft:husk", 1) else {
+        unreachable!()
+    };
+    value.insert(
+        "example:infection".to_string(),
+        nbt!({ "Stage": 2_i8, "Source": [I; 1, 2, 3, 4] }),
+    );
+    value.insert(
+        "Passengers".to_string(),
+        Value::List(vec![Value::Compound(rider)]),
+    );
+    let value = Value::Compound(value);
+
+    let husk: Entity = decode(&value);
+    assert!(matches!(unique(&husk), UniqueMob::Husk(_)));
+    assert_eq!(
+        vec!["example:infection"],
+        husk.extra().keys().collect::<Vec<_>>()
+    );
+    assert!(husk.passengers()[0].extra().contains_key("FutureField"));
+    assert_eq!(value, encode(&husk));
+}
+
+#[test]
+fn spawn_defaults() {
+    for kind in EntityKind::iter() {
+        let entity = Entity::new(kind);
+        assert_eq!(Some(kind), entity.kind());
+        // Players are living, but only ever saved on their own
+        let living = kind.is_living() && kind != EntityKind::Player;
+        assert_eq!(living, entity.mob().is_some(), "{kind:?}");
+        assert_eq!(kind.is_living(), kind.max_health().is_some(), "{kind:?}");
+        let value = encode(&entity);
+        assert_eq!(value, encode(&decode::<Entity>(&value)), "{kind:?}");
+    }
+}
+
+#[test]
+fn tropical_fish_patterns() {
+    for pattern in Pattern::iter() {
+        assert_eq!(Ok(Some(pattern)), Pattern::from_i16(i16::from(pattern)));
+    }
+    assert_eq!(Ok(None), Pattern::from_i16(0x0600));
+}
+
+#[test]
+fn builder() {
+    let mut zombie = Entity::builder(EntityKind::Zombie)
+        .pos([0.5, 64.0, 0.5])
+        .custom_name("Gerald")
+        .persistent()
+        .build();
+    assert_eq!([0.5, 64.0, 0.5], zombie.pos());
+    assert!(zombie.custom_name().is_some());
+    assert_eq!(Some(20.0), zombie.mob().map(|mob| mob.health()));
+    zombie.mob_mut().unwrap().set_health(5.0);
+    assert!(matches!(unique(&zombie), UniqueMob::Zombie(_)));
+
+    let value = encode(&zombie);
+    let Value::Compound(fields) = &value else {
+        unreachable!()
+    };
+    assert_eq!(Some(&Value::Float(5.0)), fields.get("Health"));
+    assert_eq!(Some(&Value::Byte(1)), fields.get("PersistenceRequired"));
+    assert_eq!(value, encode(&decode::<Entity>(&value)));
+}
+
+#[test]
+fn builder_extra() {
+    let zombie = Entity::builder(EntityKind::Zombie)
+        .extra("Health", Value::Float(8.0))
+        .unwrap()
+        .extra("FutureField", Value::Long(7))
+        .unwrap()
+        .build();
+    assert_eq!(Some(8.0), zombie.mob().map(|mob| mob.health()));
+    assert_eq!(
+        vec!["FutureField"],
+        zombie.extra().keys().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn builder_extra_mistyped() {
+    let error = Entity::builder(EntityKind::Zombie)
+        .extra("Health", Value::String("full".to_string()))
+        .err()
+        .unwrap();
+    assert!(matches!(&error, EntityError::InvalidField { key, .. } if key == "Health"));
+    assert!(
+        error
+            .to_string()
+            .starts_with("Health doesn't fit this entity")
+    );
+}
+
+#[test]
+fn mutators() {
+    let mut wolf = Entity::new(EntityKind::Wolf);
+    let Some(UniqueMob::Wolf(unique)) = wolf.mob_mut().and_then(|mob| mob.unique_mut()) else {
+        panic!("Expected a wolf");
+    };
+    unique.breedable_mut().set_age(-24000);
+    unique.tameable_mut().set_sitting(true);
+
+    let Value::Compound(fields) = encode(&wolf) else {
+        unreachable!()
+    };
+    assert_eq!(Some(&Value::Int(-24000)), fields.get("Age"));
+    assert_eq!(Some(&Value::Byte(1)), fields.get("Sitting"));
+}
@@ -0,0 +1,172 @@
+use mc_server_rs::{
+    frame::{Frame, FrameCodec, FrameError},
+    types::{Type, VarInt},
+};
+use rstest::{fixture, rstest};
+
+#[fixture]
+fn small_frame() -> Frame {
+    Frame::new(0x00, b"hi".to_vec())
+}
+
+#[fixture]
+fn large_frame() -> Frame {
+    Frame::new(0x27, vec![0xab; 1024])
+}
+
+fn compressed_codec() -> FrameCodec {
+    let mut codec = FrameCodec::new();
+    codec.set_compression_threshold(256);
+    codec
+}
+
+#[rstest]
+fn uncompressed_layout(small_frame: Frame) {
+    let mut buffer = Vec::new();
+    FrameCodec::new()
+        .write_frame(&small_frame, &mut buffer)
+        .unwrap();
+    assert_eq!(vec![0x03, 0x00, b'h', b'i'], buffer);
+
+    let read = FrameCodec::new().read_frame(buffer.as_slice()).unwrap();
+    assert_eq!(small_frame, read);
+}
+
+#[rstest]
+fn below_threshold_layout(small_frame: Frame) {
+    let mut buffer = Vec::new();
+    compressed_codec()
+        .write_frame(&small_frame, &mut buffer)
+        .unwrap();
+    assert_eq!(vec![0x04, 0x00, 0x00, b'h', b'i'], buffer);
+
+    let read = compressed_codec().read_frame(buffer.as_slice()).unwrap();
+    assert_eq!(small_frame, read);
+}
+
+#[rstest]
+fn compressed_round_trip(large_frame: Frame) {
+    let mut buffer = Vec::new();
+    compressed_codec()
+        .write_frame(&large_frame, &mut buffer)
+        .unwrap();
+    assert!(buffer.len() < large_frame.data.len());
+
+    let mut reader = buffer.as_slice();
+    let length = VarInt::read(&mut reader).unwrap();
+    assert_eq!(*length as usize, reader.len());
+    let data_length = VarInt::read(&mut reader).unwrap();
+    assert_eq!(1 + large_frame.data.len(), *data_length as usize);
+
+    let read = compressed_codec().read_frame(buffer.as_slice()).unwrap();
+    assert_eq!(large_frame, read);
+}
+
+#[rstest]
+fn oversized_frame(large_frame: Frame) {
+    let mut buffer = Vec::new();
+    FrameCodec::new()
+        .write_frame(&large_frame, &mut buffer)
+        .unwrap();
+
+    let codec = FrameCodec::new().with_max_frame_size(64);
+    assert!(matches!(
+        codec.read_frame(buffer.as_slice()),
+        Err(FrameError::TooLarge { max: 64, .. })
+    ));
+    assert!(matches!(
+        codec.write_frame(&large_frame, Vec::new()),
+        Err(FrameError::TooLarge { max: 64, .. })
+    ));
+}
+
+#[test]
+fn negative_length() {
+    let mut buffer = Vec::new();
+    VarInt::new(-1).write(&mut buffer).unwrap();
+    assert!(matches!(
+        FrameCodec::new().read_frame(buffer.as_slice()),
+        Err(FrameError::NegativeLength(-1))
+    ));
+}
+
+#[test]
+fn missing_id() {
+    assert!(matches!(
+        FrameCodec::new().read_frame([0x00].as_slice()),
+        Err(FrameError::MissingId)
+    ));
+}
+
+#[test]
+fn missing_data_length() {
+    assert!(matches!(
+        compressed_codec().read_frame([0x00].as_slice()),
+        Err(FrameError::MissingDataLength)
+    ));
+}
+
+#[test]
+fn compressed_below_threshold() {
+    // Claims to be 16 bytes once decompressed, which should never have been compressed
+    let buffer = [0x02, 0x10, 0x00];
+    assert!(matches!(
+        compressed_codec().read_frame(buffer.as_slice()),
+        Err(FrameError::BelowThreshold {
+            length: 16,
+            threshold: 256
+        })
+    ));
+}
+
+#[rstest]
+fn compressed_length_mismatch(large_frame: Frame) {
+    let mut buffer = Vec::new();
+    compressed_codec()
+        .write_frame(&large_frame, &mut buffer)
+        .unwrap();
+
+    // Bump the data length by one without changing the size of the VarInt
+    let mut reader = buffer.as_slice();
+    let length = VarInt::read(&mut reader).unwrap();
+    let header = buffer.len() - reader.len();
+    buffer[header] += 1;
+
+    assert_eq!(*length as usize, buffer.len() - header);
+    assert!(matches!(
+        compressed_codec().read_frame(buffer.as_slice()),
+        Err(FrameError::LengthMismatch {
+            expected: 1026,
+            actual: 1025
+        })
+    ));
+}
+
+#[rstest]
+fn partial_buffer(small_frame: Frame, large_frame: Frame) {
+    let codec = compressed_codec();
+    let mut buffer = Vec::new();
+    codec.write_frame(&small_frame, &mut buffer).unwrap();
+    let first = buffer.len();
+    codec.write_frame(&large_frame, &mut buffer).unwrap();
+
+    assert!(codec.decode_frame(&[]).unwrap().is_none());
+    assert!(codec.decode_frame(&buffer[..first - 1]).unwrap().is_none());
+
+    let (frame, used) = codec.decode_frame(&buffer).unwrap().unwrap();
+    assert_eq!(small_frame, frame);
+    assert_eq!(first, used);
+
+    let (frame, used) = codec.decode_frame(&buffer[first..]).unwrap().unwrap();
+    assert_eq!(large_frame, frame);
+    assert_eq!(buffer.len() - first, used);
+}
+
+#[test]
+fn packet_payload() {
+    let frame = Frame::encode(0x01, &"hello".to_string()).unwrap();
+    assert_eq!("hello", frame.decode::<String>().unwrap());
+
+    let trailing = Frame::new(0x01, vec![0x00, 0x00]);
+    assert!(trailing.decode::<String>().is_err());
+}
@@ -0,0 +1,450 @@
+use fastnbt::{Value, nbt};
+use mc_server_rs::{
+    data::{
+        Identifier, Item, ItemWithSlot,
+        components::Rarity,
+        item::{ContainerKind, ItemBuilder, ItemError},
+        player::Player,
+        registry::{Entry, Registries, SyncedCodec, SyncedType, builtin},
+        slot::Slot,
+    },
+    types::Type,
+};
+use rstest::rstest;
+use std::path::Path;
+
+fn item(value: Value) -> Item {
+    fastnbt::from_bytes(&fastnbt::to_bytes(&value).unwrap()).unwrap()
+}
+
+fn to_nbt(item: &Item) -> Value {
+    fastnbt::from_bytes(&fastnbt::to_bytes(item).unwrap()).unwrap()
+}
+
+fn to_network<T: Type>(value: &T) -> Vec<u8> {
+    let mut buffer = vec![];
+    value.write(&mut buffer).unwrap();
+    buffer
+}
+
+fn to_slot(item: &Item) -> Vec<u8> {
+    let mut buffer = vec![];
+    item.write_with(&Registries::vanilla(), &mut buffer)
+        .unwrap();
+    buffer
+}
+
+fn from_slot(bytes: &[u8]) -> Item {
+    Item::read_with(&Registries::vanilla(), bytes).unwrap()
+}
+
+fn survival() -> Player {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/data/playerdata/survival.dat");
+    let file = std::fs::File::open(path).unwrap();
+    fastnbt::from_reader(flate2::read::GzDecoder::new(file)).unwrap()
+}
+
+#[rstest]
+#[case(nbt!({ "id": "minecraft:diamond" }), vec![1, 0xc4, 0x06, 0, 0])]
+#[case(nbt!({ "id": "minecraft:stick", "count": 64 }), vec![64, 0xef, 0x06, 0, 0])]
+#[case(
+    nbt!({ "id": "minecraft:diamond_sword", "components": { "minecraft:damage": 300 } }),
+    vec![1, 0xe5, 0x06, 1, 0, 3, 0xac, 0x02],
+)]
+#[case(
+    nbt!({ "id": "minecraft:stick", "components": { "!minecraft:max_stack_size": {} } }),
+    vec![1, 0xef, 0x06, 0, 1, 1],
+)]
+#[case(
+    nbt!({
+        "id": "minecraft:stick",
+        "components": { "minecraft:rarity": "epic", "minecraft:custom_name": "Wand" },
+    }),
+    vec![1, 0xef, 0x06, 2, 0, 5, 8, 0, 4, b'W', b'a', b'n', b'd', 9, 3],
+)]
+fn network(#[case] value: Value, #[case] expected: Vec<u8>) {
+    assert_eq!(expected, to_slot(&item(value)));
+    assert_eq!(expected, to_slot(&from_slot(&expected)));
+}
+
+#[test]
+fn inventory_round_trip() {
+    for slot in survival().inventory() {
+        let read = from_slot(&to_slot(slot.item()));
+        assert_eq!(slot.item().id(), read.id());
+        assert_eq!(slot.item().count(), read.count());
+
+        // Compounds aren't written in any particular order, so the bytes may differ
+        let again = from_slot(&to_slot(&read));
+        assert_eq!(to_nbt(&read), to_nbt(&again), "{}", read.id());
+    }
+}
+
+#[test]
+fn network_components() {
+    let player = survival();
+    let read = |slot: i8| {
+        let item = player.inventory().iter().find(|item| item.slot() == slot);
+        from_slot(&to_slot(item.unwrap().item()))
+    };
+
+    let pickaxe = read(0);
+    let components = pickaxe.components().unwrap();
+    assert_eq!(Some(312), components.minecraft().damage());
+    let enchantments = components.minecraft().enchantments().unwrap();
+    assert_eq!(
+        Some(5),
+        enchantments.level(&Identifier::minecraft("efficiency"))
+    );
+    let apple = read(7);
+    assert_eq!(
+        vec![
+            Identifier::minecraft("food"),
+            Identifier::minecraft("consumable")
+        ],
+        apple.components().unwrap().removed()
+    );
+
+    let shulker_box = read(9);
+    let container = shulker_box.components().unwrap().minecraft().container();
+    assert_eq!(
+        &Identifier::minecraft("firework_rocket"),
+        container.unwrap()[0].item().id()
+    );
+}
+
+#[test]
+fn empty_slot() {
+    let registries = Registries::vanilla();
+    let mut buffer = vec![];
+    Slot::write_with(&None, &registries, &mut buffer).unwrap();
+    assert_eq!(vec![0], buffer);
+    assert!(
+        Slot::read_with(&registries, [0].as_slice())
+            .unwrap()
+            .is_none()
+    );
+    assert!(Item::read_with(&registries, [0].as_slice()).is_err());
+
+    let diamond = Slot::read_with(&registries, [2, 0xc4, 0x06, 0, 0].as_slice())
+        .unwrap()
+        .unwrap();
+    assert_eq!(&Identifier::minecraft("diamond"), diamond.id());
+    assert_eq!(2, diamond.count());
+}
+
+/// Not from a game save: vanilla has no components of its own that it doesn't send
+#[test]
+fn synthetic_mod_component() {
+    let pickaxe = item(nbt!({
+        "id": "minecraft:diamond_pickaxe",
+        "components": { "minecraft:damage": 312, "example:soulbound": 1_i8 },
+    }));
+    assert_eq!(1, pickaxe.components().unwrap().other().len());
+
+    // Components of mods aren't sent
+    let read = from_slot(&to_slot(&pickaxe));
+    let components = read.components().unwrap();
+    assert_eq!(Some(312), components.minecraft().damage());
+    assert!(components.other().is_empty());
+}
+
+#[test]
+fn without_synced_registries() {
+    let sword = item(nbt!({
+        "id": "minecraft:diamond_sword",
+        "components": { "minecraft:enchantments": { "levels": { "minecraft:sharpness": 5 } } },
+    }));
+    let bytes = to_network(&sword);
+    assert_eq!(to_slot(&sword), bytes);
+    assert_eq!(bytes, to_network(&Item::read(bytes.as_slice()).unwrap()));
+}
+
+#[rstest]
+#[case(nbt!({ "id": "minecraft:not_an_item" }))]
+#[case(nbt!({ "id": "minecraft:stick", "count": 0 }))]
+#[case(nbt!({
+    "id": "minecraft:diamond_sword",
+    "components": { "minecraft:enchantments": { "levels": { "example:lifesteal": 1 } } },
+}))]
+fn unsendable(#[case] value: Value) {
+    assert!(
+        item(value)
+            .write_with(&Registries::vanilla(), &mut vec![])
+            .is_err()
+    );
+}
+
+#[test]
+fn synced_registries() {
+    let lifesteal = Identifier::new("example", "lifesteal");
+    let sword = item(nbt!({
+        "id": "minecraft:diamond_sword",
+        "components": { "minecraft:enchantments": { "levels": { "example:lifesteal": 1 } } },
+    }));
+
+    // Enchantments are sent by their index among those the client was sent
+    let mut registries = Registries::vanilla();
+    registries
+        .enchantment
+        .insert(Entry::new(lifesteal.clone(), nbt!({})));
+    let mut bytes = vec![];
+    sword.write_with(&registries, &mut bytes).unwrap();
+    let read = Item::read_with(&registries, bytes.as_slice()).unwrap();
+    let enchantments = read.components().unwrap().minecraft().enchantments();
+    assert_eq!(Some(1), enchantments.unwrap().level(&lifesteal));
+
+    assert!(Item::read_with(&Registries::vanilla(), bytes.as_slice()).is_err());
+    let efficiency = item(nbt!({
+        "id": "minecraft:diamond_pickaxe",
+        "components": { "minecraft:enchantments": { "levels": { "minecraft:efficiency": 5 } } },
+    }));
+    assert!(
+        efficiency
+            .write_with(&Registries::new(), &mut vec![])
+            .is_err()
+    );
+}
+
+#[test]
+fn registries() {
+    assert_eq!(Identifier::minecraft("item"), builtin::ITEM.id());
+    assert_eq!(
+        Some(836),
+        builtin::ITEM.network_id(&Identifier::minecraft("diamond"))
+    );
+    assert_eq!(Some(Identifier::minecraft("air")), builtin::ITEM.get(0));
+    assert_eq!(None, builtin::ITEM.get(-1));
+    assert_eq!(
+        Some(3),
+        builtin::DATA_COMPONENT_TYPE.network_id(&Identifier::minecraft("damage"))
+    );
+    assert_eq!(Some(Identifier::minecraft("stone")), builtin::BLOCK.get(1));
+}
+
+#[test]
+fn builder() {
+    let sword = Item::builder(Identifier::minecraft("diamond_sword"))
+        .custom_name("Sting")
+        .lore(["Glows blue", "when orcs are near"])
+        .enchant(Identifier::minecraft("sharpness"), 5)
+        .enchant(Identifier::minecraft("unbreaking"), 3)
+        .enchant(Identifier::minecraft("unbreaking"), 2)
+        .rarity(Rarity::Epic)
+        .damage(10)
+        .build()
+        .unwrap();
+    assert_eq!(1, sword.count());
+
+    let components = sword.components().unwrap().minecraft();
+    assert_eq!("Sting", components.custom_name().unwrap().to_plain_text());
+    assert_eq!(2, components.lore().unwrap().len());
+    assert_eq!(Some(Rarity::Epic), components.rarity());
+    assert_eq!(Some(10), components.damage());
+    let enchantments = components.enchantments().unwrap();
+    assert_eq!(2, enchantments.levels().len());
+    assert_eq!(
+        Some(2),
+        enchantments.level(&Identifier::minecraft("unbreaking"))
+    );
+
+    // Built items save like read ones
+    let saved = to_nbt(&sword);
+    assert_eq!(saved, to_nbt(&item(saved.clone())));
+    assert_eq!(to_slot(&sword), to_slot(&from_slot(&to_slot(&sword))));
+
+    let apple = Item::builder(Identifier::minecraft("golden_apple"))
+        .count(3)
+        .remove(Identifier::minecraft("food"))
+        .build()
+        .unwrap();
+    assert_eq!(3, apple.count());
+    assert_eq!(
+        [Identifier::minecraft("food")],
+        apple.components().unwrap().removed()
+    );
+}
+
+#[test]
+fn saved_text() {
+    let json = nbt!({
+        "id": "minecraft:stick",
+        "components": {
+            "minecraft:custom_name": "{\"text\":\"Wand\",\"color\":\"gold\"}",
+            "minecraft:lore": ["\"Old\"", "Bare"],
+        },
+    });
+    let stick = item(json.clone());
+    let components = stick.components().unwrap().minecraft();
+    let name = components.custom_name().unwrap();
+    assert_eq!("Wand", name.to_plain_text());
+    let lore = components.lore().unwrap();
+    assert_eq!("Old", lore[0].to_plain_text());
+    assert_eq!("Bare", lore[1].to_plain_text());
+    assert_eq!(json, to_nbt(&stick));
+
+    // 1.21.5 saves text as NBT, which is kept that way
+    let nbt = nbt!({
+        "id": "minecraft:stick",
+        "components": { "minecraft:custom_name": { "type": "text", "text": "Wand", "color": "gold" } },
+    });
+    assert_eq!(nbt, to_nbt(&item(nbt.clone())));
+
+    // New text is saved as JSON
+    let stick = Item::builder(Identifier::minecraft("stick"))
+        .custom_name("Wand")
+        .build()
+        .unwrap();
+    assert_eq!(
+        nbt!({
+            "id": "minecraft:stick",
+            "components": { "minecraft:custom_name": "\"Wand\"" },
+        }),
+        to_nbt(&stick)
+    );
+}
+
+#[test]
+fn mutators() {
+    let mut item = Item::new(Identifier::minecraft("stick"));
+    assert!(item.components().is_none());
+    item.set_count(16);
+    item.components_mut().minecraft_mut().set_max_stack_size(16);
+    item.components_mut()
+        .minecraft_mut()
+        .set_rarity(Rarity::Rare);
+    assert!(item.validate().is_ok());
+
+    item.components_mut().minecraft_mut().set_rarity(None);
+    assert_eq!(None, item.components().unwrap().minecraft().rarity());
+
+    item.components_mut().remove(Identifier::minecraft("lore"));
+    item.components_mut()
+        .restore(&Identifier::minecraft("lore"));
+    assert!(item.components().unwrap().removed().is_empty());
+
+    let mut slot = ItemWithSlot::new(3_i8, item);
+    slot.set_slot(4);
+    slot.item_mut().set_count(17);
+    assert_eq!(4, slot.slot());
+    assert!(matches!(
+        slot.validate(ContainerKind::Chest),
+        Err(ItemError::InvalidCount { count: 17, max: 16 })
+    ));
+}
+
+fn diamond_sword() -> ItemBuilder {
+    Item::builder(Identifier::minecraft("diamond_sword"))
+}
+
+#[rstest]
+#[case(diamond_sword().count(0))]
+#[case(diamond_sword().count(100))]
+#[case(diamond_sword().max_stack_size(100))]
+#[case(diamond_sword().max_stack_size(4).count(5))]
+#[case(diamond_sword().damage(-1))]
+#[case(diamond_sword().max_damage(0))]
+#[case(diamond_sword().enchant(Identifier::minecraft("sharpness"), 256))]
+#[case(Item::builder(Identifier::minecraft("air")))]
+fn invalid(#[case] builder: ItemBuilder) {
+    assert!(builder.build().is_err());
+}
+
+#[test]
+fn conflicts() {
+    assert!(matches!(
+        diamond_sword().max_damage(100).max_stack_size(16).build(),
+        Err(ItemError::Conflict(..))
+    ));
+    assert!(
+        diamond_sword()
+            .max_damage(100)
+            .max_stack_size(1)
+            .build()
+            .is_ok()
+    );
+    assert!(matches!(
+        diamond_sword()
+            .damage(5)
+            .remove(Identifier::minecraft("damage"))
+            .build(),
+        Err(ItemError::AddedAndRemoved(component)) if component == Identifier::minecraft("damage")
+    ));
+
+    // Items inside items are checked too
+    let bundle = item(nbt!({
+        "id": "minecraft:bundle",
+        "components": { "minecraft:bundle_contents": [{ "id": "minecraft:stick", "count": 0 }] },
+    }));
+    assert!(bundle.validate().is_err());
+}
+
+#[rstest]
+#[case("stick", 64, None)]
+#[case("stick", 65, Some(64))]
+#[case("ender_pearl", 16, None)]
+#[case("ender_pearl", 17, Some(16))]
+#[case("diamond_sword", 2, Some(1))]
+#[case("modded_item", 99, None)]
+fn default_max_stack_size(#[case] id: &str, #[case] count: i32, #[case] max: Option<i32>) {
+    let item = Item::builder(Identifier::minecraft(id))
+        .count(count)
+        .build();
+    match max {
+        None => assert!(item.is_ok()),
+        Some(max) => assert!(matches!(
+            item,
+            Err(ItemError::InvalidCount { count: c, max: m }) if c == count && m == max
+        )),
+    }
+}
+
+#[test]
+fn removed_max_stack_size() {
+    assert!(matches!(
+        Item::builder(Identifier::minecraft("stick"))
+            .remove(Identifier::minecraft("max_stack_size"))
+            .count(2)
+            .build(),
+        Err(ItemError::InvalidCount { count: 2, max: 1 })
+    ));
+    assert!(matches!(
+        Item::builder(Identifier::minecraft("stick"))
+            .max_damage(100)
+            .build(),
+        Err(ItemError::Conflict(..))
+    ));
+}
+
+#[rstest]
+#[case(ContainerKind::Inventory, -106, true)]
+#[case(ContainerKind::Inventory, 103, true)]
+#[case(ContainerKind::Inventory, 36, false)]
+#[case(ContainerKind::Chest, 26, true)]
+#[case(ContainerKind::Chest, 27, false)]
+#[case(ContainerKind::Dispenser, 9, false)]
+#[case(ContainerKind::Hopper, 4, true)]
+#[case(ContainerKind::Furnace, 3, false)]
+#[case(ContainerKind::ChestedHorse, 1, false)]
+#[case(ContainerKind::Item, 255, true)]
+fn slots(#[case] container: ContainerKind, #[case] slot: i32, #[case] valid: bool) {
+    assert_eq!(valid, container.contains(slot));
+}
+
+#[test]
+fn validate_inventory() {
+    let player = survival();
+    ContainerKind::Inventory
+        .validate(player.inventory())
+        .unwrap();
+
+    let stick = Item::new(Identifier::minecraft("stick"));
+    let items = [
+        ItemWithSlot::new(0_i8, stick.clone()),
+        ItemWithSlot::new(0_i8, stick),
+    ];
+    assert!(matches!(
+        ContainerKind::Chest.validate(&items),
+        Err(ItemError::DuplicateSlot(0))
+    ));
+}
@@ -0,0 +1,346 @@
+#![recursion_limit = "256"]
+
+use fastnbt::{Value, nbt};
+use flate2::{Compression, read::GzDecoder, write::GzEncoder};
+use mc_server_rs::data::{
+    BlockPos, DATA_VERSION, Identifier,
+    block::Block,
+    chunk::Chunk,
+    level::{BossBarColor, BossBarOverlay, Difficulty, Generator, LevelData},
+    player::GameType,
+    registry::Registries,
+    world::{RegionKind, World},
+};
+use std::{
+    io::{Read, Write},
+    path::Path,
+};
+
+fn level_dat() -> Value {
+    nbt!({
+        "FML": { "LoadingModList": [{ "ModId": "example" }], "Flags": [B; 1_i8, 0_i8] },
+        "Data": {
+            "allowCommands": 0_i8,
+            "BorderCenterX": 0.0_f64,
+            "BorderCenterZ": 0.0_f64,
+            "BorderDamagePerBlock": 0.2_f64,
+            "BorderSafeZone": 5.0_f64,
+            "BorderSize": 59999968.0_f64,
+            "BorderSizeLerpTarget": 59999968.0_f64,
+            "BorderSizeLerpTime": 0_i64,
+            "BorderWarningBlocks": 5.0_f64,
+            "BorderWarningTime": 15.0_f64,
+            "clearWeatherTime": 0_i32,
+            "CustomBossEvents": {
+                "minecraft:siege": {
+                    "Name": "{\"text\":\"Siege\"}",
+                    "Players": [[I; 1, 2, 3, 4]],
+                    "Color": "red",
+                    "Overlay": "notched_10",
+                    "CreateWorldFog": 0_i8,
+                    "DarkenScreen": 1_i8,
+                    "PlayBossMusic": 0_i8,
+                    "Value": 40_i32,
+                    "Max": 100_i32,
+                    "Visible": 1_i8,
+                },
+            },
+            "DataPacks": {
+                "Enabled": ["vanilla", "file/extra.zip"],
+                "Disabled": ["minecart_improvements"],
+            },
+            "DataVersion": 4189_i32,
+            "DayTime": 6000_i64,
+            "Difficulty": 2_i8,
+            "DifficultyLocked": 0_i8,
+            "DragonFight": {
+                "NeedsStateScanning": 0_i8,
+                "DragonKilled": 1_i8,
+                "PreviouslyKilled": 1_i8,
+                "IsRespawning": 0_i8,
+                "ExitPortalLocation": [I; 0, 64, 0],
+                "Gateways": [3_i32, 17_i32],
+            },
+            "enabled_features": ["minecraft:vanilla"],
+            "GameRules": {
+                "doDaylightCycle": "false",
+                "randomTickSpeed": "3",
+            },
+            "GameType": 0_i32,
+            "hardcore": 0_i8,
+            "initialized": 1_i8,
+            "LastPlayed": 1734000000000_i64,
+            "LevelName": "New World",
+            "raining": 1_i8,
+            "rainTime": 1200_i32,
+            "ScheduledEvents": [],
+            "ServerBrands": ["vanilla"],
+            "SpawnAngle": 90.0_f32,
+            "SpawnX": 16_i32,
+            "SpawnY": 70_i32,
+            "SpawnZ": -32_i32,
+            "thundering": 0_i8,
+            "thunderTime": 24000_i32,
+            "Time": 24000_i64,
+            "version": 19133_i32,
+            "Version": {
+                "Id": 4189_i32,
+                "Name": "1.21.4",
+                "Series": "main",
+                "Snapshot": 0_i8,
+            },
+            "WanderingTraderSpawnChance": 25_i32,
+            "WanderingTraderSpawnDelay": 24000_i32,
+            "WasModded": 1_i8,
+            // Saved by a mod, and kept on save
+            "example:seasons": { "Season": "autumn", "Days": [L; 12_i64, 90_i64] },
+            "WorldGenSettings": {
+                "bonus_chest": 0_i8,
+                "generate_features": 1_i8,
+                "seed": -4172144997902289642_i64,
+                "dimensions": {
+                    "minecraft:overworld": {
+                        "type": "minecraft:overworld",
+                        "generator": {
+                            "type": "minecraft:noise",
+                            "settings": "minecraft:overworld",
+                            "biome_source": {
+                                "type": "minecraft:multi_noise",
+                                "preset": "minecraft:overworld",
+                            },
+                        },
+                    },
+                    "minecraft:the_end": {
+                        "type": "minecraft:the_end",
+                        "generator": {
+                            "type": "minecraft:noise",
+                            "settings": "minecraft:end",
+                            "biome_source": { "type": "minecraft:the_end" },
+                        },
+                    },
+                    "example:flat": {
+                        "type": "minecraft:overworld",
+                        "generator": {
+                            "type": "minecraft:flat",
+                            "settings": {
+                                "layers": [
+                                    { "block": "minecraft:bedrock", "height": 1_i32 },
+                                    { "block": "minecraft:dirt", "height": 2_i32 },
+                                    { "block": "minecraft:grass_block", "height": 1_i32 },
+                                ],
+                                "biome": "minecraft:plains",
+                                "lakes": 0_i8,
+                                "features": 0_i8,
+                            },
+                        },
+                    },
+                },
+            },
+        },
+    })
+}
+
+fn write_gzip(path: &Path, value: &Value) {
+    let mut encoder = GzEncoder::new(vec![], Compression::default());
+    encoder
+        .write_all(&fastnbt::to_bytes(value).unwrap())
+        .unwrap();
+    std::fs::write(path, encoder.finish().unwrap()).unwrap();
+}
+
+fn read_gzip(path: &Path) -> Value {
+    let mut data = vec![];
+    GzDecoder::new(std::fs::File::open(path).unwrap())
+        .read_to_end(&mut data)
+        .unwrap();
+    fastnbt::from_bytes(&data).unwrap()
+}
+
+fn world() -> (tempfile::TempDir, World) {
+    let directory = tempfile::tempdir().unwrap();
+    write_gzip(&directory.path().join("level.dat"), &level_dat());
+    let world = World::open(directory.path()).unwrap();
+    (directory, world)
+}
+
+/// A world vanilla would make with default settings
+fn new_world() -> (tempfile::TempDir, World) {
+    let directory = tempfile::tempdir().unwrap();
+    let world = World::create(directory.path(), LevelData::new("New World", 0)).unwrap();
+    (directory, world)
+}
+
+#[test]
+fn level_data() {
+    let (_directory, world) = world();
+    let level = world.level();
+    assert_eq!("New World", level.level_name());
+    assert_eq!(4189, level.data_version());
+    assert_eq!(GameType::Survival, level.game_type());
+    assert_eq!(Difficulty::Normal, level.difficulty());
+    assert_eq!((BlockPos::new(16, 70, -32), 90.0), level.spawn());
+    assert_eq!(6000, level.day_time());
+    assert_eq!(24000, level.time());
+    assert!(level.weather().raining);
+    assert_eq!(24000, level.wandering_trader_spawn_delay());
+    assert_eq!(
+        vec!["vanilla", "file/extra.zip"],
+        level.data_packs().enabled
+    );
+
+    let dragon_fight = level.dragon_fight().unwrap();
+    assert!(dragon_fight.dragon_killed());
+    assert_eq!(
+        Some(BlockPos::new(0, 64, 0)),
+        dragon_fight.exit_portal_location()
+    );
+
+    let boss_event = &level.custom_boss_events()[&Identifier::minecraft("siege")];
+    assert_eq!(BossBarColor::Red, boss_event.color());
+    assert_eq!(BossBarOverlay::Notched10, boss_event.overlay());
+    assert_eq!((40, 100), boss_event.value());
+    assert!(boss_event.name().is_ok());
+
+    let settings = level.world_gen_settings();
+    assert_eq!(-4172144997902289642, settings.seed());
+    assert_eq!(3, settings.dimensions().len());
+    let Generator::Flat { settings } =
+        &settings.dimensions()[&Identifier::new("example", "flat")].generator
+    else {
+        panic!("Expected a flat generator");
+    };
+    assert_eq!(3, settings.layers().len());
+}
+
+#[test]
+fn game_rules() {
+    let (_directory, mut world) = world();
+    let rules = world.level_mut().game_rules_mut();
+    assert_eq!(Some(false), rules.get_bool("doDaylightCycle"));
+    assert_eq!(Some(3), rules.get_int("randomTickSpeed"));
+    assert_eq!(None, rules.get_int("doDaylightCycle"));
+    assert_eq!(None, rules.get("keepInventory"));
+
+    rules.set("keepInventory", true);
+    assert_eq!(Some("true"), rules.get("keepInventory"));
+}
+
+#[test]
+fn save_keeps_backup() {
+    let (directory, mut world) = world();
+    world.save_level().unwrap();
+    assert_eq!(level_dat(), read_gzip(&directory.path().join("level.dat")));
+    assert_eq!(
+        level_dat(),
+        read_gzip(&directory.path().join("level.dat_old"))
+    );
+
+    world.level_mut().set_day_time(1000);
+    world.save_level().unwrap();
+    let reopened = World::open(directory.path()).unwrap();
+    assert_eq!(1000, reopened.level().day_time());
+    // The backup is the previous save
+    assert_eq!(
+        level_dat(),
+        read_gzip(&directory.path().join("level.dat_old"))
+    );
+    assert!(!directory.path().join("level.dat.tmp").exists());
+}
+
+#[test]
+fn falls_back_to_backup() {
+    let directory = tempfile::tempdir().unwrap();
+    std::fs::write(directory.path().join("level.dat"), b"not gzip").unwrap();
+    write_gzip(&directory.path().join("level.dat_old"), &level_dat());
+    let world = World::open(directory.path()).unwrap();
+    assert_eq!("New World", world.level().level_name());
+
+    let empty = tempfile::tempdir().unwrap();
+    assert!(World::open(empty.path()).is_err());
+}
+
+#[test]
+fn create() {
+    let (directory, world) = new_world();
+    let reopened = World::open(directory.path()).unwrap();
+    let level = reopened.level();
+    assert_eq!("New World", level.level_name());
+    assert_eq!(DATA_VERSION, level.data_version());
+    assert_eq!(GameType::Survival, level.game_type());
+    assert_eq!(Difficulty::Normal, level.difficulty());
+    assert_eq!((BlockPos::new(0, 64, 0), 0.0), level.spawn());
+    assert_eq!(vec!["vanilla"], level.data_packs().enabled);
+    assert_eq!(3, level.world_gen_settings().dimensions().len());
+    assert_eq!(
+        fastnbt::to_value(world.level()).unwrap(),
+        fastnbt::to_value(level).unwrap()
+    );
+
+    assert!(World::create(directory.path(), LevelData::new("Again", 0)).is_err());
+    assert_eq!(
+        "New World",
+        World::open(directory.path()).unwrap().level().level_name()
+    );
+}
+
+#[test]
+fn dimension_paths() {
+    let (directory, world) = new_world();
+    let root = directory.path();
+    assert_eq!(
+        root,
+        world.dimension_path(&Identifier::minecraft("overworld"))
+    );
+    assert_eq!(
+        root.join("DIM-1"),
+        world.dimension_path(&Identifier::minecraft("the_nether"))
+    );
+    assert_eq!(
+        root.join("DIM1"),
+        world.dimension_path(&Identifier::minecraft("the_end"))
+    );
+    assert_eq!(
+        root.join("dimensions").join("example").join("flat"),
+        world.dimension_path(&Identifier::new("example", "flat"))
+    );
+}
+
+#[test]
+fn chunks() {
+    let (directory, world) = new_world();
+    let nether = Identifier::minecraft("the_nether");
+    let registries = Registries::vanilla();
+    let dimension = registries
+        .dimension_type
+        .get(&nether)
+        .unwrap()
+        .data
+        .clone()
+        .unwrap();
+
+    assert!(world.read_chunk(&nether, -1, 40).unwrap().is_none());
+    assert!(
+        world
+            .regions(&nether, RegionKind::Chunks)
+            .unwrap()
+            .is_empty()
+    );
+
+    let mut chunk = Chunk::new(-1, 40, &dimension);
+    chunk.set_block(3, 64, 5, Block::Cobblestone);
+    world.write_chunk(&nether, &chunk).unwrap();
+    assert!(directory.path().join("DIM-1/region/r.-1.1.mca").exists());
+    assert_eq!(
+        vec![(-1, 1)],
+        world.regions(&nether, RegionKind::Chunks).unwrap()
+    );
+
+    let chunk = world.read_chunk(&nether, -1, 40).unwrap().unwrap();
+    assert_eq!((-1, 40), chunk.position());
+    assert_eq!(Block::Cobblestone, chunk.block(3, 64, 5));
+
+    let mut entities = world.region(&nether, RegionKind::Entities, 0, 0).unwrap();
+    assert_eq!((0, 0), entities.position());
+    assert!(entities.read(0, 0).unwrap().is_none());
+    assert!(directory.path().join("DIM-1/entities/r.0.0.mca").exists());
+}
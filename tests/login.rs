@@ -0,0 +1,322 @@
+use mc_server_rs::{
+    data::player::{PlayerProperty, PlayerPropertyKind, Profile},
+    encryption::{EncryptionResponse, ServerKey, encrypt_stream, server_hash},
+    frame::FrameCodec,
+    protocol::{ConnectionState, Packet, Packets, handshaking, login},
+    server::{
+        Connection,
+        login::{
+            Login, LoginError, MojangSessionService, SessionService, is_valid_username,
+            offline_uuid,
+        },
+    },
+};
+use rstest::rstest;
+use std::{
+    io::{Read, Write},
+    net::{TcpListener, TcpStream},
+    sync::{Arc, Mutex},
+    thread::JoinHandle,
+};
+use uuid::Uuid;
+
+type Server = JoinHandle<Result<(ConnectionState, Profile), LoginError>>;
+
+const SECRET: [u8; 16] = *b"0123456789abcdef";
+
+/// Stands in for the session server, recording the hash it was asked about.
+#[derive(Clone, Default)]
+struct StandIn {
+    profile: Option<Profile>,
+    hash: Arc<Mutex<Option<String>>>,
+}
+
+impl SessionService for StandIn {
+    fn has_joined(&self, username: &str, server_hash: &str) -> std::io::Result<Option<Profile>> {
+        *self.hash.lock().unwrap() = Some(server_hash.to_string());
+        Ok(self
+            .profile
+            .clone()
+            .filter(|profile| profile.name() == Some(username)))
+    }
+}
+
+fn notch() -> Profile {
+    Profile::new(
+        Uuid::from_u128(0x069a79f444e94726a5befca90e38aaf5),
+        "Notch".to_string(),
+        vec![PlayerProperty::new(
+            PlayerPropertyKind::Textures,
+            "e30=".to_string(),
+            Some("c2lnbmF0dXJl".to_string()),
+        )],
+    )
+}
+
+/// Starts a server that runs `login` on the first connection, returning the client's end.
+fn serve<S: SessionService + Send + Sync + 'static>(login: Login<S>) -> (TcpStream, Server) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+
+    let server = std::thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut connection = Connection::new(stream);
+        connection.receive()?;
+        let (connection, profile) = login.run(connection)?;
+        Ok((connection.state(), profile))
+    });
+
+    (client, server)
+}
+
+fn start(codec: &FrameCodec, mut stream: impl Write, name: &str) {
+    let frames = [
+        handshaking::Handshake {
+            protocol_version: 769,
+            server_address: "localhost".to_string(),
+            server_port: 25565,
+            intent: handshaking::Intent::Login,
+        }
+        .to_frame()
+        .unwrap(),
+        login::LoginStart {
+            name: name.to_string(),
+            uuid: Uuid::nil(),
+        }
+        .to_frame()
+        .unwrap(),
+    ];
+    for frame in frames {
+        codec.write_frame(&frame, &mut stream).unwrap();
+    }
+}
+
+fn receive(codec: &FrameCodec, stream: impl Read) -> login::Clientbound {
+    login::Clientbound::decode(&codec.read_frame(stream).unwrap()).unwrap()
+}
+
+fn acknowledge(codec: &FrameCodec, stream: impl Write) {
+    codec
+        .write_frame(&login::LoginAcknowledged.to_frame().unwrap(), stream)
+        .unwrap();
+}
+
+#[test]
+fn offline_uuids() {
+    assert_eq!(
+        Uuid::parse_str("b50ad385-829d-3141-a216-7e7d7539ba7f").unwrap(),
+        offline_uuid("Notch")
+    );
+    assert_eq!(3, offline_uuid("jeb_").get_version_num());
+}
+
+#[test]
+fn offline() {
+    let (mut stream, server) = serve(Login::offline().with_compression_threshold(256));
+    let mut codec = FrameCodec::new();
+    start(&codec, &mut stream, "Notch");
+
+    let login::Clientbound::SetCompression(compression) = receive(&codec, &mut stream) else {
+        panic!("Expected Set Compression");
+    };
+    assert_eq!(256, compression.threshold);
+    codec.set_compression_threshold(compression.threshold);
+
+    let login::Clientbound::LoginSuccess(success) = receive(&codec, &mut stream) else {
+        panic!("Expected Login Success");
+    };
+    assert_eq!(Some(offline_uuid("Notch")), success.profile.id());
+    assert!(success.profile.properties().is_empty());
+    acknowledge(&codec, &mut stream);
+
+    let (state, profile) = server.join().unwrap().unwrap();
+    assert_eq!(ConnectionState::Configuration, state);
+    assert_eq!(success.profile, profile);
+}
+
+#[test]
+fn usernames() {
+    // Vanilla only checks the length and that every character is printable ASCII
+    for name in ["", "Notch", "jeb_", "a@b", "\"quoted\"", "0123456789abcdef"] {
+        assert!(is_valid_username(name), "{name}");
+    }
+    for name in [
+        "ThisNameIsWayTooLong",
+        "bad name",
+        "tab\t",
+        "naïve",
+        "\u{7f}",
+    ] {
+        assert!(!is_valid_username(name), "{name}");
+    }
+}
+
+#[rstest]
+#[case("ThisNameIsWayTooLong")]
+#[case("bad name")]
+#[case("naïve")]
+fn invalid_username(#[case] name: &str) {
+    let (mut stream, server) = serve(Login::offline());
+    let codec = FrameCodec::new();
+    start(&codec, &mut stream, name);
+
+    let login::Clientbound::Disconnect(disconnect) = receive(&codec, &mut stream) else {
+        panic!("Expected Disconnect");
+    };
+    assert_eq!(
+        "Invalid characters in username",
+        disconnect.reason.to_plain_text()
+    );
+    assert!(matches!(
+        server.join().unwrap(),
+        Err(LoginError::InvalidUsername(_))
+    ));
+}
+
+/// Runs the client's side of the key exchange, returning the encrypted stream and the hash the
+/// session server should have been asked about.
+fn exchange_keys(codec: &FrameCodec, mut stream: TcpStream) -> (impl Read + Write, String) {
+    let login::Clientbound::EncryptionRequest(request) = receive(codec, &mut stream) else {
+        panic!("Expected Encryption Request");
+    };
+    assert!(request.should_authenticate);
+
+    let response = EncryptionResponse::new(&request, &SECRET).unwrap();
+    codec
+        .write_frame(&response.to_frame().unwrap(), &mut stream)
+        .unwrap();
+
+    let hash = server_hash(&request.server_id, &SECRET, &request.public_key);
+    (encrypt_stream(stream, &SECRET), hash)
+}
+
+#[test]
+fn online() {
+    let session = StandIn {
+        profile: Some(notch()),
+        ..Default::default()
+    };
+    let (mut stream, server) = serve(Login::online(
+        ServerKey::generate().unwrap(),
+        session.clone(),
+    ));
+    let codec = FrameCodec::new();
+    start(&codec, &mut stream, "Notch");
+
+    let (mut stream, hash) = exchange_keys(&codec, stream);
+    let login::Clientbound::LoginSuccess(success) = receive(&codec, &mut stream) else {
+        panic!("Expected Login Success");
+    };
+    assert_eq!(notch(), success.profile);
+    acknowledge(&codec, &mut stream);
+
+    let (state, profile) = server.join().unwrap().unwrap();
+    assert_eq!(ConnectionState::Configuration, state);
+    assert_eq!(notch(), profile);
+    assert_eq!(Some(hash), session.hash.lock().unwrap().clone());
+}
+
+#[test]
+fn online_unverified() {
+    let (mut stream, server) = serve(Login::online(
+        ServerKey::generate().unwrap(),
+        StandIn::default(),
+    ));
+    let codec = FrameCodec::new();
+    start(&codec, &mut stream, "Notch");
+
+    let (mut stream, _) = exchange_keys(&codec, stream);
+    let login::Clientbound::Disconnect(disconnect) = receive(&codec, &mut stream) else {
+        panic!("Expected Disconnect");
+    };
+    assert_eq!(
+        "Failed to verify username!",
+        disconnect.reason.to_plain_text()
+    );
+    assert!(matches!(
+        server.join().unwrap(),
+        Err(LoginError::UnverifiedUsername(name)) if name == "Notch"
+    ));
+}
+
+/// Answers a single request to `hasJoined` with `body`, as the session server would.
+fn session_server(body: &'static str) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/hasJoined", listener.local_addr().unwrap());
+
+    std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut request = Vec::new();
+        while !request.ends_with(b"\r\n\r\n") {
+            let mut byte = 0;
+            stream.read_exact(std::slice::from_mut(&mut byte)).unwrap();
+            request.push(byte);
+        }
+        write!(
+            stream,
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        )
+        .unwrap();
+    });
+
+    url
+}
+
+#[test]
+fn session_json() {
+    let url = session_server(
+        r#"{
+            "id": "069a79f444e94726a5befca90e38aaf5",
+            "name": "Notch",
+            "properties": [{ "name": "textures", "value": "e30=", "signature": "c2lnbmF0dXJl" }]
+        }"#,
+    );
+    let profile = MojangSessionService::new(url)
+        .has_joined("Notch", "hash")
+        .unwrap()
+        .unwrap();
+    assert_eq!(notch(), profile);
+
+    let frame = login::LoginSuccess { profile }.to_frame().unwrap();
+    assert_eq!(
+        notch(),
+        frame.decode::<login::LoginSuccess>().unwrap().profile
+    );
+}
+
+#[test]
+fn unknown_property() {
+    let url = session_server(
+        r#"{
+            "id": "069a79f444e94726a5befca90e38aaf5",
+            "name": "Notch",
+            "properties": [{ "name": "textures", "value": "e30=" }, { "name": "custom", "value": "1" }]
+        }"#,
+    );
+    let profile = MojangSessionService::new(url)
+        .has_joined("Notch", "hash")
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        [
+            PlayerProperty::new(PlayerPropertyKind::Textures, "e30=".to_string(), None),
+            PlayerProperty::new(
+                PlayerPropertyKind::Other("custom".to_string()),
+                "1".to_string(),
+                None
+            ),
+        ],
+        profile.properties()
+    );
+
+    let frame = login::LoginSuccess {
+        profile: profile.clone(),
+    }
+    .to_frame()
+    .unwrap();
+    assert_eq!(
+        profile,
+        frame.decode::<login::LoginSuccess>().unwrap().profile
+    );
+}
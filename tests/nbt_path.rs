@@ -0,0 +1,231 @@
+use fastnbt::{Value, nbt};
+use mc_server_rs::data::{
+    NbtPath,
+    nbt_path::{NbtPathError, NbtPathNode},
+};
+use rstest::{fixture, rstest};
+
+fn path(s: &str) -> NbtPath {
+    s.parse().unwrap()
+}
+
+fn get(root: &Value, s: &str) -> Result<Vec<Value>, NbtPathError> {
+    path(s)
+        .get(root)
+        .map(|found| found.into_iter().map(|value| value.into_owned()).collect())
+}
+
+#[fixture]
+fn player() -> Value {
+    nbt!({
+        "Health": 20.0_f32,
+        "Pos": [1.0, 64.0, -3.5],
+        "Tags": ["a", "b"],
+        "Inventory": [
+            { "Slot": 0_i8, "id": "minecraft:stone", "count": 64 },
+            { "Slot": 1_i8, "id": "minecraft:dirt", "count": 3 },
+        ],
+        "bukkit": { "first played": 1_i64 },
+        "Scores": [I; 1, 2, 3],
+    })
+}
+
+#[rstest]
+#[case("foo")]
+#[case("foo.bar")]
+#[case("foo[0]")]
+#[case("foo[-1].bar")]
+#[case("foo[]")]
+#[case("foo[{a:1b}]")]
+#[case("foo{a:1b}.bar")]
+#[case("{Tags:[\"a\"]}.Health")]
+#[case("\"quoted key\".\"a.b\"")]
+#[case("Inventory[{Slot: 0b, id: 'minecraft:stone'}].count")]
+#[case("a[0][1][]")]
+fn display_round_trip(#[case] s: &str) {
+    let path = path(s);
+    assert_eq!(s, path.to_string());
+    assert_eq!(path, path.to_string().parse().unwrap());
+}
+
+#[test]
+fn nodes() {
+    let path = path("'a b'[1].x{y:1}");
+    assert!(matches!(
+        path.nodes(),
+        [NbtPathNode::Child(name), NbtPathNode::Index(1), NbtPathNode::MatchChild(..)]
+            if name == "a b"
+    ));
+    assert_eq!("\"a b\"[1].x{y:1}", path.to_string());
+
+    assert!(matches!(
+        "x[]".parse::<NbtPath>().unwrap().nodes(),
+        [NbtPathNode::Child(_), NbtPathNode::AllElements]
+    ));
+}
+
+#[rstest]
+#[case("")]
+#[case("foo.")]
+#[case("foo..bar")]
+#[case("foo[")]
+#[case("foo[x]")]
+#[case("foo[{a:1]")]
+#[case("foo.{a:1}")]
+#[case("foo[]{a:1}")]
+#[case("\"unclosed")]
+#[case("foo bar")]
+fn invalid(#[case] s: &str) {
+    assert!(s.parse::<NbtPath>().is_err(), "{s} parsed");
+}
+
+#[rstest]
+fn get_values(player: Value) {
+    assert_eq!(vec![Value::Float(20.0)], get(&player, "Health").unwrap());
+    assert_eq!(vec![Value::Double(-3.5)], get(&player, "Pos[-1]").unwrap());
+    assert_eq!(3, get(&player, "Pos[]").unwrap().len());
+    assert_eq!(
+        vec![Value::String("minecraft:dirt".to_string())],
+        get(&player, "Inventory[{Slot:1b}].id").unwrap()
+    );
+    assert_eq!(
+        vec![Value::Long(1)],
+        get(&player, "bukkit.\"first played\"").unwrap()
+    );
+    assert_eq!(vec![Value::Int(2)], get(&player, "Scores[1]").unwrap());
+    assert_eq!(1, get(&player, "{Tags:[\"b\"]}").unwrap().len());
+    assert_eq!(2, get(&player, "Inventory[].count").unwrap().len());
+
+    assert_eq!(Err(NbtPathError::NotFound), get(&player, "Missing"));
+    assert_eq!(Err(NbtPathError::NotFound), get(&player, "Pos[3]"));
+    assert_eq!(Err(NbtPathError::NotFound), get(&player, "{Tags:[\"c\"]}"));
+    assert_eq!(
+        Err(NbtPathError::NotFound),
+        get(&player, "Inventory[{Slot:5b}]")
+    );
+}
+
+#[rstest]
+fn set(mut player: Value) {
+    assert_eq!(1, path("Health").set(&mut player, &Value::Float(10.0)));
+    assert_eq!(0, path("Health").set(&mut player, &Value::Float(10.0)));
+    assert_eq!(vec![Value::Float(10.0)], get(&player, "Health").unwrap());
+
+    // Missing parents are created as whatever the next node expects
+    assert_eq!(1, path("c.d").set(&mut player, &Value::Int(1)));
+    assert_eq!(vec![Value::Int(1)], get(&player, "c.d").unwrap());
+    assert_eq!(1, path("e[].f").set(&mut player, &Value::Int(1)));
+    assert_eq!(vec![Value::Int(1)], get(&player, "e[0].f").unwrap());
+    // Indices don't create elements, but the list is still made
+    assert_eq!(0, path("a.b[0]").set(&mut player, &Value::Int(1)));
+    assert_eq!(vec![nbt!([])], get(&player, "a.b").unwrap());
+
+    // Matching children are created from the filter
+    assert_eq!(1, path("g{x:1}.y").set(&mut player, &Value::Int(2)));
+    assert_eq!(vec![nbt!({ "x": 1, "y": 2 })], get(&player, "g").unwrap());
+    assert_eq!(
+        1,
+        path("Inventory[{Slot:2b}].count").set(&mut player, &Value::Int(5))
+    );
+    assert_eq!(3, get(&player, "Inventory[]").unwrap().len());
+
+    assert_eq!(
+        3,
+        path("Inventory[].count").set(&mut player, &Value::Int(1))
+    );
+    assert_eq!(2, path("Tags[]").set(&mut player, &Value::Int(7)));
+    assert_eq!(
+        vec![Value::Int(7), Value::Int(7)],
+        get(&player, "Tags[]").unwrap()
+    );
+    assert_eq!(1, path("Scores[-1]").set(&mut player, &Value::Byte(9)));
+    assert_eq!(vec![Value::Int(9)], get(&player, "Scores[2]").unwrap());
+
+    // Lists only hold one type of tag
+    assert_eq!(0, path("Pos[0]").set(&mut player, &Value::Int(1)));
+    assert_eq!(
+        0,
+        path("Scores[0]").set(&mut player, &Value::String("x".to_string()))
+    );
+}
+
+#[rstest]
+fn remove(mut player: Value) {
+    assert_eq!(1, path("Health").remove(&mut player));
+    assert_eq!(0, path("Health").remove(&mut player));
+    assert_eq!(0, path("Missing.Child").remove(&mut player));
+
+    assert_eq!(
+        1,
+        path("Inventory[{id:\"minecraft:stone\"}]").remove(&mut player)
+    );
+    assert_eq!(
+        vec![Value::Byte(1)],
+        get(&player, "Inventory[0].Slot").unwrap()
+    );
+    assert_eq!(1, path("Pos[-1]").remove(&mut player));
+    assert_eq!(2, path("Tags[]").remove(&mut player));
+    assert_eq!(vec![nbt!([])], get(&player, "Tags").unwrap());
+    assert_eq!(1, path("Scores[0]").remove(&mut player));
+    assert_eq!(vec![nbt!([I; 2, 3])], get(&player, "Scores").unwrap());
+    assert_eq!(0, path("{Health:20f}").remove(&mut player));
+}
+
+#[rstest]
+fn insert(mut player: Value) {
+    let tags = path("Tags");
+    let values = [
+        Value::String("c".to_string()),
+        Value::String("d".to_string()),
+    ];
+
+    assert_eq!(Ok(1), tags.append(&mut player, &values));
+    assert_eq!(Ok(1), tags.prepend(&mut player, &values[..1]));
+    assert_eq!(Ok(1), tags.insert(1, &mut player, &values[1..]));
+    assert_eq!(
+        vec![nbt!(["c", "d", "a", "b", "c", "d"])],
+        get(&player, "Tags").unwrap()
+    );
+    assert_eq!(Ok(1), tags.insert(-2, &mut player, &values[..1]));
+    assert_eq!(
+        vec![Value::String("c".to_string())],
+        get(&player, "Tags[-2]").unwrap()
+    );
+
+    // Missing lists are created, and arrays take any number
+    assert_eq!(Ok(1), path("New").append(&mut player, &[Value::Int(1)]));
+    assert_eq!(vec![nbt!([1])], get(&player, "New").unwrap());
+    assert_eq!(Ok(1), path("Scores").append(&mut player, &[Value::Long(4)]));
+    assert_eq!(vec![nbt!([I; 1, 2, 3, 4])], get(&player, "Scores").unwrap());
+
+    assert_eq!(Ok(0), tags.append(&mut player, &[Value::Int(1)]));
+    assert_eq!(
+        Err(NbtPathError::NotACollection),
+        path("Health").append(&mut player, &values)
+    );
+    assert_eq!(
+        Err(NbtPathError::InvalidIndex(10)),
+        tags.insert(10, &mut player, &values)
+    );
+    assert_eq!(
+        Err(NbtPathError::NotFound),
+        path("Inventory[5]").append(&mut player, &values)
+    );
+}
+
+#[test]
+fn text_component() {
+    let component: mc_server_rs::text_component::TextComponent =
+        serde_json::from_str(r#"{"nbt": "Inventory[{Slot:0b}].id", "entity": "@s"}"#).unwrap();
+    assert!(
+        serde_json::to_string(&component)
+            .unwrap()
+            .contains(r#""nbt":"Inventory[{Slot:0b}].id""#)
+    );
+    assert!(
+        serde_json::from_str::<mc_server_rs::text_component::TextComponent>(
+            r#"{"nbt": "Inventory[", "entity": "@s"}"#
+        )
+        .is_err()
+    );
+}
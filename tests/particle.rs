@@ -0,0 +1,194 @@
+use fastnbt::{IntArray, Value, nbt};
+use mc_server_rs::{
+    data::{
+        BlockPos,
+        particle::{EntityReference, Particle, PositionSource, Vibration},
+    },
+    types::Type,
+};
+use rstest::rstest;
+
+fn from_nbt(value: &Value) -> Particle {
+    fastnbt::from_bytes(&fastnbt::to_bytes(value).unwrap()).unwrap()
+}
+
+fn to_nbt(particle: &Particle) -> Value {
+    fastnbt::from_bytes(&fastnbt::to_bytes(particle).unwrap()).unwrap()
+}
+
+fn to_network(particle: &Particle) -> Vec<u8> {
+    let mut buffer = vec![];
+    particle.write(&mut buffer).unwrap();
+    buffer
+}
+
+#[rstest]
+#[case(nbt!({ "type": "minecraft:angry_villager" }))]
+#[case(nbt!({ "type": "minecraft:dust", "color": 0xff0000, "scale": 1.5_f32 }))]
+#[case(nbt!({
+    "type": "minecraft:dust_color_transition",
+    "from_color": 0x00ff00,
+    "to_color": 0x0000ff,
+    "scale": 1_f32,
+}))]
+#[case(nbt!({
+    "type": "minecraft:block",
+    "block_state": { "Name": "minecraft:oak_log", "Properties": { "axis": "x" } },
+}))]
+#[case(nbt!({ "type": "minecraft:falling_dust", "block_state": { "Name": "minecraft:sand" } }))]
+#[case(nbt!({ "type": "minecraft:entity_effect", "color": [1_f32, 0.5_f32, 0_f32, 1_f32] }))]
+#[case(nbt!({ "type": "minecraft:item", "item": { "id": "minecraft:diamond", "count": 1 } }))]
+#[case(nbt!({
+    "type": "minecraft:vibration",
+    "destination": { "type": "minecraft:block", "pos": IntArray::new(vec![1, 64, -3]) },
+    "arrival_in_ticks": 20,
+}))]
+#[case(nbt!({
+    "type": "minecraft:vibration",
+    "destination": {
+        "type": "minecraft:entity",
+        "source_entity": IntArray::new(vec![1, 2, 3, 4]),
+        "y_offset": 0.5_f32,
+    },
+    "arrival_in_ticks": 20,
+}))]
+#[case(nbt!({ "type": "minecraft:sculk_charge", "roll": 0.25_f32 }))]
+#[case(nbt!({ "type": "minecraft:shriek", "delay": 10 }))]
+#[case(nbt!({
+    "type": "minecraft:trail",
+    "target": [1_f64, 2_f64, 3_f64],
+    "color": 0x123456,
+    "duration": 40,
+}))]
+fn round_trip(#[case] value: Value) {
+    assert_eq!(value, to_nbt(&from_nbt(&value)));
+}
+
+#[rstest]
+#[case(
+    nbt!({ "type": "dust", "color": [1_f32, 0_f32, 0_f32], "scale": 1_f32 }),
+    nbt!({ "type": "minecraft:dust", "color": 0xff0000, "scale": 1_f32 }),
+)]
+#[case(
+    nbt!({ "type": "block_marker", "block_state": "minecraft:chest" }),
+    nbt!({
+        "type": "minecraft:block_marker",
+        "block_state": {
+            "Name": "minecraft:chest",
+            "Properties": { "facing": "north", "type": "single", "waterlogged": "false" },
+        },
+    }),
+)]
+#[case(
+    nbt!({
+        "type": "minecraft:vibration",
+        "destination": { "type": "entity", "source_entity": IntArray::new(vec![1, 2, 3, 4]) },
+        "arrival_in_ticks": 5,
+    }),
+    nbt!({
+        "type": "minecraft:vibration",
+        "destination": {
+            "type": "minecraft:entity",
+            "source_entity": IntArray::new(vec![1, 2, 3, 4]),
+            "y_offset": 0_f32,
+        },
+        "arrival_in_ticks": 5,
+    }),
+)]
+fn normalized(#[case] value: Value, #[case] expected: Value) {
+    assert_eq!(expected, to_nbt(&from_nbt(&value)));
+}
+
+#[rstest]
+#[case(nbt!({ "type": "minecraft:not_a_particle" }))]
+#[case(nbt!({ "type": "minecraft:dust", "scale": 1_f32 }))]
+#[case(nbt!({ "type": "minecraft:block", "block_state": "minecraft:not_a_block" }))]
+#[case(nbt!({
+    "type": "minecraft:vibration",
+    "destination": { "type": "minecraft:block", "pos": IntArray::new(vec![1, 2]) },
+    "arrival_in_ticks": 5,
+}))]
+fn invalid(#[case] value: Value) {
+    assert!(fastnbt::from_bytes::<Particle>(&fastnbt::to_bytes(&value).unwrap()).is_err());
+}
+
+#[rstest]
+#[case(nbt!({ "type": "minecraft:angry_villager" }), vec![0])]
+#[case(
+    nbt!({ "type": "minecraft:dust", "color": 0xff0000, "scale": 1_f32 }),
+    vec![13, 0x00, 0xff, 0x00, 0x00, 0x3f, 0x80, 0x00, 0x00],
+)]
+#[case(
+    nbt!({ "type": "minecraft:entity_effect", "color": [1_f32, 1_f32, 0_f32, 0_f32] }),
+    vec![20, 0xff, 0xff, 0x00, 0x00],
+)]
+#[case(nbt!({ "type": "minecraft:shriek", "delay": 300 }), vec![101, 0xac, 0x02])]
+#[case(nbt!({ "type": "minecraft:block_crumble", "block_state": { "Name": "minecraft:stone" } }), vec![111, 1])]
+#[case(
+    nbt!({ "type": "minecraft:item", "item": { "id": "minecraft:diamond", "count": 1 } }),
+    vec![45, 1, 0xc4, 0x06, 0, 0],
+)]
+fn network(#[case] value: Value, #[case] expected: Vec<u8>) {
+    let particle = from_nbt(&value);
+    assert_eq!(expected, to_network(&particle));
+    assert_eq!(
+        expected,
+        to_network(&Particle::read(expected.as_slice()).unwrap())
+    );
+}
+
+#[test]
+fn network_enchanted_item() {
+    let value = nbt!({
+        "type": "minecraft:item",
+        "item": {
+            "id": "minecraft:diamond_sword",
+            "count": 1,
+            "components": { "minecraft:enchantments": { "levels": { "minecraft:sharpness": 5 } } },
+        },
+    });
+    let bytes = to_network(&from_nbt(&value));
+    assert_eq!(
+        bytes,
+        to_network(&Particle::read(bytes.as_slice()).unwrap())
+    );
+}
+
+#[test]
+fn network_vibration() {
+    let particle = Particle::Vibration(Vibration {
+        destination: PositionSource::Entity {
+            source_entity: EntityReference::NetworkId(7),
+            y_offset: 1.0,
+        },
+        arrival_in_ticks: 20,
+    });
+    let bytes = to_network(&particle);
+    assert_eq!(vec![46, 1, 7, 0x3f, 0x80, 0x00, 0x00, 20], bytes);
+    assert_eq!(
+        bytes,
+        to_network(&Particle::read(bytes.as_slice()).unwrap())
+    );
+
+    // Saved sources name their entity by UUID, which clients don't know
+    let saved = from_nbt(&nbt!({
+        "type": "minecraft:vibration",
+        "destination": { "type": "minecraft:entity", "source_entity": IntArray::new(vec![1, 2, 3, 4]) },
+        "arrival_in_ticks": 20,
+    }));
+    assert!(saved.write(&mut vec![]).is_err());
+}
+
+#[test]
+fn block_pos() {
+    let pos = BlockPos::new(18357644, 831, -20882616);
+    let mut buffer = vec![];
+    pos.write(&mut buffer).unwrap();
+    assert_eq!(0x4607632c15b4833f_i64.to_be_bytes().to_vec(), buffer);
+    assert_eq!(pos, BlockPos::read(buffer.as_slice()).unwrap());
+}
+
+#[test]
+fn unknown_network_id() {
+    assert!(Particle::read([112].as_slice()).is_err());
+}
@@ -0,0 +1,283 @@
+use fastnbt::Value;
+use mc_server_rs::{
+    data::{
+        BlockPos, Identifier,
+        entity::McUuid,
+        level::LevelData,
+        player::{GameType, Player},
+        world::World,
+    },
+    nbt::snbt,
+};
+use rstest::rstest;
+use std::path::Path;
+
+/// A gzipped player file in the 1.21.4 format. These were written by hand to match what the game
+/// saves, rather than saved by it.
+fn corpus(name: &str) -> Value {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/data/playerdata")
+        .join(name)
+        .with_extension("dat");
+    let file = std::fs::File::open(path).unwrap();
+    fastnbt::from_reader(flate2::read::GzDecoder::new(file)).unwrap()
+}
+
+fn player(name: &str) -> Player {
+    fastnbt::from_bytes(&fastnbt::to_bytes(&corpus(name)).unwrap()).unwrap()
+}
+
+fn to_value(player: &Player) -> Value {
+    fastnbt::from_bytes(&fastnbt::to_bytes(player).unwrap()).unwrap()
+}
+
+fn uuid(s: &str) -> McUuid {
+    McUuid::from(uuid::Uuid::parse_str(s).unwrap())
+}
+
+#[rstest]
+#[case("new_player")]
+#[case("survival")]
+#[case("creative_nether")]
+#[case("sleeping")]
+fn round_trip(#[case] name: &str) {
+    assert_eq!(corpus(name), to_value(&player(name)));
+}
+
+/// Fields no version of vanilla saves, as a mod or a later version might add
+#[test]
+fn synthetic_unknown_fields() {
+    let Value::Compound(mut value) = corpus("survival") else {
+        unreachable!()
+    };
+    value.insert(
+        "example:mana".to_string(),
+        snbt::parse("{Current: 20.5f, Max: 40.0f, Spells: [I; 1, 4, 9]}").unwrap(),
+    );
+    value.insert("FutureField".to_string(), Value::Long(7));
+    let value = Value::Compound(value);
+
+    let player: Player = fastnbt::from_bytes(&fastnbt::to_bytes(&value).unwrap()).unwrap();
+    assert_eq!(2, player.extra().len());
+    assert_eq!(Some(&Value::Long(7)), player.extra().get("FutureField"));
+    assert_eq!(value, to_value(&player));
+}
+
+#[test]
+fn fields() {
+    let player = player("survival");
+    assert_eq!(
+        "5b2af6e4-b80a-4cd2-9210-c2002e095911",
+        player.uuid().hyphenated().to_string()
+    );
+    assert_eq!(Identifier::minecraft("overworld"), *player.dimension());
+    assert_eq!(17.5, player.health());
+    assert_eq!(23, player.xp_level());
+    assert_eq!(14, player.inventory().len());
+    assert_eq!(2, player.ender_items().len());
+
+    let (respawn, angle) = player.respawn_point().unwrap();
+    assert_eq!(BlockPos::new(-140, 64, 420), respawn.pos());
+    assert_eq!(90.0, angle);
+    let death = player.last_death_location().unwrap();
+    assert_eq!(Identifier::minecraft("the_nether"), *death.dimension());
+    assert_eq!(BlockPos::new(-20, 31, 52), death.pos());
+
+    assert!(self::player("new_player").respawn_point().is_none());
+}
+
+#[test]
+fn item_components() {
+    let player = player("survival");
+    let components = |slot: i8| {
+        let item = player.inventory().iter().find(|item| item.slot() == slot);
+        item.unwrap().item().components().unwrap()
+    };
+
+    let pickaxe = components(0);
+    let enchantments = pickaxe.minecraft().enchantments().unwrap();
+    assert_eq!(
+        Some(5),
+        enchantments.level(&Identifier::minecraft("efficiency"))
+    );
+    assert_eq!(None, enchantments.level(&Identifier::minecraft("mending")));
+    assert_eq!(Some(312), pickaxe.minecraft().damage());
+    assert!(pickaxe.other().is_empty());
+
+    let sword = components(1).minecraft();
+    assert_eq!("Oathkeeper", sword.custom_name().unwrap().to_plain_text());
+    let lore = sword.lore().unwrap();
+    assert_eq!(2, lore.len());
+    assert_eq!("+5", lore[1].to_plain_text());
+    assert!(!sword.unbreakable());
+
+    let book = components(4).minecraft().written_book_content().unwrap();
+    assert_eq!("Notes", book.title());
+    assert_eq!("Steve", book.author());
+    assert_eq!(2, book.pages().len());
+    assert_eq!("Day 1", book.pages()[0].raw().to_plain_text());
+
+    assert!(components(5).minecraft().unbreakable());
+    assert!(
+        components(6)
+            .minecraft()
+            .bundle_contents()
+            .unwrap()
+            .is_empty()
+    );
+
+    let apple = components(7);
+    assert!(apple.minecraft().food().is_none());
+    assert_eq!(2, apple.removed().len());
+    assert!(apple.removed().contains(&Identifier::minecraft("food")));
+
+    let shulker = components(9).minecraft();
+    let rocket = shulker.container().unwrap()[0].item();
+    assert_eq!(3, rocket.count());
+    assert_eq!(Identifier::minecraft("firework_rocket"), *rocket.id());
+    assert!(shulker.custom_data().is_some());
+}
+
+/// Components on items that don't have them by default, and one from a mod
+#[test]
+fn synthetic_item_components() {
+    let Value::Compound(mut value) = corpus("survival") else {
+        unreachable!()
+    };
+    let Some(Value::List(inventory)) = value.get_mut("Inventory") else {
+        panic!("Expected an inventory");
+    };
+    inventory.push(
+        snbt::parse(
+            r##"{
+                Slot: 10b, count: 1, id: "minecraft:stick",
+                components: {
+                    "example:soulbound": 1b,
+                    "minecraft:max_stack_size": 16,
+                    "minecraft:food": {nutrition: 2, saturation: 1.2f, can_always_eat: 1b},
+                    "minecraft:consumable": {
+                        consume_seconds: 0.8f, animation: "eat",
+                        on_consume_effects: [
+                            {
+                                type: "minecraft:apply_effects", probability: 0.5f,
+                                effects: [
+                                    {id: "minecraft:haste", duration: 200, amplifier: 0b, show_icon: 1b}
+                                ]
+                            },
+                            {type: "minecraft:clear_all_effects"}
+                        ]
+                    },
+                    "minecraft:tool": {
+                        rules: [
+                            {blocks: "#minecraft:mineable/axe", speed: 8.0f, correct_for_drops: 1b}
+                        ],
+                        damage_per_block: 2
+                    },
+                    "minecraft:can_break": {
+                        predicates: [{blocks: "minecraft:stone"}], show_in_tooltip: 0b
+                    }
+                }
+            }"##,
+        )
+        .unwrap(),
+    );
+    let value = Value::Compound(value);
+
+    let player: Player = fastnbt::from_bytes(&fastnbt::to_bytes(&value).unwrap()).unwrap();
+    assert_eq!(value, to_value(&player));
+
+    let stick = player.inventory().iter().find(|item| item.slot() == 10);
+    let components = stick.unwrap().item().components().unwrap();
+    assert_eq!(
+        Some(&Value::Byte(1)),
+        components
+            .other()
+            .get(&Identifier::new("example", "soulbound"))
+    );
+    let stick = components.minecraft();
+    assert_eq!(Some(16), stick.max_stack_size());
+    assert!(stick.food().is_some());
+    assert_eq!(1, stick.tool().unwrap().rules().len());
+}
+
+#[test]
+fn selected_item() {
+    // Derived from the inventory, since it isn't saved on its own
+    let survival = player("survival");
+    assert_eq!(2, survival.selected_item_slot());
+    assert!(survival.selected_item().is_some());
+    let Value::Compound(value) = to_value(&survival) else {
+        panic!("Expected a compound");
+    };
+    assert!(!value.contains_key("SelectedItem"));
+
+    assert!(player("creative_nether").selected_item().is_none());
+}
+
+#[test]
+fn game_type() {
+    let mut player = player("new_player");
+    assert_eq!(GameType::Survival, player.game_type());
+    player.set_game_type(GameType::Creative);
+    assert_eq!(GameType::Creative, player.game_type());
+
+    let Value::Compound(value) = to_value(&player) else {
+        panic!("Expected a compound");
+    };
+    assert_eq!(Some(&Value::Int(1)), value.get("playerGameType"));
+    assert_eq!(Some(&Value::Int(0)), value.get("previousPlayerGameType"));
+}
+
+#[test]
+fn save_and_load() {
+    let directory = tempfile::tempdir().unwrap();
+    // Player files don't depend on anything in `level.dat`
+    let world = World::create(directory.path(), LevelData::new("Players", 0)).unwrap();
+    let mut player = player("survival");
+    let uuid = player.uuid().clone();
+    let path = directory
+        .path()
+        .join("playerdata/5b2af6e4-b80a-4cd2-9210-c2002e095911.dat");
+
+    assert!(world.read_player(&uuid).unwrap().is_none());
+    assert!(world.players().unwrap().is_empty());
+
+    world.write_player(&player).unwrap();
+    assert!(path.exists());
+    assert!(
+        !directory
+            .path()
+            .join("playerdata/5b2af6e4-b80a-4cd2-9210-c2002e095911.dat_old")
+            .exists()
+    );
+    let loaded = world.read_player(&uuid).unwrap().unwrap();
+    assert_eq!(corpus("survival"), to_value(&loaded));
+
+    // The previous save is kept as a backup
+    player.set_pos([0.5, 80.0, 0.5]);
+    world.write_player(&player).unwrap();
+    let mut backup = path.clone().into_os_string();
+    backup.push("_old");
+    assert!(Path::new(&backup).exists());
+    assert_eq!(
+        [0.5, 80.0, 0.5],
+        world.read_player(&uuid).unwrap().unwrap().pos()
+    );
+
+    // And read if the save itself is broken
+    std::fs::write(&path, b"not gzip").unwrap();
+    assert_eq!(
+        corpus("survival"),
+        to_value(&world.read_player(&uuid).unwrap().unwrap())
+    );
+
+    let players = world.players().unwrap();
+    assert_eq!(1, players.len());
+    assert_eq!(*uuid, *players[0]);
+    assert!(
+        world
+            .read_player(&self::uuid("00000000-0000-0000-0000-000000000002"))
+            .unwrap()
+            .is_none()
+    );
+}
@@ -0,0 +1,181 @@
+use mc_server_rs::{
+    data::player::Profile,
+    frame::Frame,
+    protocol::{
+        AnyPacket, ConnectionState, Direction, Packet, PacketError, Registry, StateMachine,
+        configuration, handshaking, login, play, status,
+    },
+};
+use rstest::{fixture, rstest};
+use uuid::Uuid;
+
+#[fixture]
+fn registry() -> Registry {
+    Registry::vanilla()
+}
+
+fn handshake(intent: handshaking::Intent) -> AnyPacket {
+    handshaking::Serverbound::from(handshaking::Handshake {
+        protocol_version: 769,
+        server_address: "localhost".to_string(),
+        server_port: 25565,
+        intent,
+    })
+    .into()
+}
+
+fn login_success() -> AnyPacket {
+    login::Clientbound::from(login::LoginSuccess {
+        profile: Profile::new(Uuid::nil(), "Notch".to_string(), vec![]),
+    })
+    .into()
+}
+
+fn login_acknowledged() -> AnyPacket {
+    login::Serverbound::from(login::LoginAcknowledged).into()
+}
+
+fn finish_configuration() -> AnyPacket {
+    configuration::Clientbound::from(configuration::FinishConfiguration).into()
+}
+
+fn acknowledge_finish_configuration() -> AnyPacket {
+    configuration::Serverbound::from(configuration::AcknowledgeFinishConfiguration).into()
+}
+
+#[rstest]
+fn decode(registry: Registry) {
+    let frame = status::PingRequest { timestamp: 1234 }.to_frame().unwrap();
+    let packet = registry
+        .decode(ConnectionState::Status, Direction::Serverbound, &frame)
+        .unwrap();
+
+    assert!(matches!(
+        packet,
+        AnyPacket::StatusServerbound(status::Serverbound::PingRequest(status::PingRequest {
+            timestamp: 1234
+        }))
+    ));
+    assert_eq!(frame, packet.encode().unwrap());
+}
+
+#[rstest]
+fn same_id_different_state(registry: Registry) {
+    let frame = Frame::new(0x00, vec![]);
+
+    assert!(matches!(
+        registry.decode(ConnectionState::Status, Direction::Serverbound, &frame),
+        Ok(AnyPacket::StatusServerbound(
+            status::Serverbound::StatusRequest(_)
+        ))
+    ));
+    assert!(matches!(
+        registry.decode(ConnectionState::Play, Direction::Serverbound, &frame),
+        Err(PacketError::Io(_))
+    ));
+}
+
+#[rstest]
+fn unknown_id(registry: Registry) {
+    let frame = Frame::new(0x7f, vec![1, 2, 3]);
+    let Err(error) = registry.decode(ConnectionState::Login, Direction::Serverbound, &frame) else {
+        panic!("Unknown packet was decoded");
+    };
+
+    assert!(error.is_recoverable());
+    assert!(matches!(
+        error,
+        PacketError::UnknownId {
+            state: ConnectionState::Login,
+            direction: Direction::Serverbound,
+            id: 0x7f
+        }
+    ));
+}
+
+#[test]
+fn disconnect_nbt() {
+    let disconnect = play::Disconnect {
+        reason: Box::new("Goodbye".into()),
+    };
+    let frame = disconnect.to_frame().unwrap();
+
+    let Ok(AnyPacket::PlayClientbound(play::Clientbound::Disconnect(decoded))) =
+        Registry::vanilla().decode(ConnectionState::Play, Direction::Clientbound, &frame)
+    else {
+        panic!("Expected Disconnect");
+    };
+    assert_eq!(disconnect, decoded);
+}
+
+#[test]
+fn status_transition() {
+    let mut machine = StateMachine::new();
+    machine
+        .received(&handshake(handshaking::Intent::Status))
+        .unwrap();
+    assert_eq!(ConnectionState::Status, machine.state());
+}
+
+#[rstest]
+#[case(handshaking::Intent::Login)]
+#[case(handshaking::Intent::Transfer)]
+fn full_transition(#[case] intent: handshaking::Intent) {
+    let mut machine = StateMachine::new();
+    machine.received(&handshake(intent)).unwrap();
+    assert_eq!(ConnectionState::Login, machine.state());
+
+    machine.sent(&login_success()).unwrap();
+    assert!(machine.is_awaiting_acknowledgement());
+    machine.received(&login_acknowledged()).unwrap();
+    assert_eq!(ConnectionState::Configuration, machine.state());
+
+    machine.sent(&finish_configuration()).unwrap();
+    machine
+        .received(&acknowledge_finish_configuration())
+        .unwrap();
+    assert_eq!(ConnectionState::Play, machine.state());
+
+    machine
+        .sent(&play::Clientbound::from(play::StartConfiguration).into())
+        .unwrap();
+    machine
+        .received(&play::Serverbound::from(play::ConfigurationAcknowledged).into())
+        .unwrap();
+    assert_eq!(ConnectionState::Configuration, machine.state());
+}
+
+#[test]
+fn acknowledgement_without_request() {
+    let mut machine = StateMachine::new();
+    machine
+        .received(&handshake(handshaking::Intent::Login))
+        .unwrap();
+
+    assert!(matches!(
+        machine.received(&login_acknowledged()),
+        Err(PacketError::UnexpectedPacket {
+            state: ConnectionState::Login,
+            id: 0x03,
+            ..
+        })
+    ));
+    assert_eq!(ConnectionState::Login, machine.state());
+}
+
+#[test]
+fn wrong_state() {
+    let mut machine = StateMachine::new();
+    assert!(machine.received(&login_acknowledged()).is_err());
+    assert!(machine.sent(&login_success()).is_err());
+    assert_eq!(ConnectionState::Handshaking, machine.state());
+}
+
+#[test]
+fn wrong_direction() {
+    let mut machine = StateMachine::new();
+    machine
+        .received(&handshake(handshaking::Intent::Login))
+        .unwrap();
+    assert!(machine.received(&login_success()).is_err());
+}
@@ -0,0 +1,224 @@
+use fastnbt::{LongArray, Value, nbt};
+use mc_server_rs::data::{
+    chunk::Chunk,
+    region::{Compression, Region, RegionError},
+};
+use rstest::rstest;
+use std::path::Path;
+use tempfile::TempDir;
+
+fn chunk(x: i32, z: i32) -> Value {
+    nbt!({
+        "DataVersion": 4189,
+        "xPos": x,
+        "yPos": -4,
+        "zPos": z,
+        "Status": "minecraft:full",
+        "LastUpdate": 1200_i64,
+        "InhabitedTime": 30_i64,
+        "isLightOn": 1_i8,
+        "sections": [{
+            "Y": -4_i8,
+            "block_states": { "palette": [{ "Name": "minecraft:stone" }] },
+            "biomes": { "palette": ["minecraft:plains"] },
+        }],
+        "block_entities": [{ "id": "minecraft:chest", "x": x * 16, "y": -60, "z": z * 16 }],
+        "Heightmaps": { "WORLD_SURFACE": LongArray::new(vec![0; 37]) },
+        "block_ticks": [],
+        "fluid_ticks": [],
+        "PostProcessing": [[], [1_i16, 2_i16]],
+        "structures": { "References": {}, "starts": {} },
+    })
+}
+
+/// NBT that doesn't compress, so it takes up as many sectors as it is long
+fn noise(length: usize) -> Vec<u8> {
+    let mut state = 0x2545f491_u32;
+    let bytes = (0..length)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            state as u8
+        })
+        .collect::<Vec<_>>();
+    fastnbt::to_bytes(
+        &nbt!({ "noise": fastnbt::ByteArray::new(bytes.into_iter().map(|b| b as i8).collect()) }),
+    )
+    .unwrap()
+}
+
+fn open(directory: &TempDir) -> Region {
+    Region::open(directory.path().join("r.-1.2.mca")).unwrap()
+}
+
+fn file_length(path: &Path) -> u64 {
+    std::fs::metadata(path).unwrap().len()
+}
+
+#[rstest]
+#[case(Compression::Gzip)]
+#[case(Compression::Zlib)]
+#[case(Compression::None)]
+#[case(Compression::Lz4)]
+fn round_trip(#[case] compression: Compression) {
+    let directory = tempfile::tempdir().unwrap();
+    let mut region = open(&directory);
+    assert_eq!((-1, 2), region.position());
+    assert!(region.read(-30, 70).unwrap().is_none());
+
+    let value = chunk(-30, 70);
+    region
+        .write(-30, 70, &fastnbt::to_bytes(&value).unwrap(), compression)
+        .unwrap();
+    assert!(region.timestamp(-30, 70).unwrap() > 0);
+    assert_eq!(vec![(-30, 70)], region.chunks().collect::<Vec<_>>());
+
+    // Reopened so nothing is served from memory
+    let mut region = open(&directory);
+    let chunk = region.read_chunk::<Chunk>(-30, 70).unwrap().unwrap();
+    assert_eq!((-30, 70), chunk.position());
+    let bytes = fastnbt::to_bytes(&chunk).unwrap();
+    assert_eq!(value, fastnbt::from_bytes::<Value>(&bytes).unwrap());
+}
+
+#[test]
+fn lz4_blocks() {
+    // Bigger than one 64 KiB LZ4 block, and mixing compressible and incompressible blocks
+    let mut data = noise(100_000);
+    data.extend(vec![0; 100_000]);
+
+    let directory = tempfile::tempdir().unwrap();
+    let mut region = open(&directory);
+    region.write(0, 0, &data, Compression::Lz4).unwrap();
+    assert_eq!(data, region.read(0, 0).unwrap().unwrap());
+}
+
+#[test]
+fn sectors_are_reused() {
+    let directory = tempfile::tempdir().unwrap();
+    let path = directory.path().join("r.-1.2.mca");
+    let mut region = open(&directory);
+
+    region
+        .write(0, 0, &noise(10_000), Compression::None)
+        .unwrap();
+    region.write(1, 0, &noise(100), Compression::None).unwrap();
+    // 2 header sectors, 3 for the first chunk and 1 for the second
+    assert_eq!(6 * 4096, file_length(&path));
+
+    // Growing moves the chunk to the end, shrinking it again fills the gap it left
+    region
+        .write(0, 0, &noise(20_000), Compression::None)
+        .unwrap();
+    assert_eq!(11 * 4096, file_length(&path));
+    region
+        .write(1, 0, &noise(5_000), Compression::None)
+        .unwrap();
+    assert_eq!(11 * 4096, file_length(&path));
+    region.write(2, 0, &noise(100), Compression::None).unwrap();
+    assert_eq!(11 * 4096, file_length(&path));
+
+    let mut region = open(&directory);
+    assert_eq!(noise(20_000), region.read(0, 0).unwrap().unwrap());
+    assert_eq!(noise(5_000), region.read(1, 0).unwrap().unwrap());
+    assert_eq!(noise(100), region.read(2, 0).unwrap().unwrap());
+}
+
+#[test]
+fn defragment() {
+    let directory = tempfile::tempdir().unwrap();
+    let path = directory.path().join("r.-1.2.mca");
+    let mut region = open(&directory);
+
+    region
+        .write(0, 0, &noise(10_000), Compression::None)
+        .unwrap();
+    region
+        .write(1, 0, &noise(10_000), Compression::Zlib)
+        .unwrap();
+    region
+        .write(2, 0, &noise(10_000), Compression::None)
+        .unwrap();
+    region.remove(0, 0).unwrap();
+    region.remove(5, 5).unwrap();
+    region.write(1, 0, &noise(100), Compression::Zlib).unwrap();
+    let timestamp = region.timestamp(2, 0);
+    assert_eq!(11 * 4096, file_length(&path));
+
+    region.defragment().unwrap();
+    assert_eq!(6 * 4096, file_length(&path));
+    assert_eq!(timestamp, region.timestamp(2, 0));
+    assert!(!region.contains(0, 0));
+
+    region.write(3, 0, &noise(100), Compression::Gzip).unwrap();
+    assert_eq!(7 * 4096, file_length(&path));
+
+    let mut region = open(&directory);
+    assert!(region.read(0, 0).unwrap().is_none());
+    assert_eq!(noise(100), region.read(1, 0).unwrap().unwrap());
+    assert_eq!(noise(10_000), region.read(2, 0).unwrap().unwrap());
+    assert_eq!(noise(100), region.read(3, 0).unwrap().unwrap());
+}
+
+#[test]
+fn oversized_chunks_are_external() {
+    let directory = tempfile::tempdir().unwrap();
+    let path = directory.path().join("r.-1.2.mca");
+    // Region -1, 2 starts at chunk -32, 64
+    let external = directory.path().join("c.-31.67.mcc");
+    let mut region = open(&directory);
+
+    let data = noise(2 * 1024 * 1024);
+    region.write(1, 3, &data, Compression::None).unwrap();
+    assert!(external.exists());
+    assert_eq!(3 * 4096, file_length(&path));
+    assert_eq!(data, open(&directory).read(1, 3).unwrap().unwrap());
+
+    region.write(1, 3, &noise(100), Compression::None).unwrap();
+    assert!(!external.exists());
+    assert_eq!(noise(100), region.read(1, 3).unwrap().unwrap());
+
+    region.write(1, 3, &data, Compression::Zlib).unwrap();
+    region.remove(1, 3).unwrap();
+    assert!(!external.exists());
+}
+
+#[rstest]
+#[case("r.0.0")]
+#[case("r.0.mca")]
+#[case("c.0.0.mca")]
+#[case("r.a.0.mca")]
+fn invalid_file_name(#[case] name: &str) {
+    let directory = tempfile::tempdir().unwrap();
+    assert!(matches!(
+        Region::open(directory.path().join(name)),
+        Err(RegionError::InvalidFileName)
+    ));
+}
+
+#[test]
+fn corrupted() {
+    let directory = tempfile::tempdir().unwrap();
+    let path = directory.path().join("r.-1.2.mca");
+    let mut region = open(&directory);
+    region.write(0, 0, &noise(100), Compression::Zlib).unwrap();
+    region.write(1, 0, &noise(100), Compression::Zlib).unwrap();
+
+    let mut bytes = std::fs::read(&path).unwrap();
+    // Chunk 0's length runs past its one sector
+    bytes[2 * 4096..2 * 4096 + 4].copy_from_slice(&5000_u32.to_be_bytes());
+    // Chunk 1 claims an unknown compression
+    bytes[3 * 4096 + 4] = 99;
+    std::fs::write(&path, bytes).unwrap();
+
+    let mut region = open(&directory);
+    assert!(matches!(
+        region.read(0, 0),
+        Err(RegionError::CorruptedChunk { x: 0, z: 0 })
+    ));
+    assert!(matches!(
+        region.read(1, 0),
+        Err(RegionError::UnknownCompression(99))
+    ));
+}
@@ -0,0 +1,160 @@
+use mc_server_rs::{
+    data::{
+        Identifier, Selector,
+        player::GameType,
+        selector::{
+            AdvancementPredicate, Bounds, EntityTypeFilter, Negatable, SelectorArgument,
+            SelectorKind, Sort, WrittenIdentifier,
+        },
+    },
+    nbt::snbt,
+};
+use rstest::rstest;
+use uuid::Uuid;
+
+#[rstest]
+#[case("@p")]
+#[case("@r")]
+#[case("@a")]
+#[case("@e")]
+#[case("@s")]
+#[case("@n")]
+#[case("Notch")]
+#[case("069a79f4-44e9-4726-a5be-fca90e38aaf5")]
+#[case("@e[type=minecraft:zombie,limit=1,sort=nearest]")]
+#[case("@e[type=zombie,predicate=is_night]")]
+#[case("@e[type=!#minecraft:skeletons,type=!#raiders,tag=!,team=red]")]
+#[case("@e[name=!a,name=!b,gamemode=!creative,gamemode=!spectator,tag=a,tag=b]")]
+#[case("@a[name=\"Sir Notch\",gamemode=!spectator,level=10..]")]
+#[case("@e[x=1.5,y=-64,z=0,dx=10,dy=5,dz=10,distance=..5.5]")]
+#[case("@e[x_rotation=-90..0,y_rotation=45]")]
+#[case("@a[scores={kills=1..,deaths=..3},tag=alive]")]
+#[case(
+    "@a[advancements={minecraft:story/mine_stone=true,minecraft:husbandry/balanced_diet={apple=false}}]"
+)]
+#[case("@a[advancements={story/mine_stone=true}]")]
+#[case("@e[nbt=!{CustomName:\"{x}\",Tags:[\"a}\"]},nbt={OnGround:1b},predicate=example:is_night]")]
+fn display_round_trip(#[case] s: &str) {
+    let selector = s.parse::<Selector>().unwrap();
+    assert_eq!(s, selector.to_string());
+    assert_eq!(selector, selector.to_string().parse().unwrap());
+}
+
+#[test]
+fn arguments() {
+    let selector = r#"@e[ type = !zombie , distance=1..5, scores={ kills = 3 }, name="it's" ]"#
+        .parse::<Selector>()
+        .unwrap();
+
+    assert_eq!(
+        Selector::Target {
+            kind: SelectorKind::AllEntities,
+            arguments: vec![
+                SelectorArgument::Type(Negatable::not(EntityTypeFilter::Kind(WrittenIdentifier {
+                    id: Identifier::minecraft("zombie"),
+                    namespaced: false,
+                }))),
+                SelectorArgument::Distance(Bounds {
+                    min: Some(1.0),
+                    max: Some(5.0),
+                }),
+                SelectorArgument::Scores(vec![("kills".to_string(), Bounds::exactly(3))]),
+                SelectorArgument::Name(Negatable::new("it's".to_string())),
+            ],
+        },
+        selector
+    );
+    assert_eq!(
+        "@e[type=!zombie,distance=1..5,scores={kills=3},name=\"it's\"]",
+        selector.to_string()
+    );
+}
+
+#[test]
+fn built() {
+    let selector = Selector::Target {
+        kind: SelectorKind::AllPlayers,
+        arguments: vec![
+            SelectorArgument::Gamemode(Negatable::new(GameType::Creative)),
+            SelectorArgument::Sort(Sort::Random),
+            SelectorArgument::Advancements(vec![(
+                Identifier::minecraft("adventure/root").into(),
+                AdvancementPredicate::Done(false),
+            )]),
+        ],
+    };
+    assert_eq!(
+        "@a[gamemode=creative,sort=random,advancements={minecraft:adventure/root=false}]",
+        selector.to_string()
+    );
+    assert_eq!(
+        Selector::from(SelectorKind::Executor),
+        "@s".parse().unwrap()
+    );
+}
+
+#[test]
+fn nbt() {
+    let selector = r#"@e[nbt=!{Tags:["a}"],CustomName:'{x}'}]"#.parse::<Selector>().unwrap();
+
+    assert_eq!(
+        Selector::Target {
+            kind: SelectorKind::AllEntities,
+            arguments: vec![SelectorArgument::Nbt(Negatable::not(
+                snbt::parse(r#"{Tags:["a}"],CustomName:"{x}"}"#).unwrap()
+            ))],
+        },
+        selector
+    );
+    assert_eq!(
+        r#"@e[nbt=!{CustomName:"{x}",Tags:["a}"]}]"#,
+        selector.to_string()
+    );
+}
+
+#[test]
+fn uuid() {
+    assert_eq!(
+        Selector::Uuid(Uuid::from_u128(0x069a79f444e94726a5befca90e38aaf5)),
+        "069a79f4-44e9-4726-a5be-fca90e38aaf5".parse().unwrap()
+    );
+}
+
+#[rstest]
+#[case("")]
+#[case("@")]
+#[case("@x")]
+#[case("@e[")]
+#[case("@e[limit=1")]
+#[case("@e[limit=one]")]
+#[case("@e[distance=..]")]
+#[case("@e[unknown=1]")]
+#[case("@e[sort=sideways]")]
+#[case("@e[gamemode=hardcore]")]
+#[case("@e[nbt={a:1]")]
+#[case("@e[nbt={a:1b}}]")]
+#[case("@e[limit=0]")]
+#[case("@e[distance=-1]")]
+#[case("@e[distance=..-1]")]
+#[case("@a[level=-1..]")]
+#[case("@e[x=1,x=2]")]
+#[case("@e[limit=1,limit=2]")]
+#[case("@e[sort=nearest,sort=random]")]
+#[case("@e[scores={a=1},scores={b=1}]")]
+#[case("@e[name=a,name=b]")]
+#[case("@e[name=!a,name=b]")]
+#[case("@e[name=a,name=!b]")]
+#[case("@e[gamemode=creative,gamemode=survival]")]
+#[case("@e[type=zombie,type=!skeleton]")]
+#[case("@e[type=!zombie,type=skeleton]")]
+#[case("@a[type=player]")]
+#[case("@s[limit=1]")]
+#[case("@s[sort=nearest]")]
+#[case("@e[name=\"unclosed]")]
+#[case("@e[scores={=1}]")]
+#[case("@e]")]
+#[case("ThisNameIsWayTooLong")]
+#[case("two words")]
+fn invalid(#[case] s: &str) {
+    assert!(s.parse::<Selector>().is_err(), "{s} parsed");
+}
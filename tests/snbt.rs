@@ -0,0 +1,252 @@
+use fastnbt::{IntArray, Value, nbt};
+use mc_server_rs::{
+    data::entity::{Entity, EntityKind},
+    nbt::snbt,
+};
+use rstest::rstest;
+use serde::{Deserialize, Serialize};
+
+#[rstest]
+#[case("1b", Value::Byte(1))]
+#[case("-2S", Value::Short(-2))]
+#[case("3", Value::Int(3))]
+#[case("+4l", Value::Long(4))]
+#[case("1.5f", Value::Float(1.5))]
+#[case("2F", Value::Float(2.0))]
+#[case("1.5", Value::Double(1.5))]
+#[case(".5", Value::Double(0.5))]
+#[case("1e3d", Value::Double(1000.0))]
+#[case("1.e-1", Value::Double(0.1))]
+#[case("true", Value::Byte(1))]
+#[case("false", Value::Byte(0))]
+#[case("foo_bar.baz-1", Value::String("foo_bar.baz-1".to_string()))]
+#[case("\"minecraft:stone\"", Value::String("minecraft:stone".to_string()))]
+#[case("\"a \\\" b\"", Value::String("a \" b".to_string()))]
+#[case("'it\\'s'", Value::String("it's".to_string()))]
+#[case("1i", Value::Int(1))]
+#[case("1_000", Value::Int(1000))]
+#[case("0xFF", Value::Int(255))]
+#[case("0xFFFFFFFF", Value::Int(-1))]
+#[case("0xFFub", Value::Byte(-1))]
+#[case("0x7fsb", Value::Byte(127))]
+#[case("0b101", Value::Int(5))]
+#[case("0b1_0s", Value::Short(2))]
+#[case("0b", Value::Byte(0))]
+#[case("255ub", Value::Byte(-1))]
+#[case("-1sl", Value::Long(-1))]
+#[case("1_000.5f", Value::Float(1000.5))]
+#[case("bool(2)", Value::Byte(1))]
+#[case("bool(0.0d)", Value::Byte(0))]
+#[case(
+    r#"uuid("f81d4fae-7dec-11d0-a765-00a0c91e6bf6")"#,
+    Value::IntArray(IntArray::new(vec![-132296786, 2112623056, -1486552928, -920753162]))
+)]
+#[case(
+    r#""\x41\u00e9\U0001F600\N{snowman}\N{CJK UNIFIED IDEOGRAPH-4E00}\s\t\"""#,
+    Value::String("A\u{e9}\u{1f600}\u{2603}\u{4e00} \t\"".to_string())
+)]
+#[case(
+    r#""\N{GREEK SMALL LETTER ALPHA}\N{ hangul syllable gag }\N{CJK UNIFIED IDEOGRAPH-2A700}""#,
+    Value::String("\u{3b1}\u{ac01}\u{2a700}".to_string())
+)]
+fn scalars(#[case] s: &str, #[case] expected: Value) {
+    assert_eq!(expected, snbt::parse(s).unwrap());
+}
+
+#[test]
+fn nested() {
+    assert_eq!(
+        nbt!({
+            "id": "minecraft:zombie",
+            "Health": 20.0_f32,
+            "Tags": ["a", "b"],
+            "Pos": [1.0, 2.0, 3.0],
+            "UUID": [I; 1, -2, 3, 4],
+            "Bytes": [B; 1_i8, 0_i8],
+            "Longs": [L; 5_i64],
+            "Empty": [],
+            "Nested": { "key with spaces": {} },
+        }),
+        snbt::parse(
+            r#" {
+                id: "minecraft:zombie", Health: 20f, Tags: [a, b], Pos: [1.0d, 2d, 3.],
+                UUID: [I; 1, -2, 3, 4], Bytes: [B; 1b, false], Longs: [L; 5L], Empty: [],
+                Nested: {"key with spaces": {},},
+            } "#
+        )
+        .unwrap()
+    );
+}
+
+#[rstest]
+#[case("", 0)]
+#[case("{", 1)]
+#[case("{a}", 2)]
+#[case("{a:}", 3)]
+#[case("[1, 2b]", 4)]
+#[case("[I; 1b]", 4)]
+#[case("[X; 1]", 1)]
+#[case("'unclosed", 9)]
+#[case("{} {}", 3)]
+#[case("[B; 1, 2s]", 7)]
+#[case("[I; 1.5]", 4)]
+#[case(r#""\q""#, 1)]
+#[case(r#""a\x4""#, 2)]
+#[case(r#""\N{NOT A CHARACTER}""#, 1)]
+#[case(r#""\N{CJK UNIFIED IDEOGRAPH-41}""#, 1)]
+#[case("128b", 0)]
+#[case("01", 0)]
+#[case("1e3", 0)]
+#[case("256ub", 0)]
+#[case("-1ub", 0)]
+#[case("0xFFsb", 0)]
+#[case("1__", 0)]
+#[case("1e39f", 0)]
+#[case("[a, +b]", 4)]
+#[case("{a: .x}", 4)]
+#[case("bool('a')", 0)]
+#[case("uuid(1)", 0)]
+fn errors(#[case] s: &str, #[case] position: usize) {
+    assert_eq!(position, snbt::parse(s).unwrap_err().position);
+}
+
+#[test]
+fn too_deep() {
+    let s = "[".repeat(600) + &"]".repeat(600);
+    assert!(snbt::parse(&s).is_err());
+}
+
+#[test]
+fn arrays() {
+    assert_eq!(
+        nbt!({
+            "Bytes": [B; 1_i8, -1_i8, 1_i8],
+            "Longs": [L; 1_i64, 2_i64],
+            "Empty": [I;],
+        }),
+        snbt::parse("{Bytes: [B; 1, 255ub, true], Longs: [L; 1, 2L], Empty: [I;]}").unwrap()
+    );
+}
+
+#[test]
+fn print() {
+    let value = nbt!({
+        "id": "minecraft:zombie",
+        "Health": 20.0_f32,
+        "Pos": [1.0, 2.5, -3.0],
+        "UUID": [I; 1, -2, 3, 4],
+        "Tags": ["a", "it's", "say \"hi\"\n"],
+        "Items": [{ "id": "minecraft:stone", "count": 1_i8 }],
+        "Empty": {},
+        "key with spaces": [L;],
+        "Time": 5_i64,
+        "Fuse": 30_i16,
+    });
+
+    let compact = snbt::print(&value);
+    assert_eq!(
+        r#"{Empty:{},Fuse:30s,Health:20.0f,Items:[{count:1b,id:"minecraft:stone"}],Pos:[1.0d,2.5d,-3.0d],Tags:["a","it's",'say "hi"\n'],Time:5L,UUID:[I;1,-2,3,4],id:"minecraft:zombie","key with spaces":[L;]}"#,
+        compact
+    );
+    assert_eq!(value, snbt::parse(&compact).unwrap());
+
+    let pretty = snbt::print_pretty(&value);
+    assert_eq!(
+        r#"{
+    Empty: {},
+    Fuse: 30s,
+    Health: 20.0f,
+    Items: [
+        {
+            count: 1b,
+            id: "minecraft:stone"
+        }
+    ],
+    Pos: [1.0d, 2.5d, -3.0d],
+    Tags: ["a", "it's", 'say "hi"\n'],
+    Time: 5L,
+    UUID: [I; 1, -2, 3, 4],
+    id: "minecraft:zombie",
+    "key with spaces": [L;]
+}"#,
+        pretty
+    );
+    assert_eq!(value, snbt::parse(&pretty).unwrap());
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+enum Shape {
+    Point,
+    Circle { radius: f32 },
+    Line(Vec<[i32; 3]>),
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "PascalCase")]
+struct Thing {
+    name: String,
+    visible: bool,
+    color: u8,
+    pos: [i32; 3],
+    uuid: IntArray,
+    owner: Option<String>,
+    shape: Shape,
+    shapes: Vec<Shape>,
+}
+
+#[test]
+fn serde() {
+    let thing = Thing {
+        name: "it's a \"thing\"".to_string(),
+        visible: true,
+        color: 200,
+        pos: [1, 2, 3],
+        uuid: IntArray::new(vec![1, 2, 3, 4]),
+        owner: None,
+        shape: Shape::Point,
+        shapes: vec![
+            Shape::Circle { radius: 1.5 },
+            Shape::Line(vec![[0, 0, 0], [1, 1, 1]]),
+        ],
+    };
+    let snbt = snbt::to_string(&thing).unwrap();
+    assert_eq!(
+        r#"{Color:-56b,Name:"it's a \"thing\"",Pos:[1,2,3],Shape:"Point",Shapes:[{Circle:{radius:1.5f}},{Line:[[0,0,0],[1,1,1]]}],Uuid:[I;1,2,3,4],Visible:1b}"#,
+        snbt
+    );
+    assert_eq!(thing, snbt::from_str(&snbt).unwrap());
+
+    // Written by hand, the way commands are
+    assert_eq!(
+        Thing {
+            owner: Some("Steve".to_string()),
+            ..thing
+        },
+        snbt::from_str(
+            r#"{
+                Name: 'it\'s a "thing"', Visible: true, Color: 200ub, Pos: [I; 1, 2, 3],
+                Uuid: [I; 1, 2, 3, 4], Owner: Steve,
+                Shape: Point, Shapes: [{Circle: {radius: 1.5f}}, {Line: [[0, 0, 0], [1, 1, 1]]}]
+            }"#
+        )
+        .unwrap()
+    );
+
+    let error = snbt::from_str::<Thing>("{Name: 1}").unwrap_err();
+    assert!(matches!(error, snbt::Error::Data(_)), "{error}");
+    let error = snbt::from_str::<Thing>("{Name: }").unwrap_err();
+    assert!(matches!(error, snbt::Error::Syntax(_)), "{error}");
+}
+
+#[test]
+fn entities() {
+    for kind in [
+        EntityKind::Zombie,
+        EntityKind::ItemDisplay,
+        EntityKind::Arrow,
+    ] {
+        let snbt = snbt::to_string_pretty(&Entity::new(kind)).unwrap();
+        let entity: Entity = snbt::from_str(&snbt).unwrap();
+        assert_eq!(snbt, snbt::to_string_pretty(&entity).unwrap());
+    }
+}
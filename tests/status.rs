@@ -0,0 +1,218 @@
+use mc_server_rs::{
+    frame::FrameCodec,
+    protocol::{
+        Packet, Packets, handshaking,
+        status::{self, Favicon, PlayerSample, Status},
+    },
+    server::status::{LEGACY_PROTOCOL_VERSION, LEGACY_TIMEOUT, StatusServer},
+};
+use rstest::{fixture, rstest};
+use std::{
+    io::{Read, Write},
+    net::{SocketAddr, TcpStream},
+};
+use uuid::Uuid;
+
+fn status() -> Status {
+    let mut status = Status::new("A Minecraft Server", 20);
+    let players = status.players.as_mut().unwrap();
+    players.online = 1;
+    players.sample.push(PlayerSample {
+        name: "Notch".to_string(),
+        id: Uuid::from_u128(0x069a79f444e94726a5befca90e38aaf5),
+    });
+    status
+}
+
+fn png(width: u32, height: u32) -> Vec<u8> {
+    let mut out = vec![0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+    out.extend_from_slice(&13u32.to_be_bytes());
+    out.extend_from_slice(b"IHDR");
+    out.extend_from_slice(&width.to_be_bytes());
+    out.extend_from_slice(&height.to_be_bytes());
+    out
+}
+
+#[fixture]
+fn server() -> SocketAddr {
+    let server = StatusServer::bind("127.0.0.1:0", status).unwrap();
+    let addr = server.local_addr().unwrap();
+    std::thread::spawn(move || server.run());
+    addr
+}
+
+fn legacy_response(mut stream: TcpStream) -> String {
+    let mut header = [0; 3];
+    stream.read_exact(&mut header).unwrap();
+    assert_eq!(0xff, header[0]);
+
+    let mut body = vec![0; u16::from_be_bytes([header[1], header[2]]) as usize * 2];
+    stream.read_exact(&mut body).unwrap();
+    String::from_utf16(
+        &body
+            .chunks(2)
+            .map(|unit| u16::from_be_bytes([unit[0], unit[1]]))
+            .collect::<Vec<_>>(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn json_shape() {
+    let mut status = status();
+    status.favicon = Some(Favicon::from_png(png(64, 64)).unwrap());
+    let json = serde_json::to_value(&status).unwrap();
+
+    assert_eq!(
+        serde_json::json!({ "name": "1.21.4", "protocol": 769 }),
+        json["version"]
+    );
+    assert_eq!(
+        serde_json::json!({
+            "max": 20,
+            "online": 1,
+            "sample": [{ "name": "Notch", "id": "069a79f4-44e9-4726-a5be-fca90e38aaf5" }]
+        }),
+        json["players"]
+    );
+    assert_eq!("A Minecraft Server", json["description"]["text"]);
+    assert!(json["description"].get("color").is_none());
+    assert!(
+        json["favicon"]
+            .as_str()
+            .unwrap()
+            .starts_with("data:image/png;base64,")
+    );
+    assert_eq!(false, json["enforcesSecureChat"]);
+
+    let read: Status = serde_json::from_value(json).unwrap();
+    assert_eq!(status.favicon, read.favicon);
+    assert_eq!(status.players, read.players);
+}
+
+#[rstest]
+#[case(png(64, 64), true)]
+#[case(png(128, 128), false)]
+#[case(b"GIF89a".to_vec(), false)]
+fn favicon(#[case] png: Vec<u8>, #[case] valid: bool) {
+    assert_eq!(valid, Favicon::from_png(png).is_ok());
+}
+
+#[rstest]
+#[case::localhost("localhost".to_string())]
+// Makes the Handshake's frame 254 bytes long, so that it starts with the same 0xfe 0x01 as a
+// legacy ping
+#[case::long_address("a".repeat(246))]
+fn modern_ping(server: SocketAddr, #[case] server_address: String) {
+    let codec = FrameCodec::new();
+    let mut stream = TcpStream::connect(server).unwrap();
+
+    for frame in [
+        handshaking::Handshake {
+            protocol_version: 769,
+            server_address,
+            server_port: server.port(),
+            intent: handshaking::Intent::Status,
+        }
+        .to_frame()
+        .unwrap(),
+        status::StatusRequest.to_frame().unwrap(),
+    ] {
+        codec.write_frame(&frame, &mut stream).unwrap();
+    }
+
+    let response = codec.read_frame(&mut stream).unwrap();
+    let status::Clientbound::StatusResponse(response) =
+        status::Clientbound::decode(&response).unwrap()
+    else {
+        panic!("Expected a status response");
+    };
+    assert_eq!(
+        Some(1),
+        response.status.players.map(|players| players.online)
+    );
+    assert_eq!(
+        "A Minecraft Server",
+        response.status.description.to_plain_text()
+    );
+
+    codec
+        .write_frame(
+            &status::PingRequest { timestamp: 1234 }.to_frame().unwrap(),
+            &mut stream,
+        )
+        .unwrap();
+    let pong = codec.read_frame(&mut stream).unwrap();
+    assert_eq!(
+        status::PongResponse { timestamp: 1234 },
+        pong.decode().unwrap()
+    );
+
+    // The server hangs up after the pong
+    assert_eq!(0, stream.read(&mut [0]).unwrap());
+}
+
+#[rstest]
+fn slow_long_handshake(server: SocketAddr) {
+    let codec = FrameCodec::new();
+    let mut stream = TcpStream::connect(server).unwrap();
+
+    let mut handshake = Vec::new();
+    let frame = handshaking::Handshake {
+        protocol_version: 769,
+        server_address: "a".repeat(246),
+        server_port: server.port(),
+        intent: handshaking::Intent::Status,
+    }
+    .to_frame()
+    .unwrap();
+    codec.write_frame(&frame, &mut handshake).unwrap();
+
+    // Once the start is known not to be a legacy ping, the rest may take longer than the legacy
+    // timeout to arrive
+    stream.write_all(&handshake[..1]).unwrap();
+    std::thread::sleep(LEGACY_TIMEOUT / 4);
+    stream.write_all(&handshake[1..3]).unwrap();
+    std::thread::sleep(LEGACY_TIMEOUT * 3);
+    stream.write_all(&handshake[3..]).unwrap();
+    codec
+        .write_frame(&status::StatusRequest.to_frame().unwrap(), &mut stream)
+        .unwrap();
+
+    let response = codec.read_frame(&mut stream).unwrap();
+    assert!(matches!(
+        status::Clientbound::decode(&response).unwrap(),
+        status::Clientbound::StatusResponse(_)
+    ));
+}
+
+#[rstest]
+fn legacy_ping(server: SocketAddr) {
+    let mut stream = TcpStream::connect(server).unwrap();
+    stream.write_all(&[0xfe, 0x01]).unwrap();
+
+    assert_eq!(
+        format!("§1\0{LEGACY_PROTOCOL_VERSION}\01.21.4\0A Minecraft Server\01\020"),
+        legacy_response(stream)
+    );
+}
+
+#[rstest]
+fn plugin_message_ping(server: SocketAddr) {
+    let mut stream = TcpStream::connect(server).unwrap();
+    // 1.6 follows the payload with an MC|PingHost plugin message, which isn't needed to answer
+    stream.write_all(&[0xfe, 0x01, 0xfa, 0x00, 0x0b]).unwrap();
+
+    assert_eq!(
+        format!("§1\0{LEGACY_PROTOCOL_VERSION}\01.21.4\0A Minecraft Server\01\020"),
+        legacy_response(stream)
+    );
+}
+
+#[rstest]
+fn beta_ping(server: SocketAddr) {
+    let mut stream = TcpStream::connect(server).unwrap();
+    stream.write_all(&[0xfe]).unwrap();
+
+    assert_eq!("A Minecraft Server§1§20", legacy_response(stream));
+}
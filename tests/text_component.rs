@@ -0,0 +1,164 @@
+use fastnbt::{Value, nbt};
+use mc_server_rs::{
+    text_component::{NbtComponent, SelectorComponent, TextComponent},
+    types::Type,
+};
+use rstest::rstest;
+
+fn json(s: &str) -> TextComponent {
+    serde_json::from_str(s).unwrap()
+}
+
+fn to_nbt(component: &TextComponent) -> Vec<u8> {
+    let mut out = vec![];
+    component.write(&mut out).unwrap();
+    out
+}
+
+fn from_nbt(value: &Value) -> TextComponent {
+    let mut bytes = vec![];
+    value.write(&mut bytes).unwrap();
+    TextComponent::read(bytes.as_slice()).unwrap()
+}
+
+#[rstest]
+#[case::text(r#"{"text": "Hello", "color": "red", "bold": true, "extra": [{"text": " world", "italic": false}]}"#)]
+#[case::tagged_text(r#"{"type": "text", "text": "Hello", "shadow_color": -16777216}"#)]
+#[case::translatable(
+    r##"{"translate": "chat.type.text", "fallback": "<%s> %s", "with": [{"text": "Notch", "color": "#ff8800"}, "Hi"]}"##
+)]
+#[case::score(r#"{"score": {"name": "@s", "objective": "kills"}, "underlined": true}"#)]
+#[case::selector(r#"{"selector": "@a", "separator": {"text": " | "}}"#)]
+#[case::keybind(r#"{"keybind": "key.jump", "shadow_color": [1.0, 0.5, 0.25, 0.0]}"#)]
+#[case::nbt_block(r#"{"nbt": "Items[0].id", "block": "~ ~1 ~", "interpret": true}"#)]
+#[case::nbt_entity(r#"{"nbt": "Health", "entity": "@s", "source": "entity"}"#)]
+#[case::nbt_storage(r#"{"nbt": "foo", "storage": "example:store", "separator": ", "}"#)]
+#[case::events(
+    r#"{"text": "Click", "clickEvent": {"action": "open_url", "value": "https://example.com"}, "hoverEvent": {"action": "show_text", "contents": "Hi"}}"#
+)]
+fn round_trip(#[case] s: &str) {
+    let component = json(s);
+
+    let json = serde_json::to_string(&component).unwrap();
+    assert_eq!(component, serde_json::from_str(&json).unwrap());
+
+    let nbt = to_nbt(&component);
+    assert_eq!(component, TextComponent::read(nbt.as_slice()).unwrap());
+}
+
+#[test]
+fn plain_text_is_a_string_tag() {
+    assert_eq!(
+        [&[0x08, 0x00, 0x05][..], b"Hello"].concat(),
+        to_nbt(&"Hello".into())
+    );
+    assert_eq!(0x0a, to_nbt(&json(r#"{"text": "Hello", "bold": true}"#))[0]);
+}
+
+#[test]
+fn modified_utf8() {
+    let component = TextComponent::from("nul \0 and \u{1f600}");
+    let nbt = to_nbt(&component);
+
+    // The null is two bytes and the emoji a surrogate pair of three bytes each
+    assert_eq!(3 + 4 + 2 + 5 + 6, nbt.len());
+    assert_eq!(component, TextComponent::read(nbt.as_slice()).unwrap());
+}
+
+#[test]
+fn list_root() {
+    let component = from_nbt(&nbt!(["Hello", " ", "world"]));
+    assert_eq!(json(r#"["Hello", " ", "world"]"#), component);
+    assert_eq!(
+        json(r#"{"text": "Hello", "extra": [" ", "world"]}"#),
+        component
+    );
+    assert_eq!("Hello world", component.to_plain_text());
+}
+
+#[test]
+fn mixed_list() {
+    let component = from_nbt(&nbt!([
+        { "": "Hello " },
+        { "": { "text": "world", "bold": 1_i8 } },
+    ]));
+    assert_eq!(
+        json(r#"{"text": "Hello ", "extra": [{"text": "world", "bold": true}]}"#),
+        component
+    );
+}
+
+#[rstest]
+#[case::named("gold", true)]
+#[case::hex("#ff8800", true)]
+#[case::upper_hex("#FF8800", true)]
+#[case::bracketed("#<ff8800>", false)]
+#[case::short("#f80", false)]
+#[case::not_hex("#gg8800", false)]
+#[case::unknown("orange", false)]
+fn colors(#[case] color: &str, #[case] valid: bool) {
+    let component = format!(r#"{{"text": "Hi", "color": "{color}"}}"#);
+    assert_eq!(
+        valid,
+        serde_json::from_str::<TextComponent>(&component).is_ok()
+    );
+}
+
+#[rstest]
+#[case::vanilla_key("chat.type.text")]
+#[case::any_string("My Custom Key: %s")]
+fn translation_key(#[case] key: &str) {
+    let component = json(&format!(r#"{{"translate": "{key}"}}"#));
+    assert_eq!(
+        format!(r#"{{"type":"translatable","translate":"{key}"}}"#),
+        serde_json::to_string(&component).unwrap()
+    );
+}
+
+#[rstest]
+#[case::block(r#"{"nbt": "Items", "block": "1 64 -3"}"#)]
+#[case::entity(r#"{"nbt": "Health", "entity": "@s", "interpret": true}"#)]
+#[case::storage(r#"{"nbt": "foo", "storage": "example:store"}"#)]
+fn nbt_sources(#[case] s: &str) {
+    let component: NbtComponent = serde_json::from_str(s).unwrap();
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(s).unwrap(),
+        serde_json::to_value(&component).unwrap()
+    );
+}
+
+#[test]
+fn nbt_source_field_is_ignored() {
+    assert_eq!(
+        serde_json::from_str::<NbtComponent>(r#"{"nbt": "Health", "entity": "@s"}"#).unwrap(),
+        serde_json::from_str(r#"{"nbt": "Health", "entity": "@s", "source": "entity"}"#).unwrap()
+    );
+    assert!(serde_json::from_str::<NbtComponent>(r#"{"nbt": "Health"}"#).is_err());
+}
+
+#[test]
+fn default_separators() {
+    let selector: SelectorComponent = serde_json::from_str(r#"{"selector": "@a"}"#).unwrap();
+    assert_eq!(
+        json(r#"{"text": ", ", "color": "gray"}"#),
+        selector.separator()
+    );
+    let nbt: NbtComponent = serde_json::from_str(r#"{"nbt": "foo", "storage": "a:b"}"#).unwrap();
+    assert_eq!(TextComponent::from(", "), nbt.separator());
+
+    let nbt: NbtComponent =
+        serde_json::from_str(r#"{"nbt": "foo", "storage": "a:b", "separator": " | "}"#).unwrap();
+    assert_eq!(TextComponent::from(" | "), nbt.separator());
+}
+
+#[rstest]
+#[case::packed(-16777216)]
+#[case::floats([1.0, 0.0, 0.0, 0.0])]
+fn shadow_color(#[case] color: impl serde::Serialize) {
+    let component = json(&serde_json::json!({ "text": "Hi", "shadow_color": color }).to_string());
+    let json = serde_json::to_value(&component).unwrap();
+    assert_eq!(
+        Some(&serde_json::json!([1.0, 0.0, 0.0, 0.0])),
+        json.get("shadow_color")
+    );
+}
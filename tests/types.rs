@@ -109,3 +109,33 @@ fn string() {
     let read = String::read(buffer.as_slice()).unwrap();
     assert_eq!(value, read);
 }
+
+#[test]
+fn string_too_long() {
+    let mut buffer = Vec::new();
+    VarInt::new(i32::MAX).write(&mut buffer).unwrap();
+    let error = String::read(buffer.as_slice()).unwrap_err();
+    assert_eq!(std::io::ErrorKind::InvalidData, error.kind());
+}
+
+#[test]
+fn write_string_too_long() {
+    // The limit is in UTF-16 code units, not bytes
+    let value = "a".repeat(32768);
+    let error = value.write(&mut Vec::new()).unwrap_err();
+    assert_eq!(std::io::ErrorKind::InvalidInput, error.kind());
+
+    let value = "\u{20ac}".repeat(32767);
+    let mut buffer = Vec::new();
+    value.write(&mut buffer).unwrap();
+    assert_eq!(value, String::read(buffer.as_slice()).unwrap());
+}
+
+#[test]
+fn read_string_too_long() {
+    let mut buffer = Vec::new();
+    VarInt::new(32768).write(&mut buffer).unwrap();
+    buffer.extend(std::iter::repeat_n(b'a', 32768));
+    let error = String::read(buffer.as_slice()).unwrap_err();
+    assert_eq!(std::io::ErrorKind::InvalidData, error.kind());
+}